@@ -91,6 +91,10 @@ chmod +x "$INSTALL_DIR/mode"
 
 echo "[OK] MODE installed to $INSTALL_DIR/mode"
 
+# Fish gets the installer's lines in their own conf.d file; conf.d/mode.fish
+# belongs to mode, which rewrites it
+FISH_RC_FILE="${XDG_CONFIG_HOME:-$HOME/.config}/fish/conf.d/mode-wrapper.fish"
+
 # Add to PATH if not already there
 PATH_ADDED=false
 if [[ ":$PATH:" != *":$INSTALL_DIR:"* ]]; then
@@ -102,12 +106,23 @@ if [[ ":$PATH:" != *":$INSTALL_DIR:"* ]]; then
         RC_FILE="$HOME/.bashrc"
     elif [ "$CURRENT_SHELL" = "zsh" ]; then
         RC_FILE="$HOME/.zshrc"
+    elif [ "$CURRENT_SHELL" = "fish" ]; then
+        RC_FILE="$FISH_RC_FILE"
     else
         RC_FILE="$HOME/.profile"
     fi
 
     # Add PATH export if not already present
-    if ! grep -q "export PATH=\"\$HOME/.local/bin:\$PATH\"" "$RC_FILE" 2>/dev/null; then
+    if [ "$CURRENT_SHELL" = "fish" ]; then
+        if ! grep -q "fish_add_path \$HOME/.local/bin" "$RC_FILE" 2>/dev/null; then
+            mkdir -p "$(dirname "$RC_FILE")"
+            echo "" >> "$RC_FILE"
+            echo "# Added by MODE installer" >> "$RC_FILE"
+            echo "fish_add_path \$HOME/.local/bin" >> "$RC_FILE"
+            PATH_ADDED=true
+            echo "[OK] Added $INSTALL_DIR to PATH in $RC_FILE"
+        fi
+    elif ! grep -q "export PATH=\"\$HOME/.local/bin:\$PATH\"" "$RC_FILE" 2>/dev/null; then
         echo "" >> "$RC_FILE"
         echo "# Added by MODE installer" >> "$RC_FILE"
         echo "export PATH=\"\$HOME/.local/bin:\$PATH\"" >> "$RC_FILE"
@@ -135,23 +150,57 @@ if [ "$CURRENT_SHELL" = "bash" ]; then
 elif [ "$CURRENT_SHELL" = "zsh" ]; then
     RC_FILE="$HOME/.zshrc"
     WRAPPER_FILE="$(pwd)/mode-wrapper.zsh"
+elif [ "$CURRENT_SHELL" = "fish" ]; then
+    RC_FILE="$FISH_RC_FILE"
+    WRAPPER_FILE="$(pwd)/mode-wrapper.fish"
+    mkdir -p "$(dirname "$RC_FILE")"
 else
-    echo "[WARNING] Unknown shell: $CURRENT_SHELL (expected bash or zsh)"
+    echo "[WARNING] Unknown shell: $CURRENT_SHELL (expected bash, zsh or fish)"
     echo "[SKIP] Shell integration not configured automatically"
 fi
 
+# The wrapper sits between these markers so a reinstall can replace it
+WRAPPER_START="# >>> mode shell integration >>>"
+WRAPPER_END="# <<< mode shell integration <<<"
+# Older installers appended the wrapper's mode() function after this comment
+LEGACY_WRAPPER="# Mode shell integration - auto-reload"
+
 # Install shell integration if we detected the shell
 if [ -n "$RC_FILE" ] && [ -f "$WRAPPER_FILE" ]; then
-    # Check if shell integration already exists
-    if grep -q "# Mode shell integration - auto-reload" "$RC_FILE" 2>/dev/null; then
-        echo "[OK] Shell integration already configured in $RC_FILE"
-    else
-        # Add the wrapper function to the RC file
-        echo "" >> "$RC_FILE"
-        echo "# Mode shell integration - auto-reload after creating aliases/bookmarks" >> "$RC_FILE"
-        cat "$WRAPPER_FILE" >> "$RC_FILE"
-        echo "[OK] Shell integration added to $RC_FILE"
+    WRAPPER_ACTION="added to"
+    if grep -qF "$WRAPPER_START" "$RC_FILE" 2>/dev/null; then
+        # Drop the section (and the blank line before it) from the last install
+        awk -v start="$WRAPPER_START" -v end="$WRAPPER_END" '
+            skip { if ($0 == end) skip = 0; next }
+            $0 == "" { if (blank) print ""; blank = 1; next }
+            $0 == start { blank = 0; skip = 1; next }
+            { if (blank) print ""; blank = 0; print }
+            END { if (blank) print "" }
+        ' "$RC_FILE" > "$RC_FILE.mode-install" && cat "$RC_FILE.mode-install" > "$RC_FILE"
+        rm -f "$RC_FILE.mode-install"
+        WRAPPER_ACTION="updated in"
+    elif grep -qF "$LEGACY_WRAPPER" "$RC_FILE" 2>/dev/null; then
+        # That function ends at the first line that is just "}"
+        cp "$RC_FILE" "$RC_FILE.mode-install.bak"
+        awk -v legacy="$LEGACY_WRAPPER" '
+            skip { if ($0 == "}") skip = 0; next }
+            $0 == "" { if (blank) print ""; blank = 1; next }
+            index($0, legacy) == 1 { blank = 0; skip = 1; next }
+            { if (blank) print ""; blank = 0; print }
+            END { if (blank) print "" }
+        ' "$RC_FILE" > "$RC_FILE.mode-install" && cat "$RC_FILE.mode-install" > "$RC_FILE"
+        rm -f "$RC_FILE.mode-install"
+        echo "[OK] Replaced the old shell integration (previous file: $RC_FILE.mode-install.bak)"
+        WRAPPER_ACTION="updated in"
     fi
+
+    {
+        echo ""
+        echo "$WRAPPER_START"
+        cat "$WRAPPER_FILE"
+        echo "$WRAPPER_END"
+    } >> "$RC_FILE"
+    echo "[OK] Shell integration $WRAPPER_ACTION $RC_FILE"
 fi
 
 # Return to original directory
@@ -0,0 +1,39 @@
+#!/usr/bin/env fish
+# Mode wrapper function for automatic shell sourcing
+# install.sh copies this into ~/.config/fish/conf.d/mode-wrapper.fish, kept
+# apart from conf.d/mode.fish, which mode manages
+
+function mode --description 'Run mode and apply the command it leaves behind'
+    set -l cmd_file "$HOME/.mode_exit_cmd"
+
+    # Remove old command file
+    rm -f $cmd_file
+
+    # Run mode using the installed binary (finds it in PATH)
+    command mode $argv
+    set -l exit_code $status
+
+    # Check if mode wrote an exit command
+    if test -f $cmd_file
+        set -l exit_cmd (cat $cmd_file | string collect)
+        rm -f $cmd_file
+
+        if test -n "$exit_cmd"
+            eval $exit_cmd
+            # Jumps and ssh sessions are their own feedback; everything else reloads configuration
+            switch $exit_cmd
+                case 'cd *' 'ssh *'
+                case '*'
+                    echo "✓ Shell configuration reloaded"
+            end
+        end
+    end
+
+    return $exit_code
+end
+
+# Record directory visits for the frecency jumper (Bookmarks > Frequent, `mode jump`)
+function _mode_track_dir --on-variable PWD
+    command mode visit $PWD >/dev/null 2>&1 &
+    disown 2>/dev/null
+end
@@ -20,9 +20,26 @@ mode() {
 
         if [ -n "$exit_cmd" ]; then
             eval "$exit_cmd"
-            echo "✓ Shell configuration reloaded"
+            # Jumps and ssh sessions are their own feedback; everything else reloads configuration
+            case "$exit_cmd" in
+                cd\ *|ssh\ *) ;;
+                *) echo "✓ Shell configuration reloaded" ;;
+            esac
         fi
     fi
 
     return $exit_code
 }
+
+# Record directory visits for the frecency jumper (Bookmarks > Frequent, `mode jump`)
+_mode_track_dir() {
+    if [ "$PWD" != "${_MODE_LAST_DIR:-}" ]; then
+        _MODE_LAST_DIR="$PWD"
+        (command mode visit "$PWD" >/dev/null 2>&1 &)
+    fi
+}
+
+case ";${PROMPT_COMMAND:-};" in
+    *";_mode_track_dir;"*) ;;
+    *) PROMPT_COMMAND="_mode_track_dir${PROMPT_COMMAND:+;$PROMPT_COMMAND}" ;;
+esac
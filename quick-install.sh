@@ -46,6 +46,8 @@ else
         RC_FILE="$HOME/.bashrc"
     elif [ "$CURRENT_SHELL" = "zsh" ]; then
         RC_FILE="$HOME/.zshrc"
+    elif [ "$CURRENT_SHELL" = "fish" ]; then
+        RC_FILE="${XDG_CONFIG_HOME:-$HOME/.config}/fish/conf.d/mode-wrapper.fish"
     else
         RC_FILE="$HOME/.bashrc"
     fi
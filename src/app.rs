@@ -1,19 +1,28 @@
 use crate::{
     event::Event,
-    features::{AliasManager, AliasManagerState, BookmarkManager, BookmarkManagerState, PlaceholderFeature, ProcessManager, ProcessManagerState, Scanner, ScannerState, UsageViewer, UsageViewerState},
+    features::{AliasManager, AliasManagerState, Backups, BackupsState, BookmarkManager, BookmarkManagerState, EnvManager, EnvManagerState, GitShortcuts, GitShortcutsState, ModeConfig, PathManager, PathManagerState, PlaceholderFeature, ProcessManager, ProcessManagerState, Scanner, ScannerState, Settings, SettingsState, SshManager, SshManagerState, SystemInfo, UsageViewer, UsageViewerState},
     menu::{MenuItem, MenuState},
+    ui_components::theme::{ColorSupport, Theme},
     utils::Result,
 };
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::sync::mpsc::Sender;
 
 /// Active feature types
 #[derive(Debug)]
 pub enum ActiveFeature {
     AliasManager(AliasManager),
-    ProcessManager(ProcessManager),
-    BookmarkManager(BookmarkManager),
+    ProcessManager(Box<ProcessManager>),
+    BookmarkManager(Box<BookmarkManager>),
     UsageViewer(UsageViewer),
-    Scanner(Scanner),
+    Scanner(Box<Scanner>),
+    EnvManager(Box<EnvManager>),
+    PathManager(Box<PathManager>),
+    SshManager(Box<SshManager>),
+    GitShortcuts(Box<GitShortcuts>),
+    SystemInfo(Box<SystemInfo>),
+    Backups(Box<Backups>),
+    Settings(Box<Settings>),
     Placeholder(PlaceholderFeature),
 }
 
@@ -40,33 +49,84 @@ pub struct App {
     pub error_message: Option<String>,
     /// Command to execute on exit (for shell integration)
     pub exit_command: Option<String>,
+    /// Settings from `config.toml`
+    pub config: ModeConfig,
+    /// Colors every screen is drawn with
+    pub theme: Theme,
+    /// Sender for features that stream results from background threads
+    event_tx: Option<Sender<Event>>,
 }
 
 impl App {
     /// Creates a new application
+    ///
+    /// If `config.toml` or the theme is invalid the defaults are used and the
+    /// problem is shown under the menu.
     pub fn new() -> Self {
-        Self {
+        let mut app = Self {
             state: AppState::MainMenu,
             menu_state: MenuState::new(),
             should_quit: false,
             error_message: None,
             exit_command: None,
+            config: ModeConfig::default(),
+            theme: Theme::default(),
+            event_tx: None,
+        };
+
+        match ModeConfig::load() {
+            Ok(config) => app.apply_config(config),
+            Err(e) => {
+                app.apply_config(ModeConfig::default());
+                app.error_message = Some(format!("Using default settings: {}", e));
+            }
+        }
+        app
+    }
+
+    /// Applies settings: theme, hidden menu items, and feature defaults
+    fn apply_config(&mut self, config: ModeConfig) {
+        let theme = Theme::load(&config.theme).unwrap_or_else(|e| {
+            self.error_message = Some(format!("Using the default theme: {}", e));
+            Theme::default()
+        });
+        self.theme = theme.for_terminal(ColorSupport::detect());
+
+        // Keep the selection on the same item if it is still shown
+        let selected = self.menu_state.selected_item();
+        self.menu_state = MenuState::with_hidden(&config.hidden_menu_items);
+        if let Some(index) = self.menu_state.items().iter().position(|item| *item == selected) {
+            self.menu_state.select(index);
         }
+
+        self.config = config;
+    }
+
+    /// Sets the sender used by background work to feed events into the loop
+    pub fn set_event_sender(&mut self, tx: Sender<Event>) {
+        self.event_tx = Some(tx);
     }
 
     /// Handles an event
     pub fn handle_event(&mut self, event: Event) -> Result<()> {
         match event {
             Event::Key(key) => self.handle_key(key)?,
-            Event::Tick => {
-                // Handle periodic updates if needed
-            }
+            Event::Tick => match &mut self.state {
+                AppState::FeatureActive(ActiveFeature::ProcessManager(manager)) => manager.tick(),
+                AppState::FeatureActive(ActiveFeature::SystemInfo(info)) => info.tick(),
+                _ => {}
+            },
             Event::Resize(_, _) => {
                 // Terminal resize handled by ratatui
             }
             Event::Mouse(_) => {
                 // Mouse events not implemented yet
             }
+            Event::Scan(update) => {
+                if let AppState::FeatureActive(ActiveFeature::Scanner(scanner)) = &mut self.state {
+                    scanner.handle_scan_update(update);
+                }
+            }
         }
 
         Ok(())
@@ -85,17 +145,16 @@ impl App {
         } else {
             // Must be FeatureActive - handle inline to avoid borrow issues
             let mut should_return_to_menu = false;
+            let mut saved_config = None;
 
             if let AppState::FeatureActive(feature) = &mut self.state {
                 match feature {
                     ActiveFeature::AliasManager(manager) => {
                         should_return_to_menu = Self::handle_alias_manager_key_static(key, manager)?;
 
-                        // If alias was successfully created, set exit command to source RC file
-                        if manager.is_done() && matches!(manager.state, crate::features::AliasManagerState::Success { .. }) {
-                            if let Some(rc_file) = manager.get_rc_file() {
-                                self.exit_command = Some(format!("source {}", rc_file.display()));
-                            }
+                        // If aliases were changed, set exit command to apply them to the shell
+                        if manager.is_modified() {
+                            self.exit_command = manager.get_exit_command();
                         }
                     }
                     ActiveFeature::ProcessManager(manager) => {
@@ -105,12 +164,10 @@ impl App {
                         let result = Self::handle_bookmark_manager_key_static(key, manager)?;
                         should_return_to_menu = result.0;
 
-                        // If bookmark was successful, exit the app and set exit command
+                        // Jumping to a bookmark exits so the shell wrapper can cd there
                         if result.1 {
                             self.should_quit = true;
-                            if let Some(rc_file) = manager.get_rc_file() {
-                                self.exit_command = Some(format!("source {}", rc_file.display()));
-                            }
+                            self.exit_command = manager.get_exit_command();
                         }
                     }
                     ActiveFeature::UsageViewer(viewer) => {
@@ -119,6 +176,60 @@ impl App {
                     ActiveFeature::Scanner(scanner) => {
                         should_return_to_menu = Self::handle_scanner_key_static(key, scanner)?;
                     }
+                    ActiveFeature::EnvManager(manager) => {
+                        should_return_to_menu = Self::handle_env_manager_key_static(key, manager)?;
+
+                        // If exports were changed, set exit command to apply them to the shell
+                        if manager.is_modified() {
+                            self.exit_command = manager.get_exit_command();
+                        }
+                    }
+                    ActiveFeature::PathManager(manager) => {
+                        should_return_to_menu = Self::handle_path_manager_key_static(key, manager)?;
+
+                        // If PATH was saved, set exit command to apply it to the shell
+                        if manager.is_modified() {
+                            self.exit_command = manager.get_exit_command();
+                        }
+                    }
+                    ActiveFeature::SshManager(manager) => {
+                        let result = Self::handle_ssh_manager_key_static(key, manager)?;
+                        should_return_to_menu = result.0;
+
+                        // Connecting exits so the shell wrapper can run ssh
+                        if result.1 {
+                            self.should_quit = true;
+                            self.exit_command = manager.get_exit_command();
+                        }
+                    }
+                    ActiveFeature::GitShortcuts(shortcuts) => {
+                        should_return_to_menu = Self::handle_git_shortcuts_key_static(key, shortcuts)?;
+                    }
+                    ActiveFeature::SystemInfo(_) => {
+                        // The dashboard is read-only; ESC goes back
+                        if matches!(key.code, KeyCode::Esc | KeyCode::Char('q')) {
+                            should_return_to_menu = true;
+                        }
+                    }
+                    ActiveFeature::Backups(backups) => {
+                        should_return_to_menu = Self::handle_backups_key_static(key, backups)?;
+
+                        // A restored RC file is sourced on exit, a restored config.toml applied on leaving
+                        if backups.is_modified() {
+                            self.exit_command = backups.get_exit_command();
+                        }
+                        if should_return_to_menu {
+                            saved_config = backups.restored_config().cloned();
+                        }
+                    }
+                    ActiveFeature::Settings(settings) => {
+                        should_return_to_menu = Self::handle_settings_key_static(key, settings)?;
+
+                        // Apply saved settings when leaving the screen
+                        if should_return_to_menu && settings.is_modified() {
+                            saved_config = Some(settings.saved_config().clone());
+                        }
+                    }
                     ActiveFeature::Placeholder(_) => {
                         // Just ESC to go back
                         if matches!(key.code, KeyCode::Esc) {
@@ -131,6 +242,9 @@ impl App {
             if should_return_to_menu {
                 self.state = AppState::MainMenu;
             }
+            if let Some(config) = saved_config {
+                self.apply_config(config);
+            }
 
             Ok(())
         }
@@ -138,6 +252,9 @@ impl App {
 
     /// Handles keyboard input in main menu
     fn handle_main_menu_key(&mut self, key: KeyEvent) -> Result<()> {
+        // Any key dismisses the last error
+        self.error_message = None;
+
         match key.code {
             KeyCode::Char('q') | KeyCode::Char('Q') => {
                 self.should_quit = true;
@@ -178,15 +295,15 @@ impl App {
                     }
                 }
                 MenuItem::ProcessManager => {
-                    let manager = ProcessManager::new();
-                    self.state = AppState::FeatureActive(ActiveFeature::ProcessManager(manager));
+                    let manager = ProcessManager::with_config(self.config.process.clone());
+                    self.state = AppState::FeatureActive(ActiveFeature::ProcessManager(Box::new(manager)));
                 }
                 MenuItem::Bookmark => {
                     let mut manager = BookmarkManager::new();
                     if let Err(e) = manager.initialize() {
                         self.error_message = Some(format!("Failed to initialize: {}", e));
                     } else {
-                        self.state = AppState::FeatureActive(ActiveFeature::BookmarkManager(manager));
+                        self.state = AppState::FeatureActive(ActiveFeature::BookmarkManager(Box::new(manager)));
                     }
                 }
                 MenuItem::UsageViewer => {
@@ -198,11 +315,65 @@ impl App {
                     self.state = AppState::FeatureActive(ActiveFeature::UsageViewer(viewer));
                 }
                 MenuItem::Scanner => {
-                    let scanner = Scanner::new();
-                    self.state = AppState::FeatureActive(ActiveFeature::Scanner(scanner));
+                    let mut scanner = Scanner::new();
+                    scanner.apply_settings(self.config.scanner);
+                    if let Some(tx) = &self.event_tx {
+                        scanner.set_event_sender(tx.clone());
+                    }
+                    self.state = AppState::FeatureActive(ActiveFeature::Scanner(Box::new(scanner)));
+                }
+                MenuItem::EnvManager => {
+                    let mut manager = EnvManager::new();
+                    if let Err(e) = manager.initialize() {
+                        self.error_message = Some(format!("Failed to initialize: {}", e));
+                    } else {
+                        self.state = AppState::FeatureActive(ActiveFeature::EnvManager(Box::new(manager)));
+                    }
+                }
+                MenuItem::PathManager => {
+                    let mut manager = PathManager::new();
+                    if let Err(e) = manager.initialize() {
+                        self.error_message = Some(format!("Failed to initialize: {}", e));
+                    } else {
+                        self.state = AppState::FeatureActive(ActiveFeature::PathManager(Box::new(manager)));
+                    }
                 }
-                _ => {
-                    // Should not happen as we check is_active()
+                MenuItem::SshManager => {
+                    let mut manager = SshManager::new();
+                    if let Err(e) = manager.initialize() {
+                        self.error_message = Some(format!("Failed to initialize: {}", e));
+                    } else {
+                        self.state = AppState::FeatureActive(ActiveFeature::SshManager(Box::new(manager)));
+                    }
+                }
+                MenuItem::GitShortcuts => {
+                    let mut shortcuts = GitShortcuts::new();
+                    if let Err(e) = shortcuts.initialize() {
+                        self.error_message = Some(format!("Failed to initialize: {}", e));
+                    } else {
+                        self.state = AppState::FeatureActive(ActiveFeature::GitShortcuts(Box::new(shortcuts)));
+                    }
+                }
+                MenuItem::SystemInfo => {
+                    let mut info = SystemInfo::new();
+                    info.initialize();
+                    self.state = AppState::FeatureActive(ActiveFeature::SystemInfo(Box::new(info)));
+                }
+                MenuItem::Backups => {
+                    let mut backups = Backups::new();
+                    if let Err(e) = backups.initialize() {
+                        self.error_message = Some(format!("Failed to initialize: {}", e));
+                    } else {
+                        self.state = AppState::FeatureActive(ActiveFeature::Backups(Box::new(backups)));
+                    }
+                }
+                MenuItem::Settings => {
+                    let mut settings = Settings::new();
+                    if let Err(e) = settings.initialize() {
+                        self.error_message = Some(format!("Failed to initialize: {}", e));
+                    } else {
+                        self.state = AppState::FeatureActive(ActiveFeature::Settings(Box::new(settings)));
+                    }
                 }
             }
         } else {
@@ -215,113 +386,178 @@ impl App {
         Ok(())
     }
 
-    /// Handles keyboard input in alias manager (static method to avoid borrow issues)
+    /// Handles keyboard input in the backups screen (static method to avoid borrow issues)
     /// Returns true if should return to main menu
-    fn handle_alias_manager_key_static(key: KeyEvent, manager: &mut AliasManager) -> Result<bool> {
+    fn handle_backups_key_static(key: KeyEvent, backups: &mut Backups) -> Result<bool> {
         let mut return_to_menu = false;
 
-        match &manager.state {
-            AliasManagerState::EnteringName { .. } | AliasManagerState::EnteringCommand { .. } => {
+        match &backups.state {
+            BackupsState::Listing { .. } => {
                 match key.code {
-                    KeyCode::Char(c) => {
-                        manager.handle_char(c);
+                    KeyCode::Up | KeyCode::Char('k') => {
+                        backups.previous();
                     }
-                    KeyCode::Backspace => {
-                        manager.handle_backspace();
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        backups.next();
                     }
-                    KeyCode::Enter => {
-                        manager.advance()?;
+                    KeyCode::Enter | KeyCode::Char('d') => {
+                        backups.view_diff();
                     }
-                    KeyCode::Esc => {
+                    KeyCode::Char('r') | KeyCode::Char('R') => {
+                        backups.start_restore();
+                    }
+                    KeyCode::Esc | KeyCode::Char('q') => {
                         return_to_menu = true;
                     }
                     _ => {}
                 }
             }
-            AliasManagerState::Confirming { .. } => {
+            BackupsState::ViewingDiff { .. } => {
+                match key.code {
+                    KeyCode::Up | KeyCode::Char('k') => {
+                        backups.previous();
+                    }
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        backups.next();
+                    }
+                    KeyCode::PageUp => {
+                        backups.page_up();
+                    }
+                    KeyCode::PageDown | KeyCode::Char(' ') => {
+                        backups.page_down();
+                    }
+                    KeyCode::Char('r') | KeyCode::Char('R') => {
+                        backups.start_restore();
+                    }
+                    KeyCode::Esc | KeyCode::Char('q') => {
+                        backups.go_back();
+                    }
+                    _ => {}
+                }
+            }
+            BackupsState::ConfirmingRestore { .. } => {
                 match key.code {
                     KeyCode::Char('y') | KeyCode::Char('Y') => {
-                        manager.advance()?;
+                        backups.advance();
                     }
                     KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
-                        manager.go_back();
+                        backups.go_back();
                     }
                     _ => {}
                 }
             }
-            AliasManagerState::Success { .. } | AliasManagerState::Error { .. } => {
-                // Any key returns to main menu
+            BackupsState::Success { .. } | BackupsState::Error { .. } => {
+                // Any key returns to the backups list
                 if matches!(key.code, KeyCode::Enter | KeyCode::Esc) {
-                    return_to_menu = true;
+                    backups.go_back();
                 }
             }
-            _ => {}
         }
 
         Ok(return_to_menu)
     }
 
-    /// Handles keyboard input in process manager (static method to avoid borrow issues)
+    /// Handles keyboard input in settings (static method to avoid borrow issues)
     /// Returns true if should return to main menu
-    fn handle_process_manager_key_static(key: KeyEvent, manager: &mut ProcessManager) -> Result<bool> {
+    fn handle_settings_key_static(key: KeyEvent, settings: &mut Settings) -> Result<bool> {
         let mut return_to_menu = false;
 
-        match &manager.state {
-            ProcessManagerState::SelectingAction { .. } => {
+        match &settings.state {
+            SettingsState::Browsing { .. } => {
                 match key.code {
                     KeyCode::Up | KeyCode::Char('k') => {
-                        manager.previous();
+                        settings.previous();
                     }
                     KeyCode::Down | KeyCode::Char('j') => {
-                        manager.next();
+                        settings.next();
+                    }
+                    KeyCode::Enter | KeyCode::Char(' ') => {
+                        settings.activate();
+                    }
+                    KeyCode::Char('s') | KeyCode::Char('S') => {
+                        settings.start_save();
+                    }
+                    KeyCode::Esc | KeyCode::Char('q') => {
+                        return_to_menu = settings.request_exit();
+                    }
+                    _ => {}
+                }
+            }
+            SettingsState::Editing { .. } => {
+                match key.code {
+                    KeyCode::Char(c) => {
+                        settings.handle_char(c);
+                    }
+                    KeyCode::Backspace => {
+                        settings.handle_backspace();
                     }
                     KeyCode::Enter => {
-                        manager.confirm_selection();
+                        settings.advance();
                     }
                     KeyCode::Esc => {
-                        return_to_menu = true;
+                        settings.go_back();
                     }
                     _ => {}
                 }
             }
-            ProcessManagerState::Confirming { .. } => {
+            SettingsState::ConfirmingSave { .. } => {
                 match key.code {
                     KeyCode::Char('y') | KeyCode::Char('Y') => {
-                        manager.execute_action();
+                        settings.advance();
                     }
                     KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
-                        manager.go_back();
+                        settings.go_back();
                     }
                     _ => {}
                 }
             }
-            ProcessManagerState::Success { .. } | ProcessManagerState::Error { .. } => {
-                // Any key returns to main menu
+            SettingsState::ConfirmingDiscard { .. } => {
+                match key.code {
+                    KeyCode::Char('y') | KeyCode::Char('Y') => {
+                        return_to_menu = true;
+                    }
+                    KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                        settings.go_back();
+                    }
+                    _ => {}
+                }
+            }
+            SettingsState::Success { .. } | SettingsState::Error { .. } => {
+                // Any key returns to the settings list
                 if matches!(key.code, KeyCode::Enter | KeyCode::Esc) {
-                    return_to_menu = true;
+                    settings.go_back();
                 }
             }
-            _ => {}
         }
 
         Ok(return_to_menu)
     }
 
-    /// Handles keyboard input in bookmark manager (static method to avoid borrow issues)
-    /// Returns (should_return_to_menu, should_exit_app) tuple
-    fn handle_bookmark_manager_key_static(key: KeyEvent, manager: &mut BookmarkManager) -> Result<(bool, bool)> {
+    /// Handles keyboard input in alias manager (static method to avoid borrow issues)
+    /// Returns true if should return to main menu
+    fn handle_alias_manager_key_static(key: KeyEvent, manager: &mut AliasManager) -> Result<bool> {
         let mut return_to_menu = false;
-        let mut exit_app = false;
 
         match &manager.state {
-            BookmarkManagerState::Confirming { .. } => {
+            AliasManagerState::Listing { .. } => {
                 match key.code {
-                    KeyCode::Enter => {
-                        manager.confirm_bookmark()?;
-                        // Check if we should exit after confirming
-                        if manager.should_exit_app() {
-                            exit_app = true;
-                        }
+                    KeyCode::Up | KeyCode::Char('k') => {
+                        manager.previous();
+                    }
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        manager.next();
+                    }
+                    KeyCode::Char('a') | KeyCode::Char('A') => {
+                        manager.start_add();
+                    }
+                    KeyCode::Enter | KeyCode::Char('e') | KeyCode::Char('E') => {
+                        manager.start_edit();
+                    }
+                    KeyCode::Char('r') | KeyCode::Char('R') => {
+                        manager.start_rename();
+                    }
+                    KeyCode::Char('d') | KeyCode::Char('D') | KeyCode::Delete => {
+                        manager.start_delete();
                     }
                     KeyCode::Esc => {
                         return_to_menu = true;
@@ -329,68 +565,65 @@ impl App {
                     _ => {}
                 }
             }
-            BookmarkManagerState::Success { .. } => {
-                // Any key exits the app
-                if matches!(key.code, KeyCode::Enter | KeyCode::Esc) {
-                    exit_app = true;
-                }
-            }
-            BookmarkManagerState::Error { .. } => {
-                // Any key returns to main menu
-                if matches!(key.code, KeyCode::Enter | KeyCode::Esc) {
-                    return_to_menu = true;
+            AliasManagerState::EnteringName { .. }
+            | AliasManagerState::EnteringCommand { .. }
+            | AliasManagerState::EditingCommand { .. }
+            | AliasManagerState::Renaming { .. } => {
+                match key.code {
+                    KeyCode::Char(c) => {
+                        manager.handle_char(c);
+                    }
+                    KeyCode::Backspace => {
+                        manager.handle_backspace();
+                    }
+                    KeyCode::Enter => {
+                        manager.advance()?;
+                    }
+                    KeyCode::Esc => {
+                        manager.go_back();
+                    }
+                    _ => {}
                 }
             }
-            _ => {}
-        }
-
-        Ok((return_to_menu, exit_app))
-    }
-
-    /// Handles keyboard input in usage viewer (static method to avoid borrow issues)
-    /// Returns true if should return to main menu
-    fn handle_usage_viewer_key_static(key: KeyEvent, viewer: &mut UsageViewer) -> Result<bool> {
-        let mut return_to_menu = false;
-
-        match &viewer.state {
-            UsageViewerState::Ready => {
-                // This shouldn't happen as we open immediately, but handle it
-                if matches!(key.code, KeyCode::Enter) {
-                    viewer.open_browser()?;
-                } else if matches!(key.code, KeyCode::Esc) {
-                    return_to_menu = true;
+            AliasManagerState::Confirming { .. } | AliasManagerState::ConfirmingDelete { .. } => {
+                match key.code {
+                    KeyCode::Char('y') | KeyCode::Char('Y') => {
+                        manager.advance()?;
+                    }
+                    KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                        manager.go_back();
+                    }
+                    _ => {}
                 }
             }
-            UsageViewerState::Opening => {
-                // Just wait
-            }
-            UsageViewerState::Success { .. } | UsageViewerState::Error { .. } => {
-                // Any key returns to main menu
+            AliasManagerState::Success { .. } | AliasManagerState::Error { .. } => {
+                // Any key returns to the alias list
                 if matches!(key.code, KeyCode::Enter | KeyCode::Esc) {
-                    return_to_menu = true;
+                    manager.go_back();
                 }
             }
+            _ => {}
         }
 
         Ok(return_to_menu)
     }
 
-    /// Handles keyboard input in scanner (static method to avoid borrow issues)
+    /// Handles keyboard input in process manager (static method to avoid borrow issues)
     /// Returns true if should return to main menu
-    fn handle_scanner_key_static(key: KeyEvent, scanner: &mut Scanner) -> Result<bool> {
+    fn handle_process_manager_key_static(key: KeyEvent, manager: &mut ProcessManager) -> Result<bool> {
         let mut return_to_menu = false;
 
-        match &scanner.state {
-            ScannerState::SelectingScanType { .. } => {
+        match &manager.state {
+            ProcessManagerState::SelectingAction { .. } => {
                 match key.code {
                     KeyCode::Up | KeyCode::Char('k') => {
-                        scanner.previous();
+                        manager.previous();
                     }
                     KeyCode::Down | KeyCode::Char('j') => {
-                        scanner.next();
+                        manager.next();
                     }
                     KeyCode::Enter => {
-                        scanner.confirm_scan_type();
+                        manager.confirm_selection();
                     }
                     KeyCode::Esc => {
                         return_to_menu = true;
@@ -398,47 +631,718 @@ impl App {
                     _ => {}
                 }
             }
-            ScannerState::EnteringTarget { .. } => {
+            ProcessManagerState::Confirming { .. } => {
                 match key.code {
-                    KeyCode::Char(c) => {
-                        scanner.handle_char(c);
-                    }
-                    KeyCode::Backspace => {
-                        scanner.handle_backspace();
-                    }
-                    KeyCode::Enter => {
-                        scanner.advance_to_options();
+                    KeyCode::Up | KeyCode::Char('k') => {
+                        manager.previous();
                     }
-                    KeyCode::Esc => {
-                        scanner.go_back();
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        manager.next();
                     }
-                    _ => {}
-                }
-            }
-            ScannerState::EnteringPortRange { .. } => {
-                match key.code {
-                    KeyCode::Char(c) => {
-                        scanner.handle_port_range_char(c);
+                    KeyCode::Char(' ') => {
+                        manager.toggle_check();
                     }
-                    KeyCode::Backspace => {
-                        scanner.handle_port_range_backspace();
+                    KeyCode::Char('a') => {
+                        manager.toggle_check_all();
                     }
-                    KeyCode::Enter => {
-                        scanner.advance_from_port_range();
+                    KeyCode::Char('y') | KeyCode::Char('Y') => {
+                        manager.execute_action();
                     }
-                    KeyCode::Esc => {
-                        scanner.go_back();
+                    KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                        manager.go_back();
                     }
                     _ => {}
                 }
             }
-            ScannerState::SelectingOptions { .. } => {
+            ProcessManagerState::Browsing { .. } => {
                 match key.code {
                     KeyCode::Up | KeyCode::Char('k') => {
-                        scanner.previous();
+                        manager.previous();
                     }
                     KeyCode::Down | KeyCode::Char('j') => {
-                        scanner.next();
+                        manager.next();
+                    }
+                    KeyCode::Char(' ') => {
+                        manager.toggle_mark();
+                        manager.next();
+                    }
+                    KeyCode::Char('a') => {
+                        manager.toggle_mark_all();
+                    }
+                    KeyCode::Char('/') => {
+                        manager.start_filter();
+                    }
+                    KeyCode::Char('p') => {
+                        manager.cycle_quick_filter();
+                    }
+                    KeyCode::Char('s') => {
+                        manager.cycle_sort();
+                    }
+                    KeyCode::Char('o') => {
+                        manager.toggle_sort_order();
+                    }
+                    KeyCode::Char('r') => {
+                        manager.refresh();
+                    }
+                    KeyCode::Enter | KeyCode::Char('x') | KeyCode::Delete => {
+                        manager.request_kill();
+                    }
+                    KeyCode::Esc => {
+                        manager.go_back();
+                    }
+                    _ => {}
+                }
+            }
+            ProcessManagerState::EnteringPorts { .. } => {
+                match key.code {
+                    KeyCode::Char(c) => {
+                        manager.add_port_char(c);
+                    }
+                    KeyCode::Backspace => {
+                        manager.remove_port_char();
+                    }
+                    KeyCode::Enter => {
+                        manager.submit_ports();
+                    }
+                    KeyCode::Esc => {
+                        manager.go_back();
+                    }
+                    _ => {}
+                }
+            }
+            ProcessManagerState::Filtering { .. } => {
+                match key.code {
+                    KeyCode::Char(c) => {
+                        manager.add_filter_char(c);
+                    }
+                    KeyCode::Backspace => {
+                        manager.remove_filter_char();
+                    }
+                    KeyCode::Enter => {
+                        manager.finish_filter();
+                    }
+                    KeyCode::Esc => {
+                        manager.cancel_filter();
+                    }
+                    _ => {}
+                }
+            }
+            ProcessManagerState::ConfirmingKill { .. } => {
+                match key.code {
+                    KeyCode::Left | KeyCode::Right | KeyCode::Tab => {
+                        manager.toggle_signal();
+                    }
+                    KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
+                        manager.execute_kill();
+                    }
+                    KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                        manager.go_back();
+                    }
+                    _ => {}
+                }
+            }
+            ProcessManagerState::Success { .. } | ProcessManagerState::Error { .. } => {
+                // Enter goes back to the process table after a browser kill,
+                // otherwise any of these keys returns to main menu
+                match key.code {
+                    KeyCode::Enter => {
+                        return_to_menu = !manager.resume_browsing();
+                    }
+                    KeyCode::Esc => {
+                        return_to_menu = true;
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+
+        Ok(return_to_menu)
+    }
+
+    /// Handles keyboard input in bookmark manager (static method to avoid borrow issues)
+    /// Returns (should_return_to_menu, should_exit_app) tuple
+    fn handle_bookmark_manager_key_static(key: KeyEvent, manager: &mut BookmarkManager) -> Result<(bool, bool)> {
+        let mut return_to_menu = false;
+        let mut exit_app = false;
+
+        match &manager.state {
+            BookmarkManagerState::Listing { .. } => {
+                match key.code {
+                    KeyCode::Up | KeyCode::Char('k') => {
+                        manager.previous();
+                    }
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        manager.next();
+                    }
+                    KeyCode::Enter => {
+                        exit_app = manager.jump();
+                    }
+                    KeyCode::Char('a') | KeyCode::Char('A') => {
+                        manager.start_add();
+                    }
+                    KeyCode::Char('r') | KeyCode::Char('R') => {
+                        manager.start_rename();
+                    }
+                    KeyCode::Char('d') | KeyCode::Char('D') | KeyCode::Delete => {
+                        manager.start_delete();
+                    }
+                    KeyCode::Tab => {
+                        manager.show_frequent();
+                    }
+                    KeyCode::Esc => {
+                        return_to_menu = true;
+                    }
+                    _ => {}
+                }
+            }
+            BookmarkManagerState::Frequent { .. } => {
+                // Typing filters, so navigation stays on the arrow keys
+                match key.code {
+                    KeyCode::Up => {
+                        manager.previous();
+                    }
+                    KeyCode::Down => {
+                        manager.next();
+                    }
+                    KeyCode::Enter => {
+                        exit_app = manager.jump();
+                    }
+                    KeyCode::Delete => {
+                        manager.forget_selected();
+                    }
+                    KeyCode::Char(c) => {
+                        manager.handle_char(c);
+                    }
+                    KeyCode::Backspace => {
+                        manager.handle_backspace();
+                    }
+                    KeyCode::Tab => {
+                        manager.show_list(None);
+                    }
+                    KeyCode::Esc => {
+                        // First ESC clears the filter, the second leaves
+                        return_to_menu = !manager.clear_query();
+                    }
+                    _ => {}
+                }
+            }
+            BookmarkManagerState::Adding { .. } | BookmarkManagerState::Renaming { .. } => {
+                match key.code {
+                    KeyCode::Char(c) => {
+                        manager.handle_char(c);
+                    }
+                    KeyCode::Backspace => {
+                        manager.handle_backspace();
+                    }
+                    KeyCode::Enter => {
+                        manager.advance();
+                    }
+                    KeyCode::Esc => {
+                        manager.go_back();
+                    }
+                    _ => {}
+                }
+            }
+            BookmarkManagerState::ConfirmingDelete { .. } => {
+                match key.code {
+                    KeyCode::Char('y') | KeyCode::Char('Y') => {
+                        manager.advance();
+                    }
+                    KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                        manager.go_back();
+                    }
+                    _ => {}
+                }
+            }
+            BookmarkManagerState::Success { .. } | BookmarkManagerState::Error { .. } => {
+                // Any key returns to the bookmark list
+                if matches!(key.code, KeyCode::Enter | KeyCode::Esc) {
+                    manager.go_back();
+                }
+            }
+        }
+
+        Ok((return_to_menu, exit_app))
+    }
+
+    /// Handles keyboard input in env manager (static method to avoid borrow issues)
+    /// Returns true if should return to main menu
+    fn handle_env_manager_key_static(key: KeyEvent, manager: &mut EnvManager) -> Result<bool> {
+        let mut return_to_menu = false;
+
+        match &manager.state {
+            EnvManagerState::Listing { .. } => {
+                match key.code {
+                    KeyCode::Up | KeyCode::Char('k') => {
+                        manager.previous();
+                    }
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        manager.next();
+                    }
+                    KeyCode::Char('/') => {
+                        manager.start_filter();
+                    }
+                    KeyCode::Char('a') | KeyCode::Char('A') => {
+                        manager.start_add();
+                    }
+                    KeyCode::Enter | KeyCode::Char('e') | KeyCode::Char('E') => {
+                        manager.start_edit();
+                    }
+                    KeyCode::Char('d') | KeyCode::Char('D') | KeyCode::Char('u') | KeyCode::Delete => {
+                        manager.start_unset();
+                    }
+                    KeyCode::Esc => {
+                        return_to_menu = true;
+                    }
+                    _ => {}
+                }
+            }
+            EnvManagerState::Filtering { .. } => {
+                match key.code {
+                    KeyCode::Up => {
+                        manager.previous();
+                    }
+                    KeyCode::Down => {
+                        manager.next();
+                    }
+                    KeyCode::Char(c) => {
+                        manager.handle_char(c);
+                    }
+                    KeyCode::Backspace => {
+                        manager.handle_backspace();
+                    }
+                    KeyCode::Enter => {
+                        manager.finish_filter();
+                    }
+                    KeyCode::Esc => {
+                        manager.cancel_filter();
+                    }
+                    _ => {}
+                }
+            }
+            EnvManagerState::EnteringName { .. }
+            | EnvManagerState::EnteringValue { .. }
+            | EnvManagerState::EditingValue { .. } => {
+                match key.code {
+                    KeyCode::Char(c) => {
+                        manager.handle_char(c);
+                    }
+                    KeyCode::Backspace => {
+                        manager.handle_backspace();
+                    }
+                    KeyCode::Enter => {
+                        manager.advance();
+                    }
+                    KeyCode::Esc => {
+                        manager.go_back();
+                    }
+                    _ => {}
+                }
+            }
+            EnvManagerState::Confirming { .. } | EnvManagerState::ConfirmingUnset { .. } => {
+                match key.code {
+                    KeyCode::Char('y') | KeyCode::Char('Y') => {
+                        manager.advance();
+                    }
+                    KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                        manager.go_back();
+                    }
+                    _ => {}
+                }
+            }
+            EnvManagerState::Success { .. } | EnvManagerState::Error { .. } => {
+                // Any key returns to the variable list
+                if matches!(key.code, KeyCode::Enter | KeyCode::Esc) {
+                    manager.go_back();
+                }
+            }
+        }
+
+        Ok(return_to_menu)
+    }
+
+    /// Handles keyboard input in PATH manager (static method to avoid borrow issues)
+    /// Returns true if should return to main menu
+    fn handle_path_manager_key_static(key: KeyEvent, manager: &mut PathManager) -> Result<bool> {
+        let mut return_to_menu = false;
+
+        match &manager.state {
+            PathManagerState::Editing { .. } => {
+                let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+                match key.code {
+                    KeyCode::Up if shift => {
+                        manager.move_up();
+                    }
+                    KeyCode::Down if shift => {
+                        manager.move_down();
+                    }
+                    KeyCode::Char('K') => {
+                        manager.move_up();
+                    }
+                    KeyCode::Char('J') => {
+                        manager.move_down();
+                    }
+                    KeyCode::Up | KeyCode::Char('k') => {
+                        manager.previous();
+                    }
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        manager.next();
+                    }
+                    KeyCode::Char('a') | KeyCode::Char('A') => {
+                        manager.start_add();
+                    }
+                    KeyCode::Char('d') | KeyCode::Char('D') | KeyCode::Delete => {
+                        manager.remove_selected();
+                    }
+                    KeyCode::Char('c') | KeyCode::Char('C') => {
+                        manager.clean_up();
+                    }
+                    KeyCode::Char('s') | KeyCode::Char('S') => {
+                        manager.start_save();
+                    }
+                    KeyCode::Esc => {
+                        return_to_menu = manager.request_exit();
+                    }
+                    _ => {}
+                }
+            }
+            PathManagerState::Adding { .. } => {
+                match key.code {
+                    KeyCode::Char(c) => {
+                        manager.handle_char(c);
+                    }
+                    KeyCode::Backspace => {
+                        manager.handle_backspace();
+                    }
+                    KeyCode::Enter => {
+                        manager.advance();
+                    }
+                    KeyCode::Esc => {
+                        manager.go_back();
+                    }
+                    _ => {}
+                }
+            }
+            PathManagerState::ConfirmingSave { .. } => {
+                match key.code {
+                    KeyCode::Char('y') | KeyCode::Char('Y') => {
+                        manager.advance();
+                    }
+                    KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                        manager.go_back();
+                    }
+                    _ => {}
+                }
+            }
+            PathManagerState::ConfirmingDiscard { .. } => {
+                match key.code {
+                    KeyCode::Char('y') | KeyCode::Char('Y') => {
+                        return_to_menu = true;
+                    }
+                    KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                        manager.go_back();
+                    }
+                    _ => {}
+                }
+            }
+            PathManagerState::Success { .. } | PathManagerState::Error { .. } => {
+                // Any key returns to the PATH list
+                if matches!(key.code, KeyCode::Enter | KeyCode::Esc) {
+                    manager.go_back();
+                }
+            }
+        }
+
+        Ok(return_to_menu)
+    }
+
+    /// Handles keyboard input in SSH manager (static method to avoid borrow issues)
+    /// Returns (should_return_to_menu, should_exit_app) tuple
+    fn handle_ssh_manager_key_static(key: KeyEvent, manager: &mut SshManager) -> Result<(bool, bool)> {
+        let mut return_to_menu = false;
+        let mut exit_app = false;
+
+        match &manager.state {
+            SshManagerState::Listing { .. } => {
+                match key.code {
+                    KeyCode::Up | KeyCode::Char('k') => {
+                        manager.previous();
+                    }
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        manager.next();
+                    }
+                    KeyCode::Enter | KeyCode::Char('c') | KeyCode::Char('C') => {
+                        exit_app = manager.connect();
+                    }
+                    KeyCode::Char('a') | KeyCode::Char('A') => {
+                        manager.start_add();
+                    }
+                    KeyCode::Char('e') | KeyCode::Char('E') => {
+                        manager.start_edit();
+                    }
+                    KeyCode::Char('d') | KeyCode::Char('D') | KeyCode::Delete => {
+                        manager.start_delete();
+                    }
+                    KeyCode::Esc => {
+                        return_to_menu = true;
+                    }
+                    _ => {}
+                }
+            }
+            SshManagerState::Editing { .. } => {
+                match key.code {
+                    KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        manager.submit_form();
+                    }
+                    KeyCode::Tab | KeyCode::Down => {
+                        manager.next_field();
+                    }
+                    KeyCode::BackTab | KeyCode::Up => {
+                        manager.previous_field();
+                    }
+                    KeyCode::Char(c) => {
+                        manager.handle_char(c);
+                    }
+                    KeyCode::Backspace => {
+                        manager.handle_backspace();
+                    }
+                    KeyCode::Enter => {
+                        manager.advance();
+                    }
+                    KeyCode::Esc => {
+                        manager.go_back();
+                    }
+                    _ => {}
+                }
+            }
+            SshManagerState::Confirming { .. } | SshManagerState::ConfirmingDelete { .. } => {
+                match key.code {
+                    KeyCode::Char('y') | KeyCode::Char('Y') => {
+                        manager.advance();
+                    }
+                    KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                        manager.go_back();
+                    }
+                    _ => {}
+                }
+            }
+            SshManagerState::Success { .. } | SshManagerState::Error { .. } => {
+                // Any key returns to the host list
+                if matches!(key.code, KeyCode::Enter | KeyCode::Esc) {
+                    manager.go_back();
+                }
+            }
+        }
+
+        Ok((return_to_menu, exit_app))
+    }
+
+    /// Handles keyboard input in git shortcuts (static method to avoid borrow issues)
+    /// Returns true if should return to main menu
+    fn handle_git_shortcuts_key_static(key: KeyEvent, shortcuts: &mut GitShortcuts) -> Result<bool> {
+        let mut return_to_menu = false;
+
+        match &shortcuts.state {
+            GitShortcutsState::Listing { .. } => {
+                match key.code {
+                    KeyCode::Up | KeyCode::Char('k') => {
+                        shortcuts.previous();
+                    }
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        shortcuts.next();
+                    }
+                    KeyCode::Char('a') | KeyCode::Char('A') => {
+                        shortcuts.start_add();
+                    }
+                    KeyCode::Enter | KeyCode::Char('e') | KeyCode::Char('E') => {
+                        shortcuts.start_edit();
+                    }
+                    KeyCode::Char('d') | KeyCode::Char('D') | KeyCode::Delete => {
+                        shortcuts.start_delete();
+                    }
+                    KeyCode::Char('t') | KeyCode::Char('T') => {
+                        shortcuts.show_templates();
+                    }
+                    KeyCode::Char('g') | KeyCode::Char('G') => {
+                        shortcuts.toggle_scope();
+                    }
+                    KeyCode::Esc => {
+                        return_to_menu = true;
+                    }
+                    _ => {}
+                }
+            }
+            GitShortcutsState::Templates { .. } => {
+                match key.code {
+                    KeyCode::Up | KeyCode::Char('k') => {
+                        shortcuts.previous();
+                    }
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        shortcuts.next();
+                    }
+                    KeyCode::Char(' ') => {
+                        shortcuts.toggle_template();
+                    }
+                    KeyCode::Enter => {
+                        shortcuts.start_install_templates();
+                    }
+                    KeyCode::Esc => {
+                        shortcuts.go_back();
+                    }
+                    _ => {}
+                }
+            }
+            GitShortcutsState::EnteringName { .. }
+            | GitShortcutsState::EnteringCommand { .. }
+            | GitShortcutsState::EditingCommand { .. } => {
+                match key.code {
+                    KeyCode::Char(c) => {
+                        shortcuts.handle_char(c);
+                    }
+                    KeyCode::Backspace => {
+                        shortcuts.handle_backspace();
+                    }
+                    KeyCode::Enter => {
+                        shortcuts.advance();
+                    }
+                    KeyCode::Esc => {
+                        shortcuts.go_back();
+                    }
+                    _ => {}
+                }
+            }
+            GitShortcutsState::ConfirmingDelete { .. } | GitShortcutsState::ConfirmingTemplates { .. } => {
+                match key.code {
+                    KeyCode::Char('y') | KeyCode::Char('Y') => {
+                        shortcuts.advance();
+                    }
+                    KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                        shortcuts.go_back();
+                    }
+                    _ => {}
+                }
+            }
+            GitShortcutsState::Success { .. } | GitShortcutsState::Error { .. } => {
+                // Any key returns to the alias list
+                if matches!(key.code, KeyCode::Enter | KeyCode::Esc) {
+                    shortcuts.go_back();
+                }
+            }
+        }
+
+        Ok(return_to_menu)
+    }
+
+    /// Handles keyboard input in usage viewer (static method to avoid borrow issues)
+    /// Returns true if should return to main menu
+    fn handle_usage_viewer_key_static(key: KeyEvent, viewer: &mut UsageViewer) -> Result<bool> {
+        let mut return_to_menu = false;
+
+        match &viewer.state {
+            UsageViewerState::Ready => {
+                // This shouldn't happen as we open immediately, but handle it
+                if matches!(key.code, KeyCode::Enter) {
+                    viewer.open_browser()?;
+                } else if matches!(key.code, KeyCode::Esc) {
+                    return_to_menu = true;
+                }
+            }
+            UsageViewerState::Opening => {
+                // Just wait
+            }
+            UsageViewerState::Success { .. } | UsageViewerState::Error { .. } => {
+                // Any key returns to main menu
+                if matches!(key.code, KeyCode::Enter | KeyCode::Esc) {
+                    return_to_menu = true;
+                }
+            }
+        }
+
+        Ok(return_to_menu)
+    }
+
+    /// Handles keyboard input in scanner (static method to avoid borrow issues)
+    /// Returns true if should return to main menu
+    fn handle_scanner_key_static(key: KeyEvent, scanner: &mut Scanner) -> Result<bool> {
+        let mut return_to_menu = false;
+
+        match &scanner.state {
+            ScannerState::SelectingScanType { .. } => {
+                match key.code {
+                    KeyCode::Up | KeyCode::Char('k') => {
+                        scanner.previous();
+                    }
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        scanner.next();
+                    }
+                    KeyCode::Enter => {
+                        scanner.confirm_scan_type();
+                    }
+                    KeyCode::Esc => {
+                        return_to_menu = true;
+                    }
+                    _ => {}
+                }
+            }
+            ScannerState::EnteringTarget { .. } => {
+                match key.code {
+                    KeyCode::Char(c) => {
+                        scanner.handle_char(c);
+                    }
+                    KeyCode::Backspace => {
+                        scanner.handle_backspace();
+                    }
+                    KeyCode::Enter => {
+                        scanner.advance_to_options();
+                    }
+                    KeyCode::Esc => {
+                        scanner.go_back();
+                    }
+                    _ => {}
+                }
+            }
+            ScannerState::EnteringPortRange { .. } => {
+                match key.code {
+                    KeyCode::Char(c) => {
+                        scanner.handle_port_range_char(c);
+                    }
+                    KeyCode::Backspace => {
+                        scanner.handle_port_range_backspace();
+                    }
+                    KeyCode::Enter => {
+                        scanner.advance_from_port_range();
+                    }
+                    KeyCode::Esc => {
+                        scanner.go_back();
+                    }
+                    _ => {}
+                }
+            }
+            ScannerState::EnteringOutputDir { .. } => {
+                match key.code {
+                    KeyCode::Char(c) => {
+                        scanner.handle_char(c);
+                    }
+                    KeyCode::Backspace => {
+                        scanner.handle_backspace();
+                    }
+                    KeyCode::Enter => {
+                        scanner.advance_from_output_dir();
+                    }
+                    KeyCode::Esc => {
+                        scanner.go_back();
+                    }
+                    _ => {}
+                }
+            }
+            ScannerState::SelectingOptions { .. } => {
+                match key.code {
+                    KeyCode::Up | KeyCode::Char('k') => {
+                        scanner.previous();
+                    }
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        scanner.next();
                     }
                     KeyCode::Char(' ') => {
                         scanner.toggle_option();
@@ -464,7 +1368,9 @@ impl App {
                 }
             }
             ScannerState::Scanning { .. } => {
-                // Can't interrupt scanning
+                if matches!(key.code, KeyCode::Esc) {
+                    scanner.cancel_scan();
+                }
             }
             ScannerState::ViewingResults { .. } => {
                 match key.code {
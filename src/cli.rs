@@ -0,0 +1,849 @@
+use crate::{
+    features::{
+        process_manager::parse_port_spec, scanner::count_open_ports, bookmark_store, frecency, AliasManager, BookmarkStore, EnvManager,
+        ExportFormat, ExportSettings, FrecencyDb, ModeConfig, ProcessAction, ProcessConfig, ScanConfig, Scanner, ScannerSettings,
+        ScanType,
+    },
+    utils::{shell, ModeError, Result, ShellType},
+};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Usage text printed by `mode --help`
+pub const USAGE: &str = "\
+Usage: mode [COMMAND]
+
+Run without a command to open the interactive menu.
+
+Commands:
+  alias list                      List aliases defined in your RC file
+  alias add <NAME> <COMMAND>...   Create a new alias
+  alias edit <NAME> <COMMAND>...  Replace the command of an existing alias
+  alias rename <OLD> <NEW>        Rename an existing alias
+  alias rm <NAME>                 Delete an alias
+  bookmark [add] [NAME]           Bookmark the current directory (name defaults
+                                  to the directory name)
+  bookmark list                   List bookmarks
+  bookmark go <NAME>              cd to a bookmark (through the shell wrapper)
+  bookmark rename <OLD> <NEW>     Rename a bookmark
+  bookmark rm <NAME>              Delete a bookmark
+  env list                        List variables exported from your RC file
+  env set <NAME> <VALUE>          Add or update an export in your RC file
+  env unset <NAME>                Remove an export from your RC file
+  jump <QUERY>...                 cd to the best-ranked visited directory matching
+                                  the query (through the shell wrapper)
+  visit <DIR>                     Record a directory visit (run by the shell wrapper)
+  kill <PRESET>                   Kill processes (cursor, unneeded, dev-servers, or
+                                  a preset from ~/.config/mode/config.toml)
+  kill --port <PORTS>             Kill whatever listens on ports, e.g. 3000,8000-8010
+  scan <TARGETS> [OPTIONS]        Scan ports on hosts, e.g. 10.0.0.1-20,example.com
+
+Scan options:
+  --type <TYPE>     Port set to scan: quick, standard, full (default: quick)
+  --ports <SPEC>    Custom ports, e.g. 80,443 or 1-1000
+  --services        Detect services on open ports
+  --all-addresses   Scan every address a hostname resolves to
+  --concurrency <N> Number of parallel workers (default: 100, or
+                    [scanner].concurrency)
+  --timeout <MS>    Connect timeout per port in milliseconds (default: 500, or
+                    [scanner].timeout_ms)
+  --json            Print results (with scan metadata) as JSON
+  --save            Save results to a file
+  --format <FMT>    Saved file format: text, json, csv, xml (default: text)
+  --output <DIR>    Directory to save results in (default: current directory)
+
+Scan defaults come from the [scanner] table of ~/.config/mode/config.toml;
+--services and --all-addresses switch on what it leaves off.
+
+Options:
+  -h, --help        Print this help
+  -V, --version     Print version";
+
+/// A command parsed from the command line
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    /// Launch the interactive TUI
+    Tui,
+    /// Print usage
+    Help,
+    /// Print version
+    Version,
+    /// List aliases
+    AliasList,
+    /// Create an alias
+    AliasAdd { name: String, command: String },
+    /// Replace the command of an alias
+    AliasEdit { name: String, command: String },
+    /// Rename an alias
+    AliasRename { old_name: String, new_name: String },
+    /// Delete an alias
+    AliasRemove { name: String },
+    /// Bookmark the current directory
+    BookmarkAdd { name: Option<String> },
+    /// List bookmarks
+    BookmarkList,
+    /// cd to a bookmark
+    BookmarkGo { name: String },
+    /// Rename a bookmark
+    BookmarkRename { old_name: String, new_name: String },
+    /// Delete a bookmark
+    BookmarkRemove { name: String },
+    /// List RC exports
+    EnvList,
+    /// Add or update an RC export
+    EnvSet { name: String, value: String },
+    /// Remove an RC export
+    EnvUnset { name: String },
+    /// cd to the best frecency match
+    Jump { query: String },
+    /// Record a directory visit
+    Visit { dir: PathBuf },
+    /// Run a process cleanup preset (built-in or from config.toml)
+    Kill { preset: String },
+    /// Stop whatever listens on the given ports
+    KillPort { ports: Vec<u16> },
+    /// Scan ports on a target
+    Scan {
+        target: String,
+        ports: Vec<u16>,
+        json: bool,
+        /// Where to save results, if requested
+        export: Option<ExportSettings>,
+        /// Options given on the command line, applied over `[scanner]`
+        flags: ScanFlags,
+    },
+}
+
+/// Scan options given as flags, which override the `[scanner]` table of `config.toml`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ScanFlags {
+    pub concurrency: Option<usize>,
+    pub timeout: Option<Duration>,
+    /// `--all-addresses` was given
+    pub all_addresses: bool,
+    /// `--services` was given
+    pub service_detection: bool,
+}
+
+impl ScanFlags {
+    /// Returns the scan config and whether to detect services
+    pub fn apply(&self, defaults: &ScannerSettings) -> (ScanConfig, bool) {
+        let mut config = defaults.scan;
+        if let Some(concurrency) = self.concurrency {
+            config.concurrency = concurrency;
+        }
+        if let Some(timeout) = self.timeout {
+            config.timeout = timeout;
+        }
+        config.all_addresses |= self.all_addresses;
+        (config, defaults.service_detection || self.service_detection)
+    }
+}
+
+/// Result of running a CLI command
+#[derive(Debug, Default)]
+pub struct Outcome {
+    /// Text to print on stdout
+    pub output: String,
+    /// Command for the shell wrapper to run after mode exits
+    pub exit_command: Option<String>,
+}
+
+/// Parses command line arguments (without the program name)
+pub fn parse_args<I>(args: I) -> Result<Command>
+where
+    I: IntoIterator<Item = String>,
+{
+    let args: Vec<String> = args.into_iter().collect();
+    let mut rest = args.iter().map(String::as_str);
+
+    let command = match rest.next() {
+        None => return Ok(Command::Tui),
+        Some(command) => command,
+    };
+
+    match command {
+        "-h" | "--help" | "help" => Ok(Command::Help),
+        "-V" | "--version" => Ok(Command::Version),
+        "alias" => parse_alias_args(rest.collect()),
+        "bookmark" => parse_bookmark_args(rest.collect()),
+        "env" => parse_env_args(rest.collect()),
+        "jump" => {
+            let terms: Vec<&str> = rest.collect();
+            if terms.is_empty() {
+                return Err(ModeError::InvalidInput("jump requires a query".to_string()));
+            }
+            Ok(Command::Jump {
+                query: terms.join(" "),
+            })
+        }
+        "visit" => {
+            let mut rest: Vec<&str> = rest.collect();
+            if rest.is_empty() {
+                return Err(ModeError::InvalidInput("visit requires a directory".to_string()));
+            }
+            let dir = PathBuf::from(rest.remove(0));
+            expect_no_more(rest)?;
+            Ok(Command::Visit { dir })
+        }
+        "kill" => {
+            let mut rest: Vec<&str> = rest.collect();
+            if rest.is_empty() {
+                return Err(ModeError::InvalidInput("kill requires a preset name".to_string()));
+            }
+            let preset = rest.remove(0);
+            if preset == "--port" || preset == "-p" {
+                if rest.is_empty() {
+                    return Err(ModeError::InvalidInput("--port requires a value".to_string()));
+                }
+                let ports = parse_port_spec(rest.remove(0))?;
+                expect_no_more(rest)?;
+                return Ok(Command::KillPort { ports });
+            }
+            expect_no_more(rest)?;
+            Ok(Command::Kill {
+                preset: preset.to_string(),
+            })
+        }
+        "scan" => parse_scan_args(rest.collect()),
+        other => Err(ModeError::InvalidInput(format!("Unknown command '{}'", other))),
+    }
+}
+
+/// Parses `mode alias ...` arguments
+fn parse_alias_args(args: Vec<&str>) -> Result<Command> {
+    let (subcommand, rest) = args
+        .split_first()
+        .ok_or_else(|| ModeError::InvalidInput("alias requires a subcommand".to_string()))?;
+
+    match (*subcommand, rest) {
+        ("list" | "ls", []) => Ok(Command::AliasList),
+        ("add", [name, command @ ..]) if !command.is_empty() => Ok(Command::AliasAdd {
+            name: name.to_string(),
+            command: command.join(" "),
+        }),
+        ("edit", [name, command @ ..]) if !command.is_empty() => Ok(Command::AliasEdit {
+            name: name.to_string(),
+            command: command.join(" "),
+        }),
+        ("rename" | "mv", [old_name, new_name]) => Ok(Command::AliasRename {
+            old_name: old_name.to_string(),
+            new_name: new_name.to_string(),
+        }),
+        ("rm" | "remove", [name]) => Ok(Command::AliasRemove {
+            name: name.to_string(),
+        }),
+        ("list" | "ls" | "add" | "edit" | "rename" | "mv" | "rm" | "remove", _) => Err(
+            ModeError::InvalidInput(format!("Wrong number of arguments for 'alias {}'", subcommand)),
+        ),
+        (other, _) => Err(ModeError::InvalidInput(format!(
+            "Unknown alias subcommand '{}'",
+            other
+        ))),
+    }
+}
+
+/// Parses `mode bookmark ...` arguments
+fn parse_bookmark_args(args: Vec<&str>) -> Result<Command> {
+    let Some((subcommand, rest)) = args.split_first() else {
+        return Ok(Command::BookmarkAdd { name: None });
+    };
+
+    match (*subcommand, rest) {
+        ("add", []) => Ok(Command::BookmarkAdd { name: None }),
+        ("add", [name]) => Ok(Command::BookmarkAdd {
+            name: Some(name.to_string()),
+        }),
+        ("list" | "ls", []) => Ok(Command::BookmarkList),
+        ("go", [name]) => Ok(Command::BookmarkGo {
+            name: name.to_string(),
+        }),
+        ("rename" | "mv", [old_name, new_name]) => Ok(Command::BookmarkRename {
+            old_name: old_name.to_string(),
+            new_name: new_name.to_string(),
+        }),
+        ("rm" | "remove", [name]) => Ok(Command::BookmarkRemove {
+            name: name.to_string(),
+        }),
+        ("add" | "list" | "ls" | "go" | "rename" | "mv" | "rm" | "remove", _) => Err(
+            ModeError::InvalidInput(format!("Wrong number of arguments for 'bookmark {}'", subcommand)),
+        ),
+        (other, _) => Err(ModeError::InvalidInput(format!(
+            "Unknown bookmark subcommand '{}'",
+            other
+        ))),
+    }
+}
+
+/// Parses `mode env ...` arguments
+fn parse_env_args(args: Vec<&str>) -> Result<Command> {
+    let (subcommand, rest) = args
+        .split_first()
+        .ok_or_else(|| ModeError::InvalidInput("env requires a subcommand".to_string()))?;
+
+    match (*subcommand, rest) {
+        ("list" | "ls", []) => Ok(Command::EnvList),
+        ("set", [name, value]) => Ok(Command::EnvSet {
+            name: name.to_string(),
+            value: value.to_string(),
+        }),
+        ("unset" | "rm", [name]) => Ok(Command::EnvUnset {
+            name: name.to_string(),
+        }),
+        ("list" | "ls" | "set" | "unset" | "rm", _) => Err(ModeError::InvalidInput(format!(
+            "Wrong number of arguments for 'env {}'",
+            subcommand
+        ))),
+        (other, _) => Err(ModeError::InvalidInput(format!("Unknown env subcommand '{}'", other))),
+    }
+}
+
+/// Parses `mode scan ...` arguments
+fn parse_scan_args(args: Vec<&str>) -> Result<Command> {
+    let mut target = None;
+    let mut scan_type = ScanType::QuickScan;
+    let mut custom_ports = None;
+    let mut json = false;
+    let mut save = false;
+    let mut format = None;
+    let mut output = None;
+    let mut flags = ScanFlags::default();
+
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg {
+            "--type" | "-t" => {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| ModeError::InvalidInput("--type requires a value".to_string()))?;
+                scan_type = ScanType::from_slug(value)
+                    .filter(|t| *t != ScanType::CustomRange)
+                    .ok_or_else(|| ModeError::InvalidInput(format!("Unknown scan type '{}'", value)))?;
+            }
+            "--ports" | "-p" => {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| ModeError::InvalidInput("--ports requires a value".to_string()))?;
+                custom_ports = Some(Scanner::parse_port_range(value)?);
+            }
+            "--concurrency" | "-c" => {
+                flags.concurrency = Some(parse_number(iter.next(), "--concurrency")?);
+            }
+            "--timeout" => {
+                flags.timeout = Some(Duration::from_millis(parse_number(iter.next(), "--timeout")?));
+            }
+            "--services" | "-s" => flags.service_detection = true,
+            "--all-addresses" | "-a" => flags.all_addresses = true,
+            "--json" => json = true,
+            "--save" => save = true,
+            "--format" | "-f" => {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| ModeError::InvalidInput("--format requires a value".to_string()))?;
+                format = Some(
+                    ExportFormat::from_slug(value)
+                        .ok_or_else(|| ModeError::InvalidInput(format!("Unknown export format '{}'", value)))?,
+                );
+            }
+            "--output" | "-o" => {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| ModeError::InvalidInput("--output requires a value".to_string()))?;
+                output = Some(crate::utils::expand_home(value));
+            }
+            flag if flag.starts_with('-') => {
+                return Err(ModeError::InvalidInput(format!("Unknown scan option '{}'", flag)));
+            }
+            value if target.is_none() => target = Some(value.to_string()),
+            value => {
+                return Err(ModeError::InvalidInput(format!("Unexpected argument '{}'", value)));
+            }
+        }
+    }
+
+    let target = target.ok_or_else(|| ModeError::InvalidInput("scan requires a target".to_string()))?;
+    Scanner::validate_target(&target)?;
+
+    // --format and --output only make sense when saving, so they imply --save
+    let export = (save || format.is_some() || output.is_some()).then(|| ExportSettings {
+        format: format.unwrap_or_default(),
+        directory: output.unwrap_or_else(|| PathBuf::from(".")),
+    });
+
+    Ok(Command::Scan {
+        target,
+        ports: custom_ports.unwrap_or_else(|| scan_type.get_ports()),
+        json,
+        export,
+        flags,
+    })
+}
+
+/// Parses a positive integer option value
+fn parse_number<T>(value: Option<&str>, flag: &str) -> Result<T>
+where
+    T: std::str::FromStr + Default + PartialEq,
+{
+    let value = value.ok_or_else(|| ModeError::InvalidInput(format!("{} requires a value", flag)))?;
+    match value.parse::<T>() {
+        Ok(number) if number != T::default() => Ok(number),
+        _ => Err(ModeError::InvalidInput(format!(
+            "{} must be a positive number, got '{}'",
+            flag, value
+        ))),
+    }
+}
+
+/// Rejects trailing arguments
+fn expect_no_more(rest: Vec<&str>) -> Result<()> {
+    match rest.first() {
+        Some(arg) => Err(ModeError::InvalidInput(format!("Unexpected argument '{}'", arg))),
+        None => Ok(()),
+    }
+}
+
+/// Runs a parsed command headlessly
+pub fn run(command: Command) -> Result<Outcome> {
+    match command {
+        Command::Tui => Ok(Outcome::default()),
+        Command::Help => Ok(Outcome {
+            output: USAGE.to_string(),
+            exit_command: None,
+        }),
+        Command::Version => Ok(Outcome {
+            output: format!("mode {}", env!("CARGO_PKG_VERSION")),
+            exit_command: None,
+        }),
+        Command::AliasList => {
+            let mut manager = AliasManager::new();
+            manager.initialize()?;
+
+            let output = manager
+                .aliases()
+                .iter()
+                .map(|alias| format!("{}\t{}", alias.name, alias.command))
+                .collect::<Vec<_>>()
+                .join("\n");
+            Ok(Outcome {
+                output,
+                exit_command: None,
+            })
+        }
+        Command::AliasAdd { name, command } => {
+            let mut manager = AliasManager::new();
+            manager.initialize()?;
+            let backup_path = manager.add_alias(&name, &command)?;
+
+            Ok(Outcome {
+                output: format!("✓ Alias '{}' created (backup: {})", name, backup_path.display()),
+                exit_command: manager.get_exit_command(),
+            })
+        }
+        Command::AliasEdit { name, command } => {
+            let mut manager = AliasManager::new();
+            manager.initialize()?;
+            let backup_path = manager.update_command(&name, &command)?;
+
+            Ok(Outcome {
+                output: format!("✓ Alias '{}' updated (backup: {})", name, backup_path.display()),
+                exit_command: manager.get_exit_command(),
+            })
+        }
+        Command::AliasRename { old_name, new_name } => {
+            let mut manager = AliasManager::new();
+            manager.initialize()?;
+            let backup_path = manager.rename_alias(&old_name, &new_name)?;
+
+            Ok(Outcome {
+                output: format!(
+                    "✓ Alias '{}' renamed to '{}' (backup: {})",
+                    old_name,
+                    new_name,
+                    backup_path.display()
+                ),
+                exit_command: manager.get_exit_command(),
+            })
+        }
+        Command::AliasRemove { name } => {
+            let mut manager = AliasManager::new();
+            manager.initialize()?;
+            let backup_path = manager.delete_alias(&name)?;
+
+            Ok(Outcome {
+                output: format!("✓ Alias '{}' deleted (backup: {})", name, backup_path.display()),
+                exit_command: manager.get_exit_command(),
+            })
+        }
+        Command::BookmarkAdd { name } => {
+            let mut store = BookmarkStore::load()?;
+            let directory = std::env::current_dir()?;
+            let name = name.unwrap_or_else(|| bookmark_store::default_name(&directory));
+            store.add(&name, &directory)?;
+
+            Ok(Outcome {
+                output: format!("✓ Bookmarked {} as '{}'", directory.display(), name),
+                exit_command: None,
+            })
+        }
+        Command::BookmarkList => {
+            let store = BookmarkStore::load()?;
+            let output = store
+                .bookmarks()
+                .iter()
+                .map(|bookmark| format!("{}\t{}", bookmark.name, bookmark.path.display()))
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            Ok(Outcome {
+                output,
+                exit_command: None,
+            })
+        }
+        Command::BookmarkGo { name } => {
+            let store = BookmarkStore::load()?;
+            let bookmark = store
+                .get(&name)
+                .ok_or_else(|| ModeError::InvalidInput(format!("No bookmark named '{}'", name)))?;
+            if !bookmark.exists() {
+                return Err(ModeError::FileOperation(format!(
+                    "Directory for '{}' no longer exists: {}",
+                    name,
+                    bookmark.path.display()
+                )));
+            }
+
+            let shell = shell::detect_shell().unwrap_or(ShellType::Bash);
+            Ok(Outcome {
+                output: String::new(),
+                exit_command: Some(bookmark.cd_command(shell)),
+            })
+        }
+        Command::BookmarkRename { old_name, new_name } => {
+            let mut store = BookmarkStore::load()?;
+            store.rename(&old_name, &new_name)?;
+
+            Ok(Outcome {
+                output: format!("✓ Bookmark '{}' renamed to '{}'", old_name, new_name),
+                exit_command: None,
+            })
+        }
+        Command::BookmarkRemove { name } => {
+            let mut store = BookmarkStore::load()?;
+            store.remove(&name)?;
+
+            Ok(Outcome {
+                output: format!("✓ Bookmark '{}' deleted", name),
+                exit_command: None,
+            })
+        }
+        Command::EnvList => {
+            let mut manager = EnvManager::new();
+            manager.initialize()?;
+
+            let output = manager
+                .visible_vars()
+                .iter()
+                .filter_map(|var| var.rc_value.as_ref().map(|value| format!("{}\t{}", var.name, value)))
+                .collect::<Vec<_>>()
+                .join("\n");
+            Ok(Outcome {
+                output,
+                exit_command: None,
+            })
+        }
+        Command::EnvSet { name, value } => {
+            let mut manager = EnvManager::new();
+            manager.initialize()?;
+            let backup_path = manager.set_var(&name, &value)?;
+
+            Ok(Outcome {
+                output: format!("✓ {} saved (backup: {})", name, backup_path.display()),
+                exit_command: manager.get_exit_command(),
+            })
+        }
+        Command::EnvUnset { name } => {
+            let mut manager = EnvManager::new();
+            manager.initialize()?;
+            let backup_path = manager.unset_var(&name)?;
+
+            Ok(Outcome {
+                output: format!("✓ {} removed (backup: {})", name, backup_path.display()),
+                exit_command: manager.get_exit_command(),
+            })
+        }
+        Command::Jump { query } => {
+            let history = FrecencyDb::load()?;
+            let current_dir = std::env::current_dir().ok();
+            let best = history
+                .ranked(&query, frecency::now())
+                .into_iter()
+                .find(|visit| Some(&visit.path) != current_dir.as_ref())
+                .ok_or_else(|| ModeError::Generic(format!("No visited directory matches '{}'", query)))?;
+
+            let shell = shell::detect_shell().unwrap_or(ShellType::Bash);
+            Ok(Outcome {
+                output: best.path.display().to_string(),
+                exit_command: Some(shell::cd_command(shell, &best.path)),
+            })
+        }
+        Command::Visit { dir } => {
+            if !dir.is_absolute() || !dir.is_dir() {
+                return Err(ModeError::InvalidInput(format!(
+                    "'{}' is not an absolute path to a directory",
+                    dir.display()
+                )));
+            }
+
+            // Home is one keystroke away already, so it would only crowd the list
+            let home = std::env::var_os("HOME").map(PathBuf::from);
+            if home.as_ref() != Some(&dir) {
+                let mut history = FrecencyDb::load()?;
+                history.record(&dir, frecency::now());
+                history.save()?;
+            }
+
+            Ok(Outcome::default())
+        }
+        Command::Kill { preset } => {
+            let presets = ProcessConfig::load()?.presets;
+            let action = ProcessAction::from_slug(&presets, &preset).ok_or_else(|| {
+                let slugs: Vec<&str> = presets.iter().map(|a| a.slug.as_str()).collect();
+                ModeError::InvalidInput(format!(
+                    "Unknown preset '{}' (expected one of: {})",
+                    preset,
+                    slugs.join(", ")
+                ))
+            })?;
+
+            Ok(Outcome {
+                output: action.execute()?,
+                exit_command: None,
+            })
+        }
+        Command::KillPort { ports } => {
+            let config = ProcessConfig::load()?;
+            let action = ProcessAction::for_ports(&ports, config.grace_period);
+
+            Ok(Outcome {
+                output: action.execute()?,
+                exit_command: None,
+            })
+        }
+        Command::Scan {
+            target,
+            ports,
+            json,
+            export,
+            flags,
+        } => {
+            let (config, service_detection) = flags.apply(&ModeConfig::load()?.scanner);
+            let mut scanner = Scanner::new();
+            scanner.set_config(config);
+            let hosts = scanner.scan(&target, &ports, service_detection)?;
+            let probes = hosts.len() * ports.len();
+            let report = scanner.report(&target, ports, probes, service_detection, false, hosts.clone());
+
+            let mut lines = Vec::new();
+            if json {
+                lines.push(report.render(ExportFormat::Json)?);
+            } else if count_open_ports(&hosts) == 0 {
+                lines.push(format!(
+                    "No open ports found on {} ({} hosts, {} probes)",
+                    target,
+                    hosts.len(),
+                    probes
+                ));
+            } else {
+                for result in hosts.iter().filter(|h| !h.open_ports.is_empty()) {
+                    // Only label sections when there is more than one host to tell apart
+                    if hosts.len() > 1 {
+                        if !lines.is_empty() {
+                            lines.push(String::new());
+                        }
+                        lines.push(format!("Host: {}", result.host.label()));
+                    }
+                    lines.push("PORT     STATE    SERVICE".to_string());
+                    for port_info in &result.open_ports {
+                        let service = port_info.describe();
+                        lines.push(format!("{:<8} {:<8} {}", port_info.port, "open", service));
+                    }
+                }
+            }
+
+            if let Some(export) = export {
+                let path = report.save(&export)?;
+                // Keep stdout machine-readable when printing JSON
+                eprintln!("Results saved to {}", path.display());
+            }
+
+            Ok(Outcome {
+                output: lines.join("\n"),
+                exit_command: None,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn parse(args: &[&str]) -> Result<Command> {
+        parse_args(args.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn test_parse_no_args_launches_tui() {
+        assert_eq!(parse(&[]).unwrap(), Command::Tui);
+    }
+
+    #[test]
+    fn test_parse_alias_commands() {
+        assert_eq!(
+            parse(&["alias", "add", "ll", "ls", "-la"]).unwrap(),
+            Command::AliasAdd {
+                name: "ll".to_string(),
+                command: "ls -la".to_string()
+            }
+        );
+        assert_eq!(
+            parse(&["alias", "rm", "ll"]).unwrap(),
+            Command::AliasRemove {
+                name: "ll".to_string()
+            }
+        );
+        assert_eq!(parse(&["alias", "list"]).unwrap(), Command::AliasList);
+        assert!(parse(&["alias", "add", "ll"]).is_err());
+        assert!(parse(&["alias", "frobnicate"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_bookmark() {
+        assert_eq!(parse(&["bookmark"]).unwrap(), Command::BookmarkAdd { name: None });
+        assert_eq!(
+            parse(&["bookmark", "add", "api"]).unwrap(),
+            Command::BookmarkAdd {
+                name: Some("api".to_string())
+            }
+        );
+        assert_eq!(
+            parse(&["bookmark", "go", "api"]).unwrap(),
+            Command::BookmarkGo {
+                name: "api".to_string()
+            }
+        );
+        assert!(matches!(parse(&["bookmark", "go"]), Err(ModeError::InvalidInput(_))));
+        assert!(matches!(parse(&["bookmark", "jump", "api"]), Err(ModeError::InvalidInput(_))));
+        assert_eq!(
+            parse(&["jump", "src", "api"]).unwrap(),
+            Command::Jump {
+                query: "src api".to_string()
+            }
+        );
+        assert!(matches!(parse(&["jump"]), Err(ModeError::InvalidInput(_))));
+        assert!(matches!(parse(&["visit", "/a", "/b"]), Err(ModeError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_parse_env() {
+        assert_eq!(parse(&["env", "ls"]).unwrap(), Command::EnvList);
+        assert_eq!(
+            parse(&["env", "set", "EDITOR", "nvim"]).unwrap(),
+            Command::EnvSet {
+                name: "EDITOR".to_string(),
+                value: "nvim".to_string()
+            }
+        );
+        assert!(matches!(parse(&["env", "set", "EDITOR"]), Err(ModeError::InvalidInput(_))));
+        assert!(matches!(parse(&["env"]), Err(ModeError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_parse_kill() {
+        assert_eq!(
+            parse(&["kill", "dev-servers"]).unwrap(),
+            Command::Kill {
+                preset: "dev-servers".to_string()
+            }
+        );
+        assert_eq!(
+            parse(&["kill", "--port", ":3000,8000-8001"]).unwrap(),
+            Command::KillPort {
+                ports: vec![3000, 8000, 8001]
+            }
+        );
+        assert!(matches!(parse(&["kill", "--port"]), Err(ModeError::InvalidInput(_))));
+        assert!(matches!(parse(&["kill", "--port", "http"]), Err(ModeError::InvalidInput(_))));
+        assert!(matches!(parse(&["kill"]), Err(ModeError::InvalidInput(_))));
+        assert!(matches!(parse(&["kill", "a", "b"]), Err(ModeError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_parse_scan() {
+        match parse(&["scan", "127.0.0.1", "--ports", "1-1000", "--json"]).unwrap() {
+            Command::Scan {
+                target, ports, json, ..
+            } => {
+                assert_eq!(target, "127.0.0.1");
+                assert_eq!(ports.len(), 1000);
+                assert!(json);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+
+        match parse(&["scan", "localhost", "-c", "8", "--timeout", "50"]).unwrap() {
+            Command::Scan { flags, .. } => {
+                assert_eq!(flags.concurrency, Some(8));
+                assert_eq!(flags.timeout, Some(Duration::from_millis(50)));
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+
+        assert!(parse(&["scan"]).is_err());
+        assert!(parse(&["scan", "127.0.0.1", "--concurrency", "0"]).is_err());
+        assert!(parse(&["scan", "127.0.0.1", "--type", "custom"]).is_err());
+        assert!(parse(&["scan", "127.0.0.1", "--bogus"]).is_err());
+        assert!(parse(&["scan", "127.0.0.1", "--format", "pdf"]).is_err());
+
+        match parse(&["scan", "127.0.0.1", "--format", "xml", "-o", "/tmp/reports"]).unwrap() {
+            Command::Scan { export: Some(export), .. } => {
+                assert_eq!(export.format, ExportFormat::Xml);
+                assert_eq!(export.directory, PathBuf::from("/tmp/reports"));
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+
+        match parse(&["scan", "127.0.0.1"]).unwrap() {
+            Command::Scan { export, .. } => assert!(export.is_none()),
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_scan_flags_apply_over_config() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(
+            &path,
+            "[scanner]\ntimeout_ms = 250\nconcurrency = 7\nall_addresses = true\nservice_detection = true\n",
+        )
+        .unwrap();
+        let defaults = ModeConfig::load_from(&path).unwrap().scanner;
+
+        let (config, service_detection) = ScanFlags::default().apply(&defaults);
+        assert_eq!(config.timeout, Duration::from_millis(250));
+        assert_eq!(config.concurrency, 7);
+        assert!(config.all_addresses && service_detection);
+
+        // Flags still win over the config
+        let flags = ScanFlags {
+            concurrency: Some(9),
+            ..ScanFlags::default()
+        };
+        let (config, _) = flags.apply(&defaults);
+        assert_eq!(config.concurrency, 9);
+        assert_eq!(config.timeout, Duration::from_millis(250));
+    }
+}
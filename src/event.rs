@@ -1,3 +1,4 @@
+use crate::features::ScanUpdate;
 use crossterm::event::{self, Event as CrosstermEvent, KeyEvent, MouseEvent};
 use std::sync::mpsc;
 use std::thread;
@@ -14,11 +15,14 @@ pub enum Event {
     Resize(u16, u16),
     /// Tick event for animations/updates
     Tick,
+    /// Progress from a background port scan
+    Scan(ScanUpdate),
 }
 
 /// Event handler for the application
 /// Spawns a background thread to listen for terminal events
 pub struct EventHandler {
+    tx: mpsc::Sender<Event>,
     rx: mpsc::Receiver<Event>,
     _handle: thread::JoinHandle<()>,
 }
@@ -30,6 +34,7 @@ impl EventHandler {
     /// * `tick_rate` - Duration between tick events (e.g., Duration::from_millis(250))
     pub fn new(tick_rate: Duration) -> Self {
         let (tx, rx) = mpsc::channel();
+        let event_tx = tx.clone();
         let handle = thread::spawn(move || {
             let mut last_tick = std::time::Instant::now();
             loop {
@@ -40,20 +45,16 @@ impl EventHandler {
 
                 if event::poll(timeout).unwrap_or(false) {
                     match event::read() {
-                        Ok(CrosstermEvent::Key(key)) => {
-                            if tx.send(Event::Key(key)).is_err() {
-                                return;
-                            }
+                        Ok(CrosstermEvent::Key(key)) if tx.send(Event::Key(key)).is_err() => {
+                            return;
                         }
-                        Ok(CrosstermEvent::Mouse(mouse)) => {
-                            if tx.send(Event::Mouse(mouse)).is_err() {
-                                return;
-                            }
+                        Ok(CrosstermEvent::Mouse(mouse)) if tx.send(Event::Mouse(mouse)).is_err() => {
+                            return;
                         }
-                        Ok(CrosstermEvent::Resize(width, height)) => {
-                            if tx.send(Event::Resize(width, height)).is_err() {
-                                return;
-                            }
+                        Ok(CrosstermEvent::Resize(width, height))
+                            if tx.send(Event::Resize(width, height)).is_err() =>
+                        {
+                            return;
                         }
                         _ => {}
                     }
@@ -70,11 +71,17 @@ impl EventHandler {
         });
 
         EventHandler {
+            tx: event_tx,
             rx,
             _handle: handle,
         }
     }
 
+    /// Returns a sender for background work to feed events into the loop
+    pub fn sender(&self) -> mpsc::Sender<Event> {
+        self.tx.clone()
+    }
+
     /// Attempts to receive the next event
     /// Returns None if no events are available
     pub fn try_next(&self) -> Option<Event> {
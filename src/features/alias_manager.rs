@@ -1,15 +1,24 @@
-use crate::utils::{self, file_ops, Result, ShellType};
+use crate::utils::backup::BackupStore;
+use crate::utils::{self, file_ops, file_ops::AliasEntry, ModeError, Result, ShellType};
 use std::path::PathBuf;
 
 /// Alias Manager state machine
 #[derive(Debug, Clone)]
 pub enum AliasManagerState {
+    /// Browsing the aliases defined in the RC file
+    Listing { selected: usize },
     /// Entering alias name
     EnteringName { input: String },
     /// Entering command
     EnteringCommand { name: String, input: String },
     /// Confirming the alias before creating
     Confirming { name: String, command: String },
+    /// Editing the command of an existing alias
+    EditingCommand { name: String, input: String },
+    /// Renaming an existing alias
+    Renaming { old_name: String, input: String },
+    /// Confirming deletion of an existing alias
+    ConfirmingDelete { name: String, command: String },
     /// Processing (creating alias and backup)
     Processing,
     /// Success state with message
@@ -24,17 +33,27 @@ pub struct AliasManager {
     pub state: AliasManagerState,
     shell: Option<ShellType>,
     rc_file: Option<PathBuf>,
+    /// Where the RC file is backed up before each write
+    backups: Option<BackupStore>,
+    /// Aliases currently defined in the RC file
+    aliases: Vec<AliasEntry>,
+    /// Alias names that were deleted or renamed away and must be unaliased in the live shell
+    removed: Vec<String>,
+    /// Whether the RC file was modified during this session
+    modified: bool,
 }
 
 impl AliasManager {
     /// Creates a new Alias Manager
     pub fn new() -> Self {
         Self {
-            state: AliasManagerState::EnteringName {
-                input: String::new(),
-            },
+            state: AliasManagerState::Listing { selected: 0 },
             shell: None,
             rc_file: None,
+            backups: None,
+            aliases: Vec::new(),
+            removed: Vec::new(),
+            modified: false,
         }
     }
 
@@ -45,17 +64,117 @@ impl AliasManager {
 
         self.shell = Some(shell);
         self.rc_file = Some(rc_file);
+        self.backups = Some(BackupStore::open()?);
+        self.refresh_aliases()?;
 
         Ok(())
     }
 
+    /// Reloads the alias list from the RC file
+    pub fn refresh_aliases(&mut self) -> Result<()> {
+        if let Some(rc_file) = &self.rc_file {
+            self.aliases = file_ops::list_aliases(rc_file)?;
+        }
+
+        if let AliasManagerState::Listing { selected } = &mut self.state {
+            *selected = (*selected).min(self.aliases.len().saturating_sub(1));
+        }
+
+        Ok(())
+    }
+
+    /// Returns to the alias list, focusing the named alias if it still exists
+    pub fn show_list(&mut self, focus: Option<&str>) {
+        if let Err(e) = self.refresh_aliases() {
+            self.state = AliasManagerState::Error {
+                message: format!("Failed to read aliases: {}", e),
+            };
+            return;
+        }
+
+        let selected = focus
+            .and_then(|name| self.aliases.iter().position(|a| a.name == name))
+            .unwrap_or(0);
+        self.state = AliasManagerState::Listing { selected };
+    }
+
+    /// Returns the aliases currently defined in the RC file
+    pub fn aliases(&self) -> &[AliasEntry] {
+        &self.aliases
+    }
+
+    /// Returns the alias highlighted in the list, if any
+    pub fn selected_alias(&self) -> Option<&AliasEntry> {
+        match self.state {
+            AliasManagerState::Listing { selected } => self.aliases.get(selected),
+            _ => None,
+        }
+    }
+
+    /// Moves selection up
+    pub fn previous(&mut self) {
+        if let AliasManagerState::Listing { selected } = &mut self.state {
+            let total = self.aliases.len();
+            if total > 0 {
+                *selected = if *selected == 0 { total - 1 } else { *selected - 1 };
+            }
+        }
+    }
+
+    /// Moves selection down
+    pub fn next(&mut self) {
+        if let AliasManagerState::Listing { selected } = &mut self.state {
+            let total = self.aliases.len();
+            if total > 0 {
+                *selected = (*selected + 1) % total;
+            }
+        }
+    }
+
+    /// Starts creating a new alias
+    pub fn start_add(&mut self) {
+        self.state = AliasManagerState::EnteringName {
+            input: String::new(),
+        };
+    }
+
+    /// Starts editing the command of the selected alias
+    pub fn start_edit(&mut self) {
+        if let Some(alias) = self.selected_alias().cloned() {
+            self.state = AliasManagerState::EditingCommand {
+                name: alias.name,
+                input: alias.command,
+            };
+        }
+    }
+
+    /// Starts renaming the selected alias
+    pub fn start_rename(&mut self) {
+        if let Some(alias) = self.selected_alias().cloned() {
+            self.state = AliasManagerState::Renaming {
+                input: alias.name.clone(),
+                old_name: alias.name,
+            };
+        }
+    }
+
+    /// Starts deleting the selected alias
+    pub fn start_delete(&mut self) {
+        if let Some(alias) = self.selected_alias().cloned() {
+            self.state = AliasManagerState::ConfirmingDelete {
+                name: alias.name,
+                command: alias.command,
+            };
+        }
+    }
+
     /// Handles input for the current state
     pub fn handle_char(&mut self, c: char) {
         match &mut self.state {
-            AliasManagerState::EnteringName { input } => {
-                input.push(c);
-            }
-            AliasManagerState::EnteringCommand { input, .. } => {
+            AliasManagerState::EnteringName { input }
+            | AliasManagerState::EnteringCommand { input, .. }
+            | AliasManagerState::EditingCommand { input, .. }
+            | AliasManagerState::Renaming { input, .. } => {
                 input.push(c);
             }
             _ => {}
@@ -65,10 +184,10 @@ impl AliasManager {
     /// Handles backspace
     pub fn handle_backspace(&mut self) {
         match &mut self.state {
-            AliasManagerState::EnteringName { input } => {
-                input.pop();
-            }
-            AliasManagerState::EnteringCommand { input, .. } => {
+            AliasManagerState::EnteringName { input }
+            | AliasManagerState::EnteringCommand { input, .. }
+            | AliasManagerState::EditingCommand { input, .. }
+            | AliasManagerState::Renaming { input, .. } => {
                 input.pop();
             }
             _ => {}
@@ -137,30 +256,107 @@ impl AliasManager {
                 self.state = AliasManagerState::Processing;
 
                 // Create the alias
-                if let Some(rc_file) = &self.rc_file {
-                    match file_ops::append_alias(rc_file, &name_clone, &command_clone) {
-                        Ok(backup_path) => {
-                            let shell_name = self.shell.map(|s| s.name()).unwrap_or("bash");
-
-                            self.state = AliasManagerState::Success {
-                                message: format!(
-                                    "✓ Alias '{}' created successfully!\n\n\
-                                    Backup: {}\n\n\
-                                    Reload your shell with this command:\n\n\
-                                    exec {}\n\n\
-                                    Then you can use: {}",
-                                    name_clone,
-                                    backup_path.display(),
-                                    shell_name,
-                                    name_clone
-                                ),
-                            };
-                        }
-                        Err(e) => {
-                            self.state = AliasManagerState::Error {
-                                message: format!("Failed to create alias: {}", e),
-                            };
-                        }
+                match self.add_alias(&name_clone, &command_clone) {
+                    Ok(backup_path) => {
+                        let shell_name = self.shell().name();
+
+                        self.state = AliasManagerState::Success {
+                            message: format!(
+                                "✓ Alias '{}' created successfully!\n\n\
+                                Backup: {}\n\n\
+                                Reload your shell with this command:\n\n\
+                                exec {}\n\n\
+                                Then you can use: {}",
+                                name_clone,
+                                backup_path.display(),
+                                shell_name,
+                                name_clone
+                            ),
+                        };
+                    }
+                    Err(e) => {
+                        self.state = AliasManagerState::Error {
+                            message: format!("Failed to create alias: {}", e),
+                        };
+                    }
+                }
+            }
+            AliasManagerState::EditingCommand { name, input } => {
+                let name = name.clone();
+                let command = input.trim().to_string();
+
+                self.state = AliasManagerState::Processing;
+
+                match self.update_command(&name, &command) {
+                    Ok(backup_path) => {
+                        self.state = AliasManagerState::Success {
+                            message: format!(
+                                "✓ Alias '{}' updated!\n\n\
+                                Command: {}\n\
+                                Backup: {}",
+                                name,
+                                command,
+                                backup_path.display()
+                            ),
+                        };
+                    }
+                    Err(e) => {
+                        self.state = AliasManagerState::Error {
+                            message: format!("Failed to update alias: {}", e),
+                        };
+                    }
+                }
+            }
+            AliasManagerState::Renaming { old_name, input, .. } => {
+                let old_name = old_name.clone();
+                let new_name = input.trim().to_string();
+
+                if new_name == old_name {
+                    self.show_list(Some(&old_name));
+                    return Ok(());
+                }
+
+                self.state = AliasManagerState::Processing;
+
+                match self.rename_alias(&old_name, &new_name) {
+                    Ok(backup_path) => {
+                        self.state = AliasManagerState::Success {
+                            message: format!(
+                                "✓ Alias '{}' renamed to '{}'!\n\n\
+                                Backup: {}",
+                                old_name,
+                                new_name,
+                                backup_path.display()
+                            ),
+                        };
+                    }
+                    Err(e) => {
+                        self.state = AliasManagerState::Error {
+                            message: format!("Failed to rename alias: {}", e),
+                        };
+                    }
+                }
+            }
+            AliasManagerState::ConfirmingDelete { name, .. } => {
+                let name = name.clone();
+
+                self.state = AliasManagerState::Processing;
+
+                match self.delete_alias(&name) {
+                    Ok(backup_path) => {
+                        self.state = AliasManagerState::Success {
+                            message: format!(
+                                "✓ Alias '{}' deleted!\n\n\
+                                Backup: {}",
+                                name,
+                                backup_path.display()
+                            ),
+                        };
+                    }
+                    Err(e) => {
+                        self.state = AliasManagerState::Error {
+                            message: format!("Failed to delete alias: {}", e),
+                        };
                     }
                 }
             }
@@ -170,18 +366,127 @@ impl AliasManager {
         Ok(())
     }
 
+    /// Returns the detected RC file or an error if the manager is not initialized
+    fn require_rc_file(&self) -> Result<PathBuf> {
+        self.rc_file
+            .clone()
+            .ok_or_else(|| ModeError::RcFileNotFound("shell RC file has not been detected".to_string()))
+    }
+
+    /// Returns the backup store, or an error if the manager is not initialized
+    fn require_backups(&self) -> Result<&BackupStore> {
+        self.backups
+            .as_ref()
+            .ok_or_else(|| ModeError::Config("backup store has not been opened".to_string()))
+    }
+
+    /// Returns the detected shell, assuming bash before detection
+    fn shell(&self) -> ShellType {
+        self.shell.unwrap_or(ShellType::Bash)
+    }
+
+    /// Creates a new alias after validating its name and checking for duplicates
+    ///
+    /// Returns the path to the RC file backup
+    pub fn add_alias(&mut self, name: &str, command: &str) -> Result<PathBuf> {
+        let rc_file = self.require_rc_file()?;
+        utils::validate_alias_name(name)?;
+
+        let command = command.trim();
+        if command.is_empty() {
+            return Err(ModeError::InvalidInput("Command cannot be empty".to_string()));
+        }
+
+        if file_ops::check_duplicate_alias(&rc_file, name)? {
+            return Err(ModeError::DuplicateAlias(name.to_string()));
+        }
+
+        let backup_path = file_ops::append_alias(&rc_file, self.shell(), self.require_backups()?, name, command)?;
+        self.modified = true;
+        self.refresh_aliases()?;
+
+        Ok(backup_path)
+    }
+
+    /// Replaces the command of an existing alias
+    ///
+    /// Returns the path to the RC file backup
+    pub fn update_command(&mut self, name: &str, command: &str) -> Result<PathBuf> {
+        let rc_file = self.require_rc_file()?;
+
+        let command = command.trim();
+        if command.is_empty() {
+            return Err(ModeError::InvalidInput("Command cannot be empty".to_string()));
+        }
+
+        let backup_path = file_ops::update_alias(&rc_file, self.shell(), self.require_backups()?, name, name, command)?;
+        self.modified = true;
+        self.refresh_aliases()?;
+
+        Ok(backup_path)
+    }
+
+    /// Renames an existing alias, keeping its command
+    ///
+    /// Returns the path to the RC file backup
+    pub fn rename_alias(&mut self, old_name: &str, new_name: &str) -> Result<PathBuf> {
+        let rc_file = self.require_rc_file()?;
+        utils::validate_alias_name(new_name)?;
+
+        let command = self
+            .aliases
+            .iter()
+            .find(|a| a.name == old_name)
+            .map(|a| a.command.clone())
+            .ok_or_else(|| ModeError::InvalidInput(format!("Alias '{}' not found in RC file", old_name)))?;
+
+        let backup_path = file_ops::update_alias(
+            &rc_file,
+            self.shell(),
+            self.require_backups()?,
+            old_name,
+            new_name,
+            &command,
+        )?;
+        self.modified = true;
+        self.removed.push(old_name.to_string());
+        self.refresh_aliases()?;
+
+        Ok(backup_path)
+    }
+
+    /// Deletes an existing alias
+    ///
+    /// Returns the path to the RC file backup
+    pub fn delete_alias(&mut self, name: &str) -> Result<PathBuf> {
+        let rc_file = self.require_rc_file()?;
+
+        let backup_path = file_ops::delete_alias(&rc_file, self.shell(), self.require_backups()?, name)?;
+        self.modified = true;
+        self.removed.push(name.to_string());
+        self.refresh_aliases()?;
+
+        Ok(backup_path)
+    }
+
     /// Goes back to the previous state or cancels
     pub fn go_back(&mut self) {
         match &self.state {
-            AliasManagerState::EnteringCommand { .. } => {
+            AliasManagerState::EnteringCommand { .. } | AliasManagerState::Confirming { .. } => {
                 self.state = AliasManagerState::EnteringName {
                     input: String::new(),
                 };
             }
-            AliasManagerState::Confirming { .. } => {
-                self.state = AliasManagerState::EnteringName {
-                    input: String::new(),
-                };
+            AliasManagerState::EditingCommand { name, .. }
+            | AliasManagerState::ConfirmingDelete { name, .. }
+            | AliasManagerState::Renaming { old_name: name, .. } => {
+                let name = name.clone();
+                self.show_list(Some(&name));
+            }
+            AliasManagerState::EnteringName { .. }
+            | AliasManagerState::Success { .. }
+            | AliasManagerState::Error { .. } => {
+                self.show_list(None);
             }
             _ => {}
         }
@@ -197,14 +502,16 @@ impl AliasManager {
 
     /// Resets the manager to initial state
     pub fn reset(&mut self) {
-        self.state = AliasManagerState::EnteringName {
-            input: String::new(),
-        };
+        self.show_list(None);
     }
 
     /// Gets the current prompt text
     pub fn get_prompt(&self) -> String {
         match &self.state {
+            AliasManagerState::Listing { .. } => match &self.rc_file {
+                Some(rc_file) => format!("{} aliases in {}", self.aliases.len(), rc_file.display()),
+                None => format!("{} aliases", self.aliases.len()),
+            },
             AliasManagerState::EnteringName { .. } => {
                 "Enter alias name (e.g., 'll', 'gs'):".to_string()
             }
@@ -214,7 +521,16 @@ impl AliasManager {
             AliasManagerState::Confirming { name, command } => {
                 format!("{}\n{}", name, command)
             }
-            AliasManagerState::Processing => "Creating alias...".to_string(),
+            AliasManagerState::EditingCommand { name, .. } => {
+                format!("Edit command for alias '{}':", name)
+            }
+            AliasManagerState::Renaming { old_name, .. } => {
+                format!("Enter new name for alias '{}':", old_name)
+            }
+            AliasManagerState::ConfirmingDelete { name, command } => {
+                format!("{}\n{}", name, command)
+            }
+            AliasManagerState::Processing => "Updating aliases...".to_string(),
             AliasManagerState::Success { message } => message.clone(),
             AliasManagerState::Error { message } => format!("Error: {}", message),
         }
@@ -223,7 +539,10 @@ impl AliasManager {
     /// Gets confirmation data for structured display
     pub fn get_confirmation_data(&self) -> Option<(String, String)> {
         match &self.state {
-            AliasManagerState::Confirming { name, command } => Some((name.clone(), command.clone())),
+            AliasManagerState::Confirming { name, command }
+            | AliasManagerState::ConfirmingDelete { name, command } => {
+                Some((name.clone(), command.clone()))
+            }
             _ => None,
         }
     }
@@ -231,8 +550,10 @@ impl AliasManager {
     /// Gets the current input text
     pub fn get_input(&self) -> String {
         match &self.state {
-            AliasManagerState::EnteringName { input } => input.clone(),
-            AliasManagerState::EnteringCommand { input, .. } => input.clone(),
+            AliasManagerState::EnteringName { input }
+            | AliasManagerState::EnteringCommand { input, .. }
+            | AliasManagerState::EditingCommand { input, .. }
+            | AliasManagerState::Renaming { input, .. } => input.clone(),
             _ => String::new(),
         }
     }
@@ -241,6 +562,41 @@ impl AliasManager {
     pub fn get_rc_file(&self) -> Option<&PathBuf> {
         self.rc_file.as_ref()
     }
+
+    /// Returns whether the RC file was modified during this session
+    pub fn is_modified(&self) -> bool {
+        self.modified
+    }
+
+    /// Builds the command that applies this session's changes to the live shell
+    ///
+    /// Removed or renamed aliases are unaliased before the RC file is sourced again
+    pub fn get_exit_command(&self) -> Option<String> {
+        if !self.modified {
+            return None;
+        }
+
+        let rc_file = self.rc_file.as_ref()?;
+        if self.removed.is_empty() {
+            return Some(format!("source {}", rc_file.display()));
+        }
+
+        let removed = self.removed.join(" ");
+        match self.shell() {
+            ShellType::Bash | ShellType::Zsh => Some(format!(
+                "unalias {} 2>/dev/null; source {}",
+                removed,
+                rc_file.display()
+            )),
+            // Fish aliases are functions; abbreviations are erased separately
+            ShellType::Fish => Some(format!(
+                "functions --erase {} 2>/dev/null; abbr --erase {} 2>/dev/null; source {}",
+                removed,
+                removed,
+                rc_file.display()
+            )),
+        }
+    }
 }
 
 impl Default for AliasManager {
@@ -0,0 +1,377 @@
+use super::git_config;
+use super::mode_config::ModeConfig;
+use super::ssh_config::SshConfig;
+use crate::utils::backup::{self, Backup, BackupStore};
+use crate::utils::config::config_file_path;
+use crate::utils::{detect_shell, get_rc_file_path, ModeError, Result};
+use std::path::PathBuf;
+
+/// Lines a page key scrolls the diff by
+const DIFF_PAGE: usize = 10;
+
+/// Backups screen state machine
+#[derive(Debug, Clone)]
+pub enum BackupsState {
+    /// Browsing the backups of every managed file
+    Listing { selected: usize },
+    /// Reading the diff between a backup and its file
+    ViewingDiff {
+        selected: usize,
+        /// Unified diff lines, empty when the backup matches the file
+        lines: Vec<String>,
+        /// Index of the first line shown
+        scroll: usize,
+    },
+    /// Confirming a restore
+    ConfirmingRestore { selected: usize },
+    /// Success state with message
+    Success { message: String },
+    /// Error state with message
+    Error { message: String },
+}
+
+/// Backups feature
+#[derive(Debug)]
+pub struct Backups {
+    pub state: BackupsState,
+    /// Store the backups are kept in
+    store: Option<BackupStore>,
+    /// Files mode writes backups of
+    files: Vec<PathBuf>,
+    /// Backups of every managed file, grouped by file, newest first
+    backups: Vec<Backup>,
+    /// The shell's RC file, sourced on exit after it is restored
+    rc_file: Option<PathBuf>,
+    /// `config.toml`, reloaded by the app after it is restored
+    config_file: Option<PathBuf>,
+    /// Whether the RC file was restored
+    rc_restored: bool,
+    /// Settings read back from a restored `config.toml`
+    restored_config: Option<ModeConfig>,
+}
+
+impl Backups {
+    /// Creates a new Backups screen with nothing listed
+    pub fn new() -> Self {
+        Self {
+            state: BackupsState::Listing { selected: 0 },
+            store: None,
+            files: Vec::new(),
+            backups: Vec::new(),
+            rc_file: None,
+            config_file: None,
+            rc_restored: false,
+            restored_config: None,
+        }
+    }
+
+    /// Initializes the screen by finding the backups of every managed file
+    ///
+    /// Managed files are the shell's RC file, `~/.ssh/config`, `~/.gitconfig`
+    /// and mode's `config.toml`; any whose location can't be determined are
+    /// left out. Backups older versions of mode left next to them are moved
+    /// into the store first.
+    pub fn initialize(&mut self) -> Result<()> {
+        let store = BackupStore::open()?;
+        self.rc_file = detect_shell().and_then(get_rc_file_path).ok();
+        self.config_file = config_file_path().ok();
+
+        self.files = [
+            self.rc_file.clone(),
+            SshConfig::file_path().ok(),
+            git_config::global_config_path().ok(),
+            self.config_file.clone(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        for file in &self.files {
+            store.adopt_legacy(file)?;
+        }
+        self.store = Some(store);
+        self.reload()
+    }
+
+    /// Returns the store, once the screen is initialized
+    fn store(&self) -> Result<&BackupStore> {
+        self.store
+            .as_ref()
+            .ok_or_else(|| ModeError::Config("Backup store not opened".to_string()))
+    }
+
+    /// Lists the backups of the managed files again
+    fn reload(&mut self) -> Result<()> {
+        let store = self.store()?;
+        let mut backups = Vec::new();
+        for file in &self.files {
+            backups.extend(store.list(file)?);
+        }
+        self.backups = backups;
+        Ok(())
+    }
+
+    /// Returns the files backups are listed for
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    /// Returns every listed backup
+    pub fn backups(&self) -> &[Backup] {
+        &self.backups
+    }
+
+    /// Returns the backup the current state is about, if any
+    pub fn selected_backup(&self) -> Option<&Backup> {
+        match &self.state {
+            BackupsState::Listing { selected }
+            | BackupsState::ViewingDiff { selected, .. }
+            | BackupsState::ConfirmingRestore { selected } => self.backups.get(*selected),
+            _ => None,
+        }
+    }
+
+    /// Moves selection up, or scrolls the diff up
+    pub fn previous(&mut self) {
+        let total = self.backups.len();
+        match &mut self.state {
+            BackupsState::Listing { selected } if total > 0 => {
+                *selected = if *selected == 0 { total - 1 } else { *selected - 1 };
+            }
+            BackupsState::ViewingDiff { scroll, .. } => *scroll = scroll.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    /// Moves selection down, or scrolls the diff down
+    pub fn next(&mut self) {
+        let total = self.backups.len();
+        match &mut self.state {
+            BackupsState::Listing { selected } if total > 0 => {
+                *selected = (*selected + 1) % total;
+            }
+            BackupsState::ViewingDiff { lines, scroll, .. } => {
+                *scroll = (*scroll + 1).min(lines.len().saturating_sub(1));
+            }
+            _ => {}
+        }
+    }
+
+    /// Scrolls the diff up a page
+    pub fn page_up(&mut self) {
+        if let BackupsState::ViewingDiff { scroll, .. } = &mut self.state {
+            *scroll = scroll.saturating_sub(DIFF_PAGE);
+        }
+    }
+
+    /// Scrolls the diff down a page
+    pub fn page_down(&mut self) {
+        if let BackupsState::ViewingDiff { lines, scroll, .. } = &mut self.state {
+            *scroll = (*scroll + DIFF_PAGE).min(lines.len().saturating_sub(1));
+        }
+    }
+
+    /// Shows the diff between the selected backup and its file
+    pub fn view_diff(&mut self) {
+        let BackupsState::Listing { selected } = self.state else {
+            return;
+        };
+        let Some(backup) = self.backups.get(selected) else {
+            return;
+        };
+
+        self.state = match backup::diff_against_current(backup) {
+            Ok(diff) => BackupsState::ViewingDiff {
+                selected,
+                lines: diff.lines().map(|line| line.to_string()).collect(),
+                scroll: 0,
+            },
+            Err(e) => BackupsState::Error {
+                message: format!("Failed to diff {}: {}", backup.file_name(), e),
+            },
+        };
+    }
+
+    /// Starts restoring the selected backup, asking for confirmation first
+    pub fn start_restore(&mut self) {
+        let selected = match self.state {
+            BackupsState::Listing { selected } | BackupsState::ViewingDiff { selected, .. } => selected,
+            _ => return,
+        };
+        if selected < self.backups.len() {
+            self.state = BackupsState::ConfirmingRestore { selected };
+        }
+    }
+
+    /// Restores the backup being confirmed
+    pub fn advance(&mut self) {
+        let BackupsState::ConfirmingRestore { selected } = self.state else {
+            return;
+        };
+        let Some(backup) = self.backups.get(selected).cloned() else {
+            return;
+        };
+
+        self.state = match self.restore(&backup) {
+            Ok(message) => BackupsState::Success { message },
+            Err(e) => BackupsState::Error {
+                message: e.to_string(),
+            },
+        };
+    }
+
+    /// Restores a backup and lists the backups again
+    fn restore(&mut self, backup: &Backup) -> Result<String> {
+        let current_backup = self.store()?.restore(backup)?;
+
+        let mut message = format!(
+            "✓ Restored {} from {}",
+            backup.original.display(),
+            backup.file_name()
+        );
+        if let Some(current_backup) = current_backup {
+            message.push_str(&format!("\n\nPrevious version: {}", current_backup.display()));
+        }
+
+        if self.rc_file.as_ref() == Some(&backup.original) {
+            self.rc_restored = true;
+        }
+        if self.config_file.as_ref() == Some(&backup.original) {
+            match ModeConfig::load_from(&backup.original) {
+                Ok(config) => self.restored_config = Some(config),
+                Err(e) => message.push_str(&format!("\n\nThe restored settings can't be applied: {}", e)),
+            }
+        }
+
+        self.reload()?;
+        Ok(message)
+    }
+
+    /// Goes back to the list, keeping the selection where it was
+    pub fn go_back(&mut self) {
+        let selected = match self.state {
+            BackupsState::Listing { selected }
+            | BackupsState::ViewingDiff { selected, .. }
+            | BackupsState::ConfirmingRestore { selected } => selected,
+            _ => 0,
+        };
+        self.state = BackupsState::Listing {
+            selected: selected.min(self.backups.len().saturating_sub(1)),
+        };
+    }
+
+    /// Returns whether the feature is done (success or error)
+    pub fn is_done(&self) -> bool {
+        matches!(
+            self.state,
+            BackupsState::Success { .. } | BackupsState::Error { .. }
+        )
+    }
+
+    /// Gets the prompt text for the current state
+    pub fn get_prompt(&self) -> String {
+        match &self.state {
+            BackupsState::Listing { .. } => format!(
+                "{} backup(s) of {} managed file(s)",
+                self.backups.len(),
+                self.files.len()
+            ),
+            BackupsState::ViewingDiff { lines, .. } => {
+                if lines.is_empty() {
+                    "The backup matches the current file".to_string()
+                } else {
+                    "- only in the backup   + only in the current file".to_string()
+                }
+            }
+            BackupsState::ConfirmingRestore { .. } => "Restore this backup?".to_string(),
+            BackupsState::Success { message } => message.clone(),
+            BackupsState::Error { message } => format!("Error: {}", message),
+        }
+    }
+
+    /// Gets the restore to confirm as (label, value) pairs
+    pub fn get_confirmation_data(&self) -> Option<Vec<(String, String)>> {
+        match &self.state {
+            BackupsState::ConfirmingRestore { .. } => {
+                let backup = self.selected_backup()?;
+                Some(vec![
+                    ("File".to_string(), backup.original.display().to_string()),
+                    ("Backup".to_string(), backup.file_name()),
+                    (
+                        "Taken".to_string(),
+                        backup.created.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    ),
+                    (
+                        "Current file".to_string(),
+                        "backed up before it is replaced".to_string(),
+                    ),
+                ])
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns whether the RC file was restored, so the shell should reload it
+    pub fn is_modified(&self) -> bool {
+        self.rc_restored
+    }
+
+    /// Builds the command that reloads a restored RC file in the live shell
+    pub fn get_exit_command(&self) -> Option<String> {
+        if !self.rc_restored {
+            return None;
+        }
+        self.rc_file
+            .as_ref()
+            .map(|rc_file| format!("source {}", rc_file.display()))
+    }
+
+    /// Returns the settings of a restored `config.toml`, for the app to apply
+    pub fn restored_config(&self) -> Option<&ModeConfig> {
+        self.restored_config.as_ref()
+    }
+}
+
+impl Default for Backups {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::backup::BackupSettings;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_restore_flow() {
+        let dir = tempdir().unwrap();
+        let rc = dir.path().join(".bashrc");
+        let store = BackupStore::new(dir.path().join("store"), BackupSettings::default());
+        fs::write(&rc, "alias a=b\n").unwrap();
+        store.back_up(&rc).unwrap();
+        fs::write(&rc, "alias a=b\nalias c=d\n").unwrap();
+
+        let mut backups = Backups::new();
+        backups.store = Some(store);
+        backups.files = vec![rc.clone()];
+        backups.rc_file = Some(rc.clone());
+        backups.reload().unwrap();
+        assert_eq!(backups.backups().len(), 1);
+
+        backups.view_diff();
+        assert!(matches!(&backups.state, BackupsState::ViewingDiff { lines, .. } if lines.contains(&"+alias c=d".to_string())));
+
+        backups.start_restore();
+        assert!(backups.get_confirmation_data().is_some());
+        backups.advance();
+        assert!(matches!(backups.state, BackupsState::Success { .. }), "{:?}", backups.state);
+        assert_eq!(fs::read_to_string(&rc).unwrap(), "alias a=b\n");
+
+        // The replaced version is listed as a backup of its own
+        assert_eq!(backups.backups().len(), 2);
+        assert_eq!(backups.get_exit_command(), Some(format!("source {}", rc.display())));
+    }
+}
@@ -1,15 +1,23 @@
-use crate::utils::{self, file_ops, Result, ShellType};
+use crate::features::bookmark_store::{self, Bookmark, BookmarkStore};
+use crate::features::frecency::{self, DirVisit, FrecencyDb};
+use crate::utils::{shell, Result, ShellType};
 use std::env;
 use std::path::PathBuf;
 
 /// Bookmark Manager state machine
 #[derive(Debug, Clone)]
 pub enum BookmarkManagerState {
-    /// Confirming the bookmark
-    Confirming { directory: String },
-    /// Processing (creating/updating alias)
-    Processing,
-    /// Success state - triggers immediate exit
+    /// Browsing saved bookmarks
+    Listing { selected: usize },
+    /// Browsing visited directories ranked by frecency, filtered by the query
+    Frequent { selected: usize, query: String },
+    /// Naming a bookmark for the current directory
+    Adding { input: String },
+    /// Renaming an existing bookmark
+    Renaming { old_name: String, input: String },
+    /// Confirming deletion of a bookmark
+    ConfirmingDelete { name: String, path: String },
+    /// Success state with message
     Success { message: String },
     /// Error state with message
     Error { message: String },
@@ -19,91 +27,284 @@ pub enum BookmarkManagerState {
 #[derive(Debug)]
 pub struct BookmarkManager {
     pub state: BookmarkManagerState,
-    shell: Option<ShellType>,
-    rc_file: Option<PathBuf>,
+    store: Option<BookmarkStore>,
+    /// Directories recorded by the shell wrapper
+    history: Option<FrecencyDb>,
+    /// Visited directories matching the current query, best first
+    matches: Vec<DirVisit>,
+    /// Directory mode was started from, offered when adding a bookmark
+    current_dir: PathBuf,
+    /// `cd` command for the bookmark the user jumped to
+    exit_command: Option<String>,
+    /// Shell the exit command is written for
+    shell: ShellType,
 }
 
 impl BookmarkManager {
     /// Creates a new Bookmark Manager
     pub fn new() -> Self {
-        // Get current directory immediately
-        let current_dir = env::current_dir()
-            .map(|p| p.display().to_string())
-            .unwrap_or_else(|_| String::from("unknown"));
+        let current_dir = env::current_dir().unwrap_or_else(|_| PathBuf::from("/"));
 
         Self {
-            state: BookmarkManagerState::Confirming {
-                directory: current_dir,
-            },
-            shell: None,
-            rc_file: None,
+            state: BookmarkManagerState::Listing { selected: 0 },
+            store: None,
+            history: None,
+            matches: Vec::new(),
+            current_dir,
+            exit_command: None,
+            shell: ShellType::Bash,
         }
     }
 
-    /// Initializes the bookmark manager by detecting shell and RC file
+    /// Initializes the bookmark manager by loading bookmarks and visit history
     pub fn initialize(&mut self) -> Result<()> {
-        let shell = utils::detect_shell()?;
-        let rc_file = utils::get_rc_file_path(shell)?;
+        self.store = Some(BookmarkStore::load()?);
+        self.history = Some(FrecencyDb::load()?);
+        self.shell = shell::detect_shell().unwrap_or(ShellType::Bash);
+        Ok(())
+    }
 
-        self.shell = Some(shell);
-        self.rc_file = Some(rc_file);
+    /// Returns the saved bookmarks
+    pub fn bookmarks(&self) -> &[Bookmark] {
+        self.store.as_ref().map(|s| s.bookmarks()).unwrap_or(&[])
+    }
 
-        Ok(())
+    /// Returns the bookmark highlighted in the list, if any
+    pub fn selected_bookmark(&self) -> Option<&Bookmark> {
+        match self.state {
+            BookmarkManagerState::Listing { selected } => self.bookmarks().get(selected),
+            _ => None,
+        }
     }
 
-    /// Confirms and creates the bookmark
-    pub fn confirm_bookmark(&mut self) -> Result<()> {
-        if let BookmarkManagerState::Confirming { directory } = &self.state {
-            let dir_clone = directory.clone();
-            self.state = BookmarkManagerState::Processing;
+    /// Returns to the list, focusing the named bookmark if it still exists
+    pub fn show_list(&mut self, focus: Option<&str>) {
+        let selected = focus
+            .and_then(|name| self.bookmarks().iter().position(|b| b.name == name))
+            .unwrap_or(0);
+        self.state = BookmarkManagerState::Listing { selected };
+    }
 
-            // Create the temp alias - will overwrite if it exists
-            if let Some(rc_file) = &self.rc_file {
-                // First, remove any existing "temp" alias
-                if let Err(e) = file_ops::remove_alias(rc_file, "temp") {
-                    // It's okay if removal fails (alias might not exist)
-                    eprintln!("Note: Could not remove existing temp alias: {}", e);
-                }
+    /// Switches to the visited directories ranked by frecency
+    pub fn show_frequent(&mut self) {
+        self.state = BookmarkManagerState::Frequent {
+            selected: 0,
+            query: String::new(),
+        };
+        self.refresh_matches();
+    }
 
-                // Now add the new temp alias
-                // Use double quotes inside the command to handle spaces
-                match file_ops::append_alias(rc_file, "temp", &format!("cd \"{}\"", dir_clone)) {
-                    Ok(backup_path) => {
-                        let shell_name = self.shell.map(|s| s.name()).unwrap_or("bash");
-
-                        self.state = BookmarkManagerState::Success {
-                            message: format!(
-                                "✓ Temporary bookmark created!\n\n\
-                                Directory: {}\n\
-                                Alias: temp\n\
-                                Backup: {}\n\n\
-                                IMPORTANT: Reload your shell to use the alias:\n\n\
-                                source ~/.{}rc\n\
-                                OR\n\
-                                exec {}\n\n\
-                                Then type: temp",
-                                dir_clone,
-                                backup_path.display(),
-                                shell_name,
-                                shell_name
-                            ),
-                        };
-                    }
-                    Err(e) => {
-                        self.state = BookmarkManagerState::Error {
-                            message: format!("Failed to create bookmark: {}", e),
-                        };
-                    }
-                }
+    /// Returns visited directories matching the query, best first
+    pub fn frequent_dirs(&self) -> &[DirVisit] {
+        &self.matches
+    }
+
+    /// Re-ranks visited directories against the current query
+    fn refresh_matches(&mut self) {
+        if let BookmarkManagerState::Frequent { selected, query } = &mut self.state {
+            self.matches = match &self.history {
+                Some(history) => history.ranked(query, frecency::now()),
+                None => Vec::new(),
+            };
+            *selected = (*selected).min(self.matches.len().saturating_sub(1));
+        }
+    }
+
+    /// Returns the number of entries in the visible list
+    fn visible_len(&self) -> usize {
+        match self.state {
+            BookmarkManagerState::Frequent { .. } => self.matches.len(),
+            _ => self.bookmarks().len(),
+        }
+    }
+
+    /// Moves selection up
+    pub fn previous(&mut self) {
+        let total = self.visible_len();
+        if let BookmarkManagerState::Listing { selected } | BookmarkManagerState::Frequent { selected, .. } =
+            &mut self.state
+        {
+            if total > 0 {
+                *selected = if *selected == 0 { total - 1 } else { *selected - 1 };
             }
         }
+    }
 
-        Ok(())
+    /// Moves selection down
+    pub fn next(&mut self) {
+        let total = self.visible_len();
+        if let BookmarkManagerState::Listing { selected } | BookmarkManagerState::Frequent { selected, .. } =
+            &mut self.state
+        {
+            if total > 0 {
+                *selected = (*selected + 1) % total;
+            }
+        }
+    }
+
+    /// Clears the frecency filter, returning false if it was already empty
+    pub fn clear_query(&mut self) -> bool {
+        if let BookmarkManagerState::Frequent { selected, query } = &mut self.state {
+            if !query.is_empty() {
+                query.clear();
+                *selected = 0;
+                self.refresh_matches();
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Removes the selected directory from the visit history
+    pub fn forget_selected(&mut self) {
+        let BookmarkManagerState::Frequent { selected, .. } = self.state else {
+            return;
+        };
+        let (Some(entry), Some(history)) = (self.matches.get(selected), self.history.as_mut()) else {
+            return;
+        };
+
+        history.remove(&entry.path);
+        if let Err(e) = history.save() {
+            self.state = BookmarkManagerState::Error {
+                message: format!("Failed to update visit history: {}", e),
+            };
+            return;
+        }
+        self.refresh_matches();
+    }
+
+    /// Starts bookmarking the current directory, suggesting its name
+    pub fn start_add(&mut self) {
+        self.state = BookmarkManagerState::Adding {
+            input: bookmark_store::default_name(&self.current_dir),
+        };
+    }
+
+    /// Starts renaming the selected bookmark
+    pub fn start_rename(&mut self) {
+        if let Some(bookmark) = self.selected_bookmark().cloned() {
+            self.state = BookmarkManagerState::Renaming {
+                input: bookmark.name.clone(),
+                old_name: bookmark.name,
+            };
+        }
+    }
+
+    /// Starts deleting the selected bookmark
+    pub fn start_delete(&mut self) {
+        if let Some(bookmark) = self.selected_bookmark().cloned() {
+            self.state = BookmarkManagerState::ConfirmingDelete {
+                name: bookmark.name,
+                path: bookmark.path.display().to_string(),
+            };
+        }
+    }
+
+    /// Jumps to the selected bookmark or visited directory
+    ///
+    /// Returns true when the app should exit so the shell wrapper can `cd`
+    pub fn jump(&mut self) -> bool {
+        let target = match &self.state {
+            BookmarkManagerState::Listing { .. } => self.selected_bookmark().map(|b| b.path.clone()),
+            BookmarkManagerState::Frequent { selected, .. } => self.matches.get(*selected).map(|e| e.path.clone()),
+            _ => None,
+        };
+        let Some(path) = target else {
+            return false;
+        };
+
+        if !path.is_dir() {
+            self.state = BookmarkManagerState::Error {
+                message: format!("Directory no longer exists:\n\n{}", path.display()),
+            };
+            return false;
+        }
+
+        self.exit_command = Some(shell::cd_command(self.shell, &path));
+        true
+    }
+
+    /// Handles input for the current state
+    pub fn handle_char(&mut self, c: char) {
+        match &mut self.state {
+            BookmarkManagerState::Adding { input } | BookmarkManagerState::Renaming { input, .. } => {
+                input.push(c);
+            }
+            BookmarkManagerState::Frequent { selected, query } => {
+                query.push(c);
+                *selected = 0;
+                self.refresh_matches();
+            }
+            _ => {}
+        }
+    }
+
+    /// Handles backspace
+    pub fn handle_backspace(&mut self) {
+        match &mut self.state {
+            BookmarkManagerState::Adding { input } | BookmarkManagerState::Renaming { input, .. } => {
+                input.pop();
+            }
+            BookmarkManagerState::Frequent { selected, query } => {
+                query.pop();
+                *selected = 0;
+                self.refresh_matches();
+            }
+            _ => {}
+        }
+    }
+
+    /// Applies the pending add, rename or delete
+    pub fn advance(&mut self) {
+        let result = match self.state.clone() {
+            BookmarkManagerState::Adding { input } => {
+                let name = input.trim().to_string();
+                let directory = self.current_dir.clone();
+                self.with_store(|store| store.add(&name, &directory)).map(|_| {
+                    format!("✓ Bookmarked {} as '{}'", directory.display(), name)
+                })
+            }
+            BookmarkManagerState::Renaming { old_name, input } => {
+                let new_name = input.trim().to_string();
+                if new_name == old_name {
+                    self.show_list(Some(&old_name));
+                    return;
+                }
+                self.with_store(|store| store.rename(&old_name, &new_name))
+                    .map(|_| format!("✓ Bookmark '{}' renamed to '{}'", old_name, new_name))
+            }
+            BookmarkManagerState::ConfirmingDelete { name, .. } => self
+                .with_store(|store| store.remove(&name))
+                .map(|_| format!("✓ Bookmark '{}' deleted", name)),
+            _ => return,
+        };
+
+        self.state = match result {
+            Ok(message) => BookmarkManagerState::Success { message },
+            Err(e) => BookmarkManagerState::Error {
+                message: e.to_string(),
+            },
+        };
     }
 
-    /// Cancels the bookmark
-    pub fn cancel(&mut self) {
-        // Don't change state - let the app handle going back to menu
+    /// Runs an operation against the loaded store
+    fn with_store<T>(&mut self, op: impl FnOnce(&mut BookmarkStore) -> Result<T>) -> Result<T> {
+        let store = match self.store.take() {
+            Some(store) => store,
+            None => BookmarkStore::load()?,
+        };
+        op(self.store.insert(store))
+    }
+
+    /// Goes back to the previous state
+    pub fn go_back(&mut self) {
+        let focus = match &self.state {
+            BookmarkManagerState::Renaming { old_name: name, .. }
+            | BookmarkManagerState::ConfirmingDelete { name, .. } => Some(name.clone()),
+            _ => None,
+        };
+        self.show_list(focus.as_deref());
     }
 
     /// Returns whether the feature is done (success or error)
@@ -114,34 +315,52 @@ impl BookmarkManager {
         )
     }
 
-    /// Returns true if the bookmark was successfully created (should exit app)
-    pub fn should_exit_app(&self) -> bool {
-        matches!(self.state, BookmarkManagerState::Success { .. })
-    }
-
     /// Gets the prompt text for the current state
     pub fn get_prompt(&self) -> String {
         match &self.state {
-            BookmarkManagerState::Confirming { directory } => {
-                directory.clone()
+            BookmarkManagerState::Listing { .. } => match &self.store {
+                Some(store) => format!("{} bookmarks in {}", self.bookmarks().len(), store.path().display()),
+                None => format!("{} bookmarks", self.bookmarks().len()),
+            },
+            BookmarkManagerState::Frequent { query, .. } if query.is_empty() => {
+                format!("{} visited directories, ranked by frecency", self.matches.len())
+            }
+            BookmarkManagerState::Frequent { query, .. } => {
+                format!("{} directories match '{}'", self.matches.len(), query)
+            }
+            BookmarkManagerState::Adding { .. } => {
+                format!("Name for {}:", self.current_dir.display())
             }
-            BookmarkManagerState::Processing => "Creating bookmark...".to_string(),
+            BookmarkManagerState::Renaming { old_name, .. } => {
+                format!("Enter new name for bookmark '{}':", old_name)
+            }
+            BookmarkManagerState::ConfirmingDelete { name, path } => format!("{}\n{}", name, path),
             BookmarkManagerState::Success { message } => message.clone(),
             BookmarkManagerState::Error { message } => format!("Error: {}", message),
         }
     }
 
     /// Gets confirmation data for structured display
-    pub fn get_confirmation_data(&self) -> Option<String> {
+    pub fn get_confirmation_data(&self) -> Option<(String, String)> {
         match &self.state {
-            BookmarkManagerState::Confirming { directory } => Some(directory.clone()),
+            BookmarkManagerState::ConfirmingDelete { name, path } => Some((name.clone(), path.clone())),
             _ => None,
         }
     }
 
-    /// Gets the RC file path
-    pub fn get_rc_file(&self) -> Option<&PathBuf> {
-        self.rc_file.as_ref()
+    /// Gets the current input text
+    pub fn get_input(&self) -> String {
+        match &self.state {
+            BookmarkManagerState::Adding { input } | BookmarkManagerState::Renaming { input, .. } => {
+                input.clone()
+            }
+            _ => String::new(),
+        }
+    }
+
+    /// Returns the `cd` command for the bookmark the user jumped to
+    pub fn get_exit_command(&self) -> Option<String> {
+        self.exit_command.clone()
     }
 }
 
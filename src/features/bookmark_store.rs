@@ -0,0 +1,339 @@
+use crate::utils::backup::BackupStore;
+use crate::utils::{config, detect_shell, file_ops, get_rc_file_path, shell, ModeError, Result, ShellType};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Longest accepted bookmark name
+const MAX_NAME_LEN: usize = 64;
+
+/// Alias older versions of mode wrote to the RC file as their only bookmark
+const LEGACY_ALIAS: &str = "temp";
+
+/// A named directory
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Bookmark {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+impl Bookmark {
+    /// Returns whether the directory still exists
+    pub fn exists(&self) -> bool {
+        self.path.is_dir()
+    }
+
+    /// Builds the command that takes the given shell to this bookmark
+    pub fn cd_command(&self, shell: ShellType) -> String {
+        shell::cd_command(shell, &self.path)
+    }
+}
+
+/// On-disk layout of the bookmarks file
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BookmarkFile {
+    #[serde(default, rename = "bookmark")]
+    bookmarks: Vec<Bookmark>,
+}
+
+/// Bookmarks persisted in mode's own data file rather than the shell RC file
+#[derive(Debug)]
+pub struct BookmarkStore {
+    path: PathBuf,
+    bookmarks: Vec<Bookmark>,
+}
+
+impl BookmarkStore {
+    /// Returns the location of the bookmarks file
+    pub fn file_path() -> Result<PathBuf> {
+        Ok(config::data_dir()?.join("bookmarks.toml"))
+    }
+
+    /// Loads bookmarks from the default location
+    ///
+    /// The first time, before the file exists, the bookmark older versions
+    /// kept in the RC file is imported if it can be, and the file is written
+    /// so the import isn't attempted again
+    pub fn load() -> Result<Self> {
+        let path = Self::file_path()?;
+        let first_load = !path.exists();
+        let mut store = Self::load_from(path)?;
+
+        if first_load {
+            // A missing or unreadable RC file just means there is nothing to import
+            let _ = store.import_from_rc_file();
+            // If this fails the import is tried again next time, which is harmless
+            let _ = store.save();
+        }
+        Ok(store)
+    }
+
+    /// Imports the legacy bookmark from the detected shell's RC file
+    fn import_from_rc_file(&mut self) -> Result<bool> {
+        let shell = detect_shell()?;
+        let rc_file = get_rc_file_path(shell)?;
+        self.import_legacy(&rc_file, shell, &BackupStore::open()?)
+    }
+
+    /// Loads bookmarks from a file, starting empty if it doesn't exist yet
+    pub fn load_from(path: PathBuf) -> Result<Self> {
+        let bookmarks = if path.exists() {
+            let content = fs::read_to_string(&path)?;
+            let mut file: BookmarkFile = toml::from_str(&content).map_err(|e| {
+                ModeError::FileOperation(format!("Failed to parse {}: {}", path.display(), e))
+            })?;
+            file.bookmarks.sort_by(|a, b| a.name.cmp(&b.name));
+            file.bookmarks
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self { path, bookmarks })
+    }
+
+    /// Returns the file the bookmarks are stored in
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns all bookmarks sorted by name
+    pub fn bookmarks(&self) -> &[Bookmark] {
+        &self.bookmarks
+    }
+
+    /// Looks up a bookmark by name
+    pub fn get(&self, name: &str) -> Option<&Bookmark> {
+        self.bookmarks.iter().find(|b| b.name == name)
+    }
+
+    /// Saves a directory under a new name
+    pub fn add(&mut self, name: &str, path: &Path) -> Result<()> {
+        validate_bookmark_name(name)?;
+        if self.get(name).is_some() {
+            return Err(ModeError::InvalidInput(format!("Bookmark '{}' already exists", name)));
+        }
+        if !path.is_absolute() {
+            return Err(ModeError::InvalidInput(format!(
+                "Bookmark path must be absolute, got '{}'",
+                path.display()
+            )));
+        }
+
+        self.bookmarks.push(Bookmark {
+            name: name.to_string(),
+            path: path.to_path_buf(),
+        });
+        self.bookmarks.sort_by(|a, b| a.name.cmp(&b.name));
+        self.save()
+    }
+
+    /// Renames a bookmark, keeping its directory
+    pub fn rename(&mut self, old_name: &str, new_name: &str) -> Result<()> {
+        validate_bookmark_name(new_name)?;
+        if old_name != new_name && self.get(new_name).is_some() {
+            return Err(ModeError::InvalidInput(format!("Bookmark '{}' already exists", new_name)));
+        }
+
+        let bookmark = self
+            .bookmarks
+            .iter_mut()
+            .find(|b| b.name == old_name)
+            .ok_or_else(|| not_found(old_name))?;
+        bookmark.name = new_name.to_string();
+        self.bookmarks.sort_by(|a, b| a.name.cmp(&b.name));
+        self.save()
+    }
+
+    /// Deletes a bookmark, returning it
+    pub fn remove(&mut self, name: &str) -> Result<Bookmark> {
+        let index = self
+            .bookmarks
+            .iter()
+            .position(|b| b.name == name)
+            .ok_or_else(|| not_found(name))?;
+        let bookmark = self.bookmarks.remove(index);
+        self.save()?;
+        Ok(bookmark)
+    }
+
+    /// Moves the `temp` alias older versions wrote into the store
+    ///
+    /// Those versions saved a single directory as `alias temp='cd "..."'`; it
+    /// becomes a bookmark named `temp` and the alias is deleted from the RC
+    /// file, which is backed up first. Aliases named `temp` that mode didn't
+    /// write or that do something else are left alone. Returns whether a
+    /// bookmark was imported.
+    pub fn import_legacy(&mut self, rc_file: &Path, shell: ShellType, backups: &BackupStore) -> Result<bool> {
+        let Some(dir) = file_ops::list_aliases(rc_file)?
+            .into_iter()
+            .find(|alias| alias.name == LEGACY_ALIAS && alias.managed)
+            .and_then(|alias| legacy_bookmark_dir(&alias.command))
+        else {
+            return Ok(false);
+        };
+
+        file_ops::delete_alias(rc_file, shell, backups, LEGACY_ALIAS)?;
+        if self.get(LEGACY_ALIAS).is_some() {
+            return Ok(false);
+        }
+        self.add(LEGACY_ALIAS, &dir)?;
+        Ok(true)
+    }
+
+    /// Writes the bookmarks file atomically
+    fn save(&self) -> Result<()> {
+        let file = BookmarkFile {
+            bookmarks: self.bookmarks.clone(),
+        };
+        let content = toml::to_string(&file)
+            .map_err(|e| ModeError::FileOperation(format!("Failed to serialize bookmarks: {}", e)))?;
+
+        file_ops::write_atomic(&self.path, &content)
+    }
+}
+
+/// Validates a bookmark name
+///
+/// Names may contain letters, digits, `-`, `_` and `.`
+pub fn validate_bookmark_name(name: &str) -> Result<()> {
+    if name.is_empty() {
+        return Err(ModeError::InvalidInput("Bookmark name cannot be empty".to_string()));
+    }
+    if name.len() > MAX_NAME_LEN {
+        return Err(ModeError::InvalidInput(format!(
+            "Bookmark name cannot be longer than {} characters",
+            MAX_NAME_LEN
+        )));
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        return Err(ModeError::InvalidInput(format!(
+            "Bookmark name '{}' may only contain letters, digits, '-', '_' and '.'",
+            name
+        )));
+    }
+    Ok(())
+}
+
+/// Suggests a bookmark name for a directory from its last component
+pub fn default_name(dir: &Path) -> String {
+    let name: String = dir
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') { c } else { '_' })
+        .take(MAX_NAME_LEN)
+        .collect();
+
+    if name.is_empty() {
+        "root".to_string()
+    } else {
+        name
+    }
+}
+
+/// Returns the directory of a legacy `cd "..."` bookmark alias
+fn legacy_bookmark_dir(command: &str) -> Option<PathBuf> {
+    let dir = command
+        .strip_prefix("cd ")?
+        .trim()
+        .strip_prefix('"')?
+        .strip_suffix('"')?;
+    let dir = PathBuf::from(dir);
+    dir.is_absolute().then_some(dir)
+}
+
+fn not_found(name: &str) -> ModeError {
+    ModeError::InvalidInput(format!("No bookmark named '{}'", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::backup::BackupSettings;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_add_rename_remove_round_trip() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("mode").join("bookmarks.toml");
+
+        let mut store = BookmarkStore::load_from(path.clone()).unwrap();
+        assert!(store.bookmarks().is_empty());
+        store.add("work", Path::new("/srv/work")).unwrap();
+        store.add("api", Path::new("/srv/my api")).unwrap();
+
+        let mut store = BookmarkStore::load_from(path.clone()).unwrap();
+        let names: Vec<&str> = store.bookmarks().iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(names, vec!["api", "work"]);
+
+        store.rename("api", "backend").unwrap();
+        store.remove("work").unwrap();
+
+        let store = BookmarkStore::load_from(path).unwrap();
+        assert_eq!(store.bookmarks().len(), 1);
+        assert_eq!(store.get("backend").unwrap().path, PathBuf::from("/srv/my api"));
+    }
+
+    #[test]
+    fn test_rejects_duplicate_and_invalid_names() {
+        let dir = tempdir().unwrap();
+        let mut store = BookmarkStore::load_from(dir.path().join("bookmarks.toml")).unwrap();
+        store.add("a", Path::new("/tmp")).unwrap();
+        store.add("b", Path::new("/tmp")).unwrap();
+
+        assert!(store.add("a", Path::new("/var")).is_err());
+        assert!(store.rename("a", "b").is_err());
+        assert!(store.rename("missing", "c").is_err());
+        assert!(store.add("has space", Path::new("/tmp")).is_err());
+        assert!(store.add("rel", Path::new("relative/dir")).is_err());
+        assert!(store.remove("missing").is_err());
+    }
+
+    #[test]
+    fn test_import_legacy_temp_alias() {
+        let dir = tempdir().unwrap();
+        let backups = BackupStore::new(dir.path().join("backups"), BackupSettings::default());
+        let rc_file = dir.path().join(".bashrc");
+        fs::write(
+            &rc_file,
+            "alias ll='ls -la'\n\n# Added by mode on 2024-01-01 00:00:00\nalias temp='cd \"/srv/old project\"'\n",
+        )
+        .unwrap();
+
+        let mut store = BookmarkStore::load_from(dir.path().join("bookmarks.toml")).unwrap();
+        assert!(store.import_legacy(&rc_file, ShellType::Bash, &backups).unwrap());
+        assert_eq!(store.get("temp").unwrap().path, PathBuf::from("/srv/old project"));
+        assert_eq!(fs::read_to_string(&rc_file).unwrap(), "alias ll='ls -la'\n");
+        assert_eq!(backups.list(&rc_file).unwrap().len(), 1);
+
+        // A hand-written alias named temp is not a bookmark
+        fs::write(&rc_file, "alias temp='sensors'\n").unwrap();
+        let mut store = BookmarkStore::load_from(dir.path().join("other.toml")).unwrap();
+        assert!(!store.import_legacy(&rc_file, ShellType::Bash, &backups).unwrap());
+        assert!(store.bookmarks().is_empty());
+        assert_eq!(fs::read_to_string(&rc_file).unwrap(), "alias temp='sensors'\n");
+    }
+
+    #[test]
+    fn test_cd_command_quotes_path() {
+        let bookmark = Bookmark {
+            name: "x".to_string(),
+            path: PathBuf::from("/home/me/it's here"),
+        };
+        assert_eq!(bookmark.cd_command(ShellType::Bash), r"cd '/home/me/it'\''s here'");
+        assert_eq!(bookmark.cd_command(ShellType::Fish), r"cd '/home/me/it\'s here'");
+    }
+
+    #[test]
+    fn test_default_name() {
+        assert_eq!(default_name(Path::new("/home/me/my project")), "my_project");
+        assert_eq!(default_name(Path::new("/srv/api.v2")), "api.v2");
+        assert_eq!(default_name(Path::new("/")), "root");
+    }
+}
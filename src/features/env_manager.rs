@@ -0,0 +1,607 @@
+use crate::utils::backup::BackupStore;
+use crate::utils::{self, file_ops, ModeError, Result, ShellType};
+use std::collections::BTreeMap;
+use std::env;
+use std::path::PathBuf;
+
+/// Environment Variables Manager state machine
+#[derive(Debug, Clone)]
+pub enum EnvManagerState {
+    /// Browsing variables
+    Listing { selected: usize },
+    /// Typing a filter for the list
+    Filtering { selected: usize },
+    /// Entering the name of a new variable
+    EnteringName { input: String },
+    /// Entering the value of a new variable
+    EnteringValue { name: String, input: String },
+    /// Confirming the new export before writing it
+    Confirming { name: String, value: String },
+    /// Editing the value of an existing variable
+    EditingValue { name: String, input: String },
+    /// Confirming removal of an export
+    ConfirmingUnset { name: String, value: String },
+    /// Success state with message
+    Success { message: String },
+    /// Error state with message
+    Error { message: String },
+}
+
+/// A variable from the process environment, the RC file, or both
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvVar {
+    pub name: String,
+    /// Value in the environment mode was started with
+    pub value: Option<String>,
+    /// Value as exported in the RC file (last definition wins)
+    pub rc_value: Option<String>,
+    /// Whether the RC export was added by mode
+    pub managed: bool,
+}
+
+impl EnvVar {
+    /// Returns whether the variable is exported from the RC file
+    pub fn in_rc(&self) -> bool {
+        self.rc_value.is_some()
+    }
+
+    /// Returns the value to show: the live one, falling back to the RC text
+    pub fn display_value(&self) -> &str {
+        self.value.as_deref().or(self.rc_value.as_deref()).unwrap_or("")
+    }
+}
+
+/// Environment Variables Manager feature
+#[derive(Debug)]
+pub struct EnvManager {
+    pub state: EnvManagerState,
+    shell: Option<ShellType>,
+    rc_file: Option<PathBuf>,
+    /// Where the RC file is backed up before each write
+    backups: Option<BackupStore>,
+    /// Environment mode was started with
+    process_env: BTreeMap<String, String>,
+    /// Process and RC variables merged, sorted by name
+    vars: Vec<EnvVar>,
+    /// Case-insensitive substring filter on names and values
+    filter: String,
+    /// Commands that apply this session's changes to the live shell, in order
+    pending: Vec<String>,
+}
+
+impl EnvManager {
+    /// Creates a new Environment Variables Manager
+    pub fn new() -> Self {
+        Self {
+            state: EnvManagerState::Listing { selected: 0 },
+            shell: None,
+            rc_file: None,
+            backups: None,
+            process_env: env::vars().collect(),
+            vars: Vec::new(),
+            filter: String::new(),
+            pending: Vec::new(),
+        }
+    }
+
+    /// Initializes the manager by detecting the RC file and reading its exports
+    pub fn initialize(&mut self) -> Result<()> {
+        let shell = utils::detect_shell()?;
+        self.rc_file = Some(utils::get_rc_file_path(shell)?);
+        self.shell = Some(shell);
+        self.backups = Some(BackupStore::open()?);
+        self.refresh_vars()
+    }
+
+    /// Rebuilds the variable list from the process environment and the RC file
+    pub fn refresh_vars(&mut self) -> Result<()> {
+        let mut merged: BTreeMap<String, EnvVar> = self
+            .process_env
+            .iter()
+            .map(|(name, value)| {
+                let var = EnvVar {
+                    name: name.clone(),
+                    value: Some(value.clone()),
+                    rc_value: None,
+                    managed: false,
+                };
+                (name.clone(), var)
+            })
+            .collect();
+
+        if let Some(rc_file) = &self.rc_file {
+            for export in file_ops::list_exports(rc_file)? {
+                let var = merged.entry(export.name.clone()).or_insert_with(|| EnvVar {
+                    name: export.name.clone(),
+                    value: None,
+                    rc_value: None,
+                    managed: false,
+                });
+                var.rc_value = Some(export.value);
+                var.managed = export.managed;
+            }
+        }
+
+        self.vars = merged.into_values().collect();
+        self.clamp_selection();
+        Ok(())
+    }
+
+    /// Returns to the list, focusing the named variable if it is visible
+    pub fn show_list(&mut self, focus: Option<&str>) {
+        if let Err(e) = self.refresh_vars() {
+            self.state = EnvManagerState::Error {
+                message: format!("Failed to read exports: {}", e),
+            };
+            return;
+        }
+
+        let selected = focus
+            .and_then(|name| self.visible_vars().iter().position(|v| v.name == name))
+            .unwrap_or(0);
+        self.state = EnvManagerState::Listing { selected };
+    }
+
+    /// Returns the variables matching the filter
+    pub fn visible_vars(&self) -> Vec<&EnvVar> {
+        let filter = self.filter.to_lowercase();
+        self.vars
+            .iter()
+            .filter(|v| {
+                filter.is_empty()
+                    || v.name.to_lowercase().contains(&filter)
+                    || v.display_value().to_lowercase().contains(&filter)
+            })
+            .collect()
+    }
+
+    /// Returns the number of variables exported from the RC file
+    pub fn rc_count(&self) -> usize {
+        self.vars.iter().filter(|v| v.in_rc()).count()
+    }
+
+    /// Returns the current filter text
+    pub fn filter(&self) -> &str {
+        &self.filter
+    }
+
+    /// Returns the variable highlighted in the list, if any
+    pub fn selected_var(&self) -> Option<&EnvVar> {
+        match self.state {
+            EnvManagerState::Listing { selected } | EnvManagerState::Filtering { selected } => {
+                self.visible_vars().get(selected).copied()
+            }
+            _ => None,
+        }
+    }
+
+    /// Keeps the selection inside the visible list
+    fn clamp_selection(&mut self) {
+        let total = self.visible_vars().len();
+        if let EnvManagerState::Listing { selected } | EnvManagerState::Filtering { selected } = &mut self.state {
+            *selected = (*selected).min(total.saturating_sub(1));
+        }
+    }
+
+    /// Moves selection up
+    pub fn previous(&mut self) {
+        let total = self.visible_vars().len();
+        if let EnvManagerState::Listing { selected } | EnvManagerState::Filtering { selected } = &mut self.state {
+            if total > 0 {
+                *selected = if *selected == 0 { total - 1 } else { *selected - 1 };
+            }
+        }
+    }
+
+    /// Moves selection down
+    pub fn next(&mut self) {
+        let total = self.visible_vars().len();
+        if let EnvManagerState::Listing { selected } | EnvManagerState::Filtering { selected } = &mut self.state {
+            if total > 0 {
+                *selected = (*selected + 1) % total;
+            }
+        }
+    }
+
+    /// Starts typing a filter
+    pub fn start_filter(&mut self) {
+        if let EnvManagerState::Listing { selected } = self.state {
+            self.state = EnvManagerState::Filtering { selected };
+        }
+    }
+
+    /// Keeps the filter and returns to the list
+    pub fn finish_filter(&mut self) {
+        if let EnvManagerState::Filtering { selected } = self.state {
+            self.state = EnvManagerState::Listing { selected };
+        }
+    }
+
+    /// Clears the filter and returns to the list
+    pub fn cancel_filter(&mut self) {
+        self.filter.clear();
+        self.state = EnvManagerState::Listing { selected: 0 };
+    }
+
+    /// Starts adding a new variable
+    pub fn start_add(&mut self) {
+        self.state = EnvManagerState::EnteringName {
+            input: String::new(),
+        };
+    }
+
+    /// Starts editing the selected variable
+    ///
+    /// RC exports are edited as written (so `$HOME` stays a reference);
+    /// variables only in the environment start from their live value and are
+    /// persisted to the RC file on save
+    pub fn start_edit(&mut self) {
+        if let Some(var) = self.selected_var().cloned() {
+            self.state = EnvManagerState::EditingValue {
+                input: var.rc_value.clone().or(var.value).unwrap_or_default(),
+                name: var.name,
+            };
+        }
+    }
+
+    /// Starts removing the selected variable's export
+    pub fn start_unset(&mut self) {
+        let Some(var) = self.selected_var().cloned() else {
+            return;
+        };
+
+        match (&var.rc_value, &self.rc_file) {
+            (Some(value), _) => {
+                self.state = EnvManagerState::ConfirmingUnset {
+                    name: var.name,
+                    value: value.clone(),
+                };
+            }
+            (None, rc_file) => {
+                let source = rc_file
+                    .as_ref()
+                    .map(|f| f.display().to_string())
+                    .unwrap_or_else(|| "your RC file".to_string());
+                self.state = EnvManagerState::Error {
+                    message: format!(
+                        "{} is not exported from {}.\n\n\
+                        It comes from elsewhere (system profile, parent process), \
+                        so there is no line for mode to remove.",
+                        var.name, source
+                    ),
+                };
+            }
+        }
+    }
+
+    /// Handles input for the current state
+    pub fn handle_char(&mut self, c: char) {
+        match &mut self.state {
+            EnvManagerState::EnteringName { input }
+            | EnvManagerState::EnteringValue { input, .. }
+            | EnvManagerState::EditingValue { input, .. } => {
+                input.push(c);
+            }
+            EnvManagerState::Filtering { selected } => {
+                self.filter.push(c);
+                *selected = 0;
+            }
+            _ => {}
+        }
+    }
+
+    /// Handles backspace
+    pub fn handle_backspace(&mut self) {
+        match &mut self.state {
+            EnvManagerState::EnteringName { input }
+            | EnvManagerState::EnteringValue { input, .. }
+            | EnvManagerState::EditingValue { input, .. } => {
+                input.pop();
+            }
+            EnvManagerState::Filtering { selected } => {
+                self.filter.pop();
+                *selected = 0;
+            }
+            _ => {}
+        }
+    }
+
+    /// Advances to the next state
+    pub fn advance(&mut self) {
+        match self.state.clone() {
+            EnvManagerState::EnteringName { input } => {
+                let name = input.trim().to_string();
+
+                if let Err(e) = validate_var_name(&name) {
+                    self.state = EnvManagerState::Error {
+                        message: e.to_string(),
+                    };
+                    return;
+                }
+
+                if self.vars.iter().any(|v| v.name == name && v.in_rc()) {
+                    self.state = EnvManagerState::Error {
+                        message: format!("{} is already exported in RC file; edit it instead", name),
+                    };
+                    return;
+                }
+
+                // Start from the live value when persisting an inherited variable
+                let input = self.process_env.get(&name).cloned().unwrap_or_default();
+                self.state = EnvManagerState::EnteringValue { name, input };
+            }
+            EnvManagerState::EnteringValue { name, input } => {
+                self.state = EnvManagerState::Confirming { name, value: input };
+            }
+            EnvManagerState::Confirming { name, value } | EnvManagerState::EditingValue { name, input: value } => {
+                self.state = match self.set_var(&name, &value) {
+                    Ok(backup_path) => EnvManagerState::Success {
+                        message: format!(
+                            "✓ {} saved to RC file!\n\n\
+                            {}\n\
+                            Backup: {}",
+                            name,
+                            file_ops::export_line(self.shell(), &name, &value),
+                            backup_path.display()
+                        ),
+                    },
+                    Err(e) => EnvManagerState::Error {
+                        message: format!("Failed to save {}: {}", name, e),
+                    },
+                };
+            }
+            EnvManagerState::ConfirmingUnset { name, .. } => {
+                self.state = match self.unset_var(&name) {
+                    Ok(backup_path) => EnvManagerState::Success {
+                        message: format!(
+                            "✓ {} removed from RC file and unset!\n\n\
+                            Backup: {}",
+                            name,
+                            backup_path.display()
+                        ),
+                    },
+                    Err(e) => EnvManagerState::Error {
+                        message: format!("Failed to unset {}: {}", name, e),
+                    },
+                };
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns the detected RC file or an error if the manager is not initialized
+    fn require_rc_file(&self) -> Result<PathBuf> {
+        self.rc_file
+            .clone()
+            .ok_or_else(|| ModeError::RcFileNotFound("shell RC file has not been detected".to_string()))
+    }
+
+    /// Returns the backup store, or an error if the manager is not initialized
+    fn require_backups(&self) -> Result<&BackupStore> {
+        self.backups
+            .as_ref()
+            .ok_or_else(|| ModeError::Config("backup store has not been opened".to_string()))
+    }
+
+    /// Returns the detected shell, assuming bash before detection
+    fn shell(&self) -> ShellType {
+        self.shell.unwrap_or(ShellType::Bash)
+    }
+
+    /// Persists a variable, rewriting its export if the RC file already has one
+    ///
+    /// Returns the path to the RC file backup
+    pub fn set_var(&mut self, name: &str, value: &str) -> Result<PathBuf> {
+        let rc_file = self.require_rc_file()?;
+        validate_var_name(name)?;
+
+        let exported = file_ops::list_exports(&rc_file)?.iter().any(|e| e.name == name);
+        let backup_path = if exported {
+            file_ops::update_export(&rc_file, self.shell(), self.require_backups()?, name, value)?
+        } else {
+            file_ops::append_export(&rc_file, self.shell(), self.require_backups()?, name, value)?
+        };
+
+        self.pending.push(file_ops::export_line(self.shell(), name, value));
+        self.refresh_vars()?;
+
+        Ok(backup_path)
+    }
+
+    /// Removes a variable's exports from the RC file and unsets it in the live shell
+    ///
+    /// Returns the path to the RC file backup
+    pub fn unset_var(&mut self, name: &str) -> Result<PathBuf> {
+        let rc_file = self.require_rc_file()?;
+
+        let backup_path = file_ops::delete_export(&rc_file, self.shell(), self.require_backups()?, name)?;
+        self.process_env.remove(name);
+        self.pending.push(match self.shell() {
+            ShellType::Bash | ShellType::Zsh => format!("unset {}", name),
+            ShellType::Fish => format!("set --erase {}", name),
+        });
+        self.refresh_vars()?;
+
+        Ok(backup_path)
+    }
+
+    /// Goes back to the previous state or cancels
+    pub fn go_back(&mut self) {
+        match &self.state {
+            EnvManagerState::EnteringValue { .. } | EnvManagerState::Confirming { .. } => {
+                self.state = EnvManagerState::EnteringName {
+                    input: String::new(),
+                };
+            }
+            EnvManagerState::EditingValue { name, .. } | EnvManagerState::ConfirmingUnset { name, .. } => {
+                let name = name.clone();
+                self.show_list(Some(&name));
+            }
+            EnvManagerState::EnteringName { .. }
+            | EnvManagerState::Success { .. }
+            | EnvManagerState::Error { .. } => {
+                self.show_list(None);
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns whether the feature is done (success or error)
+    pub fn is_done(&self) -> bool {
+        matches!(
+            self.state,
+            EnvManagerState::Success { .. } | EnvManagerState::Error { .. }
+        )
+    }
+
+    /// Gets the current prompt text
+    pub fn get_prompt(&self) -> String {
+        match &self.state {
+            EnvManagerState::Listing { .. } | EnvManagerState::Filtering { .. } => match &self.rc_file {
+                Some(rc_file) => format!(
+                    "{} variables, {} exported from {}",
+                    self.vars.len(),
+                    self.rc_count(),
+                    rc_file.display()
+                ),
+                None => format!("{} variables", self.vars.len()),
+            },
+            EnvManagerState::EnteringName { .. } => "Enter variable name (e.g., 'EDITOR', 'GOPATH'):".to_string(),
+            EnvManagerState::EnteringValue { name, .. } => {
+                format!("Enter value for {} ($VAR references expand when the shell starts):", name)
+            }
+            EnvManagerState::EditingValue { name, .. } => format!("Edit value of {}:", name),
+            EnvManagerState::Confirming { name, value } | EnvManagerState::ConfirmingUnset { name, value } => {
+                format!("{}\n{}", name, value)
+            }
+            EnvManagerState::Success { message } => message.clone(),
+            EnvManagerState::Error { message } => format!("Error: {}", message),
+        }
+    }
+
+    /// Gets confirmation data for structured display
+    pub fn get_confirmation_data(&self) -> Option<(String, String)> {
+        match &self.state {
+            EnvManagerState::Confirming { name, value } | EnvManagerState::ConfirmingUnset { name, value } => {
+                Some((name.clone(), value.clone()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Gets the current input text
+    pub fn get_input(&self) -> String {
+        match &self.state {
+            EnvManagerState::EnteringName { input }
+            | EnvManagerState::EnteringValue { input, .. }
+            | EnvManagerState::EditingValue { input, .. } => input.clone(),
+            _ => String::new(),
+        }
+    }
+
+    /// Returns whether the RC file was modified during this session
+    pub fn is_modified(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Builds the command that applies this session's changes to the live shell
+    pub fn get_exit_command(&self) -> Option<String> {
+        if self.pending.is_empty() {
+            None
+        } else {
+            Some(self.pending.join("; "))
+        }
+    }
+}
+
+impl Default for EnvManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Validates an environment variable name
+pub fn validate_var_name(name: &str) -> Result<()> {
+    if file_ops::is_valid_var_name(name) {
+        Ok(())
+    } else {
+        Err(ModeError::InvalidInput(format!(
+            "'{}' is not a valid variable name (letters, digits and underscores, not starting with a digit)",
+            name
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::backup::BackupSettings;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn manager_with(rc_content: &str, process_env: &[(&str, &str)]) -> (tempfile::TempDir, EnvManager) {
+        let dir = tempdir().unwrap();
+        let rc_file = dir.path().join(".bashrc");
+        fs::write(&rc_file, rc_content).unwrap();
+
+        let mut manager = EnvManager::new();
+        manager.rc_file = Some(rc_file);
+        manager.backups = Some(BackupStore::new(dir.path().join("backups"), BackupSettings::default()));
+        manager.process_env = process_env
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        manager.refresh_vars().unwrap();
+        (dir, manager)
+    }
+
+    #[test]
+    fn test_merges_process_env_with_rc_exports() {
+        let (_dir, manager) = manager_with(
+            "export EDITOR=vim\nexport NOT_LOADED=1\n",
+            &[("EDITOR", "vim"), ("HOME", "/home/me")],
+        );
+
+        let names: Vec<&str> = manager.vars.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, vec!["EDITOR", "HOME", "NOT_LOADED"]);
+        assert!(manager.vars[0].in_rc());
+        assert!(!manager.vars[1].in_rc());
+        assert_eq!(manager.vars[2].value, None);
+        assert_eq!(manager.vars[2].display_value(), "1");
+        assert_eq!(manager.rc_count(), 2);
+    }
+
+    #[test]
+    fn test_set_and_unset_build_exit_command() {
+        let (_dir, mut manager) = manager_with("export EDITOR=vim\n", &[("EDITOR", "vim")]);
+
+        manager.set_var("EDITOR", "nvim").unwrap();
+        manager.set_var("GOPATH", "$HOME/go").unwrap();
+        manager.unset_var("EDITOR").unwrap();
+
+        assert_eq!(
+            manager.get_exit_command().unwrap(),
+            "export EDITOR=\"nvim\"; export GOPATH=\"$HOME/go\"; unset EDITOR"
+        );
+        let gopath = manager.vars.iter().find(|v| v.name == "GOPATH").unwrap();
+        assert_eq!(gopath.rc_value.as_deref(), Some("$HOME/go"));
+        assert!(gopath.managed);
+        assert!(!manager.vars.iter().any(|v| v.name == "EDITOR"));
+    }
+
+    #[test]
+    fn test_filter_and_unset_of_inherited_variable() {
+        let (_dir, mut manager) = manager_with("", &[("HOME", "/home/me"), ("SHELL", "/bin/bash")]);
+
+        manager.start_filter();
+        for c in "bash".chars() {
+            manager.handle_char(c);
+        }
+        manager.finish_filter();
+        assert_eq!(manager.selected_var().unwrap().name, "SHELL");
+
+        // Inherited variables have no RC line to remove
+        manager.start_unset();
+        assert!(matches!(manager.state, EnvManagerState::Error { .. }));
+    }
+}
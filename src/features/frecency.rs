@@ -0,0 +1,268 @@
+use crate::utils::{config, file_ops, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Once visit counts add up to this, all of them are scaled down so stale
+/// directories fade out of the list
+const MAX_TOTAL_RANK: f64 = 10_000.0;
+
+/// Factor applied to every count when the database is aged
+const AGING_FACTOR: f64 = 0.9;
+
+/// Aged entries whose count drops below this are forgotten
+const MIN_RANK: f64 = 1.0;
+
+const HOUR: u64 = 60 * 60;
+const DAY: u64 = 24 * HOUR;
+const WEEK: u64 = 7 * DAY;
+
+/// A directory the shell has visited
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirVisit {
+    pub path: PathBuf,
+    /// Number of visits, reduced as the database ages
+    pub rank: f64,
+    /// Unix time of the most recent visit
+    pub last_visit: u64,
+}
+
+impl DirVisit {
+    /// Scores the directory by visit count weighted by how recently it was visited
+    pub fn score(&self, now: u64) -> f64 {
+        let age = now.saturating_sub(self.last_visit);
+        let weight = if age < HOUR {
+            4.0
+        } else if age < DAY {
+            2.0
+        } else if age < WEEK {
+            0.5
+        } else {
+            0.25
+        };
+        self.rank * weight
+    }
+}
+
+/// Directory visit history recorded by the shell wrapper
+///
+/// Stored as tab-separated `rank, last visit, path` lines in mode's data directory.
+#[derive(Debug)]
+pub struct FrecencyDb {
+    path: PathBuf,
+    entries: Vec<DirVisit>,
+}
+
+impl FrecencyDb {
+    /// Returns the location of the history file
+    pub fn file_path() -> Result<PathBuf> {
+        Ok(config::data_dir()?.join("dirs.tsv"))
+    }
+
+    /// Loads history from the default location
+    pub fn load() -> Result<Self> {
+        Self::load_from(Self::file_path()?)
+    }
+
+    /// Loads history from a file, starting empty if it doesn't exist yet
+    ///
+    /// Malformed lines are skipped so a damaged file never blocks the wrapper
+    pub fn load_from(path: PathBuf) -> Result<Self> {
+        let entries = if path.exists() {
+            fs::read_to_string(&path)?.lines().filter_map(parse_line).collect()
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self { path, entries })
+    }
+
+    /// Returns every recorded directory
+    pub fn entries(&self) -> &[DirVisit] {
+        &self.entries
+    }
+
+    /// Records a visit to a directory at the given time
+    pub fn record(&mut self, dir: &Path, now: u64) {
+        match self.entries.iter_mut().find(|e| e.path == dir) {
+            Some(entry) => {
+                entry.rank += 1.0;
+                entry.last_visit = now;
+            }
+            None => self.entries.push(DirVisit {
+                path: dir.to_path_buf(),
+                rank: 1.0,
+                last_visit: now,
+            }),
+        }
+
+        let total: f64 = self.entries.iter().map(|e| e.rank).sum();
+        if total > MAX_TOTAL_RANK {
+            for entry in &mut self.entries {
+                entry.rank *= AGING_FACTOR;
+            }
+            self.entries.retain(|e| e.rank >= MIN_RANK);
+        }
+    }
+
+    /// Forgets a directory, returning whether it was recorded
+    pub fn remove(&mut self, dir: &Path) -> bool {
+        let before = self.entries.len();
+        self.entries.retain(|e| e.path != dir);
+        self.entries.len() != before
+    }
+
+    /// Writes the history file atomically
+    pub fn save(&self) -> Result<()> {
+        let content: String = self
+            .entries
+            .iter()
+            .map(|e| format!("{:.3}\t{}\t{}\n", e.rank, e.last_visit, e.path.display()))
+            .collect();
+        file_ops::write_atomic(&self.path, &content)
+    }
+
+    /// Returns existing directories matching the query, best first
+    ///
+    /// Every whitespace-separated term must fuzzy-match the path. Matches whose
+    /// last component fits the last term are preferred, so `mode jump api`
+    /// favours `~/src/api` over `~/src/api/docs`.
+    pub fn ranked(&self, query: &str, now: u64) -> Vec<DirVisit> {
+        let last_term = query.split_whitespace().last();
+        let mut matches: Vec<(f64, &DirVisit)> = self
+            .entries
+            .iter()
+            .filter(|e| fuzzy_match(query, &e.path.display().to_string()))
+            .filter(|e| e.path.is_dir())
+            .map(|e| {
+                let name = e.path.file_name().map(|n| n.to_string_lossy().into_owned());
+                let bonus = match (last_term, name) {
+                    (Some(term), Some(name)) if fuzzy_match(term, &name) => 2.0,
+                    _ => 1.0,
+                };
+                (e.score(now) * bonus, e)
+            })
+            .collect();
+
+        matches.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| a.1.path.cmp(&b.1.path)));
+        matches.into_iter().map(|(_, e)| e.clone()).collect()
+    }
+}
+
+/// Returns the current Unix time in seconds
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Returns whether each whitespace-separated term appears in the text as an
+/// in-order (not necessarily contiguous), case-insensitive subsequence
+pub fn fuzzy_match(query: &str, text: &str) -> bool {
+    let text = text.to_lowercase();
+    query.split_whitespace().all(|term| {
+        let mut chars = text.chars();
+        term.to_lowercase().chars().all(|c| chars.any(|t| t == c))
+    })
+}
+
+/// Parses one `rank<TAB>last_visit<TAB>path` line
+fn parse_line(line: &str) -> Option<DirVisit> {
+    let mut fields = line.splitn(3, '\t');
+    let rank: f64 = fields.next()?.parse().ok()?;
+    let last_visit = fields.next()?.parse().ok()?;
+    let path = PathBuf::from(fields.next()?);
+
+    if !rank.is_finite() || rank <= 0.0 || !path.is_absolute() {
+        return None;
+    }
+
+    Some(DirVisit {
+        path,
+        rank,
+        last_visit,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_fuzzy_match() {
+        assert!(fuzzy_match("", "/home/me/src"));
+        assert!(fuzzy_match("src", "/home/me/src"));
+        assert!(fuzzy_match("hmsrc", "/home/me/src"));
+        assert!(fuzzy_match("me SRC", "/home/me/src"));
+        assert!(!fuzzy_match("crs", "/home/me/src"));
+        assert!(!fuzzy_match("src docs", "/home/me/src"));
+    }
+
+    #[test]
+    fn test_score_prefers_recent_visits() {
+        let now = 100 * WEEK;
+        let visit = |rank, age| DirVisit {
+            path: PathBuf::from("/tmp"),
+            rank,
+            last_visit: now - age,
+        };
+        assert!(visit(2.0, 60).score(now) > visit(2.0, 2 * DAY).score(now));
+        // Frequency can still outweigh recency
+        assert!(visit(20.0, 2 * DAY).score(now) > visit(2.0, 60).score(now));
+    }
+
+    #[test]
+    fn test_record_save_and_rank() {
+        let dir = tempdir().unwrap();
+        let api = dir.path().join("api");
+        let docs = api.join("docs");
+        fs::create_dir_all(&docs).unwrap();
+        let gone = dir.path().join("gone");
+
+        let db_path = dir.path().join("mode").join("dirs.tsv");
+        let mut db = FrecencyDb::load_from(db_path.clone()).unwrap();
+        let now = 10 * WEEK;
+        for _ in 0..3 {
+            db.record(&docs, now);
+        }
+        db.record(&api, now);
+        db.record(&api, now);
+        db.record(&gone, now);
+        db.save().unwrap();
+
+        let db = FrecencyDb::load_from(db_path).unwrap();
+        assert_eq!(db.entries().len(), 3);
+
+        // Missing directories are hidden; more visits rank higher
+        let all: Vec<PathBuf> = db.ranked("", now).into_iter().map(|e| e.path).collect();
+        assert_eq!(all, vec![docs.clone(), api.clone()]);
+
+        // A last component matching the query outweighs a few extra visits
+        let api_first: Vec<PathBuf> = db.ranked("api", now).into_iter().map(|e| e.path).collect();
+        assert_eq!(api_first, vec![api, docs]);
+    }
+
+    #[test]
+    fn test_aging_forgets_rare_directories() {
+        let dir = tempdir().unwrap();
+        let mut db = FrecencyDb::load_from(dir.path().join("dirs.tsv")).unwrap();
+        db.record(Path::new("/rare"), 0);
+        for _ in 0..MAX_TOTAL_RANK as usize {
+            db.record(Path::new("/busy"), 0);
+        }
+
+        assert_eq!(db.entries().len(), 1);
+        assert_eq!(db.entries()[0].path, PathBuf::from("/busy"));
+        assert!(db.entries()[0].rank < MAX_TOTAL_RANK);
+    }
+
+    #[test]
+    fn test_parse_line_skips_malformed_entries() {
+        assert!(parse_line("2.5\t1700000000\t/home/me/my src").is_some());
+        assert!(parse_line("x\t1700000000\t/home").is_none());
+        assert!(parse_line("1\t1700000000\trelative").is_none());
+        assert!(parse_line("1\t1700000000").is_none());
+    }
+}
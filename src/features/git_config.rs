@@ -0,0 +1,386 @@
+use crate::utils::backup::BackupStore;
+use crate::utils::{file_ops, ModeError, Result};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// An entry from an `[alias]` section of a git config file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitAlias {
+    pub name: String,
+    pub command: String,
+}
+
+/// Which git config file aliases are read from and written to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitScope {
+    /// `~/.gitconfig`
+    Global,
+    /// `.git/config` of the repository mode was started in
+    Local,
+}
+
+impl GitScope {
+    /// Returns the scope's display name, matching `git config --global/--local`
+    pub fn name(&self) -> &'static str {
+        match self {
+            GitScope::Global => "global",
+            GitScope::Local => "local",
+        }
+    }
+}
+
+/// A ready-made alias offered in the template catalogue
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AliasTemplate {
+    pub name: &'static str,
+    pub command: &'static str,
+    pub description: &'static str,
+}
+
+/// Aliases offered by the template catalogue
+pub const TEMPLATES: &[AliasTemplate] = &[
+    AliasTemplate {
+        name: "st",
+        command: "status -sb",
+        description: "Short status with branch info",
+    },
+    AliasTemplate {
+        name: "co",
+        command: "checkout",
+        description: "Switch branches or restore files",
+    },
+    AliasTemplate {
+        name: "br",
+        command: "branch",
+        description: "List, create, or delete branches",
+    },
+    AliasTemplate {
+        name: "ci",
+        command: "commit",
+        description: "Record changes",
+    },
+    AliasTemplate {
+        name: "lg",
+        command: "log --graph --oneline --decorate --all",
+        description: "Compact graph of every branch",
+    },
+    AliasTemplate {
+        name: "last",
+        command: "log -1 HEAD --stat",
+        description: "Show the last commit and the files it touched",
+    },
+    AliasTemplate {
+        name: "amend",
+        command: "commit --amend --no-edit",
+        description: "Fold staged changes into the last commit",
+    },
+    AliasTemplate {
+        name: "undo",
+        command: "reset --soft HEAD~1",
+        description: "Undo the last commit, keeping its changes staged",
+    },
+    AliasTemplate {
+        name: "unstage",
+        command: "restore --staged",
+        description: "Remove files from the index, keeping the changes",
+    },
+    AliasTemplate {
+        name: "wip",
+        command: "!git add -A && git commit --no-verify -m 'WIP'",
+        description: "Commit everything as a work-in-progress snapshot",
+    },
+    AliasTemplate {
+        name: "aliases",
+        command: "config --get-regexp ^alias\\.",
+        description: "List every configured alias",
+    },
+];
+
+/// Returns the path of the global git config (`~/.gitconfig`)
+pub fn global_config_path() -> Result<PathBuf> {
+    let home = env::var("HOME")
+        .map_err(|_| ModeError::Config("HOME environment variable not set".to_string()))?;
+    Ok(PathBuf::from(home).join(".gitconfig"))
+}
+
+/// Finds `.git/config` for the repository containing `dir`, if any
+pub fn local_config_path(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .map(|d| d.join(".git").join("config"))
+        .find(|config| config.is_file())
+}
+
+/// Lists every alias in a git config file, in file order
+///
+/// A missing file has no aliases
+pub fn list_git_aliases(path: &Path) -> Result<Vec<GitAlias>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(path)?;
+    let mut in_alias = false;
+    let mut aliases = Vec::new();
+
+    for line in content.lines() {
+        if let Some(section) = parse_section(line) {
+            in_alias = section == "alias";
+        } else if in_alias {
+            if let Some((name, command)) = parse_entry(line) {
+                aliases.push(GitAlias { name, command });
+            }
+        }
+    }
+
+    Ok(aliases)
+}
+
+/// Adds or replaces aliases in one write
+///
+/// Existing entries are rewritten in place keeping their indentation; new
+/// ones go at the end of the last `[alias]` section, which is created if the
+/// file has none. Returns the backup path, or None if the file was created.
+pub fn set_git_aliases(path: &Path, store: &BackupStore, aliases: &[GitAlias]) -> Result<Option<PathBuf>> {
+    for alias in aliases {
+        validate_git_alias_name(&alias.name)?;
+        if alias.command.trim().is_empty() {
+            return Err(ModeError::InvalidInput(format!("Alias '{}' needs a command", alias.name)));
+        }
+    }
+
+    let content = if path.exists() {
+        fs::read_to_string(path)?
+    } else {
+        String::new()
+    };
+    let mut lines: Vec<String> = content.lines().map(|line| line.to_string()).collect();
+
+    for alias in aliases {
+        let entry = format!("{} = {}", alias.name, quote_value(alias.command.trim()));
+        match find_entry(&lines, &alias.name) {
+            Some(index) => {
+                let indent: String = lines[index].chars().take_while(|c| c.is_whitespace()).collect();
+                lines[index] = format!("{}{}", indent, entry);
+            }
+            None => match last_alias_entry(&lines) {
+                Some(index) => lines.insert(index + 1, format!("\t{}", entry)),
+                None => {
+                    if lines.last().is_some_and(|line| !line.trim().is_empty()) {
+                        lines.push(String::new());
+                    }
+                    lines.push("[alias]".to_string());
+                    lines.push(format!("\t{}", entry));
+                }
+            },
+        }
+    }
+
+    write_lines(path, store, &lines)
+}
+
+/// Removes an alias, returning the backup path
+pub fn delete_git_alias(path: &Path, store: &BackupStore, name: &str) -> Result<Option<PathBuf>> {
+    let content = fs::read_to_string(path)?;
+    let mut lines: Vec<String> = content.lines().map(|line| line.to_string()).collect();
+    let index =
+        find_entry(&lines, name).ok_or_else(|| ModeError::InvalidInput(format!("No git alias named '{}'", name)))?;
+    lines.remove(index);
+
+    write_lines(path, store, &lines)
+}
+
+/// Validates a git alias name
+///
+/// Git config keys start with a letter and contain only letters, digits and `-`
+pub fn validate_git_alias_name(name: &str) -> Result<()> {
+    if name.is_empty() {
+        return Err(ModeError::InvalidInput("Alias name cannot be empty".to_string()));
+    }
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return Err(ModeError::InvalidInput(format!(
+            "Git alias '{}' must start with a letter",
+            name
+        )));
+    }
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return Err(ModeError::InvalidInput(format!(
+            "Git alias '{}' may only contain letters, digits and '-'",
+            name
+        )));
+    }
+    Ok(())
+}
+
+/// Writes lines through the shared backup-and-replace path
+fn write_lines(path: &Path, store: &BackupStore, lines: &[String]) -> Result<Option<PathBuf>> {
+    if path.exists() {
+        file_ops::rewrite_with_backup(path, store, lines).map(Some)
+    } else {
+        file_ops::write_atomic(path, &(lines.join("\n") + "\n"))?;
+        Ok(None)
+    }
+}
+
+/// Returns the index of the last line belonging to an `[alias]` section
+fn last_alias_entry(lines: &[String]) -> Option<usize> {
+    let mut in_alias = false;
+    let mut last = None;
+
+    for (i, line) in lines.iter().enumerate() {
+        if let Some(section) = parse_section(line) {
+            in_alias = section == "alias";
+            if in_alias {
+                last = Some(i);
+            }
+        } else if in_alias && parse_entry(line).is_some() {
+            last = Some(i);
+        }
+    }
+
+    last
+}
+
+/// Returns the line index of an alias entry (names are case-insensitive in git)
+fn find_entry(lines: &[String], name: &str) -> Option<usize> {
+    let mut in_alias = false;
+
+    lines.iter().position(|line| {
+        if let Some(section) = parse_section(line) {
+            in_alias = section == "alias";
+            return false;
+        }
+        in_alias && parse_entry(line).is_some_and(|(entry, _)| entry.eq_ignore_ascii_case(name))
+    })
+}
+
+/// Returns the lowercased section name of a `[section]` header line
+fn parse_section(line: &str) -> Option<String> {
+    let trimmed = line.trim();
+    let inner = trimmed.strip_prefix('[')?.split(']').next()?;
+    Some(inner.trim().to_lowercase())
+}
+
+/// Parses a `name = value` entry, unquoting the value and dropping trailing comments
+fn parse_entry(line: &str) -> Option<(String, String)> {
+    let trimmed = line.trim();
+    if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with(';') {
+        return None;
+    }
+
+    let (name, raw) = trimmed.split_once('=')?;
+    let name = name.trim();
+    if name.is_empty() || name.contains(char::is_whitespace) {
+        return None;
+    }
+
+    let mut value = String::new();
+    let mut in_quotes = false;
+    let mut chars = raw.trim().chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            '\\' => match chars.next() {
+                Some('n') => value.push('\n'),
+                Some('t') => value.push('\t'),
+                Some(other) => value.push(other),
+                None => {}
+            },
+            '#' | ';' if !in_quotes => break,
+            _ => value.push(c),
+        }
+    }
+
+    Some((name.to_string(), value.trim_end().to_string()))
+}
+
+/// Quotes a value when git would otherwise misread it
+fn quote_value(value: &str) -> String {
+    if value.contains(['"', '\\', '#', ';']) || value.trim() != value {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::backup::BackupSettings;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_parse_entry_unquotes_values() {
+        assert_eq!(
+            parse_entry("\tlg = log --graph # comment"),
+            Some(("lg".to_string(), "log --graph".to_string()))
+        );
+        assert_eq!(
+            parse_entry(r#"  wip = "!git commit -m \"WIP; later\"""#),
+            Some(("wip".to_string(), r#"!git commit -m "WIP; later""#.to_string()))
+        );
+        assert_eq!(parse_entry("# just a comment"), None);
+        assert_eq!(parse_section("[alias]"), Some("alias".to_string()));
+        assert_eq!(parse_section(r#"[remote "origin"]"#), Some("remote \"origin\"".to_string()));
+    }
+
+    #[test]
+    fn test_set_and_delete_preserve_other_sections() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(".gitconfig");
+        let store = BackupStore::new(dir.path().join("backups"), BackupSettings::default());
+        fs::write(
+            &path,
+            "[user]\n\tname = Me\n[alias]\n    st = status\n[core]\n\teditor = vim\n",
+        )
+        .unwrap();
+
+        let backup = set_git_aliases(
+            &path,
+            &store,
+            &[
+                GitAlias {
+                    name: "st".to_string(),
+                    command: "status -sb".to_string(),
+                },
+                GitAlias {
+                    name: "wip".to_string(),
+                    command: "!git commit -m 'a;b'".to_string(),
+                },
+            ],
+        )
+        .unwrap();
+        assert!(backup.unwrap().exists());
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "[user]\n\tname = Me\n[alias]\n    st = status -sb\n\twip = \"!git commit -m 'a;b'\"\n[core]\n\teditor = vim\n"
+        );
+
+        let aliases = list_git_aliases(&path).unwrap();
+        assert_eq!(aliases[1].command, "!git commit -m 'a;b'");
+
+        delete_git_alias(&path, &store, "st").unwrap();
+        assert_eq!(list_git_aliases(&path).unwrap().len(), 1);
+        assert!(delete_git_alias(&path, &store, "st").is_err());
+    }
+
+    #[test]
+    fn test_creates_alias_section_and_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(".gitconfig");
+        let store = BackupStore::new(dir.path().join("backups"), BackupSettings::default());
+        let lg = GitAlias {
+            name: "lg".to_string(),
+            command: "log --oneline".to_string(),
+        };
+
+        assert_eq!(set_git_aliases(&path, &store, std::slice::from_ref(&lg)).unwrap(), None);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "[alias]\n\tlg = log --oneline\n");
+
+        let bad = GitAlias {
+            name: "2fast".to_string(),
+            command: "log".to_string(),
+        };
+        assert!(set_git_aliases(&path, &store, &[bad]).is_err());
+    }
+}
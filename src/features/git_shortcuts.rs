@@ -0,0 +1,418 @@
+use crate::features::git_config::{self, AliasTemplate, GitAlias, GitScope, TEMPLATES};
+use crate::utils::backup::BackupStore;
+use crate::utils::{ModeError, Result};
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// Git Shortcuts state machine
+#[derive(Debug, Clone)]
+pub enum GitShortcutsState {
+    /// Browsing aliases in the current scope
+    Listing { selected: usize },
+    /// Entering the name of a new alias
+    EnteringName { input: String },
+    /// Entering the command for a new alias
+    EnteringCommand { name: String, input: String },
+    /// Editing the command of an existing alias
+    EditingCommand { name: String, input: String },
+    /// Confirming deletion of an alias
+    ConfirmingDelete { name: String, command: String },
+    /// Picking templates to install
+    Templates { selected: usize, checked: Vec<bool> },
+    /// Confirming the checked templates
+    ConfirmingTemplates { checked: Vec<bool> },
+    /// Success state with message
+    Success { message: String },
+    /// Error state with message
+    Error { message: String },
+}
+
+/// How a template relates to the aliases already configured
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateStatus {
+    /// No alias with this name yet
+    New,
+    /// An alias with this name and command already exists
+    Installed,
+    /// An alias with this name runs something else and would be replaced
+    Conflicts,
+}
+
+/// Git Shortcuts feature
+#[derive(Debug)]
+pub struct GitShortcuts {
+    pub state: GitShortcutsState,
+    scope: GitScope,
+    global_path: Option<PathBuf>,
+    /// `.git/config` of the repository mode was started in, if any
+    local_path: Option<PathBuf>,
+    aliases: Vec<GitAlias>,
+    /// Where config files are backed up before each write
+    backups: Option<BackupStore>,
+}
+
+impl GitShortcuts {
+    /// Creates a new Git Shortcuts feature
+    pub fn new() -> Self {
+        let local_path = env::current_dir()
+            .ok()
+            .and_then(|dir| git_config::local_config_path(&dir));
+
+        Self {
+            state: GitShortcutsState::Listing { selected: 0 },
+            scope: GitScope::Global,
+            global_path: None,
+            local_path,
+            aliases: Vec::new(),
+            backups: None,
+        }
+    }
+
+    /// Initializes the feature by reading aliases from `~/.gitconfig`
+    pub fn initialize(&mut self) -> Result<()> {
+        self.global_path = Some(git_config::global_config_path()?);
+        self.backups = Some(BackupStore::open()?);
+        self.refresh_aliases()
+    }
+
+    /// Returns the config file for the current scope
+    pub fn config_path(&self) -> Option<&Path> {
+        match self.scope {
+            GitScope::Global => self.global_path.as_deref(),
+            GitScope::Local => self.local_path.as_deref(),
+        }
+    }
+
+    /// Returns the scope aliases are read from and written to
+    pub fn scope(&self) -> GitScope {
+        self.scope
+    }
+
+    /// Re-reads aliases from the current scope's config file
+    pub fn refresh_aliases(&mut self) -> Result<()> {
+        self.aliases = match self.config_path() {
+            Some(path) => git_config::list_git_aliases(path)?,
+            None => Vec::new(),
+        };
+        Ok(())
+    }
+
+    /// Switches between global and repository aliases
+    pub fn toggle_scope(&mut self) {
+        if !matches!(self.state, GitShortcutsState::Listing { .. }) {
+            return;
+        }
+        if self.local_path.is_none() {
+            self.state = GitShortcutsState::Error {
+                message: "mode wasn't started inside a git repository, so there are no local aliases".to_string(),
+            };
+            return;
+        }
+
+        self.scope = match self.scope {
+            GitScope::Global => GitScope::Local,
+            GitScope::Local => GitScope::Global,
+        };
+        self.show_list(None);
+    }
+
+    /// Returns the aliases in the current scope
+    pub fn aliases(&self) -> &[GitAlias] {
+        &self.aliases
+    }
+
+    /// Returns the alias highlighted in the list, if any
+    pub fn selected_alias(&self) -> Option<&GitAlias> {
+        match self.state {
+            GitShortcutsState::Listing { selected } => self.aliases.get(selected),
+            _ => None,
+        }
+    }
+
+    /// Returns to the list, focusing the named alias if it still exists
+    pub fn show_list(&mut self, focus: Option<&str>) {
+        if let Err(e) = self.refresh_aliases() {
+            self.state = GitShortcutsState::Error {
+                message: format!("Failed to read git config: {}", e),
+            };
+            return;
+        }
+
+        let selected = focus
+            .and_then(|name| self.aliases.iter().position(|a| a.name == name))
+            .unwrap_or(0);
+        self.state = GitShortcutsState::Listing { selected };
+    }
+
+    /// Returns the number of entries in the visible list
+    fn visible_len(&self) -> usize {
+        match self.state {
+            GitShortcutsState::Templates { .. } => TEMPLATES.len(),
+            _ => self.aliases.len(),
+        }
+    }
+
+    /// Moves selection up
+    pub fn previous(&mut self) {
+        let total = self.visible_len();
+        if let GitShortcutsState::Listing { selected } | GitShortcutsState::Templates { selected, .. } =
+            &mut self.state
+        {
+            if total > 0 {
+                *selected = if *selected == 0 { total - 1 } else { *selected - 1 };
+            }
+        }
+    }
+
+    /// Moves selection down
+    pub fn next(&mut self) {
+        let total = self.visible_len();
+        if let GitShortcutsState::Listing { selected } | GitShortcutsState::Templates { selected, .. } =
+            &mut self.state
+        {
+            if total > 0 {
+                *selected = (*selected + 1) % total;
+            }
+        }
+    }
+
+    /// Starts adding a new alias
+    pub fn start_add(&mut self) {
+        self.state = GitShortcutsState::EnteringName { input: String::new() };
+    }
+
+    /// Starts editing the selected alias
+    pub fn start_edit(&mut self) {
+        if let Some(alias) = self.selected_alias().cloned() {
+            self.state = GitShortcutsState::EditingCommand {
+                name: alias.name,
+                input: alias.command,
+            };
+        }
+    }
+
+    /// Starts deleting the selected alias
+    pub fn start_delete(&mut self) {
+        if let Some(alias) = self.selected_alias().cloned() {
+            self.state = GitShortcutsState::ConfirmingDelete {
+                name: alias.name,
+                command: alias.command,
+            };
+        }
+    }
+
+    /// Opens the template catalogue with nothing checked
+    pub fn show_templates(&mut self) {
+        self.state = GitShortcutsState::Templates {
+            selected: 0,
+            checked: vec![false; TEMPLATES.len()],
+        };
+    }
+
+    /// Returns how a template relates to the configured aliases
+    pub fn template_status(&self, template: &AliasTemplate) -> TemplateStatus {
+        match self.aliases.iter().find(|a| a.name.eq_ignore_ascii_case(template.name)) {
+            None => TemplateStatus::New,
+            Some(alias) if alias.command == template.command => TemplateStatus::Installed,
+            Some(_) => TemplateStatus::Conflicts,
+        }
+    }
+
+    /// Checks or unchecks the highlighted template
+    pub fn toggle_template(&mut self) {
+        if let GitShortcutsState::Templates { selected, checked } = &mut self.state {
+            if let Some(value) = checked.get_mut(*selected) {
+                *value = !*value;
+            }
+        }
+    }
+
+    /// Asks to install the checked templates, if any
+    pub fn start_install_templates(&mut self) {
+        if let GitShortcutsState::Templates { checked, .. } = &self.state {
+            if checked.iter().any(|c| *c) {
+                self.state = GitShortcutsState::ConfirmingTemplates {
+                    checked: checked.clone(),
+                };
+            }
+        }
+    }
+
+    /// Returns the templates checked for installation
+    pub fn checked_templates(&self) -> Vec<&'static AliasTemplate> {
+        match &self.state {
+            GitShortcutsState::Templates { checked, .. } | GitShortcutsState::ConfirmingTemplates { checked } => {
+                TEMPLATES.iter().zip(checked).filter(|(_, c)| **c).map(|(t, _)| t).collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Handles input for the current state
+    pub fn handle_char(&mut self, c: char) {
+        match &mut self.state {
+            GitShortcutsState::EnteringName { input }
+            | GitShortcutsState::EnteringCommand { input, .. }
+            | GitShortcutsState::EditingCommand { input, .. } => {
+                input.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    /// Handles backspace
+    pub fn handle_backspace(&mut self) {
+        match &mut self.state {
+            GitShortcutsState::EnteringName { input }
+            | GitShortcutsState::EnteringCommand { input, .. }
+            | GitShortcutsState::EditingCommand { input, .. } => {
+                input.pop();
+            }
+            _ => {}
+        }
+    }
+
+    /// Advances to the next step, writing the config on the last one
+    pub fn advance(&mut self) {
+        let result = match self.state.clone() {
+            GitShortcutsState::EnteringName { input } => {
+                let name = input.trim().to_string();
+                if let Err(e) = git_config::validate_git_alias_name(&name) {
+                    self.state = GitShortcutsState::Error {
+                        message: e.to_string(),
+                    };
+                } else if self.aliases.iter().any(|a| a.name.eq_ignore_ascii_case(&name)) {
+                    self.state = GitShortcutsState::Error {
+                        message: format!("Git alias '{}' already exists; edit it instead", name),
+                    };
+                } else {
+                    self.state = GitShortcutsState::EnteringCommand {
+                        name,
+                        input: String::new(),
+                    };
+                }
+                return;
+            }
+            GitShortcutsState::EnteringCommand { name, input } | GitShortcutsState::EditingCommand { name, input } => {
+                let alias = GitAlias {
+                    name: name.clone(),
+                    command: input.trim().to_string(),
+                };
+                self.write(|path, store| git_config::set_git_aliases(path, store, &[alias]))
+                    .map(|backup| with_backup(format!("✓ Git alias '{}' saved", name), backup))
+            }
+            GitShortcutsState::ConfirmingDelete { name, .. } => self
+                .write(|path, store| git_config::delete_git_alias(path, store, &name))
+                .map(|backup| with_backup(format!("✓ Git alias '{}' deleted", name), backup)),
+            GitShortcutsState::ConfirmingTemplates { .. } => {
+                let aliases: Vec<GitAlias> = self
+                    .checked_templates()
+                    .iter()
+                    .map(|t| GitAlias {
+                        name: t.name.to_string(),
+                        command: t.command.to_string(),
+                    })
+                    .collect();
+                let names: Vec<String> = aliases.iter().map(|a| a.name.clone()).collect();
+                self.write(|path, store| git_config::set_git_aliases(path, store, &aliases)).map(|backup| {
+                    with_backup(format!("✓ Installed {}", names.join(", ")), backup)
+                })
+            }
+            _ => return,
+        };
+
+        self.state = match result {
+            Ok(message) => GitShortcutsState::Success { message },
+            Err(e) => GitShortcutsState::Error {
+                message: e.to_string(),
+            },
+        };
+    }
+
+    /// Runs a write against the current scope's config file
+    fn write(&self, op: impl FnOnce(&Path, &BackupStore) -> Result<Option<PathBuf>>) -> Result<Option<PathBuf>> {
+        let store = self
+            .backups
+            .as_ref()
+            .ok_or_else(|| ModeError::Config("backup store has not been opened".to_string()))?;
+        match self.config_path() {
+            Some(path) => op(path, store),
+            None => Err(ModeError::Config(format!(
+                "No {} git config file found",
+                self.scope.name()
+            ))),
+        }
+    }
+
+    /// Goes back to the previous state
+    pub fn go_back(&mut self) {
+        match self.state.clone() {
+            GitShortcutsState::ConfirmingTemplates { checked } => {
+                self.state = GitShortcutsState::Templates { selected: 0, checked };
+            }
+            GitShortcutsState::EnteringCommand { name, .. }
+            | GitShortcutsState::EditingCommand { name, .. }
+            | GitShortcutsState::ConfirmingDelete { name, .. } => self.show_list(Some(&name)),
+            _ => self.show_list(None),
+        }
+    }
+
+    /// Returns whether the feature is done (success or error)
+    pub fn is_done(&self) -> bool {
+        matches!(
+            self.state,
+            GitShortcutsState::Success { .. } | GitShortcutsState::Error { .. }
+        )
+    }
+
+    /// Gets the prompt text for the current state
+    pub fn get_prompt(&self) -> String {
+        let file = self
+            .config_path()
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|| "no config file".to_string());
+
+        match &self.state {
+            GitShortcutsState::Listing { .. } => {
+                format!("{} {} aliases in {}", self.aliases.len(), self.scope.name(), file)
+            }
+            GitShortcutsState::EnteringName { .. } => "Enter alias name (run as `git <name>`):".to_string(),
+            GitShortcutsState::EnteringCommand { name, .. } => {
+                format!("Command for `git {}` (prefix with ! to run a shell command):", name)
+            }
+            GitShortcutsState::EditingCommand { name, .. } => format!("Edit command for `git {}`:", name),
+            GitShortcutsState::ConfirmingDelete { name, command } => format!("{}\n{}", name, command),
+            GitShortcutsState::Templates { .. } => {
+                format!("Pick aliases to add to {}", file)
+            }
+            GitShortcutsState::ConfirmingTemplates { .. } => {
+                format!("Add {} aliases to {}?", self.checked_templates().len(), file)
+            }
+            GitShortcutsState::Success { message } => message.clone(),
+            GitShortcutsState::Error { message } => format!("Error: {}", message),
+        }
+    }
+
+    /// Gets the current input text
+    pub fn get_input(&self) -> String {
+        match &self.state {
+            GitShortcutsState::EnteringName { input }
+            | GitShortcutsState::EnteringCommand { input, .. }
+            | GitShortcutsState::EditingCommand { input, .. } => input.clone(),
+            _ => String::new(),
+        }
+    }
+}
+
+impl Default for GitShortcuts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn with_backup(message: String, backup: Option<PathBuf>) -> String {
+    match backup {
+        Some(path) => format!("{}\n\nBackup: {}", message, path.display()),
+        None => message,
+    }
+}
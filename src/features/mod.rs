@@ -1,13 +1,46 @@
 pub mod alias_manager;
+pub mod backups;
 pub mod bookmark_manager;
+pub mod bookmark_store;
+pub mod env_manager;
+pub mod frecency;
+pub mod git_config;
+pub mod git_shortcuts;
+pub mod mode_config;
+pub mod path_manager;
 pub mod placeholder;
+pub mod process_kill;
 pub mod process_manager;
+pub mod process_presets;
+pub mod scan_export;
+pub mod scan_targets;
 pub mod scanner;
+pub mod service_probe;
+pub mod settings;
+pub mod ssh_config;
+pub mod ssh_manager;
+pub mod system_info;
 pub mod usage_viewer;
 
 pub use alias_manager::{AliasManager, AliasManagerState};
+pub use backups::{Backups, BackupsState};
 pub use bookmark_manager::{BookmarkManager, BookmarkManagerState};
+pub use bookmark_store::{Bookmark, BookmarkStore};
+pub use env_manager::{EnvManager, EnvManagerState, EnvVar};
+pub use frecency::{DirVisit, FrecencyDb};
+pub use git_config::{AliasTemplate, GitAlias, GitScope};
+pub use git_shortcuts::{GitShortcuts, GitShortcutsState, TemplateStatus};
+pub use mode_config::{BackupSettings, ModeConfig, ScannerSettings};
+pub use path_manager::{PathEntry, PathManager, PathManagerState};
 pub use placeholder::PlaceholderFeature;
-pub use process_manager::{ProcessAction, ProcessManager, ProcessManagerState};
-pub use scanner::{PortInfo, PortState, ScanOption, Scanner, ScannerState, ScanType};
+pub use process_manager::{ProcessManager, ProcessManagerState, SortColumn};
+pub use process_kill::{KillOutcome, KillStrategy};
+pub use process_presets::{MatchPattern, PresetSource, ProcessAction, ProcessConfig};
+pub use scan_export::{ExportFormat, ExportSettings, ScanReport};
+pub use scan_targets::ScanHost;
+pub use scanner::{HostResult, PortInfo, PortState, ScanConfig, ScanOption, ScanUpdate, Scanner, ScannerState, ScanType};
+pub use settings::{Settings, SettingsField, SettingsState};
+pub use ssh_config::{HostBlock, HostEntry, SshConfig};
+pub use ssh_manager::{HostForm, SshManager, SshManagerState};
+pub use system_info::{FilesystemUsage, SystemInfo, SystemInfoState};
 pub use usage_viewer::{UsageViewer, UsageViewerState};
@@ -0,0 +1,401 @@
+use super::process_presets::{self, ProcessConfig};
+use super::scan_export::ExportFormat;
+use super::scanner::ScanConfig;
+use crate::menu::MenuItem;
+use crate::ui_components::theme::Theme;
+use crate::utils::config::config_file_path;
+pub use crate::utils::backup::BackupSettings;
+use crate::utils::backup::BackupStore;
+use crate::utils::{file_ops, ModeError, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use toml_edit::{value, Array, DocumentMut, Item};
+
+/// Largest number of scan workers accepted from the config
+pub const MAX_CONCURRENCY: usize = 1000;
+
+/// Accepted range for the per-port connect timeout, in milliseconds
+pub const TIMEOUT_RANGE_MS: (u64, u64) = (10, 60_000);
+
+/// Most backups that can be kept per file
+pub const MAX_BACKUPS: usize = 1000;
+
+/// Oldest backups can be allowed to get, in days
+pub const MAX_BACKUP_AGE_DAYS: u64 = 3650;
+
+/// Defaults for new port scans
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ScannerSettings {
+    /// Whether service detection starts switched on
+    pub service_detection: bool,
+    /// Whether saving results starts switched on
+    pub save_to_file: bool,
+    /// Format preselected for saved results
+    pub export_format: ExportFormat,
+    /// Worker pool, timeout and address settings
+    pub scan: ScanConfig,
+}
+
+/// mode's settings, loaded from `config.toml`
+///
+/// ```toml
+/// theme = "dark"
+///
+/// [scanner]
+/// service_detection = true
+/// save_to_file = false
+/// export_format = "json"
+/// all_addresses = false
+/// timeout_ms = 500
+/// concurrency = 100
+///
+/// [backups]
+/// keep = 10
+/// max_age_days = 30
+/// compress = false
+///
+/// [menu]
+/// hidden = ["usage"]
+/// ```
+///
+/// `[process_manager]` and `[[process_presets]]` are described on
+/// [`ProcessConfig`].
+#[derive(Debug, Clone)]
+pub struct ModeConfig {
+    /// Name of a built-in color theme or a file in [`Theme::themes_dir`]
+    pub theme: String,
+    pub scanner: ScannerSettings,
+    /// Kill presets and grace period for the Process Manager
+    pub process: ProcessConfig,
+    pub backups: BackupSettings,
+    /// Items left out of the main menu
+    pub hidden_menu_items: Vec<MenuItem>,
+}
+
+/// Top level of `config.toml`
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    theme: Option<String>,
+    #[serde(default)]
+    scanner: ScannerFile,
+    #[serde(default)]
+    backups: BackupSettings,
+    #[serde(default)]
+    menu: MenuFile,
+    // Read by ProcessConfig
+    #[serde(rename = "process_manager")]
+    _process_manager: Option<toml::Value>,
+    #[serde(rename = "process_presets")]
+    _process_presets: Option<toml::Value>,
+}
+
+/// The `[scanner]` table of `config.toml`
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScannerFile {
+    service_detection: Option<bool>,
+    save_to_file: Option<bool>,
+    export_format: Option<String>,
+    all_addresses: Option<bool>,
+    timeout_ms: Option<u64>,
+    concurrency: Option<usize>,
+}
+
+/// The `[menu]` table of `config.toml`
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MenuFile {
+    #[serde(default)]
+    hidden: Vec<String>,
+}
+
+impl ModeConfig {
+    /// Loads the settings from the user's config file
+    pub fn load() -> Result<ModeConfig> {
+        Self::load_from(&config_file_path()?)
+    }
+
+    /// Loads the settings from a config file
+    ///
+    /// A missing file yields the defaults.
+    pub fn load_from(path: &Path) -> Result<ModeConfig> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path)?;
+        Self::parse(&content).map_err(|e| ModeError::Config(format!("{}: {}", path.display(), e)))
+    }
+
+    /// Parses and validates a config file
+    fn parse(content: &str) -> std::result::Result<ModeConfig, String> {
+        let file: ConfigFile = toml::from_str(content).map_err(|e| e.message().to_string())?;
+        let defaults = ScannerSettings::default();
+
+        let export_format = match file.scanner.export_format {
+            Some(slug) => ExportFormat::from_slug(&slug).ok_or_else(|| {
+                let slugs: Vec<&str> = ExportFormat::all().iter().map(|f| f.slug()).collect();
+                format!("scanner: unknown export_format '{}' (expected {})", slug, slugs.join(", "))
+            })?,
+            None => defaults.export_format,
+        };
+
+        let mut hidden_menu_items = Vec::new();
+        for slug in &file.menu.hidden {
+            let item = MenuItem::from_slug(slug).ok_or_else(|| format!("menu: unknown menu item '{}'", slug))?;
+            if !hidden_menu_items.contains(&item) {
+                hidden_menu_items.push(item);
+            }
+        }
+
+        let config = ModeConfig {
+            theme: file.theme.unwrap_or_else(|| Theme::PRESETS[0].to_string()),
+            scanner: ScannerSettings {
+                service_detection: file.scanner.service_detection.unwrap_or(defaults.service_detection),
+                save_to_file: file.scanner.save_to_file.unwrap_or(defaults.save_to_file),
+                export_format,
+                scan: ScanConfig {
+                    concurrency: file.scanner.concurrency.unwrap_or(defaults.scan.concurrency),
+                    timeout: file
+                        .scanner
+                        .timeout_ms
+                        .map(Duration::from_millis)
+                        .unwrap_or(defaults.scan.timeout),
+                    all_addresses: file.scanner.all_addresses.unwrap_or(defaults.scan.all_addresses),
+                },
+            },
+            process: ProcessConfig::parse(content)?,
+            backups: file.backups,
+            hidden_menu_items,
+        };
+
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Checks every value against the range mode accepts
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        if !Theme::exists(&self.theme) {
+            return Err(format!(
+                "unknown theme '{}' (expected {})",
+                self.theme,
+                Theme::available().join(", ")
+            ));
+        }
+
+        let scan = &self.scanner.scan;
+        if scan.concurrency == 0 || scan.concurrency > MAX_CONCURRENCY {
+            return Err(format!("scanner: concurrency must be between 1 and {}", MAX_CONCURRENCY));
+        }
+        let (min_timeout, max_timeout) = TIMEOUT_RANGE_MS;
+        let timeout = scan.timeout.as_millis() as u64;
+        if !(min_timeout..=max_timeout).contains(&timeout) {
+            return Err(format!(
+                "scanner: timeout_ms must be between {} and {}",
+                min_timeout, max_timeout
+            ));
+        }
+
+        process_presets::parse_grace_period(self.process.grace_period.as_millis() as u64)
+            .map_err(|e| format!("process_manager: {}", e))?;
+
+        if self.backups.keep > MAX_BACKUPS {
+            return Err(format!("backups: keep must be at most {}", MAX_BACKUPS));
+        }
+        if self.backups.max_age_days > MAX_BACKUP_AGE_DAYS {
+            return Err(format!("backups: max_age_days must be at most {}", MAX_BACKUP_AGE_DAYS));
+        }
+
+        if self.hidden_menu_items.contains(&MenuItem::Settings) {
+            return Err("menu: Settings can't be hidden".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Saves the settings to the user's config file
+    pub fn save(&self) -> Result<Option<PathBuf>> {
+        self.save_to(&config_file_path()?, &BackupStore::open()?)
+    }
+
+    /// Saves the settings to a config file, backing it up into `store`
+    ///
+    /// Only values that differ from the file are rewritten, so comments,
+    /// process presets and the layout of everything else are kept. The result
+    /// is parsed again before anything is written. Returns the backup path,
+    /// or None if the file was created.
+    pub fn save_to(&self, path: &Path, store: &BackupStore) -> Result<Option<PathBuf>> {
+        self.validate().map_err(ModeError::InvalidInput)?;
+
+        let content = if path.exists() {
+            fs::read_to_string(path)?
+        } else {
+            String::new()
+        };
+        let current = Self::parse(&content).map_err(|e| ModeError::Config(format!("{}: {}", path.display(), e)))?;
+        let mut doc: DocumentMut = content
+            .parse()
+            .map_err(|e| ModeError::Config(format!("{}: {}", path.display(), e)))?;
+
+        if self.theme != current.theme {
+            doc["theme"] = value(self.theme.as_str());
+        }
+
+        let (scanner, saved) = (&self.scanner, &current.scanner);
+        if scanner.service_detection != saved.service_detection {
+            set_key(&mut doc, "scanner", "service_detection", value(scanner.service_detection));
+        }
+        if scanner.save_to_file != saved.save_to_file {
+            set_key(&mut doc, "scanner", "save_to_file", value(scanner.save_to_file));
+        }
+        if scanner.export_format != saved.export_format {
+            set_key(&mut doc, "scanner", "export_format", value(scanner.export_format.slug()));
+        }
+        if scanner.scan.all_addresses != saved.scan.all_addresses {
+            set_key(&mut doc, "scanner", "all_addresses", value(scanner.scan.all_addresses));
+        }
+        if scanner.scan.timeout != saved.scan.timeout {
+            set_key(&mut doc, "scanner", "timeout_ms", value(scanner.scan.timeout.as_millis() as i64));
+        }
+        if scanner.scan.concurrency != saved.scan.concurrency {
+            set_key(&mut doc, "scanner", "concurrency", value(scanner.scan.concurrency as i64));
+        }
+
+        if self.process.grace_period != current.process.grace_period {
+            let grace_period_ms = self.process.grace_period.as_millis() as i64;
+            set_key(&mut doc, "process_manager", "grace_period_ms", value(grace_period_ms));
+        }
+
+        if self.backups.keep != current.backups.keep {
+            set_key(&mut doc, "backups", "keep", value(self.backups.keep as i64));
+        }
+        if self.backups.max_age_days != current.backups.max_age_days {
+            set_key(&mut doc, "backups", "max_age_days", value(self.backups.max_age_days as i64));
+        }
+        if self.backups.compress != current.backups.compress {
+            set_key(&mut doc, "backups", "compress", value(self.backups.compress));
+        }
+
+        if self.hidden_menu_items != current.hidden_menu_items {
+            let hidden: Array = self.hidden_menu_items.iter().map(|item| item.slug()).collect();
+            set_key(&mut doc, "menu", "hidden", value(hidden));
+        }
+
+        let updated = doc.to_string();
+        Self::parse(&updated).map_err(|e| ModeError::Config(format!("refusing to write {}: {}", path.display(), e)))?;
+
+        if path.exists() {
+            let lines: Vec<&str> = updated.lines().collect();
+            file_ops::rewrite_with_backup(path, store, &lines).map(Some)
+        } else {
+            file_ops::write_atomic(path, &updated)?;
+            Ok(None)
+        }
+    }
+}
+
+/// Sets a key in a table, adding the table at the end of the file if needed
+fn set_key(doc: &mut DocumentMut, table: &str, key: &str, item: Item) {
+    let table = doc.entry(table).or_insert_with(toml_edit::table);
+    if let Some(table) = table.as_table_like_mut() {
+        table.insert(key, item);
+    }
+}
+
+impl Default for ModeConfig {
+    fn default() -> Self {
+        Self {
+            theme: Theme::PRESETS[0].to_string(),
+            scanner: ScannerSettings::default(),
+            process: ProcessConfig::default(),
+            backups: BackupSettings::default(),
+            hidden_menu_items: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_parse_config_sections() {
+        let content = r#"
+            theme = "dark"
+
+            [scanner]
+            service_detection = true
+            export_format = "csv"
+            timeout_ms = 250
+            concurrency = 32
+
+            [process_manager]
+            grace_period_ms = 1500
+
+            [backups]
+            keep = 5
+            compress = true
+
+            [menu]
+            hidden = ["usage", "git"]
+        "#;
+        let config = ModeConfig::parse(content).unwrap();
+        assert!(config.scanner.service_detection);
+        assert_eq!(config.scanner.export_format, ExportFormat::Csv);
+        assert_eq!(config.scanner.scan.timeout, Duration::from_millis(250));
+        assert_eq!(config.scanner.scan.concurrency, 32);
+        assert_eq!(config.process.grace_period, Duration::from_millis(1500));
+        assert_eq!(config.backups.keep, 5);
+        assert!(config.backups.compress);
+        assert_eq!(config.hidden_menu_items, vec![MenuItem::UsageViewer, MenuItem::GitShortcuts]);
+
+        let cases = [
+            ("theme = \"neon\"\n", "unknown theme"),
+            ("[scanner]\nconcurrency = 0\n", "concurrency"),
+            ("[scanner]\ntimeout_ms = 5\n", "timeout_ms"),
+            ("[scanner]\nexport_format = \"pdf\"\n", "export_format"),
+            ("[menu]\nhidden = [\"settings\"]\n", "can't be hidden"),
+            ("[menu]\nhidden = [\"nope\"]\n", "unknown menu item"),
+            ("[backups]\nkeep = 5000\n", "at most"),
+            ("colour = \"red\"\n", "unknown field"),
+        ];
+        for (content, expected) in cases {
+            let error = ModeConfig::parse(content).unwrap_err();
+            assert!(error.contains(expected), "{} should mention {}", error, expected);
+        }
+    }
+
+    #[test]
+    fn test_save_keeps_comments_and_presets() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let store = BackupStore::new(dir.path().join("backups"), BackupSettings::default());
+
+        let mut config = ModeConfig::load_from(&path).unwrap();
+        config.scanner.scan.concurrency = 50;
+        assert_eq!(config.save_to(&path, &store).unwrap(), None);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "[scanner]\nconcurrency = 50\n");
+
+        let original = "# my settings\ntheme = \"dark\" # keep\n\n[[process_presets]]\nname = \"Rails\"\nports = [3000]\n";
+        fs::write(&path, original).unwrap();
+        let mut config = ModeConfig::load_from(&path).unwrap();
+        config.backups.keep = 3;
+        config.hidden_menu_items = vec![MenuItem::UsageViewer];
+        assert!(config.save_to(&path, &store).unwrap().unwrap().exists());
+
+        let saved = fs::read_to_string(&path).unwrap();
+        assert!(saved.starts_with(original), "{}", saved);
+        let reloaded = ModeConfig::load_from(&path).unwrap();
+        assert_eq!(reloaded.backups.keep, 3);
+        assert_eq!(reloaded.hidden_menu_items, vec![MenuItem::UsageViewer]);
+        assert!(reloaded.process.presets.iter().any(|p| p.slug == "rails"));
+
+        config.scanner.scan.concurrency = 0;
+        assert!(config.save_to(&path, &store).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), saved);
+    }
+}
@@ -0,0 +1,588 @@
+use crate::utils::backup::BackupStore;
+use crate::utils::{self, file_ops, shell, ModeError, Result, ShellType};
+use std::collections::HashSet;
+use std::env;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+/// PATH Manager state machine
+#[derive(Debug, Clone)]
+pub enum PathManagerState {
+    /// Browsing and rearranging entries
+    Editing { selected: usize },
+    /// Entering a directory to add above the selected entry
+    Adding { selected: usize, input: String },
+    /// Confirming the new PATH before writing it to the RC file
+    ConfirmingSave { selected: usize },
+    /// Confirming leaving with unsaved changes
+    ConfirmingDiscard { selected: usize },
+    /// Success state with message
+    Success { message: String },
+    /// Error state with message
+    Error { message: String },
+}
+
+/// One directory in PATH along with any problems found with it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathEntry {
+    pub dir: String,
+    /// Whether the directory exists
+    pub exists: bool,
+    /// Index of the earlier entry this one repeats
+    pub duplicate_of: Option<usize>,
+    /// Executables here that hide ones with the same name in later entries
+    pub shadows: Vec<String>,
+}
+
+impl PathEntry {
+    /// Returns whether the entry is missing or repeated
+    pub fn is_redundant(&self) -> bool {
+        !self.exists || self.duplicate_of.is_some()
+    }
+}
+
+/// PATH Manager feature
+#[derive(Debug)]
+pub struct PathManager {
+    pub state: PathManagerState,
+    shell: Option<ShellType>,
+    rc_file: Option<PathBuf>,
+    /// Where the RC file is backed up before each write
+    backups: Option<BackupStore>,
+    /// PATH as last saved (or as mode was started with)
+    saved: Vec<String>,
+    /// PATH being edited, analyzed after every change
+    entries: Vec<PathEntry>,
+    /// `export PATH=...` (or `set -gx PATH ...`) applying the saved PATH to the live shell
+    exit_command: Option<String>,
+}
+
+impl PathManager {
+    /// Creates a new PATH Manager from the PATH mode was started with
+    pub fn new() -> Self {
+        let dirs = split_path(&env::var("PATH").unwrap_or_default());
+
+        Self {
+            state: PathManagerState::Editing { selected: 0 },
+            shell: None,
+            rc_file: None,
+            backups: None,
+            entries: analyze(&dirs),
+            saved: dirs,
+            exit_command: None,
+        }
+    }
+
+    /// Initializes the manager by detecting the RC file
+    pub fn initialize(&mut self) -> Result<()> {
+        let shell = utils::detect_shell()?;
+        self.rc_file = Some(utils::get_rc_file_path(shell)?);
+        self.shell = Some(shell);
+        self.backups = Some(BackupStore::open()?);
+        Ok(())
+    }
+
+    /// Returns the backup store, or an error if the manager is not initialized
+    fn require_backups(&self) -> Result<&BackupStore> {
+        self.backups
+            .as_ref()
+            .ok_or_else(|| ModeError::Config("backup store has not been opened".to_string()))
+    }
+
+    /// Returns the detected shell, assuming bash before detection
+    fn shell(&self) -> ShellType {
+        self.shell.unwrap_or(ShellType::Bash)
+    }
+
+    /// Returns the analyzed entries in order
+    pub fn entries(&self) -> &[PathEntry] {
+        &self.entries
+    }
+
+    /// Returns the entry highlighted in the list, if any
+    pub fn selected_entry(&self) -> Option<&PathEntry> {
+        match self.state {
+            PathManagerState::Editing { selected } => self.entries.get(selected),
+            _ => None,
+        }
+    }
+
+    /// Returns the directories currently in the list
+    fn dirs(&self) -> Vec<String> {
+        self.entries.iter().map(|e| e.dir.clone()).collect()
+    }
+
+    /// Returns whether the list differs from the saved PATH
+    pub fn has_changes(&self) -> bool {
+        self.dirs() != self.saved
+    }
+
+    /// Returns the number of missing or duplicate entries
+    pub fn redundant_count(&self) -> usize {
+        self.entries.iter().filter(|e| e.is_redundant()).count()
+    }
+
+    /// Replaces the list with new directories, keeping the selection on `selected`
+    fn set_dirs(&mut self, dirs: Vec<String>, selected: usize) {
+        self.entries = analyze(&dirs);
+        self.state = PathManagerState::Editing {
+            selected: selected.min(self.entries.len().saturating_sub(1)),
+        };
+    }
+
+    /// Moves selection up
+    pub fn previous(&mut self) {
+        let total = self.entries.len();
+        if let PathManagerState::Editing { selected } = &mut self.state {
+            if total > 0 {
+                *selected = if *selected == 0 { total - 1 } else { *selected - 1 };
+            }
+        }
+    }
+
+    /// Moves selection down
+    pub fn next(&mut self) {
+        let total = self.entries.len();
+        if let PathManagerState::Editing { selected } = &mut self.state {
+            if total > 0 {
+                *selected = (*selected + 1) % total;
+            }
+        }
+    }
+
+    /// Moves the selected entry one place earlier in PATH
+    pub fn move_up(&mut self) {
+        if let PathManagerState::Editing { selected } = self.state {
+            if selected > 0 && selected < self.entries.len() {
+                let mut dirs = self.dirs();
+                dirs.swap(selected, selected - 1);
+                self.set_dirs(dirs, selected - 1);
+            }
+        }
+    }
+
+    /// Moves the selected entry one place later in PATH
+    pub fn move_down(&mut self) {
+        if let PathManagerState::Editing { selected } = self.state {
+            if selected + 1 < self.entries.len() {
+                let mut dirs = self.dirs();
+                dirs.swap(selected, selected + 1);
+                self.set_dirs(dirs, selected + 1);
+            }
+        }
+    }
+
+    /// Removes the selected entry
+    pub fn remove_selected(&mut self) {
+        if let PathManagerState::Editing { selected } = self.state {
+            if selected < self.entries.len() {
+                let mut dirs = self.dirs();
+                dirs.remove(selected);
+                self.set_dirs(dirs, selected);
+            }
+        }
+    }
+
+    /// Drops every missing or duplicate entry, returning how many were removed
+    pub fn clean_up(&mut self) -> usize {
+        let removed = self.redundant_count();
+        let dirs = self
+            .entries
+            .iter()
+            .filter(|e| !e.is_redundant())
+            .map(|e| e.dir.clone())
+            .collect();
+        self.set_dirs(dirs, 0);
+        removed
+    }
+
+    /// Starts adding a directory above the selected entry
+    pub fn start_add(&mut self) {
+        if let PathManagerState::Editing { selected } = self.state {
+            self.state = PathManagerState::Adding {
+                selected,
+                input: String::new(),
+            };
+        }
+    }
+
+    /// Asks to write the list to the RC file, if anything changed
+    pub fn start_save(&mut self) {
+        let PathManagerState::Editing { selected } = self.state else {
+            return;
+        };
+        if self.has_changes() {
+            self.state = PathManagerState::ConfirmingSave { selected };
+        } else {
+            self.state = PathManagerState::Error {
+                message: "PATH has no unsaved changes".to_string(),
+            };
+        }
+    }
+
+    /// Asks before leaving with unsaved changes
+    ///
+    /// Returns true when the app can return to the menu right away
+    pub fn request_exit(&mut self) -> bool {
+        let PathManagerState::Editing { selected } = self.state else {
+            return true;
+        };
+        if self.has_changes() {
+            self.state = PathManagerState::ConfirmingDiscard { selected };
+            false
+        } else {
+            true
+        }
+    }
+
+    /// Handles input for the current state
+    pub fn handle_char(&mut self, c: char) {
+        if let PathManagerState::Adding { input, .. } = &mut self.state {
+            input.push(c);
+        }
+    }
+
+    /// Handles backspace
+    pub fn handle_backspace(&mut self) {
+        if let PathManagerState::Adding { input, .. } = &mut self.state {
+            input.pop();
+        }
+    }
+
+    /// Adds the entered directory or saves the list
+    pub fn advance(&mut self) {
+        match self.state.clone() {
+            PathManagerState::Adding { selected, input } => {
+                if let Err(e) = self.add_dir(selected, input.trim()) {
+                    self.state = PathManagerState::Error {
+                        message: e.to_string(),
+                    };
+                }
+            }
+            PathManagerState::ConfirmingSave { .. } => {
+                self.state = match self.save() {
+                    Ok(message) => PathManagerState::Success { message },
+                    Err(e) => PathManagerState::Error {
+                        message: e.to_string(),
+                    },
+                };
+            }
+            _ => {}
+        }
+    }
+
+    /// Inserts a directory above the entry that was selected before adding
+    fn add_dir(&mut self, selected: usize, input: &str) -> Result<()> {
+        if input.is_empty() {
+            return Err(ModeError::InvalidInput("Directory cannot be empty".to_string()));
+        }
+        let dir = shell::expand_home(input).display().to_string();
+        validate_path_dir(&dir)?;
+        if self.entries.iter().any(|e| same_dir(&e.dir, &dir)) {
+            return Err(ModeError::InvalidInput(format!("{} is already in PATH", dir)));
+        }
+
+        let position = selected.min(self.entries.len());
+        let mut dirs = self.dirs();
+        dirs.insert(position, dir);
+        self.set_dirs(dirs, position);
+        Ok(())
+    }
+
+    /// Writes the list to the PATH line in mode's RC file block
+    fn save(&mut self) -> Result<String> {
+        let rc_file = self.rc_file.clone().ok_or_else(|| {
+            ModeError::RcFileNotFound("RC file not detected".to_string())
+        })?;
+
+        let dirs = self.dirs();
+        let line = export_path_line(self.shell(), &dirs);
+        let backup_path = file_ops::set_managed_definition(&rc_file, self.shell(), self.require_backups()?, &line)?;
+
+        self.saved = dirs;
+        self.exit_command = Some(line);
+        Ok(format!(
+            "✓ PATH saved to {} ({} entries)\n\nBackup: {}",
+            rc_file.display(),
+            self.entries.len(),
+            backup_path.display()
+        ))
+    }
+
+    /// Goes back to the list, keeping the selection where it was
+    pub fn go_back(&mut self) {
+        let selected = match self.state {
+            PathManagerState::Editing { selected }
+            | PathManagerState::Adding { selected, .. }
+            | PathManagerState::ConfirmingSave { selected }
+            | PathManagerState::ConfirmingDiscard { selected } => selected,
+            _ => 0,
+        };
+        self.state = PathManagerState::Editing {
+            selected: selected.min(self.entries.len().saturating_sub(1)),
+        };
+    }
+
+    /// Returns whether the feature is done (success or error)
+    pub fn is_done(&self) -> bool {
+        matches!(
+            self.state,
+            PathManagerState::Success { .. } | PathManagerState::Error { .. }
+        )
+    }
+
+    /// Gets the prompt text for the current state
+    pub fn get_prompt(&self) -> String {
+        match &self.state {
+            PathManagerState::Editing { .. } => {
+                let changes = if self.has_changes() { " (unsaved changes)" } else { "" };
+                format!(
+                    "{} entries, {} missing or duplicate{}",
+                    self.entries.len(),
+                    self.redundant_count(),
+                    changes
+                )
+            }
+            PathManagerState::Adding { .. } => "Directory to add above the selected entry:".to_string(),
+            PathManagerState::ConfirmingSave { .. } => export_path_line(self.shell(), &self.dirs()),
+            PathManagerState::ConfirmingDiscard { .. } => "Discard unsaved PATH changes?".to_string(),
+            PathManagerState::Success { message } => message.clone(),
+            PathManagerState::Error { message } => format!("Error: {}", message),
+        }
+    }
+
+    /// Gets confirmation data for structured display
+    pub fn get_confirmation_data(&self) -> Option<(String, String)> {
+        match &self.state {
+            PathManagerState::ConfirmingSave { .. } => {
+                let rc_file = self
+                    .rc_file
+                    .as_ref()
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_default();
+                Some((rc_file, format!("{} entries", self.entries.len())))
+            }
+            _ => None,
+        }
+    }
+
+    /// Gets the current input text
+    pub fn get_input(&self) -> String {
+        match &self.state {
+            PathManagerState::Adding { input, .. } => input.clone(),
+            _ => String::new(),
+        }
+    }
+
+    /// Returns whether PATH was saved this session
+    pub fn is_modified(&self) -> bool {
+        self.exit_command.is_some()
+    }
+
+    /// Returns the export that applies the saved PATH to the current shell
+    pub fn get_exit_command(&self) -> Option<String> {
+        self.exit_command.clone()
+    }
+}
+
+impl Default for PathManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Splits a PATH value into its entries, keeping empty ones so they can be flagged
+pub fn split_path(path: &str) -> Vec<String> {
+    if path.is_empty() {
+        return Vec::new();
+    }
+    path.split(':').map(|dir| dir.to_string()).collect()
+}
+
+/// Builds the line that sets PATH to a list of directories
+///
+/// Fish gets `set -gx PATH` with one argument per directory, since PATH is a
+/// list there; empty entries are left out
+pub fn export_path_line(shell: ShellType, dirs: &[String]) -> String {
+    match shell {
+        ShellType::Bash | ShellType::Zsh => format!("export PATH={}", shell.quote(&dirs.join(":"))),
+        ShellType::Fish => {
+            let quoted: Vec<String> = dirs
+                .iter()
+                .filter(|dir| !dir.is_empty())
+                .map(|dir| shell.quote(dir))
+                .collect();
+            format!("set -gx PATH {}", quoted.join(" "))
+        }
+    }
+}
+
+/// Validates a directory before it is added to PATH
+pub fn validate_path_dir(dir: &str) -> Result<()> {
+    if dir.contains(':') {
+        return Err(ModeError::InvalidInput("PATH entries cannot contain ':'".to_string()));
+    }
+    if !Path::new(dir).is_absolute() {
+        return Err(ModeError::InvalidInput(format!("'{}' is not an absolute path", dir)));
+    }
+    Ok(())
+}
+
+/// Flags missing directories, repeats, and executables hidden by earlier entries
+pub fn analyze(dirs: &[String]) -> Vec<PathEntry> {
+    let executables: Vec<HashSet<String>> = dirs
+        .iter()
+        .enumerate()
+        .map(|(i, dir)| {
+            if dirs[..i].iter().any(|earlier| same_dir(earlier, dir)) {
+                HashSet::new()
+            } else {
+                list_executables(dir)
+            }
+        })
+        .collect();
+
+    dirs.iter()
+        .enumerate()
+        .map(|(i, dir)| {
+            let mut shadows: Vec<String> = executables[i]
+                .iter()
+                .filter(|name| executables[i + 1..].iter().any(|later| later.contains(*name)))
+                .cloned()
+                .collect();
+            shadows.sort();
+
+            PathEntry {
+                dir: dir.clone(),
+                exists: !dir.is_empty() && Path::new(dir).is_dir(),
+                duplicate_of: dirs[..i].iter().position(|earlier| same_dir(earlier, dir)),
+                shadows,
+            }
+        })
+        .collect()
+}
+
+/// Compares PATH entries, ignoring trailing slashes
+fn same_dir(a: &str, b: &str) -> bool {
+    a.trim_end_matches('/') == b.trim_end_matches('/')
+}
+
+/// Returns the names of executable files in a directory
+fn list_executables(dir: &str) -> HashSet<String> {
+    if dir.is_empty() {
+        return HashSet::new();
+    }
+    let Ok(read_dir) = fs::read_dir(dir) else {
+        return HashSet::new();
+    };
+
+    read_dir
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            fs::metadata(entry.path())
+                .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+                .unwrap_or(false)
+        })
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::backup::BackupSettings;
+    use tempfile::tempdir;
+
+    fn touch_executable(dir: &Path, name: &str) {
+        let path = dir.join(name);
+        fs::write(&path, "#!/bin/sh\n").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    #[test]
+    fn test_analyze_flags_missing_duplicate_and_shadowing_entries() {
+        let dir = tempdir().unwrap();
+        let local = dir.path().join("local");
+        let system = dir.path().join("system");
+        fs::create_dir_all(&local).unwrap();
+        fs::create_dir_all(&system).unwrap();
+        touch_executable(&local, "python");
+        touch_executable(&local, "only-local");
+        touch_executable(&system, "python");
+        fs::write(system.join("README"), "not executable").unwrap();
+
+        let local = local.display().to_string();
+        let system = system.display().to_string();
+        let missing = dir.path().join("missing").display().to_string();
+        let dirs = vec![local.clone(), missing, system.clone(), format!("{}/", local)];
+        let entries = analyze(&dirs);
+
+        assert_eq!(entries[0].shadows, vec!["python".to_string()]);
+        assert!(!entries[0].is_redundant());
+        assert!(!entries[1].exists);
+        assert!(entries[2].shadows.is_empty());
+        assert_eq!(entries[3].duplicate_of, Some(0));
+    }
+
+    #[test]
+    fn test_split_path_and_export_line() {
+        assert!(split_path("").is_empty());
+        let dirs = split_path("/usr/bin::/my tools");
+        assert_eq!(dirs, vec!["/usr/bin", "", "/my tools"]);
+        assert_eq!(export_path_line(ShellType::Bash, &dirs), "export PATH='/usr/bin::/my tools'");
+        assert_eq!(
+            export_path_line(ShellType::Fish, &dirs),
+            "set -gx PATH /usr/bin '/my tools'"
+        );
+
+        assert!(validate_path_dir("/opt/bin").is_ok());
+        assert!(validate_path_dir("bin").is_err());
+        assert!(validate_path_dir("/a:/b").is_err());
+    }
+
+    #[test]
+    fn test_reorder_remove_and_clean_up() {
+        let mut manager = PathManager::new();
+        manager.set_dirs(
+            vec!["/".to_string(), "/definitely/missing".to_string(), "/".to_string()],
+            0,
+        );
+        manager.saved = manager.dirs();
+
+        manager.move_down();
+        assert_eq!(manager.dirs()[1], "/");
+        assert!(manager.has_changes());
+
+        assert_eq!(manager.clean_up(), 2);
+        assert_eq!(manager.dirs(), vec!["/".to_string()]);
+        manager.remove_selected();
+        assert!(manager.entries().is_empty());
+    }
+
+    #[test]
+    fn test_fish_save_drops_removed_entries() {
+        let dir = tempdir().unwrap();
+        let rc_file = dir.path().join("mode.fish");
+        fs::write(&rc_file, "# >>> mode >>>\nfish_add_path --path --move -- /usr/bin /bin\n# <<< mode <<<\n").unwrap();
+
+        let mut manager = PathManager::new();
+        manager.shell = Some(ShellType::Fish);
+        manager.rc_file = Some(rc_file.clone());
+        manager.backups = Some(BackupStore::new(dir.path().join("backups"), BackupSettings::default()));
+        manager.set_dirs(vec!["/usr/bin".to_string(), "/bin".to_string()], 0);
+        manager.saved = manager.dirs();
+
+        manager.remove_selected();
+        manager.start_save();
+        manager.advance();
+        assert!(matches!(manager.state, PathManagerState::Success { .. }), "{:?}", manager.state);
+
+        // The old fish_add_path line is replaced, not added to
+        assert_eq!(
+            fs::read_to_string(&rc_file).unwrap(),
+            "# >>> mode >>>\nset -gx PATH /bin\n# <<< mode <<<\n"
+        );
+        assert_eq!(manager.get_exit_command(), Some("set -gx PATH /bin".to_string()));
+    }
+}
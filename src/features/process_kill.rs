@@ -0,0 +1,263 @@
+use crate::utils::procfs::{self, ProcessInfo, Signal, SignalError};
+use crate::utils::{ModeError, Result};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Grace period used when the config doesn't set one
+pub const DEFAULT_GRACE_PERIOD: Duration = Duration::from_secs(3);
+
+/// How long to wait for the kernel to reap a process after SIGKILL
+const KILL_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// How often processes are checked while waiting for them to exit
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How a set of processes is stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KillStrategy {
+    /// First signal sent to every process
+    pub signal: Signal,
+    /// How long to wait after SIGTERM before escalating to SIGKILL
+    pub grace_period: Duration,
+}
+
+impl KillStrategy {
+    /// Describes the strategy, e.g. "SIGTERM, then SIGKILL after 3s"
+    pub fn describe(&self) -> String {
+        match self.signal {
+            Signal::Term => format!(
+                "SIGTERM, then SIGKILL after {}",
+                format_duration(self.grace_period)
+            ),
+            Signal::Kill => "SIGKILL immediately".to_string(),
+        }
+    }
+}
+
+/// What happened to one process
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KillOutcome {
+    /// Exited after SIGTERM within the grace period
+    Terminated,
+    /// Exited after SIGKILL
+    Killed,
+    /// Belongs to another user
+    PermissionDenied,
+    /// Had already exited before it was signalled
+    AlreadyGone,
+    /// Could not be stopped
+    Failed(String),
+}
+
+impl KillOutcome {
+    /// Returns whether the process is no longer running
+    pub fn is_stopped(&self) -> bool {
+        matches!(
+            self,
+            KillOutcome::Terminated | KillOutcome::Killed | KillOutcome::AlreadyGone
+        )
+    }
+
+    /// Returns a short label for the report
+    pub fn label(&self) -> String {
+        match self {
+            KillOutcome::Terminated => "terminated".to_string(),
+            KillOutcome::Killed => "killed".to_string(),
+            KillOutcome::PermissionDenied => "permission denied".to_string(),
+            KillOutcome::AlreadyGone => "already gone".to_string(),
+            KillOutcome::Failed(reason) => format!("failed: {}", reason),
+        }
+    }
+}
+
+/// Returns whether a process must never be killed from mode (init and mode itself)
+pub fn is_protected(pid: u32) -> bool {
+    pid == 1 || pid == std::process::id()
+}
+
+/// Stops each process with the given strategy and verifies that it exited
+///
+/// Processes are signalled together, then polled until they exit. With
+/// SIGTERM, survivors of the grace period are sent SIGKILL. A process is
+/// tracked by PID and start time, so a recycled PID is never signalled.
+pub fn kill_processes(targets: &[ProcessInfo], strategy: KillStrategy) -> Vec<(ProcessInfo, KillOutcome)> {
+    let mut outcomes: Vec<Option<KillOutcome>> = targets
+        .iter()
+        .map(|process| send(process, strategy.signal))
+        .collect();
+
+    let timeout = match strategy.signal {
+        Signal::Term => strategy.grace_period,
+        Signal::Kill => KILL_TIMEOUT,
+    };
+    let first_outcome = match strategy.signal {
+        Signal::Term => KillOutcome::Terminated,
+        Signal::Kill => KillOutcome::Killed,
+    };
+    wait_for_exit(targets, &mut outcomes, timeout, &first_outcome);
+
+    if strategy.signal == Signal::Term {
+        for (process, outcome) in targets.iter().zip(outcomes.iter_mut()) {
+            if outcome.is_none() {
+                // Exiting between the last poll and SIGKILL still counts as terminated
+                *outcome = match send(process, Signal::Kill) {
+                    Some(KillOutcome::AlreadyGone) => Some(KillOutcome::Terminated),
+                    other => other,
+                };
+            }
+        }
+        wait_for_exit(targets, &mut outcomes, KILL_TIMEOUT, &KillOutcome::Killed);
+    }
+
+    targets
+        .iter()
+        .cloned()
+        .zip(outcomes)
+        .map(|(process, outcome)| {
+            let outcome = outcome.unwrap_or_else(|| KillOutcome::Failed("still running after SIGKILL".to_string()));
+            (process, outcome)
+        })
+        .collect()
+}
+
+/// Formats per-process outcomes for the Success or Error message
+///
+/// Fails if any process is still running.
+pub fn summarize(title: &str, results: &[(ProcessInfo, KillOutcome)]) -> Result<String> {
+    let stopped = results.iter().filter(|(_, outcome)| outcome.is_stopped()).count();
+    let lines: Vec<String> = results
+        .iter()
+        .map(|(process, outcome)| format!("{:>7} {:<16} {}", process.pid, process.name, outcome.label()))
+        .collect();
+
+    if stopped == results.len() {
+        Ok(format!(
+            "✓ {}: stopped {} process(es)\n\n{}",
+            title,
+            stopped,
+            lines.join("\n")
+        ))
+    } else {
+        Err(ModeError::Generic(format!(
+            "{}: stopped {} of {} process(es)\n\n{}",
+            title,
+            stopped,
+            results.len(),
+            lines.join("\n")
+        )))
+    }
+}
+
+/// Formats a grace period for display ("3s", "500ms")
+pub fn format_duration(duration: Duration) -> String {
+    if duration.subsec_millis() == 0 {
+        format!("{}s", duration.as_secs())
+    } else {
+        format!("{}ms", duration.as_millis())
+    }
+}
+
+/// Signals one process, returning an outcome if it is already settled
+fn send(process: &ProcessInfo, signal: Signal) -> Option<KillOutcome> {
+    if !procfs::is_alive(process.pid, process.start_ticks) {
+        return Some(KillOutcome::AlreadyGone);
+    }
+
+    match procfs::send_signal(process.pid, signal) {
+        Ok(()) => None,
+        Err(SignalError::NotFound) => Some(KillOutcome::AlreadyGone),
+        Err(SignalError::PermissionDenied) => Some(KillOutcome::PermissionDenied),
+        Err(SignalError::Other(reason)) => Some(KillOutcome::Failed(reason)),
+    }
+}
+
+/// Polls unsettled processes until they exit or the timeout passes
+fn wait_for_exit(
+    targets: &[ProcessInfo],
+    outcomes: &mut [Option<KillOutcome>],
+    timeout: Duration,
+    exited: &KillOutcome,
+) {
+    let deadline = Instant::now() + timeout;
+    loop {
+        for (process, outcome) in targets.iter().zip(outcomes.iter_mut()) {
+            if outcome.is_none() && !procfs::is_alive(process.pid, process.start_ticks) {
+                *outcome = Some(exited.clone());
+            }
+        }
+
+        if outcomes.iter().all(Option::is_some) || Instant::now() >= deadline {
+            return;
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::{Child, Command};
+
+    /// Spawns a child and describes it the way the sampler would
+    fn spawn(script: &str) -> (Child, ProcessInfo) {
+        let child = Command::new("sh").arg("-c").arg(script).spawn().unwrap();
+        // Give the shell a moment to install its trap
+        thread::sleep(Duration::from_millis(100));
+        let pid = child.id();
+        let info = ProcessInfo {
+            pid,
+            ppid: std::process::id(),
+            user: String::new(),
+            name: "sh".to_string(),
+            exe: "sh".to_string(),
+            cmdline: format!("sh -c {}", script),
+            cpu_percent: 0.0,
+            memory: 0,
+            memory_percent: 0.0,
+            listening_ports: Vec::new(),
+            start_ticks: procfs::start_ticks(pid).unwrap(),
+        };
+        (child, info)
+    }
+
+    #[test]
+    fn test_escalates_to_sigkill_when_sigterm_is_ignored() {
+        let (mut polite, polite_info) = spawn("sleep 30");
+        let (mut stubborn, stubborn_info) = spawn("trap '' TERM; while true; do sleep 1; done");
+
+        let strategy = KillStrategy {
+            signal: Signal::Term,
+            grace_period: Duration::from_millis(300),
+        };
+        let results = kill_processes(&[polite_info, stubborn_info], strategy);
+
+        assert_eq!(results[0].1, KillOutcome::Terminated);
+        assert_eq!(results[1].1, KillOutcome::Killed);
+        assert!(summarize("Test", &results).unwrap().contains("stopped 2 process(es)"));
+        polite.wait().unwrap();
+        stubborn.wait().unwrap();
+    }
+
+    #[test]
+    fn test_reports_processes_that_already_exited() {
+        let (mut child, info) = spawn("exit 0");
+        child.wait().unwrap();
+
+        let strategy = KillStrategy {
+            signal: Signal::Kill,
+            grace_period: DEFAULT_GRACE_PERIOD,
+        };
+        let results = kill_processes(&[info], strategy);
+        assert_eq!(results[0].1, KillOutcome::AlreadyGone);
+    }
+
+    #[test]
+    fn test_summary_fails_when_a_process_survives() {
+        let (mut child, info) = spawn("exit 0");
+        child.wait().unwrap();
+        let results = vec![(info, KillOutcome::PermissionDenied)];
+        let message = summarize("Test", &results).unwrap_err().to_string();
+        assert!(message.contains("stopped 0 of 1"));
+        assert!(message.contains("permission denied"));
+    }
+}
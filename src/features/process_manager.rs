@@ -1,177 +1,70 @@
+use super::process_kill::{self, KillStrategy};
+use super::process_presets::{MatchPattern, ProcessAction, ProcessConfig};
+use super::scan_export::port_ranges;
+use super::scanner::Scanner;
+use crate::utils::procfs::{self, ProcessInfo, ProcessSampler, Signal};
 use crate::utils::Result;
-use std::process::Command;
+use std::collections::HashSet;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How often the process table refreshes while browsing
+const REFRESH_INTERVAL: Duration = Duration::from_secs(2);
 
 /// Process Manager state machine
 #[derive(Debug, Clone)]
 pub enum ProcessManagerState {
     /// Selecting which process cleanup to perform
     SelectingAction { selected: usize },
-    /// Confirming the action
-    Confirming { action: ProcessAction },
-    /// Processing (killing processes)
-    Processing { action: ProcessAction },
+    /// Browsing the live process table
+    Browsing { selected: usize },
+    /// Typing a filter for the process table
+    Filtering { selected: usize },
+    /// Typing the port(s) whose listeners should be killed
+    EnteringPorts { input: String, error: Option<String> },
+    /// Choosing the signal for the marked processes
+    ConfirmingKill { selected: usize, signal: Signal },
+    /// Reviewing the processes the action would kill
+    Confirming { action: ProcessAction, selected: usize },
+    /// Processing (waiting for killed processes to exit)
+    Processing { label: String },
     /// Success state with message
     Success { message: String },
     /// Error state with message
     Error { message: String },
 }
 
-/// Available process cleanup actions
+/// Columns the process table can be sorted by
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum ProcessAction {
-    /// Kill all Cursor server processes
-    KillCursorServers,
-    /// Kill common unneeded development processes
-    KillUnneededProcesses,
-    /// Kill all development servers (Cursor, Claude Code, etc.)
-    KillAllDevServers,
+pub enum SortColumn {
+    Cpu,
+    Memory,
+    Pid,
+    User,
+    Command,
 }
 
-impl ProcessAction {
-    /// Returns all available actions
-    pub fn all() -> Vec<ProcessAction> {
-        vec![
-            ProcessAction::KillCursorServers,
-            ProcessAction::KillUnneededProcesses,
-            ProcessAction::KillAllDevServers,
-        ]
-    }
-
-    /// Returns the display name of the action
+impl SortColumn {
+    /// Returns the column header label
     pub fn name(&self) -> &'static str {
         match self {
-            ProcessAction::KillCursorServers => "Kill Cursor Servers",
-            ProcessAction::KillUnneededProcesses => "Kill Unneeded Processes",
-            ProcessAction::KillAllDevServers => "Kill All Dev Servers",
-        }
-    }
-
-    /// Returns the description of the action
-    pub fn description(&self) -> &'static str {
-        match self {
-            ProcessAction::KillCursorServers => {
-                "Kills all cursor-server processes running in WSL"
-            }
-            ProcessAction::KillUnneededProcesses => {
-                "Kills common background processes that may be lingering"
-            }
-            ProcessAction::KillAllDevServers => {
-                "Kills Cursor servers, Claude Code, and other dev tool servers"
-            }
+            SortColumn::Cpu => "CPU%",
+            SortColumn::Memory => "MEM",
+            SortColumn::Pid => "PID",
+            SortColumn::User => "USER",
+            SortColumn::Command => "COMMAND",
         }
     }
 
-    /// Executes the action and returns a result message
-    pub fn execute(&self) -> Result<String> {
+    /// Returns the next column in the cycle
+    pub fn next(&self) -> SortColumn {
         match self {
-            ProcessAction::KillCursorServers => Self::kill_cursor_servers(),
-            ProcessAction::KillUnneededProcesses => Self::kill_unneeded_processes(),
-            ProcessAction::KillAllDevServers => Self::kill_all_dev_servers(),
-        }
-    }
-
-    /// Kills all Cursor server processes
-    fn kill_cursor_servers() -> Result<String> {
-        let output = Command::new("pkill")
-            .arg("-f")
-            .arg("cursor-server")
-            .output()?;
-
-        if output.status.success() || output.status.code() == Some(1) {
-            // Exit code 1 means no processes found, which is OK
-            Ok("✓ Cursor server processes terminated successfully".to_string())
-        } else {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            Err(crate::utils::ModeError::Generic(format!("Failed to kill Cursor servers: {}", stderr)))
-        }
-    }
-
-    /// Kills common unneeded processes
-    fn kill_unneeded_processes() -> Result<String> {
-        let processes = vec![
-            "cursor-server",
-            "claude",
-            "node_modules/.bin",
-            "webpack-dev-server",
-            "vite",
-            "npm-run-all",
-        ];
-
-        let mut killed = Vec::new();
-        let mut errors = Vec::new();
-
-        for process in processes {
-            let output = Command::new("pkill")
-                .arg("-f")
-                .arg(process)
-                .output()?;
-
-            if output.status.success() {
-                killed.push(process);
-            } else if output.status.code() != Some(1) {
-                // 1 means no process found, which is fine
-                errors.push(format!("{}: {}", process, String::from_utf8_lossy(&output.stderr)));
-            }
-        }
-
-        if errors.is_empty() {
-            Ok(format!(
-                "✓ Unneeded processes terminated\n\nCleaned up: {}",
-                if killed.is_empty() {
-                    "no processes found (already clean)".to_string()
-                } else {
-                    killed.join(", ")
-                }
-            ))
-        } else {
-            Err(crate::utils::ModeError::Generic(format!("Some errors occurred:\n{}", errors.join("\n"))))
-        }
-    }
-
-    /// Kills all development server processes
-    fn kill_all_dev_servers() -> Result<String> {
-        let processes = vec![
-            "cursor-server",
-            "claude-code",
-            "claude",
-            "code-server",
-            "node_modules/.bin",
-            "webpack-dev-server",
-            "vite",
-            "npm-run-all",
-            "tsx",
-            "ts-node",
-            "nodemon",
-            "next-server",
-        ];
-
-        let mut killed = Vec::new();
-        let mut errors = Vec::new();
-
-        for process in processes {
-            let output = Command::new("pkill")
-                .arg("-f")
-                .arg(process)
-                .output()?;
-
-            if output.status.success() {
-                killed.push(process);
-            } else if output.status.code() != Some(1) {
-                errors.push(format!("{}: {}", process, String::from_utf8_lossy(&output.stderr)));
-            }
-        }
-
-        if errors.is_empty() {
-            Ok(format!(
-                "✓ All development servers terminated\n\nCleaned up: {}",
-                if killed.is_empty() {
-                    "no processes found (already clean)".to_string()
-                } else {
-                    killed.join(", ")
-                }
-            ))
-        } else {
-            Err(crate::utils::ModeError::Generic(format!("Some errors occurred:\n{}", errors.join("\n"))))
+            SortColumn::Cpu => SortColumn::Memory,
+            SortColumn::Memory => SortColumn::Pid,
+            SortColumn::Pid => SortColumn::User,
+            SortColumn::User => SortColumn::Command,
+            SortColumn::Command => SortColumn::Cpu,
         }
     }
 }
@@ -180,68 +73,586 @@ impl ProcessAction {
 #[derive(Debug)]
 pub struct ProcessManager {
     pub state: ProcessManagerState,
+    sampler: ProcessSampler,
+    processes: Vec<ProcessInfo>,
+    filter: String,
+    /// Presets shown in the menu: built-ins followed by config presets
+    actions: Vec<ProcessAction>,
+    /// Why the config presets could not be loaded, if they couldn't
+    preset_error: Option<String>,
+    /// Index into `actions` of the preset used as a quick filter
+    quick_filter: Option<usize>,
+    sort: SortColumn,
+    descending: bool,
+    marked: HashSet<u32>,
+    last_refresh: Option<Instant>,
+    killed_from_browser: bool,
+    /// Grace period before SIGTERM escalates to SIGKILL in the browser
+    grace_period: Duration,
+    /// Receives the report of a kill running in the background
+    kill_result: Option<Receiver<Result<String>>>,
+    preview: Vec<ProcessInfo>,
+    unchecked: HashSet<u32>,
+    /// Requested ports that are in use by processes mode cannot inspect
+    hidden_ports: Vec<u16>,
 }
 
 impl ProcessManager {
-    /// Creates a new Process Manager
+    /// Label of the menu entry that opens the process browser
+    pub const BROWSE_NAME: &'static str = "Browse Processes";
+
+    /// Description of the menu entry that opens the process browser
+    pub const BROWSE_DESCRIPTION: &'static str =
+        "Live table of running processes: filter, sort, select and kill";
+
+    /// Label of the menu entry that kills by listening port
+    pub const PORT_NAME: &'static str = "Kill by Port";
+
+    /// Description of the menu entry that kills by listening port
+    pub const PORT_DESCRIPTION: &'static str =
+        "Free a port (e.g. 3000, 8000-8010) by stopping whatever listens on it";
+
+    /// Number of menu entries shown before the presets
+    const FIXED_ENTRIES: usize = 2;
+
+    /// Creates a new Process Manager with the built-in and configured presets
+    ///
+    /// If the config file is invalid the built-ins are still offered and the
+    /// error is shown in the prompt.
     pub fn new() -> Self {
+        let (config, preset_error) = match ProcessConfig::load() {
+            Ok(config) => (config, None),
+            Err(e) => (ProcessConfig::default(), Some(e.to_string())),
+        };
+
+        let mut manager = Self::with_config(config);
+        manager.preset_error = preset_error;
+        manager
+    }
+
+    /// Creates a new Process Manager with presets that were already loaded
+    pub fn with_config(config: ProcessConfig) -> Self {
         Self {
             state: ProcessManagerState::SelectingAction { selected: 0 },
+            sampler: ProcessSampler::new(),
+            actions: config.presets,
+            preset_error: None,
+            processes: Vec::new(),
+            filter: String::new(),
+            quick_filter: None,
+            sort: SortColumn::Cpu,
+            descending: true,
+            marked: HashSet::new(),
+            last_refresh: None,
+            killed_from_browser: false,
+            grace_period: config.grace_period,
+            kill_result: None,
+            preview: Vec::new(),
+            unchecked: HashSet::new(),
+            hidden_ports: Vec::new(),
         }
     }
 
+    /// Returns the presets offered in the menu
+    pub fn actions(&self) -> &[ProcessAction] {
+        &self.actions
+    }
+
+    /// Number of entries in the action menu (the browser, kill by port, then each preset)
+    fn menu_len(&self) -> usize {
+        self.actions.len() + Self::FIXED_ENTRIES
+    }
+
     /// Moves selection up
     pub fn previous(&mut self) {
-        if let ProcessManagerState::SelectingAction { selected } = &mut self.state {
-            let total = ProcessAction::all().len();
-            *selected = if *selected == 0 {
-                total - 1
-            } else {
-                *selected - 1
-            };
+        let visible = self.visible_processes().len();
+        let menu_len = self.menu_len();
+        match &mut self.state {
+            ProcessManagerState::SelectingAction { selected } => {
+                let total = menu_len;
+                *selected = if *selected == 0 {
+                    total - 1
+                } else {
+                    *selected - 1
+                };
+            }
+            ProcessManagerState::Browsing { selected } if visible > 0 => {
+                *selected = if *selected == 0 {
+                    visible - 1
+                } else {
+                    *selected - 1
+                };
+            }
+            ProcessManagerState::Confirming { selected, .. } if !self.preview.is_empty() => {
+                *selected = if *selected == 0 {
+                    self.preview.len() - 1
+                } else {
+                    *selected - 1
+                };
+            }
+            _ => {}
         }
     }
 
     /// Moves selection down
     pub fn next(&mut self) {
-        if let ProcessManagerState::SelectingAction { selected } = &mut self.state {
-            let total = ProcessAction::all().len();
-            *selected = (*selected + 1) % total;
+        let visible = self.visible_processes().len();
+        let menu_len = self.menu_len();
+        match &mut self.state {
+            ProcessManagerState::SelectingAction { selected } => {
+                let total = menu_len;
+                *selected = (*selected + 1) % total;
+            }
+            ProcessManagerState::Browsing { selected } if visible > 0 => {
+                *selected = (*selected + 1) % visible;
+            }
+            ProcessManagerState::Confirming { selected, .. } if !self.preview.is_empty() => {
+                *selected = (*selected + 1) % self.preview.len();
+            }
+            _ => {}
         }
     }
 
     /// Confirms the selected action
     pub fn confirm_selection(&mut self) {
         if let ProcessManagerState::SelectingAction { selected } = self.state {
-            let action = ProcessAction::all()[selected];
-            self.state = ProcessManagerState::Confirming { action };
+            match selected {
+                0 => self.open_browser(),
+                1 => {
+                    self.state = ProcessManagerState::EnteringPorts {
+                        input: String::new(),
+                        error: None,
+                    };
+                }
+                _ => {
+                    let action = self.actions[selected - Self::FIXED_ENTRIES].clone();
+                    self.preview_action(action);
+                }
+            }
         }
     }
 
-    /// Executes the confirmed action
-    pub fn execute_action(&mut self) {
-        if let ProcessManagerState::Confirming { action } = self.state {
-            self.state = ProcessManagerState::Processing { action };
+    /// Collects the processes an action would kill so they can be reviewed
+    ///
+    /// Every match starts out checked.
+    pub fn preview_action(&mut self, action: ProcessAction) {
+        let (mut matches, protected): (Vec<ProcessInfo>, Vec<ProcessInfo>) = self
+            .sampler
+            .sample()
+            .into_iter()
+            .filter(|process| action.matches(process))
+            .partition(|process| !process_kill::is_protected(process.pid));
+        matches.sort_by_key(|process| process.pid);
+
+        // Ports with a listener whose owner we can't see (another user's
+        // process without root) are reported rather than silently ignored
+        let owned: HashSet<u16> = matches
+            .iter()
+            .chain(&protected)
+            .flat_map(|process| process.listening_ports.iter().copied())
+            .collect();
+        let mut hidden_ports: Vec<u16> = procfs::listening_sockets()
+            .into_values()
+            .filter(|port| {
+                action.patterns.iter().any(|pattern| matches!(pattern, MatchPattern::Port(p) if p == port))
+                    && !owned.contains(port)
+            })
+            .collect();
+        hidden_ports.sort_unstable();
+        hidden_ports.dedup();
+
+        self.preview = matches;
+        self.hidden_ports = hidden_ports;
+        self.unchecked.clear();
+        self.state = ProcessManagerState::Confirming { action, selected: 0 };
+    }
+
+    /// Appends a character to the port input
+    pub fn add_port_char(&mut self, c: char) {
+        if let ProcessManagerState::EnteringPorts { input, error } = &mut self.state {
+            input.push(c);
+            *error = None;
+        }
+    }
+
+    /// Removes the last character of the port input
+    pub fn remove_port_char(&mut self) {
+        if let ProcessManagerState::EnteringPorts { input, error } = &mut self.state {
+            input.pop();
+            *error = None;
+        }
+    }
+
+    /// Resolves the owners of the entered ports and shows them for review
+    ///
+    /// Accepts a single port, a list or ranges, optionally written as `:3000`.
+    pub fn submit_ports(&mut self) {
+        let ProcessManagerState::EnteringPorts { input, error } = &mut self.state else {
+            return;
+        };
+
+        match parse_port_spec(input) {
+            Ok(ports) => {
+                let action = ProcessAction::for_ports(&ports, self.grace_period);
+                self.preview_action(action);
+            }
+            Err(e) => *error = Some(e.to_string()),
+        }
+    }
 
-            match action.execute() {
-                Ok(message) => {
-                    self.state = ProcessManagerState::Success { message };
+    /// Returns ports that are listened on by processes mode cannot inspect
+    pub fn hidden_ports(&self) -> &[u16] {
+        &self.hidden_ports
+    }
+
+    /// Returns the processes found for the action under review
+    pub fn preview(&self) -> &[ProcessInfo] {
+        &self.preview
+    }
+
+    /// Returns whether a reviewed process will be killed
+    pub fn is_checked(&self, pid: u32) -> bool {
+        !self.unchecked.contains(&pid)
+    }
+
+    /// Returns the reviewed processes that are still checked
+    pub fn checked_processes(&self) -> Vec<ProcessInfo> {
+        self.preview
+            .iter()
+            .filter(|process| self.is_checked(process.pid))
+            .cloned()
+            .collect()
+    }
+
+    /// Checks or unchecks the reviewed process under the cursor
+    pub fn toggle_check(&mut self) {
+        if let ProcessManagerState::Confirming { selected, .. } = self.state {
+            if let Some(pid) = self.preview.get(selected).map(|p| p.pid) {
+                if !self.unchecked.remove(&pid) {
+                    self.unchecked.insert(pid);
                 }
-                Err(e) => {
-                    self.state = ProcessManagerState::Error {
-                        message: e.to_string(),
-                    };
+            }
+        }
+    }
+
+    /// Checks every reviewed process, or unchecks them all if all are checked
+    pub fn toggle_check_all(&mut self) {
+        if self.unchecked.is_empty() {
+            self.unchecked = self.preview.iter().map(|p| p.pid).collect();
+        } else {
+            self.unchecked.clear();
+        }
+    }
+
+    /// Kills the processes that were left checked in the review
+    ///
+    /// Nothing outside the reviewed list is touched.
+    pub fn execute_action(&mut self) {
+        if let ProcessManagerState::Confirming { action, .. } = &self.state {
+            let targets = self.checked_processes();
+            if targets.is_empty() {
+                return;
+            }
+
+            let strategy = action.strategy();
+            let label = action.name.clone();
+            self.killed_from_browser = false;
+            self.start_kill(label, targets, strategy);
+        }
+    }
+
+    /// Opens the process browser with a fresh snapshot
+    pub fn open_browser(&mut self) {
+        self.marked.clear();
+        self.refresh();
+        self.state = ProcessManagerState::Browsing { selected: 0 };
+    }
+
+    /// Re-reads the process table, keeping the cursor in range
+    pub fn refresh(&mut self) {
+        self.processes = self.sampler.sample();
+        self.last_refresh = Some(Instant::now());
+        self.sort_processes();
+
+        let live: HashSet<u32> = self.processes.iter().map(|p| p.pid).collect();
+        self.marked.retain(|pid| live.contains(pid));
+        self.clamp_selection();
+    }
+
+    /// Returns how the browser stops processes for the chosen signal
+    pub fn kill_strategy(&self, signal: Signal) -> KillStrategy {
+        KillStrategy {
+            signal,
+            grace_period: self.grace_period,
+        }
+    }
+
+    /// Stops processes on a background thread so the UI keeps drawing
+    /// during the grace period; `tick` picks up the report
+    fn start_kill(&mut self, label: String, targets: Vec<ProcessInfo>, strategy: KillStrategy) {
+        self.state = ProcessManagerState::Processing {
+            label: format!("{}: {}", label, strategy.describe()),
+        };
+
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let results = process_kill::kill_processes(&targets, strategy);
+            let _ = tx.send(process_kill::summarize(&label, &results));
+        });
+        self.kill_result = Some(rx);
+    }
+
+    /// Moves to Success or Error once a background kill has reported back
+    fn poll_kill(&mut self) {
+        let Some(rx) = &self.kill_result else {
+            return;
+        };
+
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => Err(crate::utils::ModeError::Generic(
+                "Kill was interrupted before reporting".to_string(),
+            )),
+        };
+        self.kill_result = None;
+        self.state = match result {
+            Ok(message) => ProcessManagerState::Success { message },
+            Err(e) => ProcessManagerState::Error {
+                message: e.to_string(),
+            },
+        };
+    }
+
+    /// Refreshes the table periodically while it is on screen and collects
+    /// the result of a running kill
+    pub fn tick(&mut self) {
+        self.poll_kill();
+
+        let browsing = matches!(
+            self.state,
+            ProcessManagerState::Browsing { .. } | ProcessManagerState::Filtering { .. }
+        );
+        let stale = match self.last_refresh {
+            Some(last) => last.elapsed() >= REFRESH_INTERVAL,
+            None => true,
+        };
+
+        if browsing && stale {
+            self.refresh();
+        }
+    }
+
+    /// Returns the processes that pass the preset and text filters, in display order
+    pub fn visible_processes(&self) -> Vec<&ProcessInfo> {
+        self.processes
+            .iter()
+            .filter(|process| match self.quick_filter {
+                Some(index) => self.actions[index].matches(process),
+                None => true,
+            })
+            .filter(|process| process.matches(&self.filter))
+            .collect()
+    }
+
+    /// Returns whether a process is marked for killing
+    pub fn is_marked(&self, pid: u32) -> bool {
+        self.marked.contains(&pid)
+    }
+
+    /// Returns the number of marked processes
+    pub fn marked_count(&self) -> usize {
+        self.marked.len()
+    }
+
+    /// Returns the current text filter
+    pub fn filter(&self) -> &str {
+        &self.filter
+    }
+
+    /// Returns the preset used as a quick filter, if any
+    pub fn quick_filter(&self) -> Option<&ProcessAction> {
+        self.quick_filter.map(|index| &self.actions[index])
+    }
+
+    /// Returns the sort column and whether it is descending
+    pub fn sort_order(&self) -> (SortColumn, bool) {
+        (self.sort, self.descending)
+    }
+
+    /// Marks or unmarks the process under the cursor
+    pub fn toggle_mark(&mut self) {
+        if let ProcessManagerState::Browsing { selected } = self.state {
+            if let Some(pid) = self.visible_processes().get(selected).map(|p| p.pid) {
+                if !self.marked.remove(&pid) {
+                    self.marked.insert(pid);
                 }
             }
         }
     }
 
-    /// Goes back to the selection state
+    /// Marks every visible process, or clears them if all are already marked
+    pub fn toggle_mark_all(&mut self) {
+        let visible: Vec<u32> = self.visible_processes().iter().map(|p| p.pid).collect();
+        if visible.iter().all(|pid| self.marked.contains(pid)) {
+            for pid in &visible {
+                self.marked.remove(pid);
+            }
+        } else {
+            self.marked.extend(visible);
+        }
+    }
+
+    /// Cycles the sort column
+    pub fn cycle_sort(&mut self) {
+        self.sort = self.sort.next();
+        // Numbers read best largest-first, text alphabetically
+        self.descending = matches!(self.sort, SortColumn::Cpu | SortColumn::Memory);
+        self.sort_processes();
+    }
+
+    /// Reverses the sort order
+    pub fn toggle_sort_order(&mut self) {
+        self.descending = !self.descending;
+        self.sort_processes();
+    }
+
+    /// Cycles the quick filter through the presets, then back to none
+    pub fn cycle_quick_filter(&mut self) {
+        self.quick_filter = match self.quick_filter {
+            None if !self.actions.is_empty() => Some(0),
+            Some(index) if index + 1 < self.actions.len() => Some(index + 1),
+            _ => None,
+        };
+        self.reset_selection();
+    }
+
+    /// Starts typing a text filter
+    pub fn start_filter(&mut self) {
+        if let ProcessManagerState::Browsing { selected } = self.state {
+            self.state = ProcessManagerState::Filtering { selected };
+        }
+    }
+
+    /// Appends a character to the text filter
+    pub fn add_filter_char(&mut self, c: char) {
+        self.filter.push(c);
+        self.reset_selection();
+    }
+
+    /// Removes the last character of the text filter
+    pub fn remove_filter_char(&mut self) {
+        self.filter.pop();
+        self.reset_selection();
+    }
+
+    /// Keeps the typed filter and returns to the table
+    pub fn finish_filter(&mut self) {
+        if let ProcessManagerState::Filtering { selected } = self.state {
+            self.state = ProcessManagerState::Browsing { selected };
+        }
+    }
+
+    /// Clears the text filter and returns to the table
+    pub fn cancel_filter(&mut self) {
+        if let ProcessManagerState::Filtering { .. } = self.state {
+            self.filter.clear();
+            self.state = ProcessManagerState::Browsing { selected: 0 };
+        }
+    }
+
+    /// Returns the processes a kill would target
+    ///
+    /// Marked processes take priority; otherwise the one under the cursor.
+    /// PID 1 and mode itself are never targeted.
+    pub fn kill_targets(&self) -> Vec<ProcessInfo> {
+        let candidates: Vec<ProcessInfo> = if self.marked.is_empty() {
+            let selected = match self.state {
+                ProcessManagerState::Browsing { selected }
+                | ProcessManagerState::ConfirmingKill { selected, .. } => selected,
+                _ => return Vec::new(),
+            };
+            self.visible_processes()
+                .get(selected)
+                .map(|process| vec![(*process).clone()])
+                .unwrap_or_default()
+        } else {
+            self.processes
+                .iter()
+                .filter(|process| self.marked.contains(&process.pid))
+                .cloned()
+                .collect()
+        };
+
+        candidates
+            .into_iter()
+            .filter(|process| !process_kill::is_protected(process.pid))
+            .collect()
+    }
+
+    /// Asks which signal to send to the kill targets
+    pub fn request_kill(&mut self) {
+        if let ProcessManagerState::Browsing { selected } = self.state {
+            if !self.kill_targets().is_empty() {
+                self.state = ProcessManagerState::ConfirmingKill {
+                    selected,
+                    signal: Signal::Term,
+                };
+            }
+        }
+    }
+
+    /// Switches between SIGTERM and SIGKILL
+    pub fn toggle_signal(&mut self) {
+        if let ProcessManagerState::ConfirmingKill { signal, .. } = &mut self.state {
+            *signal = signal.toggle();
+        }
+    }
+
+    /// Sends the chosen signal to every kill target
+    pub fn execute_kill(&mut self) {
+        let ProcessManagerState::ConfirmingKill { signal, .. } = self.state else {
+            return;
+        };
+
+        let targets = self.kill_targets();
+        self.marked.clear();
+        self.killed_from_browser = true;
+        let strategy = self.kill_strategy(signal);
+        self.start_kill("Kill Selected Processes".to_string(), targets, strategy);
+    }
+
+    /// Returns to the process table after a kill started there
+    ///
+    /// Returns false when the result came from a preset, in which case the
+    /// caller should go back to the main menu.
+    pub fn resume_browsing(&mut self) -> bool {
+        if self.is_done() && self.killed_from_browser {
+            self.killed_from_browser = false;
+            self.refresh();
+            self.state = ProcessManagerState::Browsing { selected: 0 };
+            self.clamp_selection();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Goes back to the previous state
     pub fn go_back(&mut self) {
-        match &self.state {
+        match self.state {
             ProcessManagerState::Confirming { .. } => {
                 self.state = ProcessManagerState::SelectingAction { selected: 0 };
             }
+            ProcessManagerState::Browsing { .. } => {
+                self.state = ProcessManagerState::SelectingAction { selected: 0 };
+            }
+            ProcessManagerState::EnteringPorts { .. } => {
+                self.state = ProcessManagerState::SelectingAction { selected: 1 };
+            }
+            ProcessManagerState::ConfirmingKill { selected, .. } => {
+                self.state = ProcessManagerState::Browsing { selected };
+            }
             _ => {}
         }
     }
@@ -271,14 +682,57 @@ impl ProcessManager {
     /// Gets the prompt text for the current state
     pub fn get_prompt(&self) -> String {
         match &self.state {
-            ProcessManagerState::SelectingAction { .. } => {
-                "Select an action (↑/↓ to navigate, Enter to select, ESC to cancel):".to_string()
+            ProcessManagerState::SelectingAction { .. } => match &self.preset_error {
+                Some(error) => format!("Using built-in presets only. {}", error),
+                None => "Select an action (↑/↓ to navigate, Enter to select, ESC to cancel):".to_string(),
+            },
+            ProcessManagerState::Browsing { .. } | ProcessManagerState::Filtering { .. } => {
+                let (column, descending) = self.sort_order();
+                let mut prompt = format!(
+                    "{} of {} processes · sorted by {} {}",
+                    self.visible_processes().len(),
+                    self.processes.len(),
+                    column.name(),
+                    if descending { "▼" } else { "▲" }
+                );
+                if let Some(action) = self.quick_filter() {
+                    prompt.push_str(&format!(" · preset: {}", action.name));
+                }
+                if !self.marked.is_empty() {
+                    prompt.push_str(&format!(" · {} marked", self.marked.len()));
+                }
+                prompt
+            }
+            ProcessManagerState::ConfirmingKill { signal, .. } => {
+                format!("Send {} to the selected processes?", signal.name())
             }
-            ProcessManagerState::Confirming { action } => {
-                format!("{}\n{}", action.name(), action.description())
+            ProcessManagerState::EnteringPorts { error, .. } => match error {
+                Some(error) => format!("{}\n\nEnter a port, list or range (e.g. 3000 or 8000-8010,9229):", error),
+                None => "Enter a port, list or range (e.g. 3000 or 8000-8010,9229):".to_string(),
+            },
+            ProcessManagerState::Confirming { action, .. } => {
+                let hidden = if self.hidden_ports.is_empty() {
+                    String::new()
+                } else {
+                    format!(
+                        " · :{} in use by another user's process (try sudo)",
+                        port_ranges(&self.hidden_ports)
+                    )
+                };
+                if self.preview.is_empty() {
+                    format!("{}: no running processes match{}", action.name, hidden)
+                } else {
+                    format!(
+                        "{}: {} of {} matching process(es) checked{}",
+                        action.name,
+                        self.checked_processes().len(),
+                        self.preview.len(),
+                        hidden
+                    )
+                }
             }
-            ProcessManagerState::Processing { action } => {
-                format!("{}...", action.name())
+            ProcessManagerState::Processing { label } => {
+                format!("{}...", label)
             }
             ProcessManagerState::Success { message } => message.clone(),
             ProcessManagerState::Error { message } => format!("Error: {}", message),
@@ -288,12 +742,60 @@ impl ProcessManager {
     /// Gets confirmation data for structured display
     pub fn get_confirmation_data(&self) -> Option<(String, String)> {
         match &self.state {
-            ProcessManagerState::Confirming { action } => {
-                Some((action.name().to_string(), action.description().to_string()))
+            ProcessManagerState::Confirming { action, .. } => {
+                Some((action.name.clone(), action.patterns_label()))
             }
             _ => None,
         }
     }
+
+    /// Sorts the full process list by the current column
+    fn sort_processes(&mut self) {
+        let sort = self.sort;
+        self.processes.sort_by(|a, b| {
+            let ordering = match sort {
+                SortColumn::Cpu => a.cpu_percent.total_cmp(&b.cpu_percent),
+                SortColumn::Memory => a.memory.cmp(&b.memory),
+                SortColumn::Pid => a.pid.cmp(&b.pid),
+                SortColumn::User => a.user.cmp(&b.user),
+                SortColumn::Command => a.cmdline.cmp(&b.cmdline),
+            };
+            // Ties fall back to PID so rows don't jump between refreshes
+            ordering.then(a.pid.cmp(&b.pid))
+        });
+        if self.descending {
+            self.processes.reverse();
+        }
+    }
+
+    /// Moves the cursor back to the top of the table
+    fn reset_selection(&mut self) {
+        if let ProcessManagerState::Browsing { selected } | ProcessManagerState::Filtering { selected } =
+            &mut self.state
+        {
+            *selected = 0;
+        }
+    }
+
+    /// Keeps the cursor within the visible rows
+    fn clamp_selection(&mut self) {
+        let last = self.visible_processes().len().saturating_sub(1);
+        if let ProcessManagerState::Browsing { selected }
+        | ProcessManagerState::Filtering { selected }
+        | ProcessManagerState::ConfirmingKill { selected, .. } = &mut self.state
+        {
+            *selected = (*selected).min(last);
+        }
+    }
+}
+
+/// Parses a port list such as `3000`, `:3000,:8080` or `8000-8010`
+pub fn parse_port_spec(input: &str) -> Result<Vec<u16>> {
+    let cleaned: Vec<&str> = input
+        .split(',')
+        .map(|part| part.trim().trim_start_matches(':'))
+        .collect();
+    Scanner::parse_port_range(&cleaned.join(","))
 }
 
 impl Default for ProcessManager {
@@ -301,3 +803,173 @@ impl Default for ProcessManager {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(pid: u32, cmdline: &str, cpu: f32) -> ProcessInfo {
+        ProcessInfo {
+            pid,
+            ppid: 1,
+            user: "dev".to_string(),
+            name: cmdline.split_whitespace().next().unwrap_or_default().to_string(),
+            exe: cmdline.split_whitespace().next().unwrap_or_default().to_string(),
+            cmdline: cmdline.to_string(),
+            cpu_percent: cpu,
+            memory: pid as u64 * 1024,
+            memory_percent: 0.1,
+            listening_ports: Vec::new(),
+            start_ticks: 0,
+        }
+    }
+
+    impl ProcessManager {
+        fn confirm_selection_at(&mut self, selected: usize) {
+            self.state = ProcessManagerState::SelectingAction { selected };
+            self.confirm_selection();
+        }
+    }
+
+    fn browsing(processes: Vec<ProcessInfo>) -> ProcessManager {
+        let mut manager = ProcessManager::new();
+        manager.actions = ProcessAction::builtin();
+        manager.processes = processes;
+        manager.sort_processes();
+        manager.state = ProcessManagerState::Browsing { selected: 0 };
+        manager
+    }
+
+    #[test]
+    fn test_presets_filter_the_table() {
+        let mut manager = browsing(vec![
+            process(100, "/usr/bin/vim notes.txt", 0.0),
+            process(200, "/home/dev/.cursor-server/bin/node", 5.0),
+            process(300, "node node_modules/.bin/vite", 1.0),
+        ]);
+        assert_eq!(manager.visible_processes().len(), 3);
+
+        manager.cycle_quick_filter();
+        assert_eq!(manager.quick_filter().map(|action| action.slug.as_str()), Some("cursor"));
+        let pids: Vec<u32> = manager.visible_processes().iter().map(|p| p.pid).collect();
+        assert_eq!(pids, vec![200]);
+
+        manager.cycle_quick_filter();
+        assert_eq!(manager.visible_processes().len(), 2);
+    }
+
+    #[test]
+    fn test_sort_and_text_filter() {
+        let mut manager = browsing(vec![
+            process(100, "python -m http.server", 2.0),
+            process(200, "node server.js", 9.0),
+            process(300, "bash", 0.5),
+        ]);
+        let pids: Vec<u32> = manager.visible_processes().iter().map(|p| p.pid).collect();
+        assert_eq!(pids, vec![200, 100, 300]);
+
+        manager.cycle_sort();
+        manager.cycle_sort();
+        assert_eq!(manager.sort_order(), (SortColumn::Pid, false));
+        assert_eq!(manager.visible_processes()[0].pid, 100);
+
+        manager.start_filter();
+        for c in "SERVER".chars() {
+            manager.add_filter_char(c);
+        }
+        manager.finish_filter();
+        assert_eq!(manager.visible_processes().len(), 2);
+    }
+
+    #[test]
+    fn test_preview_kills_only_checked_processes() {
+        use std::process::Command;
+
+        let mut child = Command::new("sleep").arg("300").spawn().unwrap();
+        let mut spared = Command::new("sleep").arg("301").spawn().unwrap();
+
+        let mut manager = ProcessManager::new();
+        let action = ProcessAction::from_slug(&ProcessAction::builtin(), "cursor").unwrap();
+        manager.preview_action(action);
+        let sleep = |pid: u32, seconds: &str| ProcessInfo {
+            cmdline: format!("sleep {}", seconds),
+            start_ticks: crate::utils::procfs::start_ticks(pid).unwrap(),
+            ..process(pid, "sleep", 0.0)
+        };
+        manager.preview = vec![sleep(child.id(), "300"), sleep(spared.id(), "301")];
+
+        manager.next();
+        manager.toggle_check();
+        assert_eq!(manager.checked_processes().len(), 1);
+
+        manager.execute_action();
+        assert!(matches!(manager.state, ProcessManagerState::Processing { .. }));
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !manager.is_done() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(20));
+            manager.tick();
+        }
+        match &manager.state {
+            ProcessManagerState::Success { message } => assert!(message.contains("terminated")),
+            other => panic!("unexpected state: {:?}", other),
+        }
+        assert!(child.wait().unwrap().code().is_none());
+        assert!(spared.try_wait().unwrap().is_none());
+        spared.kill().unwrap();
+        spared.wait().unwrap();
+    }
+
+    #[test]
+    fn test_kill_by_port_finds_the_listener() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let mut manager = ProcessManager::new();
+        manager.confirm_selection_at(1);
+        for c in format!(":{}", port).chars() {
+            manager.add_port_char(c);
+        }
+        manager.submit_ports();
+
+        assert!(matches!(manager.state, ProcessManagerState::Confirming { .. }));
+        // mode never offers to kill itself, so the test process is filtered out
+        assert!(manager.preview().iter().all(|p| p.pid != std::process::id()));
+        assert!(manager.hidden_ports().is_empty());
+
+        manager.go_back();
+        manager.confirm_selection_at(1);
+        for c in "80-".chars() {
+            manager.add_port_char(c);
+        }
+        manager.submit_ports();
+        assert!(matches!(
+            manager.state,
+            ProcessManagerState::EnteringPorts { error: Some(_), .. }
+        ));
+        assert_eq!(parse_port_spec(":3000, 8000-8002").unwrap(), vec![3000, 8000, 8001, 8002]);
+    }
+
+    #[test]
+    fn test_kill_targets_prefer_marked_and_skip_protected() {
+        let mut manager = browsing(vec![
+            process(1, "/sbin/init", 0.0),
+            process(std::process::id(), "mode", 0.0),
+            process(400, "node a.js", 3.0),
+            process(500, "node b.js", 2.0),
+        ]);
+
+        // Nothing marked: the row under the cursor
+        assert_eq!(manager.kill_targets().len(), 1);
+
+        manager.toggle_mark_all();
+        let pids: Vec<u32> = manager.kill_targets().iter().map(|p| p.pid).collect();
+        assert_eq!(pids, vec![400, 500]);
+
+        manager.request_kill();
+        manager.toggle_signal();
+        assert!(matches!(
+            manager.state,
+            ProcessManagerState::ConfirmingKill { signal: Signal::Kill, .. }
+        ));
+    }
+}
@@ -0,0 +1,478 @@
+use super::process_kill::{self, KillStrategy, DEFAULT_GRACE_PERIOD};
+use super::scan_export::port_ranges;
+use crate::utils::config::config_file_path;
+use crate::utils::procfs::{ProcessInfo, ProcessSampler, Signal};
+use crate::utils::{ModeError, Result};
+use regex::Regex;
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+/// Longest grace period accepted from the config
+const MAX_GRACE_PERIOD: Duration = Duration::from_secs(60);
+
+/// One way a preset can match a process
+#[derive(Debug, Clone)]
+pub enum MatchPattern {
+    /// Regex searched anywhere in the full command line
+    Cmdline(Regex),
+    /// Regex matched against the executable name (`comm` or argv[0] basename)
+    Exe(Regex),
+    /// Process is listening on this TCP port
+    Port(u16),
+}
+
+impl MatchPattern {
+    /// Returns whether a process matches this pattern
+    pub fn matches(&self, process: &ProcessInfo) -> bool {
+        match self {
+            MatchPattern::Cmdline(regex) => regex.is_match(&process.cmdline),
+            MatchPattern::Exe(regex) => regex.is_match(&process.name) || regex.is_match(&process.exe),
+            MatchPattern::Port(port) => process.listening_ports.contains(port),
+        }
+    }
+
+    /// Returns the pattern in the form it is written in the config
+    pub fn describe(&self) -> String {
+        match self {
+            MatchPattern::Cmdline(regex) => regex.as_str().to_string(),
+            MatchPattern::Exe(regex) => format!("exe:{}", regex.as_str()),
+            MatchPattern::Port(port) => format!(":{}", port),
+        }
+    }
+}
+
+/// Where a preset was defined
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PresetSource {
+    /// Compiled into mode
+    Builtin,
+    /// Loaded from the user's config file
+    Config,
+}
+
+/// A named set of match patterns and the signal used to kill the matches
+#[derive(Debug, Clone)]
+pub struct ProcessAction {
+    pub name: String,
+    /// Short identifier used on the command line (`mode kill <slug>`)
+    pub slug: String,
+    pub description: String,
+    pub patterns: Vec<MatchPattern>,
+    pub signal: Signal,
+    /// How long SIGTERM is given before escalating to SIGKILL
+    pub grace_period: Duration,
+    pub source: PresetSource,
+}
+
+/// Process Manager settings from the config file
+#[derive(Debug, Clone)]
+pub struct ProcessConfig {
+    /// Built-in presets followed by the user's presets
+    pub presets: Vec<ProcessAction>,
+    /// Grace period for kills that don't come from a preset
+    pub grace_period: Duration,
+}
+
+/// A preset as written in `config.toml`
+///
+/// ```toml
+/// [[process_presets]]
+/// name = "Kill Python Servers"
+/// description = "uvicorn, gunicorn and Django runserver"
+/// cmdline = ["uvicorn", "gunicorn", "manage\\.py runserver"]
+/// exe = ["^docker-proxy$"]
+/// ports = [8000]
+/// signal = "SIGTERM"
+/// grace_period_ms = 10000
+/// ```
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PresetConfig {
+    name: String,
+    slug: Option<String>,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    cmdline: Vec<String>,
+    #[serde(default)]
+    exe: Vec<String>,
+    #[serde(default)]
+    ports: Vec<u16>,
+    signal: Option<String>,
+    grace_period_ms: Option<u64>,
+}
+
+/// The `[process_manager]` table of `config.toml`
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ManagerConfig {
+    grace_period_ms: Option<u64>,
+}
+
+/// The parts of `config.toml` used by the Process Manager
+#[derive(Debug, Default, Deserialize)]
+struct PresetFile {
+    #[serde(default)]
+    process_manager: ManagerConfig,
+    #[serde(default)]
+    process_presets: Vec<PresetConfig>,
+}
+
+impl ProcessConfig {
+    /// Loads the settings from the user's config file
+    pub fn load() -> Result<ProcessConfig> {
+        Self::load_from(&config_file_path()?)
+    }
+
+    /// Loads the settings from a config file
+    ///
+    /// A missing file yields the built-in presets and default grace period.
+    pub fn load_from(path: &Path) -> Result<ProcessConfig> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path)?;
+        Self::parse(&content).map_err(|e| ModeError::Config(format!("{}: {}", path.display(), e)))
+    }
+
+    /// Parses and validates the Process Manager parts of a config file
+    ///
+    /// A config preset with the same slug as a built-in replaces it.
+    pub(crate) fn parse(content: &str) -> std::result::Result<ProcessConfig, String> {
+        let file: PresetFile = toml::from_str(content).map_err(|e| e.message().to_string())?;
+        let grace_period = match file.process_manager.grace_period_ms {
+            Some(ms) => parse_grace_period(ms).map_err(|e| format!("process_manager: {}", e))?,
+            None => DEFAULT_GRACE_PERIOD,
+        };
+
+        let mut presets = ProcessAction::builtin();
+        for preset in &mut presets {
+            preset.grace_period = grace_period;
+        }
+
+        let mut seen: Vec<String> = Vec::new();
+        for config in file.process_presets {
+            let preset = ProcessAction::from_config(config, grace_period)?;
+            if seen.contains(&preset.slug) {
+                return Err(format!("preset slug '{}' is defined more than once", preset.slug));
+            }
+            seen.push(preset.slug.clone());
+
+            match presets.iter().position(|existing| existing.slug == preset.slug) {
+                Some(index) => presets[index] = preset,
+                None => presets.push(preset),
+            }
+        }
+
+        Ok(ProcessConfig { presets, grace_period })
+    }
+}
+
+impl Default for ProcessConfig {
+    fn default() -> Self {
+        Self {
+            presets: ProcessAction::builtin(),
+            grace_period: DEFAULT_GRACE_PERIOD,
+        }
+    }
+}
+
+impl ProcessAction {
+    /// Returns the presets compiled into mode
+    pub fn builtin() -> Vec<ProcessAction> {
+        vec![
+            Self::substring_preset(
+                "Kill Cursor Servers",
+                "cursor",
+                "Kills all cursor-server processes running in WSL",
+                &["cursor-server"],
+            ),
+            Self::substring_preset(
+                "Kill Unneeded Processes",
+                "unneeded",
+                "Kills common background processes that may be lingering",
+                &[
+                    "cursor-server",
+                    "claude",
+                    "node_modules/.bin",
+                    "webpack-dev-server",
+                    "vite",
+                    "npm-run-all",
+                ],
+            ),
+            Self::substring_preset(
+                "Kill All Dev Servers",
+                "dev-servers",
+                "Kills Cursor servers, Claude Code, and other dev tool servers",
+                &[
+                    "cursor-server",
+                    "claude-code",
+                    "claude",
+                    "code-server",
+                    "node_modules/.bin",
+                    "webpack-dev-server",
+                    "vite",
+                    "npm-run-all",
+                    "tsx",
+                    "ts-node",
+                    "nodemon",
+                    "next-server",
+                ],
+            ),
+        ]
+    }
+
+    /// Looks up a preset by its command line identifier
+    pub fn from_slug(presets: &[ProcessAction], slug: &str) -> Option<ProcessAction> {
+        presets.iter().find(|action| action.slug == slug).cloned()
+    }
+
+    /// Returns how this preset stops the processes it matches
+    pub fn strategy(&self) -> KillStrategy {
+        KillStrategy {
+            signal: self.signal,
+            grace_period: self.grace_period,
+        }
+    }
+
+    /// Stops every running process this action matches and reports the outcome
+    ///
+    /// Used by `mode kill`, where there is no review step.
+    pub fn execute(&self) -> Result<String> {
+        let targets: Vec<ProcessInfo> = ProcessSampler::new()
+            .sample()
+            .into_iter()
+            .filter(|process| self.matches(process) && !process_kill::is_protected(process.pid))
+            .collect();
+
+        if targets.is_empty() {
+            return Ok(format!("✓ {}: no matching processes (already clean)", self.name));
+        }
+        let results = process_kill::kill_processes(&targets, self.strategy());
+        process_kill::summarize(&self.name, &results)
+    }
+
+    /// Returns whether a process would be targeted by this action
+    pub fn matches(&self, process: &ProcessInfo) -> bool {
+        self.patterns.iter().any(|pattern| pattern.matches(process))
+    }
+
+    /// Builds a one-off action that stops whatever listens on the given ports
+    pub fn for_ports(ports: &[u16], grace_period: Duration) -> ProcessAction {
+        ProcessAction {
+            name: "Kill by Port".to_string(),
+            slug: "port".to_string(),
+            description: format!("Processes listening on TCP {}", port_ranges(ports)),
+            patterns: ports.iter().map(|port| MatchPattern::Port(*port)).collect(),
+            signal: Signal::Term,
+            grace_period,
+            source: PresetSource::Builtin,
+        }
+    }
+
+    /// Returns the patterns formatted for display
+    ///
+    /// Port patterns are collapsed into ranges (":3000-3010").
+    pub fn patterns_label(&self) -> String {
+        let ports: Vec<u16> = self
+            .patterns
+            .iter()
+            .filter_map(|pattern| match pattern {
+                MatchPattern::Port(port) => Some(*port),
+                _ => None,
+            })
+            .collect();
+
+        let mut labels: Vec<String> = self
+            .patterns
+            .iter()
+            .filter(|pattern| !matches!(pattern, MatchPattern::Port(_)))
+            .map(MatchPattern::describe)
+            .collect();
+        if !ports.is_empty() {
+            labels.push(format!(":{}", port_ranges(&ports)));
+        }
+        labels.join(", ")
+    }
+
+    /// Builds a built-in preset that matches plain substrings of the command line
+    fn substring_preset(name: &str, slug: &str, description: &str, fragments: &[&str]) -> ProcessAction {
+        ProcessAction {
+            name: name.to_string(),
+            slug: slug.to_string(),
+            description: description.to_string(),
+            patterns: fragments
+                .iter()
+                .map(|fragment| {
+                    MatchPattern::Cmdline(Regex::new(&regex::escape(fragment)).expect("escaped regex is valid"))
+                })
+                .collect(),
+            signal: Signal::Term,
+            grace_period: DEFAULT_GRACE_PERIOD,
+            source: PresetSource::Builtin,
+        }
+    }
+
+    /// Validates one `[[process_presets]]` table
+    fn from_config(config: PresetConfig, default_grace_period: Duration) -> std::result::Result<ProcessAction, String> {
+        let name = config.name.trim().to_string();
+        if name.is_empty() {
+            return Err("process preset name cannot be empty".to_string());
+        }
+
+        let slug = config.slug.unwrap_or_else(|| slugify(&name));
+        if slug.is_empty() || !slug.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            return Err(format!(
+                "preset '{}': slug '{}' may only contain letters, digits, '-' and '_'",
+                name, slug
+            ));
+        }
+
+        let compile = |pattern: &String| {
+            Regex::new(pattern).map_err(|e| format!("preset '{}': invalid regex '{}': {}", name, pattern, e))
+        };
+        let mut patterns = Vec::new();
+        for pattern in &config.cmdline {
+            patterns.push(MatchPattern::Cmdline(compile(pattern)?));
+        }
+        for pattern in &config.exe {
+            patterns.push(MatchPattern::Exe(compile(pattern)?));
+        }
+        patterns.extend(config.ports.iter().map(|port| MatchPattern::Port(*port)));
+        if patterns.is_empty() {
+            return Err(format!(
+                "preset '{}' needs at least one of cmdline, exe or ports",
+                name
+            ));
+        }
+
+        let signal = match config.signal.as_deref() {
+            None => Signal::Term,
+            Some(value) => Signal::from_name(value)
+                .ok_or_else(|| format!("preset '{}': unknown signal '{}' (use SIGTERM or SIGKILL)", name, value))?,
+        };
+        let grace_period = match config.grace_period_ms {
+            Some(ms) => parse_grace_period(ms).map_err(|e| format!("preset '{}': {}", name, e))?,
+            None => default_grace_period,
+        };
+
+        Ok(ProcessAction {
+            description: if config.description.is_empty() {
+                "Custom preset from config.toml".to_string()
+            } else {
+                config.description
+            },
+            name,
+            slug,
+            patterns,
+            signal,
+            grace_period,
+            source: PresetSource::Config,
+        })
+    }
+}
+
+/// Checks a `grace_period_ms` value from the config
+pub(crate) fn parse_grace_period(ms: u64) -> std::result::Result<Duration, String> {
+    let grace_period = Duration::from_millis(ms);
+    if grace_period > MAX_GRACE_PERIOD {
+        return Err(format!(
+            "grace_period_ms must be at most {}",
+            MAX_GRACE_PERIOD.as_millis()
+        ));
+    }
+    Ok(grace_period)
+}
+
+/// Turns a display name into a command line identifier ("Kill Rails" -> "kill-rails")
+fn slugify(name: &str) -> String {
+    name.to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(name: &str, cmdline: &str, ports: Vec<u16>) -> ProcessInfo {
+        ProcessInfo {
+            pid: 4242,
+            ppid: 1,
+            user: "dev".to_string(),
+            name: name.to_string(),
+            exe: name.to_string(),
+            cmdline: cmdline.to_string(),
+            cpu_percent: 0.0,
+            memory: 0,
+            memory_percent: 0.0,
+            listening_ports: ports,
+            start_ticks: 0,
+        }
+    }
+
+    #[test]
+    fn test_builtin_presets_match_substrings() {
+        let presets = ProcessAction::builtin();
+        let cursor = ProcessAction::from_slug(&presets, "cursor").unwrap();
+        assert!(cursor.matches(&process("node", "/home/dev/.cursor-server/bin/node", vec![])));
+        assert!(!cursor.matches(&process("vim", "vim cursor.txt", vec![])));
+
+        // "node_modules/.bin" must be literal, not a regex wildcard
+        let dev = ProcessAction::from_slug(&presets, "dev-servers").unwrap();
+        assert!(!dev.matches(&process("node", "node node_modules_bin", vec![])));
+        assert_eq!(dev.signal, Signal::Term);
+    }
+
+    #[test]
+    fn test_parse_config_presets() {
+        let content = r#"
+            theme = "dark"
+
+            [process_manager]
+            grace_period_ms = 1500
+
+            [[process_presets]]
+            name = "Kill Python Servers"
+            cmdline = ["uvicorn", "manage\\.py runserver"]
+            exe = ["^cargo-watch$"]
+            ports = [8000]
+            signal = "KILL"
+        "#;
+        let config = ProcessConfig::parse(content).unwrap();
+        assert_eq!(config.presets.len(), ProcessAction::builtin().len() + 1);
+        assert_eq!(config.grace_period, Duration::from_millis(1500));
+        assert_eq!(config.presets[0].grace_period, Duration::from_millis(1500));
+
+        let preset = ProcessAction::from_slug(&config.presets, "kill-python-servers").unwrap();
+        assert_eq!(preset.signal, Signal::Kill);
+        assert_eq!(preset.source, PresetSource::Config);
+        assert!(preset.matches(&process("python3", "python3 -m uvicorn app:main", vec![])));
+        assert!(preset.matches(&process("cargo-watch", "cargo watch -x run", vec![])));
+        assert!(preset.matches(&process("node", "node server.js", vec![8000])));
+        assert!(!preset.matches(&process("python3", "python3 manage_py runserver", vec![])));
+    }
+
+    #[test]
+    fn test_parse_config_rejects_invalid_presets() {
+        let cases = [
+            ("[[process_presets]]\nname = \"Empty\"\n", "at least one"),
+            ("[[process_presets]]\nname = \"Bad\"\ncmdline = [\"(\"]\n", "invalid regex"),
+            ("[[process_presets]]\nname = \"Sig\"\nports = [1]\nsignal = \"HUP\"\n", "unknown signal"),
+            (
+                "[[process_presets]]\nname = \"A\"\nports = [1]\n[[process_presets]]\nname = \"a\"\nports = [2]\n",
+                "more than once",
+            ),
+            ("[process_manager]\ngrace_period_ms = 600000\n", "at most"),
+        ];
+        for (content, expected) in cases {
+            let error = ProcessConfig::parse(content).unwrap_err();
+            assert!(error.contains(expected), "{} should mention {}", error, expected);
+        }
+    }
+}
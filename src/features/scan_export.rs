@@ -0,0 +1,562 @@
+use super::scanner::{count_open_ports, HostResult, PortState};
+use crate::utils::{ModeError, Result};
+use chrono::{DateTime, Local};
+use serde::Serialize;
+use std::fmt::Write as _;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write as _};
+use std::net::IpAddr;
+use std::path::PathBuf;
+
+/// Longest part of an export's file name taken from the scan target
+const MAX_TARGET_NAME_LEN: usize = 64;
+
+/// File formats scan results can be exported in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportFormat {
+    /// Human-readable table
+    #[default]
+    Text,
+    /// Full report as JSON
+    Json,
+    /// One row per open port
+    Csv,
+    /// Nmap-compatible XML (`-oX`)
+    Xml,
+}
+
+impl ExportFormat {
+    /// Returns all available formats
+    pub fn all() -> Vec<ExportFormat> {
+        vec![
+            ExportFormat::Text,
+            ExportFormat::Json,
+            ExportFormat::Csv,
+            ExportFormat::Xml,
+        ]
+    }
+
+    /// Returns the display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExportFormat::Text => "Text",
+            ExportFormat::Json => "JSON",
+            ExportFormat::Csv => "CSV",
+            ExportFormat::Xml => "Nmap XML",
+        }
+    }
+
+    /// Returns the short identifier used on the command line
+    pub fn slug(&self) -> &'static str {
+        match self {
+            ExportFormat::Text => "text",
+            ExportFormat::Json => "json",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Xml => "xml",
+        }
+    }
+
+    /// Looks up a format by its command line identifier
+    pub fn from_slug(slug: &str) -> Option<ExportFormat> {
+        Self::all().into_iter().find(|format| format.slug() == slug)
+    }
+
+    /// Returns the file extension
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Text => "txt",
+            other => other.slug(),
+        }
+    }
+
+    /// Returns the format after this one, wrapping around
+    pub fn next(&self) -> ExportFormat {
+        let all = Self::all();
+        let idx = all.iter().position(|f| f == self).unwrap_or(0);
+        all[(idx + 1) % all.len()]
+    }
+}
+
+/// Where and how to save scan results
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportSettings {
+    pub format: ExportFormat,
+    /// Directory the results file is created in
+    pub directory: PathBuf,
+}
+
+/// Options a scan ran with
+#[derive(Debug, Clone, Serialize)]
+pub struct ScanOptions {
+    pub service_detection: bool,
+    pub concurrency: usize,
+    pub timeout_ms: u64,
+    pub all_addresses: bool,
+}
+
+/// Results and metadata of a completed (or cancelled) scan
+#[derive(Debug, Clone, Serialize)]
+pub struct ScanReport {
+    /// Targets as entered
+    pub target: String,
+    /// Ports requested on each host
+    pub ports: Vec<u16>,
+    /// Host/port pairs actually probed (fewer than requested if cancelled)
+    pub probes: usize,
+    pub started_at: DateTime<Local>,
+    pub finished_at: DateTime<Local>,
+    pub duration_ms: u64,
+    pub cancelled: bool,
+    pub options: ScanOptions,
+    /// Every scanned host, including those with no open ports
+    pub hosts: Vec<HostResult>,
+}
+
+impl ScanReport {
+    /// Renders the report in the given format
+    pub fn render(&self, format: ExportFormat) -> Result<String> {
+        match format {
+            ExportFormat::Text => Ok(self.to_text()),
+            ExportFormat::Json => serde_json::to_string_pretty(self)
+                .map_err(|e| ModeError::Generic(format!("Failed to encode results: {}", e))),
+            ExportFormat::Csv => Ok(self.to_csv()),
+            ExportFormat::Xml => Ok(self.to_xml()),
+        }
+    }
+
+    /// Writes the report to a timestamped file in the export directory
+    ///
+    /// The target is cut short in the name so long host lists stay within
+    /// file name limits. The file is created exclusively; an export from the
+    /// same second gets a `-2`, `-3`, ... suffix instead of being replaced.
+    /// Creates the directory if needed and returns the path of the written file
+    pub fn save(&self, settings: &ExportSettings) -> Result<PathBuf> {
+        if settings.directory.exists() && !settings.directory.is_dir() {
+            return Err(ModeError::InvalidInput(format!(
+                "{} is not a directory",
+                settings.directory.display()
+            )));
+        }
+        fs::create_dir_all(&settings.directory)?;
+
+        let target: String = self
+            .target
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' })
+            .take(MAX_TARGET_NAME_LEN)
+            .collect();
+        let stem = format!("scan_{}_{}", target, self.started_at.format("%Y%m%d_%H%M%S"));
+        let content = self.render(settings.format)?;
+
+        for sequence in 1.. {
+            let filename = if sequence == 1 {
+                format!("{}.{}", stem, settings.format.extension())
+            } else {
+                format!("{}-{}.{}", stem, sequence, settings.format.extension())
+            };
+            let path = settings.directory.join(filename);
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    file.write_all(content.as_bytes())?;
+                    return Ok(path);
+                }
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e.into()),
+            }
+        }
+        unreachable!("export sequence numbers ran out")
+    }
+
+    /// Human-readable table, one section per host with open ports
+    fn to_text(&self) -> String {
+        let mut out = String::new();
+        let requested = self.hosts.len() * self.ports.len();
+
+        let _ = writeln!(out, "Port Scan Results");
+        let _ = writeln!(out, "==================");
+        let _ = writeln!(out, "Target: {}", self.target);
+        let _ = writeln!(out, "Scan Time: {}", self.started_at.format("%Y-%m-%d %H:%M:%S"));
+        let _ = writeln!(out, "Duration: {:.2}s", self.duration_ms as f64 / 1000.0);
+        let _ = writeln!(out, "Hosts: {}", self.hosts.len());
+        let _ = writeln!(
+            out,
+            "Probes: {}/{}{}",
+            self.probes,
+            requested,
+            if self.cancelled { " (cancelled)" } else { "" }
+        );
+        let _ = writeln!(out, "Open Ports: {}", count_open_ports(&self.hosts));
+
+        let hosts_up: Vec<&HostResult> = self.hosts.iter().filter(|h| !h.open_ports.is_empty()).collect();
+        if hosts_up.is_empty() {
+            let _ = writeln!(out, "\nNo open ports found.");
+        }
+
+        for result in hosts_up {
+            let _ = writeln!(out, "\nHost: {}", result.host.label());
+            let _ = writeln!(out, "PORT     STATE    SERVICE");
+            let _ = writeln!(out, "----     -----    -------");
+            for port_info in &result.open_ports {
+                let _ = writeln!(
+                    out,
+                    "{:<8} {:<8} {}",
+                    port_info.port,
+                    state_name(&port_info.state),
+                    port_info.describe()
+                );
+            }
+        }
+
+        out
+    }
+
+    /// One header row, then one row per open port
+    fn to_csv(&self) -> String {
+        let mut out = String::from("host,hostname,port,protocol,state,service,product,version,tls\n");
+
+        for result in &self.hosts {
+            for port_info in &result.open_ports {
+                let fields = [
+                    result.host.ip.to_string(),
+                    result.host.hostname.clone().unwrap_or_default(),
+                    port_info.port.to_string(),
+                    "tcp".to_string(),
+                    state_name(&port_info.state).to_string(),
+                    port_info.service.clone().unwrap_or_default(),
+                    port_info.product.clone().unwrap_or_default(),
+                    port_info.version.clone().unwrap_or_default(),
+                    port_info.tls.to_string(),
+                ];
+                let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+                out.push_str(&row.join(","));
+                out.push('\n');
+            }
+        }
+
+        out
+    }
+
+    /// Nmap `-oX` style document, enough for tools that consume nmap XML
+    ///
+    /// Connect scans can't tell a down host from one with every port closed,
+    /// so only hosts with open ports are listed (as nmap does by default)
+    fn to_xml(&self) -> String {
+        let mut out = String::new();
+        let start = self.started_at.timestamp();
+        let end = self.finished_at.timestamp();
+
+        let _ = writeln!(out, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>");
+        let _ = writeln!(out, "<!DOCTYPE nmaprun>");
+        let _ = writeln!(
+            out,
+            "<nmaprun scanner=\"mode\" args=\"mode scan {}\" start=\"{}\" startstr=\"{}\" version=\"{}\" xmloutputversion=\"1.05\">",
+            xml_escape(&self.target),
+            start,
+            self.started_at.format("%a %b %e %H:%M:%S %Y"),
+            env!("CARGO_PKG_VERSION")
+        );
+        let _ = writeln!(
+            out,
+            "<scaninfo type=\"connect\" protocol=\"tcp\" numservices=\"{}\" services=\"{}\"/>",
+            self.ports.len(),
+            port_ranges(&self.ports)
+        );
+
+        let hosts_up: Vec<&HostResult> = self.hosts.iter().filter(|h| !h.open_ports.is_empty()).collect();
+        for result in &hosts_up {
+            let addrtype = match result.host.ip {
+                IpAddr::V4(_) => "ipv4",
+                IpAddr::V6(_) => "ipv6",
+            };
+
+            let _ = writeln!(out, "<host starttime=\"{}\" endtime=\"{}\">", start, end);
+            let _ = writeln!(out, "<status state=\"up\" reason=\"syn-ack\" reason_ttl=\"0\"/>");
+            let _ = writeln!(out, "<address addr=\"{}\" addrtype=\"{}\"/>", result.host.ip, addrtype);
+            match &result.host.hostname {
+                Some(hostname) => {
+                    let _ = writeln!(
+                        out,
+                        "<hostnames><hostname name=\"{}\" type=\"user\"/></hostnames>",
+                        xml_escape(hostname)
+                    );
+                }
+                None => {
+                    let _ = writeln!(out, "<hostnames/>");
+                }
+            }
+
+            let _ = writeln!(out, "<ports>");
+            for port_info in &result.open_ports {
+                let _ = write!(
+                    out,
+                    "<port protocol=\"tcp\" portid=\"{}\"><state state=\"{}\" reason=\"syn-ack\" reason_ttl=\"0\"/>",
+                    port_info.port,
+                    state_name(&port_info.state)
+                );
+                if let Some(service) = &port_info.service {
+                    let name = service.to_lowercase().replace(' ', "-");
+                    let _ = write!(out, "<service name=\"{}\"", xml_escape(&name));
+                    if let Some(product) = &port_info.product {
+                        let _ = write!(out, " product=\"{}\"", xml_escape(product));
+                    }
+                    if let Some(version) = &port_info.version {
+                        let _ = write!(out, " version=\"{}\"", xml_escape(version));
+                    }
+                    if port_info.tls {
+                        let _ = write!(out, " tunnel=\"ssl\"");
+                    }
+                    let (method, conf) = if port_info.product.is_some() || port_info.tls {
+                        ("probed", 10)
+                    } else {
+                        ("table", 3)
+                    };
+                    let _ = write!(out, " method=\"{}\" conf=\"{}\"/>", method, conf);
+                }
+                let _ = writeln!(out, "</port>");
+            }
+            let _ = writeln!(out, "</ports>");
+            let _ = writeln!(out, "</host>");
+        }
+
+        let _ = writeln!(out, "<runstats>");
+        let _ = writeln!(
+            out,
+            "<finished time=\"{}\" timestr=\"{}\" elapsed=\"{:.2}\" exit=\"{}\"/>",
+            end,
+            self.finished_at.format("%a %b %e %H:%M:%S %Y"),
+            self.duration_ms as f64 / 1000.0,
+            if self.cancelled { "error" } else { "success" }
+        );
+        let _ = writeln!(
+            out,
+            "<hosts up=\"{}\" down=\"{}\" total=\"{}\"/>",
+            hosts_up.len(),
+            self.hosts.len() - hosts_up.len(),
+            self.hosts.len()
+        );
+        let _ = writeln!(out, "</runstats>");
+        let _ = writeln!(out, "</nmaprun>");
+
+        out
+    }
+}
+
+/// Lowercase state name as used in every export format
+fn state_name(state: &PortState) -> &'static str {
+    match state {
+        PortState::Open => "open",
+        PortState::Closed => "closed",
+        PortState::Filtered => "filtered",
+    }
+}
+
+/// Quotes a CSV field if it contains a separator, quote or newline
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Escapes text for use in an XML attribute
+///
+/// Tabs and line breaks become character references so parsers don't turn
+/// them into spaces; characters XML 1.0 doesn't allow at all, such as the
+/// control characters some service banners carry, are dropped.
+fn xml_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\t' | '\n' | '\r' => {
+                let _ = write!(escaped, "&#{};", c as u32);
+            }
+            '\u{0}'..='\u{1f}' | '\u{fffe}' | '\u{ffff}' => {}
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Collapses a port list into nmap's range notation (e.g. "21-23,80")
+pub(crate) fn port_ranges(ports: &[u16]) -> String {
+    let mut sorted = ports.to_vec();
+    sorted.sort_unstable();
+    sorted.dedup();
+
+    let mut ranges = Vec::new();
+    let mut iter = sorted.into_iter().peekable();
+    while let Some(start) = iter.next() {
+        let mut end = start;
+        while iter.peek() == Some(&(end.wrapping_add(1))) && end < u16::MAX {
+            end = iter.next().unwrap_or(end);
+        }
+        if start == end {
+            ranges.push(start.to_string());
+        } else {
+            ranges.push(format!("{}-{}", start, end));
+        }
+    }
+
+    ranges.join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::scan_targets::ScanHost;
+    use crate::features::scanner::PortInfo;
+    use std::net::Ipv4Addr;
+
+    fn sample_report() -> ScanReport {
+        let now = Local::now();
+        let port = |port: u16, service: &str, product: &str, version: Option<&str>| PortInfo {
+            port,
+            service: Some(service.to_string()),
+            state: PortState::Open,
+            product: Some(product.to_string()),
+            version: version.map(str::to_string),
+            tls: false,
+        };
+
+        ScanReport {
+            target: "localhost, 10.0.0.2-3".to_string(),
+            ports: vec![21, 22, 23, 80],
+            probes: 12,
+            started_at: now,
+            finished_at: now,
+            duration_ms: 1250,
+            cancelled: false,
+            options: ScanOptions {
+                service_detection: true,
+                concurrency: 100,
+                timeout_ms: 500,
+                all_addresses: false,
+            },
+            hosts: vec![
+                HostResult {
+                    host: ScanHost {
+                        ip: IpAddr::V4(Ipv4Addr::LOCALHOST),
+                        hostname: Some("localhost".to_string()),
+                    },
+                    open_ports: vec![
+                        port(22, "SSH", "OpenSSH", Some("9.6p1")),
+                        port(80, "HTTP", "Acme, \"Web\" <Server>", None),
+                    ],
+                },
+                HostResult::new(ScanHost {
+                    ip: IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2)),
+                    hostname: None,
+                }),
+                HostResult {
+                    host: ScanHost {
+                        ip: IpAddr::V4(Ipv4Addr::new(10, 0, 0, 3)),
+                        hostname: None,
+                    },
+                    open_ports: vec![port(21, "FTP", "vsFTPd", Some("3.0.5"))],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_render_json() {
+        let json = sample_report().render(ExportFormat::Json).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["target"], "localhost, 10.0.0.2-3");
+        assert_eq!(value["options"]["concurrency"], 100);
+        assert_eq!(value["hosts"][0]["ip"], "127.0.0.1");
+        assert_eq!(value["hosts"][0]["hostname"], "localhost");
+        assert_eq!(value["hosts"][0]["open_ports"][0]["product"], "OpenSSH");
+        assert_eq!(value["hosts"][0]["open_ports"][0]["state"], "open");
+        assert_eq!(value["hosts"][1]["open_ports"].as_array().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_render_csv_quotes_fields() {
+        let csv = sample_report().render(ExportFormat::Csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines[0], "host,hostname,port,protocol,state,service,product,version,tls");
+        assert_eq!(lines[1], "127.0.0.1,localhost,22,tcp,open,SSH,OpenSSH,9.6p1,false");
+        assert_eq!(lines[2], "127.0.0.1,localhost,80,tcp,open,HTTP,\"Acme, \"\"Web\"\" <Server>\",,false");
+        assert_eq!(lines[3], "10.0.0.3,,21,tcp,open,FTP,vsFTPd,3.0.5,false");
+        assert_eq!(lines.len(), 4);
+    }
+
+    #[test]
+    fn test_render_nmap_xml() {
+        let xml = sample_report().render(ExportFormat::Xml).unwrap();
+
+        assert!(xml.contains("<scaninfo type=\"connect\" protocol=\"tcp\" numservices=\"4\" services=\"21-23,80\"/>"));
+        assert!(xml.contains("<address addr=\"127.0.0.1\" addrtype=\"ipv4\"/>"));
+        assert!(xml.contains("<address addr=\"10.0.0.3\" addrtype=\"ipv4\"/>"));
+        assert!(!xml.contains("10.0.0.2\""));
+        assert!(xml.contains("<hostname name=\"localhost\" type=\"user\"/>"));
+        assert!(xml.contains("<service name=\"ssh\" product=\"OpenSSH\" version=\"9.6p1\" method=\"probed\" conf=\"10\"/>"));
+        assert!(xml.contains("product=\"Acme, &quot;Web&quot; &lt;Server&gt;\""));
+        assert!(xml.contains("<hosts up=\"2\" down=\"1\" total=\"3\"/>"));
+        assert!(xml.trim_end().ends_with("</nmaprun>"));
+    }
+
+    #[test]
+    fn test_render_nmap_xml_drops_control_characters() {
+        let mut report = sample_report();
+        let ftp = &mut report.hosts[2].open_ports[0];
+        ftp.product = Some("vsFTPd\u{1b}[0m\u{0}".to_string());
+        ftp.version = Some("3.0.5\r\n\u{7}".to_string());
+
+        let xml = report.render(ExportFormat::Xml).unwrap();
+        assert!(xml.contains("product=\"vsFTPd[0m\" version=\"3.0.5&#13;&#10;\""), "{}", xml);
+        assert!(!xml.chars().any(|c| c < ' ' && c != '\n'));
+    }
+
+    #[test]
+    fn test_save_creates_directory() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let settings = ExportSettings {
+            format: ExportFormat::Csv,
+            directory: temp_dir.path().join("reports"),
+        };
+
+        let path = sample_report().save(&settings).unwrap();
+
+        assert_eq!(path.parent(), Some(settings.directory.as_path()));
+        assert_eq!(path.extension().and_then(|e| e.to_str()), Some("csv"));
+        assert!(fs::read_to_string(&path).unwrap().starts_with("host,hostname,port"));
+    }
+
+    #[test]
+    fn test_save_keeps_earlier_exports_and_short_names() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let settings = ExportSettings {
+            format: ExportFormat::Json,
+            directory: temp_dir.path().to_path_buf(),
+        };
+        let mut report = sample_report();
+        report.target = (1..=200).map(|i| format!("10.0.{}.0/24", i)).collect::<Vec<_>>().join(",");
+
+        let first = report.save(&settings).unwrap();
+        let second = report.save(&settings).unwrap();
+
+        assert_ne!(first, second);
+        assert!(second.to_string_lossy().ends_with("-2.json"));
+        assert!(first.exists() && second.exists());
+        assert!(first.file_name().unwrap().len() < 100);
+    }
+
+    #[test]
+    fn test_export_format_slugs() {
+        for format in ExportFormat::all() {
+            assert_eq!(ExportFormat::from_slug(format.slug()), Some(format));
+        }
+        assert_eq!(ExportFormat::Xml.next(), ExportFormat::Text);
+        assert_eq!(port_ranges(&[80, 1, 2, 3, 443, 444]), "1-3,80,443-444");
+    }
+}
@@ -0,0 +1,357 @@
+use crate::utils::{ModeError, Result};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, ToSocketAddrs};
+
+/// Most addresses a single scan may expand to (a /16)
+pub const MAX_HOSTS: usize = 65536;
+
+/// One entry of a target list, before expansion
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TargetSpec {
+    /// A single IP address
+    Address(IpAddr),
+    /// An inclusive address range (`10.0.0.1-20`, `10.0.0.1-10.0.0.20`)
+    Range(IpAddr, IpAddr),
+    /// A CIDR block (`192.168.1.0/24`, `fd00::/120`)
+    Network(IpAddr, u8),
+    /// A hostname, resolved when the scan starts
+    Hostname(String),
+}
+
+/// A single address to scan
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ScanHost {
+    pub ip: IpAddr,
+    /// Hostname the address was resolved from
+    pub hostname: Option<String>,
+}
+
+impl ScanHost {
+    /// Address, followed by the hostname when there is one
+    pub fn label(&self) -> String {
+        match &self.hostname {
+            Some(hostname) => format!("{} ({})", self.ip, hostname),
+            None => self.ip.to_string(),
+        }
+    }
+}
+
+/// Parses a target list such as `192.168.1.0/24, 10.0.0.1-20, example.com, ::1`
+///
+/// Only checks syntax and sizes; hostnames are not resolved here
+pub fn parse_targets(input: &str) -> Result<Vec<TargetSpec>> {
+    let mut specs = Vec::new();
+    let mut literal_hosts = 0usize;
+
+    for item in input.split(',').map(str::trim) {
+        if item.is_empty() {
+            continue;
+        }
+
+        let spec = parse_target(item)?;
+        literal_hosts = literal_hosts.saturating_add(spec.literal_len());
+        if literal_hosts > MAX_HOSTS {
+            return Err(ModeError::InvalidInput(format!(
+                "Too many hosts (max {} per scan)",
+                MAX_HOSTS
+            )));
+        }
+        specs.push(spec);
+    }
+
+    if specs.is_empty() {
+        return Err(ModeError::InvalidInput("Target cannot be empty".to_string()));
+    }
+
+    Ok(specs)
+}
+
+/// Expands a target list into individual hosts, resolving hostnames
+///
+/// With `all_addresses`, every address a hostname resolves to is scanned
+/// instead of only the first. Duplicate addresses are scanned once
+pub fn resolve_targets(input: &str, all_addresses: bool) -> Result<Vec<ScanHost>> {
+    let mut seen = HashSet::new();
+    let mut hosts = Vec::new();
+
+    for spec in parse_targets(input)? {
+        for host in spec.expand(all_addresses)? {
+            if seen.insert(host.ip) {
+                hosts.push(host);
+            }
+        }
+    }
+
+    if hosts.len() > MAX_HOSTS {
+        return Err(ModeError::InvalidInput(format!(
+            "Too many hosts (max {} per scan)",
+            MAX_HOSTS
+        )));
+    }
+
+    Ok(hosts)
+}
+
+impl TargetSpec {
+    /// Expands this entry into hosts
+    pub fn expand(&self, all_addresses: bool) -> Result<Vec<ScanHost>> {
+        let bare = |ip| ScanHost { ip, hostname: None };
+
+        match self {
+            TargetSpec::Address(ip) => Ok(vec![bare(*ip)]),
+            TargetSpec::Range(start, end) => Ok(address_range(*start, *end).into_iter().map(bare).collect()),
+            TargetSpec::Network(ip, prefix) => Ok(network_hosts(*ip, *prefix).into_iter().map(bare).collect()),
+            TargetSpec::Hostname(name) => {
+                let addrs = format!("{}:80", name)
+                    .to_socket_addrs()
+                    .map_err(|e| ModeError::Generic(format!("Failed to resolve target {}: {}", name, e)))?;
+
+                let mut ips: Vec<IpAddr> = Vec::new();
+                for addr in addrs {
+                    if !ips.contains(&addr.ip()) {
+                        ips.push(addr.ip());
+                    }
+                }
+
+                if ips.is_empty() {
+                    return Err(ModeError::Generic(format!("Failed to resolve hostname: {}", name)));
+                }
+                if !all_addresses {
+                    ips.truncate(1);
+                }
+
+                Ok(ips
+                    .into_iter()
+                    .map(|ip| ScanHost {
+                        ip,
+                        hostname: Some(name.clone()),
+                    })
+                    .collect())
+            }
+        }
+    }
+
+    /// Number of hosts this entry covers without DNS (hostnames count as one)
+    fn literal_len(&self) -> usize {
+        match self {
+            TargetSpec::Address(_) | TargetSpec::Hostname(_) => 1,
+            TargetSpec::Range(start, end) => {
+                let span = ip_to_u128(*end) - ip_to_u128(*start);
+                usize::try_from(span).map(|n| n.saturating_add(1)).unwrap_or(usize::MAX)
+            }
+            TargetSpec::Network(ip, prefix) => {
+                let host_bits = u32::from(max_prefix(ip) - prefix);
+                if host_bits >= usize::BITS {
+                    usize::MAX
+                } else {
+                    1usize << host_bits
+                }
+            }
+        }
+    }
+}
+
+/// Parses one comma-separated entry
+fn parse_target(item: &str) -> Result<TargetSpec> {
+    // Allow bracketed IPv6 as written in URLs
+    let item = item
+        .strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+        .unwrap_or(item);
+
+    if let Ok(ip) = item.parse::<IpAddr>() {
+        return Ok(TargetSpec::Address(ip));
+    }
+
+    if let Some((addr, prefix)) = item.split_once('/') {
+        let ip: IpAddr = addr
+            .parse()
+            .map_err(|_| ModeError::InvalidInput(format!("Invalid network address: '{}'", addr)))?;
+        let prefix: u8 = prefix
+            .parse()
+            .ok()
+            .filter(|p| *p <= max_prefix(&ip))
+            .ok_or_else(|| ModeError::InvalidInput(format!("Invalid prefix length: '{}'", prefix)))?;
+        return Ok(TargetSpec::Network(ip, prefix));
+    }
+
+    // Hostnames may contain hyphens too, so only treat this as a range if the start is an IP
+    if let Some((start, end)) = item.split_once('-') {
+        if let Ok(start) = start.trim().parse::<IpAddr>() {
+            let end = parse_range_end(start, end.trim())?;
+            if ip_to_u128(end) < ip_to_u128(start) {
+                return Err(ModeError::InvalidInput(format!(
+                    "Range end is before range start: '{}'",
+                    item
+                )));
+            }
+            return Ok(TargetSpec::Range(start, end));
+        }
+    }
+
+    validate_hostname(item)?;
+    Ok(TargetSpec::Hostname(item.to_string()))
+}
+
+/// Parses the end of a range: a full address, or a last octet for IPv4
+fn parse_range_end(start: IpAddr, end: &str) -> Result<IpAddr> {
+    if let Ok(end) = end.parse::<IpAddr>() {
+        if end.is_ipv4() != start.is_ipv4() {
+            return Err(ModeError::InvalidInput(
+                "Range start and end must be the same address family".to_string(),
+            ));
+        }
+        return Ok(end);
+    }
+
+    match (start, end.parse::<u8>()) {
+        (IpAddr::V4(start), Ok(last)) => {
+            let [a, b, c, _] = start.octets();
+            Ok(IpAddr::V4(Ipv4Addr::new(a, b, c, last)))
+        }
+        _ => Err(ModeError::InvalidInput(format!("Invalid range end: '{}'", end))),
+    }
+}
+
+/// Validates hostname format
+///
+/// Hostname rules: alphanumeric, hyphens, dots, 1-253 chars, labels 1-63 chars
+fn validate_hostname(target: &str) -> Result<()> {
+    if target.len() > 253 {
+        return Err(ModeError::InvalidInput("Hostname too long (max 253 characters)".to_string()));
+    }
+
+    for part in target.split('.') {
+        if part.is_empty() || part.len() > 63 {
+            return Err(ModeError::InvalidInput(format!("Invalid hostname format: '{}'", target)));
+        }
+        if !part.chars().all(|c| c.is_alphanumeric() || c == '-') {
+            return Err(ModeError::InvalidInput(format!(
+                "Invalid hostname format (only alphanumeric and hyphens allowed): '{}'",
+                target
+            )));
+        }
+        if part.starts_with('-') || part.ends_with('-') {
+            return Err(ModeError::InvalidInput(format!(
+                "Invalid hostname format (cannot start or end with hyphen): '{}'",
+                target
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// All addresses from `start` to `end` inclusive
+fn address_range(start: IpAddr, end: IpAddr) -> Vec<IpAddr> {
+    let v4 = start.is_ipv4();
+    let (start, end) = (ip_to_u128(start), ip_to_u128(end));
+
+    (start..=end).map(|n| u128_to_ip(n, v4)).collect()
+}
+
+/// Host addresses in a CIDR block
+///
+/// IPv4 blocks larger than /31 skip the network and broadcast addresses
+fn network_hosts(ip: IpAddr, prefix: u8) -> Vec<IpAddr> {
+    let bits = u32::from(max_prefix(&ip));
+    let host_bits = bits - u32::from(prefix);
+    let size = if host_bits >= 128 { u128::MAX } else { (1u128 << host_bits) - 1 };
+    let network = ip_to_u128(ip) & !size;
+    let broadcast = network | size;
+    let v4 = ip.is_ipv4();
+
+    let (first, last) = if v4 && host_bits >= 2 {
+        (network + 1, broadcast - 1)
+    } else {
+        (network, broadcast)
+    };
+
+    (first..=last).map(|n| u128_to_ip(n, v4)).collect()
+}
+
+/// Longest valid prefix length for the address family
+fn max_prefix(ip: &IpAddr) -> u8 {
+    match ip {
+        IpAddr::V4(_) => 32,
+        IpAddr::V6(_) => 128,
+    }
+}
+
+fn ip_to_u128(ip: IpAddr) -> u128 {
+    match ip {
+        IpAddr::V4(ip) => u128::from(u32::from(ip)),
+        IpAddr::V6(ip) => u128::from(ip),
+    }
+}
+
+fn u128_to_ip(n: u128, v4: bool) -> IpAddr {
+    if v4 {
+        IpAddr::V4(Ipv4Addr::from(n as u32))
+    } else {
+        IpAddr::V6(Ipv6Addr::from(n))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ips(input: &str) -> Vec<String> {
+        resolve_targets(input, false)
+            .unwrap()
+            .into_iter()
+            .map(|h| h.ip.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_targets() {
+        assert_eq!(
+            parse_targets("10.0.0.1-20").unwrap(),
+            vec![TargetSpec::Range("10.0.0.1".parse().unwrap(), "10.0.0.20".parse().unwrap())]
+        );
+        assert_eq!(
+            parse_targets("[::1], my-host.local").unwrap(),
+            vec![
+                TargetSpec::Address("::1".parse().unwrap()),
+                TargetSpec::Hostname("my-host.local".to_string()),
+            ]
+        );
+
+        assert!(parse_targets("").is_err());
+        assert!(parse_targets("10.0.0.20-1").is_err());
+        assert!(parse_targets("10.0.0.0/33").is_err());
+        assert!(parse_targets("10.0.0.0/8").is_err());
+        assert!(parse_targets("bad_host").is_err());
+        assert!(parse_targets("10.0.0.1-::5").is_err());
+    }
+
+    #[test]
+    fn test_expand_cidr_and_ranges() {
+        let hosts = ips("192.168.1.0/30");
+        assert_eq!(hosts, vec!["192.168.1.1", "192.168.1.2"]);
+
+        assert_eq!(ips("10.0.0.7/32"), vec!["10.0.0.7"]);
+        assert_eq!(ips("10.0.0.1-3"), vec!["10.0.0.1", "10.0.0.2", "10.0.0.3"]);
+        assert_eq!(ips("10.0.0.255-10.0.1.0"), vec!["10.0.0.255", "10.0.1.0"]);
+        assert_eq!(ips("fd00::/127"), vec!["fd00::", "fd00::1"]);
+        assert_eq!(ips("192.168.1.0/24").len(), 254);
+    }
+
+    #[test]
+    fn test_host_list_is_deduplicated() {
+        assert_eq!(ips("127.0.0.1, 127.0.0.1-2, ::1"), vec!["127.0.0.1", "127.0.0.2", "::1"]);
+    }
+
+    #[test]
+    fn test_resolve_hostname() {
+        let hosts = resolve_targets("localhost", true).unwrap();
+        assert!(!hosts.is_empty());
+        assert!(hosts.iter().all(|h| h.hostname.as_deref() == Some("localhost")));
+        assert!(hosts.iter().all(|h| h.ip.is_loopback()));
+
+        assert_eq!(resolve_targets("localhost", false).unwrap().len(), 1);
+    }
+}
@@ -1,13 +1,27 @@
-use crate::utils::Result;
-use std::net::{IpAddr, TcpStream, ToSocketAddrs};
-use std::time::Duration;
+use super::mode_config::ScannerSettings;
+use super::scan_export::{ExportFormat, ExportSettings, ScanOptions, ScanReport};
+use super::scan_targets::{self, ScanHost};
+use super::service_probe;
+use crate::{event::Event, utils::Result};
+use chrono::{DateTime, Local};
+use serde::Serialize;
+use std::net::{IpAddr, SocketAddr, TcpStream};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Minimum per-step timeout when probing an open port for its service
+const PROBE_TIMEOUT: Duration = Duration::from_millis(800);
 
 /// Scanner state machine
 #[derive(Debug, Clone)]
 pub enum ScannerState {
     /// Selecting scan type
     SelectingScanType { selected: usize },
-    /// Entering target IP or hostname
+    /// Entering targets: IPs, hostnames, CIDR blocks or ranges
     EnteringTarget { scan_type: ScanType, input: String },
     /// Entering custom port range (only for CustomRange scan type)
     EnteringPortRange { target: String, input: String },
@@ -17,15 +31,30 @@ pub enum ScannerState {
         target: String,
         selected: usize,
         service_detection: bool,
+        all_addresses: bool,
         save_to_file: bool,
+        export_format: ExportFormat,
         custom_ports: Option<Vec<u16>>,
     },
+    /// Entering the directory to save results in
+    EnteringOutputDir {
+        scan_type: ScanType,
+        target: String,
+        service_detection: bool,
+        all_addresses: bool,
+        export_format: ExportFormat,
+        custom_ports: Option<Vec<u16>>,
+        input: String,
+    },
     /// Confirming scan parameters
     Confirming {
         scan_type: ScanType,
         target: String,
         service_detection: bool,
-        save_to_file: bool,
+        all_addresses: bool,
+        export: Option<ExportSettings>,
+        /// Format picked on the options screen, kept for going back when not saving
+        export_format: ExportFormat,
         custom_ports: Option<Vec<u16>>,
     },
     /// Scanning in progress
@@ -35,14 +64,20 @@ pub enum ScannerState {
         progress: usize,
         total: usize,
         service_detection: bool,
-        save_to_file: bool,
+        export: Option<ExportSettings>,
         custom_ports: Option<Vec<u16>>,
+        /// Hosts being scanned with the open ports found so far
+        hosts: Vec<HostResult>,
     },
     /// Viewing results
     ViewingResults {
         target: String,
-        open_ports: Vec<PortInfo>,
+        /// Hosts with at least one open port
+        hosts: Vec<HostResult>,
+        /// Index of the highlighted port across all hosts
         scroll: usize,
+        /// File the results were exported to
+        saved_to: Option<PathBuf>,
     },
     /// Success state with message
     Success { message: String },
@@ -51,15 +86,71 @@ pub enum ScannerState {
 }
 
 /// Port information
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct PortInfo {
     pub port: u16,
     pub service: Option<String>,
     pub state: PortState,
+    /// Server software reported by the service (e.g. "OpenSSH")
+    pub product: Option<String>,
+    /// Server software version
+    pub version: Option<String>,
+    /// Whether the port answered a TLS handshake
+    pub tls: bool,
+}
+
+impl PortInfo {
+    /// Service name with product and version, e.g. "SSH (OpenSSH 9.6p1)"
+    pub fn describe(&self) -> String {
+        let mut description = self.service.clone().unwrap_or_else(|| "unknown".to_string());
+
+        let software = match (&self.product, &self.version) {
+            (Some(product), Some(version)) => Some(format!("{} {}", product, version)),
+            (Some(product), None) => Some(product.clone()),
+            (None, Some(version)) => Some(version.clone()),
+            (None, None) => None,
+        };
+
+        if let Some(software) = software {
+            if self.service.as_deref() != Some(software.as_str()) {
+                description.push_str(&format!(" ({})", software));
+            }
+        }
+
+        if self.tls && !description.contains("TLS") && !description.contains("HTTPS") {
+            description.push_str(" [TLS]");
+        }
+
+        description
+    }
+}
+
+/// Open ports found on one host
+#[derive(Debug, Clone, Serialize)]
+pub struct HostResult {
+    #[serde(flatten)]
+    pub host: ScanHost,
+    pub open_ports: Vec<PortInfo>,
+}
+
+impl HostResult {
+    /// Creates an empty result for a host
+    pub fn new(host: ScanHost) -> Self {
+        Self {
+            host,
+            open_ports: Vec::new(),
+        }
+    }
+}
+
+/// Total number of open ports across hosts
+pub fn count_open_ports(hosts: &[HostResult]) -> usize {
+    hosts.iter().map(|h| h.open_ports.len()).sum()
 }
 
 /// Port state
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum PortState {
     Open,
     Closed,
@@ -100,6 +191,21 @@ impl ScanType {
         }
     }
 
+    /// Returns the short identifier used on the command line
+    pub fn slug(&self) -> &'static str {
+        match self {
+            ScanType::QuickScan => "quick",
+            ScanType::StandardScan => "standard",
+            ScanType::FullScan => "full",
+            ScanType::CustomRange => "custom",
+        }
+    }
+
+    /// Looks up a scan type by its command line identifier
+    pub fn from_slug(slug: &str) -> Option<ScanType> {
+        Self::all().into_iter().find(|scan_type| scan_type.slug() == slug)
+    }
+
     /// Returns the description
     pub fn description(&self) -> &'static str {
         match self {
@@ -139,20 +245,29 @@ impl ScanType {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ScanOption {
     ServiceDetection,
+    AllAddresses,
     SaveToFile,
+    ExportFormat,
 }
 
 impl ScanOption {
     /// Returns all available options
     pub fn all() -> Vec<ScanOption> {
-        vec![ScanOption::ServiceDetection, ScanOption::SaveToFile]
+        vec![
+            ScanOption::ServiceDetection,
+            ScanOption::AllAddresses,
+            ScanOption::SaveToFile,
+            ScanOption::ExportFormat,
+        ]
     }
 
     /// Returns the display name
     pub fn name(&self) -> &'static str {
         match self {
             ScanOption::ServiceDetection => "Service Detection",
+            ScanOption::AllAddresses => "All Resolved Addresses",
             ScanOption::SaveToFile => "Save Results to File",
+            ScanOption::ExportFormat => "Export Format",
         }
     }
 
@@ -163,9 +278,57 @@ impl ScanOption {
             ScanOption::ServiceDetection => {
                 format!("[{}] Attempt to identify services running on open ports", status)
             }
+            ScanOption::AllAddresses => {
+                format!("[{}] Scan every address a hostname resolves to, not just the first", status)
+            }
             ScanOption::SaveToFile => {
                 format!("[{}] Save scan results to a file", status)
             }
+            ScanOption::ExportFormat => "Format used when saving results".to_string(),
+        }
+    }
+
+    /// Returns the description of the export format option for the chosen format
+    pub fn format_description(format: ExportFormat) -> String {
+        let names: Vec<&str> = ExportFormat::all().iter().map(|f| f.name()).collect();
+        format!("[{}] Space to cycle: {}", format.name(), names.join(", "))
+    }
+}
+
+/// Progress update streamed from scan worker threads
+#[derive(Debug, Clone)]
+pub enum ScanUpdate {
+    /// A port was probed on the host at index `host`; carries its details when it is open
+    PortScanned {
+        scan_id: u64,
+        host: usize,
+        open: Option<PortInfo>,
+    },
+    /// All ports were probed or the scan was cancelled
+    Finished { scan_id: u64 },
+    /// The targets expanded to these hosts, in the order they are scanned
+    Resolved { scan_id: u64, hosts: Vec<ScanHost> },
+    /// The scan could not start (e.g. the target did not resolve)
+    Failed { scan_id: u64, message: String },
+}
+
+/// Tunables for a scan run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScanConfig {
+    /// Number of worker threads probing ports in parallel
+    pub concurrency: usize,
+    /// Connect timeout per port
+    pub timeout: Duration,
+    /// Scan every address a hostname resolves to instead of only the first
+    pub all_addresses: bool,
+}
+
+impl Default for ScanConfig {
+    fn default() -> Self {
+        Self {
+            concurrency: 100,
+            timeout: Duration::from_millis(500),
+            all_addresses: false,
         }
     }
 }
@@ -174,6 +337,22 @@ impl ScanOption {
 #[derive(Debug)]
 pub struct Scanner {
     pub state: ScannerState,
+    /// Worker pool settings
+    config: ScanConfig,
+    /// Option toggles preselected for new scans
+    defaults: ScannerSettings,
+    /// Identifier of the current scan, used to drop updates from cancelled scans
+    scan_id: u64,
+    /// Cancellation flag shared with the running scan's workers
+    cancel: Option<Arc<AtomicBool>>,
+    /// Channel for streaming scan progress into the app's event loop
+    event_tx: Option<Sender<Event>>,
+    /// When the current scan started
+    started: Option<Instant>,
+    /// Wall-clock start time of the current scan, for reports
+    started_at: Option<DateTime<Local>>,
+    /// Whether the current scan covers every address a hostname resolves to
+    all_addresses: bool,
 }
 
 impl Scanner {
@@ -181,9 +360,45 @@ impl Scanner {
     pub fn new() -> Self {
         Self {
             state: ScannerState::SelectingScanType { selected: 0 },
+            config: ScanConfig::default(),
+            defaults: ScannerSettings::default(),
+            scan_id: 0,
+            cancel: None,
+            event_tx: None,
+            started: None,
+            started_at: None,
+            all_addresses: false,
         }
     }
 
+    /// Sets the channel used to stream progress to the event loop
+    ///
+    /// Without a sender, `execute_scan` blocks until the scan completes
+    pub fn set_event_sender(&mut self, tx: Sender<Event>) {
+        self.event_tx = Some(tx);
+    }
+
+    /// Sets the worker pool configuration
+    pub fn set_config(&mut self, config: ScanConfig) {
+        self.config = config;
+    }
+
+    /// Applies the scanner defaults from the user's settings
+    pub fn apply_settings(&mut self, settings: ScannerSettings) {
+        self.config = settings.scan;
+        self.defaults = settings;
+    }
+
+    /// Returns the worker pool configuration
+    pub fn config(&self) -> ScanConfig {
+        self.config
+    }
+
+    /// Returns how long the current scan has been running
+    pub fn elapsed(&self) -> Duration {
+        self.started.map(|t| t.elapsed()).unwrap_or_default()
+    }
+
     /// Moves selection up
     pub fn previous(&mut self) {
         match &mut self.state {
@@ -203,10 +418,8 @@ impl Scanner {
                     *selected - 1
                 };
             }
-            ScannerState::ViewingResults { scroll, .. } => {
-                if *scroll > 0 {
-                    *scroll -= 1;
-                }
+            ScannerState::ViewingResults { scroll, .. } if *scroll > 0 => {
+                *scroll -= 1;
             }
             _ => {}
         }
@@ -223,10 +436,10 @@ impl Scanner {
                 let total = ScanOption::all().len();
                 *selected = (*selected + 1) % total;
             }
-            ScannerState::ViewingResults { scroll, open_ports, .. } => {
-                if *scroll < open_ports.len().saturating_sub(1) {
-                    *scroll += 1;
-                }
+            ScannerState::ViewingResults { scroll, hosts, .. }
+                if *scroll < count_open_ports(hosts).saturating_sub(1) =>
+            {
+                *scroll += 1;
             }
             _ => {}
         }
@@ -245,53 +458,34 @@ impl Scanner {
 
     /// Handles character input for target
     pub fn handle_char(&mut self, c: char) {
-        if let ScannerState::EnteringTarget { input, .. } = &mut self.state {
-            input.push(c);
+        match &mut self.state {
+            ScannerState::EnteringTarget { input, .. } | ScannerState::EnteringOutputDir { input, .. } => {
+                input.push(c);
+            }
+            _ => {}
         }
     }
 
     /// Handles backspace in target input
     pub fn handle_backspace(&mut self) {
-        if let ScannerState::EnteringTarget { input, .. } = &mut self.state {
-            input.pop();
-        }
-    }
-
-    /// Validates target format (IP address or hostname)
-    fn validate_target(target: &str) -> Result<()> {
-        if target.is_empty() {
-            return Err(crate::utils::ModeError::Generic("Target cannot be empty".to_string()));
-        }
-
-        // Check if it's a valid IP address
-        if target.parse::<IpAddr>().is_ok() {
-            return Ok(());
-        }
-
-        // Check if it's a valid hostname format
-        // Hostname rules: alphanumeric, hyphens, dots, 1-253 chars, labels 1-63 chars
-        if target.len() > 253 {
-            return Err(crate::utils::ModeError::Generic("Hostname too long (max 253 characters)".to_string()));
-        }
-
-        let parts: Vec<&str> = target.split('.').collect();
-        for part in parts {
-            if part.is_empty() || part.len() > 63 {
-                return Err(crate::utils::ModeError::Generic("Invalid hostname format".to_string()));
-            }
-            if !part.chars().all(|c| c.is_alphanumeric() || c == '-') {
-                return Err(crate::utils::ModeError::Generic("Invalid hostname format (only alphanumeric and hyphens allowed)".to_string()));
-            }
-            if part.starts_with('-') || part.ends_with('-') {
-                return Err(crate::utils::ModeError::Generic("Invalid hostname format (cannot start or end with hyphen)".to_string()));
+        match &mut self.state {
+            ScannerState::EnteringTarget { input, .. } | ScannerState::EnteringOutputDir { input, .. } => {
+                input.pop();
             }
+            _ => {}
         }
+    }
 
-        Ok(())
+    /// Validates target format
+    ///
+    /// Accepts a comma-separated list of IPv4/IPv6 addresses, hostnames,
+    /// CIDR blocks (`192.168.1.0/24`) and ranges (`10.0.0.1-20`)
+    pub fn validate_target(target: &str) -> Result<()> {
+        scan_targets::parse_targets(target).map(|_| ())
     }
 
     /// Parses port range input (e.g., "1-1000", "80,443,8080", "1-100,443,8080-9000")
-    fn parse_port_range(input: &str) -> Result<Vec<u16>> {
+    pub fn parse_port_range(input: &str) -> Result<Vec<u16>> {
         let mut ports = Vec::new();
 
         for part in input.split(',') {
@@ -300,28 +494,28 @@ impl Scanner {
                 // Range format: "1-1000"
                 let range_parts: Vec<&str> = part.split('-').collect();
                 if range_parts.len() != 2 {
-                    return Err(crate::utils::ModeError::Generic(
+                    return Err(crate::utils::ModeError::InvalidInput(
                         format!("Invalid port range format: '{}'", part)
                     ));
                 }
 
                 let start: u16 = range_parts[0].trim().parse()
-                    .map_err(|_| crate::utils::ModeError::Generic(
+                    .map_err(|_| crate::utils::ModeError::InvalidInput(
                         format!("Invalid port number: '{}'", range_parts[0])
                     ))?;
                 let end: u16 = range_parts[1].trim().parse()
-                    .map_err(|_| crate::utils::ModeError::Generic(
+                    .map_err(|_| crate::utils::ModeError::InvalidInput(
                         format!("Invalid port number: '{}'", range_parts[1])
                     ))?;
 
                 if start > end {
-                    return Err(crate::utils::ModeError::Generic(
+                    return Err(crate::utils::ModeError::InvalidInput(
                         "Start port must be less than or equal to end port".to_string()
                     ));
                 }
 
                 if start == 0 {
-                    return Err(crate::utils::ModeError::Generic(
+                    return Err(crate::utils::ModeError::InvalidInput(
                         "Port numbers must be between 1 and 65535".to_string()
                     ));
                 }
@@ -334,12 +528,12 @@ impl Scanner {
             } else {
                 // Single port
                 let port: u16 = part.parse()
-                    .map_err(|_| crate::utils::ModeError::Generic(
+                    .map_err(|_| crate::utils::ModeError::InvalidInput(
                         format!("Invalid port number: '{}'", part)
                     ))?;
 
                 if port == 0 {
-                    return Err(crate::utils::ModeError::Generic(
+                    return Err(crate::utils::ModeError::InvalidInput(
                         "Port numbers must be between 1 and 65535".to_string()
                     ));
                 }
@@ -351,7 +545,7 @@ impl Scanner {
         }
 
         if ports.is_empty() {
-            return Err(crate::utils::ModeError::Generic(
+            return Err(crate::utils::ModeError::InvalidInput(
                 "No valid ports specified".to_string()
             ));
         }
@@ -384,8 +578,10 @@ impl Scanner {
                     scan_type,
                     target,
                     selected: 0,
-                    service_detection: false,
-                    save_to_file: false,
+                    service_detection: self.defaults.service_detection,
+                    all_addresses: self.config.all_addresses,
+                    save_to_file: self.defaults.save_to_file,
+                    export_format: self.defaults.export_format,
                     custom_ports: None,
                 };
             }
@@ -402,8 +598,10 @@ impl Scanner {
                         scan_type: ScanType::CustomRange,
                         target,
                         selected: 0,
-                        service_detection: false,
-                        save_to_file: false,
+                        service_detection: self.defaults.service_detection,
+                        all_addresses: self.config.all_addresses,
+                        save_to_file: self.defaults.save_to_file,
+                        export_format: self.defaults.export_format,
                         custom_ports: Some(ports),
                     };
                 }
@@ -438,47 +636,123 @@ impl Scanner {
         if let ScannerState::SelectingOptions {
             selected,
             service_detection,
+            all_addresses,
             save_to_file,
+            export_format,
             ..
         } = &mut self.state
         {
             let option = ScanOption::all()[*selected];
             match option {
                 ScanOption::ServiceDetection => *service_detection = !*service_detection,
+                ScanOption::AllAddresses => *all_addresses = !*all_addresses,
                 ScanOption::SaveToFile => *save_to_file = !*save_to_file,
+                ScanOption::ExportFormat => {
+                    *export_format = export_format.next();
+                    // Picking a format implies wanting the file
+                    *save_to_file = true;
+                }
             }
         }
     }
 
-    /// Advances from options to confirmation
+    /// Advances from options to confirmation, asking for an output directory first if saving
     pub fn advance_to_confirmation(&mut self) {
         if let ScannerState::SelectingOptions {
             scan_type,
             target,
             service_detection,
+            all_addresses,
             save_to_file,
+            export_format,
             custom_ports,
             ..
         } = self.state.clone()
         {
+            if save_to_file {
+                let input = std::env::current_dir()
+                    .map(|dir| dir.display().to_string())
+                    .unwrap_or_else(|_| ".".to_string());
+
+                self.state = ScannerState::EnteringOutputDir {
+                    scan_type,
+                    target,
+                    service_detection,
+                    all_addresses,
+                    export_format,
+                    custom_ports,
+                    input,
+                };
+            } else {
+                self.state = ScannerState::Confirming {
+                    scan_type,
+                    target,
+                    service_detection,
+                    all_addresses,
+                    export: None,
+                    export_format,
+                    custom_ports,
+                };
+            }
+        }
+    }
+
+    /// Advances from the output directory prompt to confirmation
+    pub fn advance_from_output_dir(&mut self) {
+        if let ScannerState::EnteringOutputDir {
+            scan_type,
+            target,
+            service_detection,
+            all_addresses,
+            export_format,
+            custom_ports,
+            input,
+        } = self.state.clone()
+        {
+            let input = input.trim();
+            if input.is_empty() {
+                self.state = ScannerState::Error {
+                    message: "Output directory cannot be empty".to_string(),
+                };
+                return;
+            }
+
+            let directory = crate::utils::expand_home(input);
+            if directory.exists() && !directory.is_dir() {
+                self.state = ScannerState::Error {
+                    message: format!("{} is not a directory", directory.display()),
+                };
+                return;
+            }
+
             self.state = ScannerState::Confirming {
                 scan_type,
                 target,
                 service_detection,
-                save_to_file,
+                all_addresses,
+                export: Some(ExportSettings {
+                    format: export_format,
+                    directory,
+                }),
+                export_format,
                 custom_ports,
             };
         }
     }
 
     /// Executes the scan
+    ///
+    /// Work is spread over a pool of background threads; progress arrives as
+    /// `Event::Scan` updates that must be passed to `handle_scan_update`
     pub fn execute_scan(&mut self) {
         if let ScannerState::Confirming {
             scan_type,
             target,
             service_detection,
-            save_to_file,
+            all_addresses,
+            export,
             custom_ports,
+            ..
         } = self.state.clone()
         {
             // Get ports to scan (use custom_ports if provided, otherwise use scan_type defaults)
@@ -491,176 +765,331 @@ impl Scanner {
                 progress: 0,
                 total,
                 service_detection,
-                save_to_file,
+                export,
                 custom_ports: custom_ports.clone(),
+                hosts: Vec::new(),
             };
 
-            // Perform the actual scan
-            match self.perform_scan(&target, &ports, service_detection) {
-                Ok(open_ports) => {
-                    if save_to_file {
-                        if let Err(e) = self.save_results(&target, &open_ports) {
-                            self.state = ScannerState::Error {
-                                message: format!("Scan completed but failed to save results: {}", e),
-                            };
-                            return;
+            let Some(tx) = self.event_tx.clone() else {
+                // No event loop to stream into, so scan synchronously
+                match self.scan_hosts(&target, &ports, service_detection, all_addresses) {
+                    Ok(results) => {
+                        if let ScannerState::Scanning { hosts, progress, total, .. } = &mut self.state {
+                            *total = results.len() * ports.len();
+                            *progress = *total;
+                            *hosts = results;
                         }
+                        self.finish_scan(false);
                     }
-
-                    if open_ports.is_empty() {
-                        self.state = ScannerState::Success {
-                            message: format!("Scan completed. No open ports found on {}", target),
-                        };
-                    } else {
-                        self.state = ScannerState::ViewingResults {
-                            target,
-                            open_ports,
-                            scroll: 0,
+                    Err(e) => {
+                        self.state = ScannerState::Error {
+                            message: format!("Scan failed: {}", e),
                         };
                     }
                 }
-                Err(e) => {
-                    self.state = ScannerState::Error {
-                        message: format!("Scan failed: {}", e),
-                    };
+                return;
+            };
+
+            self.start_workers(target, ports, service_detection, all_addresses, move |update| {
+                let _ = tx.send(Event::Scan(update));
+            });
+        }
+    }
+
+    /// Applies a progress update from the scan workers
+    pub fn handle_scan_update(&mut self, update: ScanUpdate) {
+        match update {
+            ScanUpdate::PortScanned { scan_id, host, open } if scan_id == self.scan_id => {
+                if let ScannerState::Scanning { progress, hosts, .. } = &mut self.state {
+                    *progress += 1;
+                    if let (Some(port_info), Some(result)) = (open, hosts.get_mut(host)) {
+                        let idx = result.open_ports.partition_point(|p| p.port < port_info.port);
+                        result.open_ports.insert(idx, port_info);
+                    }
+                }
+            }
+            ScanUpdate::Finished { scan_id } if scan_id == self.scan_id => {
+                if matches!(self.state, ScannerState::Scanning { .. }) {
+                    self.finish_scan(false);
+                }
+            }
+            ScanUpdate::Resolved { scan_id, hosts: resolved } if scan_id == self.scan_id => {
+                if let ScannerState::Scanning { total, hosts, .. } = &mut self.state {
+                    // Until now `total` only counted ports for a single host
+                    *total *= resolved.len();
+                    *hosts = resolved.into_iter().map(HostResult::new).collect();
                 }
             }
+            ScanUpdate::Failed { scan_id, message } if scan_id == self.scan_id => {
+                self.cancel = None;
+                self.state = ScannerState::Error {
+                    message: format!("Scan failed: {}", message),
+                };
+            }
+            _ => {
+                // Update from a scan that was already cancelled
+            }
         }
     }
 
-    /// Performs the actual port scan
-    fn perform_scan(
-        &mut self,
-        target: &str,
-        ports: &[u16],
-        service_detection: bool,
-    ) -> Result<Vec<PortInfo>> {
-        let mut open_ports = Vec::new();
+    /// Cancels the running scan and shows whatever was found so far
+    pub fn cancel_scan(&mut self) {
+        if matches!(self.state, ScannerState::Scanning { .. }) {
+            self.finish_scan(true);
+        }
+    }
+
+    /// Moves from the scanning state to results, saving them if requested
+    fn finish_scan(&mut self, cancelled: bool) {
+        if let Some(cancel) = self.cancel.take() {
+            cancel.store(true, Ordering::Relaxed);
+        }
 
-        // Resolve hostname to IP
-        let ip = self.resolve_target(target)?;
+        if let ScannerState::Scanning {
+            scan_type,
+            target,
+            progress,
+            total,
+            service_detection,
+            export,
+            custom_ports,
+            hosts,
+        } = self.state.clone()
+        {
+            let mut saved_to = None;
+            if let Some(export) = export {
+                let ports = custom_ports.unwrap_or_else(|| scan_type.get_ports());
+                let report = self.report(&target, ports, progress, service_detection, cancelled, hosts.clone());
+
+                match report.save(&export) {
+                    Ok(path) => saved_to = Some(path),
+                    Err(e) => {
+                        self.state = ScannerState::Error {
+                            message: format!("Scan completed but failed to save results: {}", e),
+                        };
+                        return;
+                    }
+                }
+            }
 
-        let timeout = Duration::from_millis(500);
+            let hosts: Vec<HostResult> = hosts.into_iter().filter(|h| !h.open_ports.is_empty()).collect();
 
-        for (idx, &port) in ports.iter().enumerate() {
-            // Update progress
-            if let ScannerState::Scanning { progress, .. } = &mut self.state {
-                *progress = idx + 1;
+            if hosts.is_empty() {
+                let mut message = if cancelled {
+                    format!(
+                        "Scan cancelled after {}/{} probes. No open ports found on {}",
+                        progress, total, target
+                    )
+                } else {
+                    format!("Scan completed. No open ports found on {}", target)
+                };
+                if let Some(path) = &saved_to {
+                    message.push_str(&format!("\nResults saved to {}", path.display()));
+                }
+                self.state = ScannerState::Success { message };
+            } else {
+                self.state = ScannerState::ViewingResults {
+                    target,
+                    hosts,
+                    scroll: 0,
+                    saved_to,
+                };
             }
+        }
+    }
 
-            let addr = format!("{}:{}", ip, port);
-            if let Ok(socket_addr) = addr.parse::<std::net::SocketAddr>() {
-                match TcpStream::connect_timeout(&socket_addr, timeout) {
-                    Ok(_) => {
-                        let service = if service_detection {
-                            Self::detect_service(port)
-                        } else {
-                            None
-                        };
+    /// Builds a report of the current scan with its timing and options
+    ///
+    /// `probes` is the number of host/port pairs actually tried
+    pub fn report(
+        &self,
+        target: &str,
+        ports: Vec<u16>,
+        probes: usize,
+        service_detection: bool,
+        cancelled: bool,
+        hosts: Vec<HostResult>,
+    ) -> ScanReport {
+        let finished_at = Local::now();
+        let started_at = self.started_at.unwrap_or(finished_at);
+
+        ScanReport {
+            target: target.to_string(),
+            ports,
+            probes,
+            started_at,
+            finished_at,
+            duration_ms: self.elapsed().as_millis() as u64,
+            cancelled,
+            options: ScanOptions {
+                service_detection,
+                concurrency: self.config.concurrency,
+                timeout_ms: self.config.timeout.as_millis() as u64,
+                all_addresses: self.all_addresses,
+            },
+            hosts,
+        }
+    }
 
-                        open_ports.push(PortInfo {
-                            port,
-                            service,
-                            state: PortState::Open,
-                        });
-                    }
-                    Err(_) => {
-                        // Port is closed or filtered
+    /// Scans the targets without going through the interactive flow
+    ///
+    /// Uses the same worker pool as the TUI and blocks until every port is probed.
+    /// Returns one entry per scanned host, in scan order
+    pub fn scan(&mut self, target: &str, ports: &[u16], service_detection: bool) -> Result<Vec<HostResult>> {
+        self.scan_hosts(target, ports, service_detection, self.config.all_addresses)
+    }
+
+    /// Scans the targets synchronously, choosing whether to use every resolved address
+    fn scan_hosts(
+        &mut self,
+        target: &str,
+        ports: &[u16],
+        service_detection: bool,
+        all_addresses: bool,
+    ) -> Result<Vec<HostResult>> {
+        Self::validate_target(target)?;
+
+        let (tx, rx) = mpsc::channel();
+        let scan_id = self.start_workers(
+            target.to_string(),
+            ports.to_vec(),
+            service_detection,
+            all_addresses,
+            move |update| {
+                let _ = tx.send(update);
+            },
+        );
+
+        let mut hosts: Vec<HostResult> = Vec::new();
+        for update in rx {
+            match update {
+                ScanUpdate::Resolved { hosts: resolved, .. } => {
+                    hosts = resolved.into_iter().map(HostResult::new).collect();
+                }
+                ScanUpdate::PortScanned { host, open: Some(port_info), .. } => {
+                    if let Some(result) = hosts.get_mut(host) {
+                        result.open_ports.push(port_info);
                     }
                 }
+                ScanUpdate::Finished { .. } => break,
+                ScanUpdate::Failed { message, .. } => return Err(crate::utils::ModeError::Generic(message)),
+                _ => {}
             }
         }
+        self.cancel = None;
+        debug_assert_eq!(scan_id, self.scan_id);
 
-        Ok(open_ports)
+        for result in &mut hosts {
+            result.open_ports.sort_by_key(|p| p.port);
+        }
+        Ok(hosts)
     }
 
-    /// Resolves target hostname to IP address
-    fn resolve_target(&self, target: &str) -> Result<IpAddr> {
-        // Try to parse as IP address first
-        if let Ok(ip) = target.parse::<IpAddr>() {
-            return Ok(ip);
+    /// Spawns the resolver and worker threads for a new scan
+    ///
+    /// Returns the id tagged onto every update of this scan
+    fn start_workers<F>(
+        &mut self,
+        target: String,
+        ports: Vec<u16>,
+        service_detection: bool,
+        all_addresses: bool,
+        sink: F,
+    ) -> u64
+    where
+        F: Fn(ScanUpdate) + Send + Sync + 'static,
+    {
+        // Stop any previous scan before starting a new one
+        if let Some(cancel) = self.cancel.take() {
+            cancel.store(true, Ordering::Relaxed);
         }
 
-        // Try to resolve as hostname
-        let addr = format!("{}:80", target);
-        match addr.to_socket_addrs() {
-            Ok(mut addrs) => {
-                if let Some(socket_addr) = addrs.next() {
-                    Ok(socket_addr.ip())
-                } else {
-                    Err(crate::utils::ModeError::Generic(format!(
-                        "Failed to resolve hostname: {}",
-                        target
-                    )))
+        self.scan_id += 1;
+        let scan_id = self.scan_id;
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.cancel = Some(Arc::clone(&cancel));
+        self.started = Some(Instant::now());
+        self.started_at = Some(Local::now());
+        self.all_addresses = all_addresses;
+
+        let ScanConfig { concurrency, timeout, .. } = self.config;
+
+        thread::spawn(move || {
+            // Expand ranges and resolve hostnames
+            let hosts = match scan_targets::resolve_targets(&target, all_addresses) {
+                Ok(hosts) => hosts,
+                Err(e) => {
+                    sink(ScanUpdate::Failed {
+                        scan_id,
+                        message: e.to_string(),
+                    });
+                    return;
                 }
-            }
-            Err(e) => Err(crate::utils::ModeError::Generic(format!(
-                "Failed to resolve target {}: {}",
-                target, e
-            ))),
-        }
-    }
-
-    /// Detects service running on a port
-    fn detect_service(port: u16) -> Option<String> {
-        let service = match port {
-            20 => "FTP Data",
-            21 => "FTP",
-            22 => "SSH",
-            23 => "Telnet",
-            25 => "SMTP",
-            53 => "DNS",
-            80 => "HTTP",
-            110 => "POP3",
-            143 => "IMAP",
-            443 => "HTTPS",
-            445 => "SMB",
-            993 => "IMAPS",
-            995 => "POP3S",
-            1433 => "MSSQL",
-            1521 => "Oracle",
-            3306 => "MySQL",
-            3389 => "RDP",
-            5432 => "PostgreSQL",
-            5900 => "VNC",
-            6379 => "Redis",
-            8080 => "HTTP Proxy",
-            8443 => "HTTPS Alt",
-            27017 => "MongoDB",
-            _ => return None,
-        };
+            };
 
-        Some(service.to_string())
-    }
+            let ips: Vec<IpAddr> = hosts.iter().map(|h| h.ip).collect();
+            sink(ScanUpdate::Resolved { scan_id, hosts });
+
+            // Work items are (host, port) pairs, one host at a time
+            let total = ips.len() * ports.len();
+            let next = AtomicUsize::new(0);
+            let workers = concurrency.clamp(1, total.max(1));
+
+            thread::scope(|scope| {
+                for _ in 0..workers {
+                    scope.spawn(|| loop {
+                        if cancel.load(Ordering::Relaxed) {
+                            break;
+                        }
 
-    /// Saves scan results to a file
-    fn save_results(&self, target: &str, results: &[PortInfo]) -> Result<()> {
-        use std::fs::File;
-        use std::io::Write;
+                        let item = next.fetch_add(1, Ordering::Relaxed);
+                        if item >= total {
+                            break;
+                        }
+                        let (host, port) = (item / ports.len(), ports[item % ports.len()]);
 
-        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
-        let filename = format!("scan_{}_{}.txt", target.replace(".", "_"), timestamp);
+                        let open = Self::probe_port(ips[host], port, timeout, service_detection);
+                        sink(ScanUpdate::PortScanned { scan_id, host, open });
+                    });
+                }
+            });
 
-        let mut file = File::create(&filename)?;
+            sink(ScanUpdate::Finished { scan_id });
+        });
 
-        writeln!(file, "Port Scan Results")?;
-        writeln!(file, "==================")?;
-        writeln!(file, "Target: {}", target)?;
-        writeln!(file, "Scan Time: {}", chrono::Local::now().format("%Y-%m-%d %H:%M:%S"))?;
-        writeln!(file, "Open Ports: {}\n", results.len())?;
+        scan_id
+    }
 
-        if results.is_empty() {
-            writeln!(file, "No open ports found.")?;
-        } else {
-            writeln!(file, "PORT     STATE    SERVICE")?;
-            writeln!(file, "----     -----    -------")?;
-            for port_info in results {
-                let service = port_info.service.as_deref().unwrap_or("unknown");
-                writeln!(file, "{:<8} {:<8} {}", port_info.port, "open", service)?;
-            }
+    /// Attempts a TCP connection to a single port
+    ///
+    /// Returns the port details if the connection succeeded
+    fn probe_port(ip: IpAddr, port: u16, timeout: Duration, service_detection: bool) -> Option<PortInfo> {
+        let socket_addr = SocketAddr::new(ip, port);
+
+        // Connection failure means the port is closed or filtered
+        TcpStream::connect_timeout(&socket_addr, timeout).ok()?;
+
+        if !service_detection {
+            return Some(PortInfo {
+                port,
+                service: None,
+                state: PortState::Open,
+                product: None,
+                version: None,
+                tls: false,
+            });
         }
 
-        Ok(())
+        let info = service_probe::probe(ip, port, timeout.max(PROBE_TIMEOUT));
+
+        Some(PortInfo {
+            port,
+            service: info.service,
+            state: PortState::Open,
+            product: info.product,
+            version: info.version,
+            tls: info.tls,
+        })
     }
 
     /// Goes back to previous state
@@ -689,13 +1118,62 @@ impl Scanner {
                     };
                 }
             }
-            ScannerState::Confirming { scan_type, target, service_detection, save_to_file, custom_ports } => {
+            ScannerState::EnteringOutputDir {
+                scan_type,
+                target,
+                service_detection,
+                all_addresses,
+                export_format,
+                custom_ports,
+                ..
+            } => {
                 self.state = ScannerState::SelectingOptions {
                     scan_type: *scan_type,
                     target: target.clone(),
                     selected: 0,
                     service_detection: *service_detection,
-                    save_to_file: *save_to_file,
+                    all_addresses: *all_addresses,
+                    save_to_file: true,
+                    export_format: *export_format,
+                    custom_ports: custom_ports.clone(),
+                };
+            }
+            ScannerState::Confirming {
+                scan_type,
+                target,
+                service_detection,
+                all_addresses,
+                export: Some(export),
+                custom_ports,
+                ..
+            } => {
+                self.state = ScannerState::EnteringOutputDir {
+                    scan_type: *scan_type,
+                    target: target.clone(),
+                    service_detection: *service_detection,
+                    all_addresses: *all_addresses,
+                    export_format: export.format,
+                    custom_ports: custom_ports.clone(),
+                    input: export.directory.display().to_string(),
+                };
+            }
+            ScannerState::Confirming {
+                scan_type,
+                target,
+                service_detection,
+                all_addresses,
+                export: None,
+                export_format,
+                custom_ports,
+            } => {
+                self.state = ScannerState::SelectingOptions {
+                    scan_type: *scan_type,
+                    target: target.clone(),
+                    selected: 0,
+                    service_detection: *service_detection,
+                    all_addresses: *all_addresses,
+                    save_to_file: false,
+                    export_format: *export_format,
                     custom_ports: custom_ports.clone(),
                 };
             }
@@ -725,6 +1203,7 @@ impl Scanner {
         match &self.state {
             ScannerState::EnteringTarget { input, .. } => input.clone(),
             ScannerState::EnteringPortRange { input, .. } => input.clone(),
+            ScannerState::EnteringOutputDir { input, .. } => input.clone(),
             _ => String::new(),
         }
     }
@@ -736,7 +1215,10 @@ impl Scanner {
                 "Select scan type (↑/↓ to navigate, Enter to select, ESC to cancel):".to_string()
             }
             ScannerState::EnteringTarget { scan_type, .. } => {
-                format!("{}\nEnter target IP address or hostname:", scan_type.name())
+                format!(
+                    "{}\nEnter targets (IP, hostname, 192.168.1.0/24, 10.0.0.1-20, comma-separated):",
+                    scan_type.name()
+                )
             }
             ScannerState::EnteringPortRange { .. } => {
                 "Enter port range (e.g., '80,443' or '1-1000' or '80,443,8000-9000'):".to_string()
@@ -744,12 +1226,33 @@ impl Scanner {
             ScannerState::SelectingOptions { .. } => {
                 "Configure scan options (↑/↓ to navigate, Space to toggle, Enter to continue):".to_string()
             }
+            ScannerState::EnteringOutputDir { export_format, .. } => {
+                format!("Save {} results to directory:", export_format.name())
+            }
             ScannerState::Confirming { .. } => "Review scan parameters:".to_string(),
-            ScannerState::Scanning { progress, total, .. } => {
-                format!("Scanning... {}/{} ports", progress, total)
+            ScannerState::Scanning { target, progress, total, hosts, .. } => {
+                format!(
+                    "Scanning {}... {}/{} probes across {} host{}, {} open ({}s)",
+                    target,
+                    progress,
+                    total,
+                    hosts.len(),
+                    if hosts.len() == 1 { "" } else { "s" },
+                    count_open_ports(hosts),
+                    self.elapsed().as_secs()
+                )
             }
-            ScannerState::ViewingResults { target, open_ports, .. } => {
-                format!("Scan Results for {} ({} open ports)", target, open_ports.len())
+            ScannerState::ViewingResults { target, hosts, saved_to, .. } => {
+                let mut prompt = format!(
+                    "Scan Results for {} ({} open ports on {} hosts)",
+                    target,
+                    count_open_ports(hosts),
+                    hosts.len()
+                );
+                if let Some(path) = saved_to {
+                    prompt.push_str(&format!(" - saved to {}", path.display()));
+                }
+                prompt
             }
             ScannerState::Success { message } => message.clone(),
             ScannerState::Error { message } => format!("Error: {}", message),
@@ -763,8 +1266,10 @@ impl Scanner {
                 scan_type,
                 target,
                 service_detection,
-                save_to_file,
+                all_addresses,
+                export,
                 custom_ports,
+                ..
             } => {
                 let port_count = custom_ports.as_ref()
                     .map(|p| p.len())
@@ -778,9 +1283,24 @@ impl Scanner {
                         "Service Detection".to_string(),
                         if *service_detection { "Enabled" } else { "Disabled" }.to_string(),
                     ),
+                    (
+                        "Resolved Addresses".to_string(),
+                        if *all_addresses { "All" } else { "First only" }.to_string(),
+                    ),
                     (
                         "Save to File".to_string(),
-                        if *save_to_file { "Yes" } else { "No" }.to_string(),
+                        match export {
+                            Some(export) => format!("{} in {}", export.format.name(), export.directory.display()),
+                            None => "No".to_string(),
+                        },
+                    ),
+                    (
+                        "Workers".to_string(),
+                        format!(
+                            "{} threads, {}ms timeout",
+                            self.config.concurrency,
+                            self.config.timeout.as_millis()
+                        ),
                     ),
                 ];
                 Some(data)
@@ -790,13 +1310,17 @@ impl Scanner {
     }
 
     /// Gets the current scan options state
-    pub fn get_options_state(&self) -> Option<(bool, bool)> {
+    ///
+    /// Returns (service detection, all addresses, save to file, export format)
+    pub fn get_options_state(&self) -> Option<(bool, bool, bool, ExportFormat)> {
         match &self.state {
             ScannerState::SelectingOptions {
                 service_detection,
+                all_addresses,
                 save_to_file,
+                export_format,
                 ..
-            } => Some((*service_detection, *save_to_file)),
+            } => Some((*service_detection, *all_addresses, *save_to_file, *export_format)),
             _ => None,
         }
     }
@@ -807,3 +1331,115 @@ impl Default for Scanner {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    #[test]
+    fn test_scan_finds_local_listener() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let open_port = listener.local_addr().unwrap().port();
+
+        let mut scanner = Scanner::new();
+        scanner.set_config(ScanConfig {
+            concurrency: 4,
+            timeout: Duration::from_millis(200),
+            ..ScanConfig::default()
+        });
+
+        let ports = vec![open_port, open_port.wrapping_add(1).max(1)];
+        let hosts = scanner.scan("127.0.0.1", &ports, false).unwrap();
+
+        assert_eq!(hosts.len(), 1);
+        assert!(hosts[0].open_ports.iter().any(|p| p.port == open_port));
+    }
+
+    #[test]
+    fn test_scan_groups_results_per_host() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let open_port = listener.local_addr().unwrap().port();
+
+        let mut scanner = Scanner::new();
+        scanner.set_config(ScanConfig {
+            concurrency: 8,
+            timeout: Duration::from_millis(200),
+            ..ScanConfig::default()
+        });
+
+        let hosts = scanner.scan("127.0.0.1-3, 127.0.0.1", &[open_port], false).unwrap();
+
+        let ips: Vec<String> = hosts.iter().map(|h| h.host.ip.to_string()).collect();
+        assert_eq!(ips, vec!["127.0.0.1", "127.0.0.2", "127.0.0.3"]);
+        assert_eq!(hosts[0].open_ports.len(), 1);
+        assert!(hosts[1].open_ports.is_empty());
+    }
+
+    #[test]
+    fn test_stale_updates_are_ignored() {
+        let mut scanner = Scanner::new();
+        scanner.scan_id = 2;
+        scanner.state = ScannerState::Scanning {
+            scan_type: ScanType::QuickScan,
+            target: "127.0.0.1".to_string(),
+            progress: 0,
+            total: 2,
+            service_detection: false,
+            export: None,
+            custom_ports: None,
+            hosts: vec![HostResult::new(ScanHost {
+                ip: "127.0.0.1".parse().unwrap(),
+                hostname: None,
+            })],
+        };
+
+        let open = Some(PortInfo {
+            port: 22,
+            service: None,
+            state: PortState::Open,
+            product: None,
+            version: None,
+            tls: false,
+        });
+
+        // Update from a previous, cancelled scan
+        scanner.handle_scan_update(ScanUpdate::PortScanned { scan_id: 1, host: 0, open: open.clone() });
+        // Update from the current scan
+        scanner.handle_scan_update(ScanUpdate::PortScanned { scan_id: 2, host: 0, open });
+
+        match &scanner.state {
+            ScannerState::Scanning { progress, hosts, .. } => {
+                assert_eq!(*progress, 1);
+                assert_eq!(count_open_ports(hosts), 1);
+            }
+            other => panic!("unexpected state: {:?}", other),
+        }
+
+        scanner.cancel_scan();
+        assert!(matches!(scanner.state, ScannerState::ViewingResults { .. }));
+    }
+
+    #[test]
+    fn test_options_stay_with_the_scan() {
+        let mut scanner = Scanner::new();
+        scanner.state = ScannerState::SelectingOptions {
+            scan_type: ScanType::QuickScan,
+            target: "127.0.0.1".to_string(),
+            selected: 1,
+            service_detection: false,
+            all_addresses: false,
+            save_to_file: false,
+            export_format: ExportFormat::Csv,
+            custom_ports: None,
+        };
+
+        scanner.toggle_option();
+        assert_eq!(scanner.get_options_state(), Some((false, true, false, ExportFormat::Csv)));
+        assert!(!scanner.config().all_addresses);
+
+        scanner.advance_to_confirmation();
+        scanner.go_back();
+        assert_eq!(scanner.get_options_state(), Some((false, true, false, ExportFormat::Csv)));
+    }
+}
@@ -0,0 +1,548 @@
+use std::io::{Read, Write};
+use std::net::{IpAddr, Shutdown, SocketAddr, TcpStream};
+use std::time::Duration;
+
+/// Largest response read from a probed service
+const MAX_RESPONSE: usize = 8192;
+
+/// Products recognised in greetings that don't follow a `name/version` pattern
+const KNOWN_PRODUCTS: &[&str] = &[
+    "Postfix", "Exim", "Sendmail", "Dovecot", "Courier", "ProFTPD", "Pure-FTPd", "vsFTPd",
+    "FileZilla", "OpenSMTPD", "Microsoft",
+];
+
+/// What active probing learned about a service
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ServiceInfo {
+    /// Protocol name (e.g. "SSH", "HTTP")
+    pub service: Option<String>,
+    /// Server software (e.g. "OpenSSH", "nginx")
+    pub product: Option<String>,
+    /// Server software version
+    pub version: Option<String>,
+    /// Whether the port completed a TLS handshake
+    pub tls: bool,
+}
+
+/// Identifies the service listening on an open port
+///
+/// Probes in order, each on a fresh connection:
+/// 1. Wait for a greeting banner (SSH, SMTP, FTP, POP3, IMAP, MySQL)
+/// 2. Send an HTTP `HEAD` request and parse the `Server` header
+/// 3. Send a TLS ClientHello and look for a handshake reply
+/// 4. Send a Redis `PING` (and `INFO server` for the version)
+///
+/// Falls back to the well-known port table when nothing answers
+pub fn probe(ip: IpAddr, port: u16, timeout: Duration) -> ServiceInfo {
+    let addr = SocketAddr::new(ip, port);
+
+    if let Some(greeting) = exchange(&addr, timeout, None, |_| true) {
+        if let Some(info) = parse_greeting(&greeting, port) {
+            return info;
+        }
+    }
+
+    let http_request = format!("HEAD / HTTP/1.0\r\nHost: {}\r\nUser-Agent: mode\r\n\r\n", ip);
+    if let Some(response) = exchange(&addr, timeout, Some(http_request.as_bytes()), |buf| {
+        contains(buf, b"\r\n\r\n")
+    }) {
+        if let Some(info) = parse_http_response(&response) {
+            return info;
+        }
+    }
+
+    if let Some(response) = exchange(&addr, timeout, Some(&client_hello()), |buf| buf.len() >= 5) {
+        if is_tls_reply(&response) {
+            let service = if matches!(port, 443 | 4443 | 8443 | 9443) {
+                "HTTPS"
+            } else {
+                "TLS"
+            };
+            return ServiceInfo {
+                service: Some(service.to_string()),
+                tls: true,
+                ..ServiceInfo::default()
+            };
+        }
+    }
+
+    if let Some(info) = probe_redis(&addr, timeout) {
+        return info;
+    }
+
+    ServiceInfo {
+        service: well_known_service(port).map(str::to_string),
+        ..ServiceInfo::default()
+    }
+}
+
+/// Returns the conventional service name for a port
+pub fn well_known_service(port: u16) -> Option<&'static str> {
+    let service = match port {
+        20 => "FTP Data",
+        21 => "FTP",
+        22 => "SSH",
+        23 => "Telnet",
+        25 => "SMTP",
+        53 => "DNS",
+        80 => "HTTP",
+        110 => "POP3",
+        143 => "IMAP",
+        443 => "HTTPS",
+        445 => "SMB",
+        993 => "IMAPS",
+        995 => "POP3S",
+        1433 => "MSSQL",
+        1521 => "Oracle",
+        3306 => "MySQL",
+        3389 => "RDP",
+        5432 => "PostgreSQL",
+        5900 => "VNC",
+        6379 => "Redis",
+        8080 => "HTTP Alt",
+        8443 => "HTTPS Alt",
+        27017 => "MongoDB",
+        _ => return None,
+    };
+
+    Some(service)
+}
+
+/// Connects, optionally sends a payload, and reads until `done` or timeout
+///
+/// Returns None if the connection failed or nothing was received
+fn exchange<F>(addr: &SocketAddr, timeout: Duration, payload: Option<&[u8]>, done: F) -> Option<Vec<u8>>
+where
+    F: Fn(&[u8]) -> bool,
+{
+    let mut stream = TcpStream::connect_timeout(addr, timeout).ok()?;
+    stream.set_read_timeout(Some(timeout)).ok()?;
+    stream.set_write_timeout(Some(timeout)).ok()?;
+
+    if let Some(payload) = payload {
+        stream.write_all(payload).ok()?;
+    }
+
+    let response = read_response(&mut stream, done);
+    let _ = stream.shutdown(Shutdown::Both);
+
+    if response.is_empty() {
+        None
+    } else {
+        Some(response)
+    }
+}
+
+/// Reads from the stream until `done` holds, EOF, timeout, or the size limit
+fn read_response<F>(stream: &mut TcpStream, done: F) -> Vec<u8>
+where
+    F: Fn(&[u8]) -> bool,
+{
+    let mut response = Vec::new();
+    let mut buf = [0u8; 2048];
+
+    while response.len() < MAX_RESPONSE {
+        match stream.read(&mut buf) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => {
+                response.extend_from_slice(&buf[..n]);
+                if done(&response) {
+                    break;
+                }
+            }
+        }
+    }
+
+    response
+}
+
+/// Identifies server-first protocols from their greeting
+fn parse_greeting(greeting: &[u8], port: u16) -> Option<ServiceInfo> {
+    if let Some(info) = parse_mysql_handshake(greeting) {
+        return Some(info);
+    }
+
+    let text = String::from_utf8_lossy(greeting);
+    let first_line = text.lines().next().unwrap_or("").trim();
+
+    if let Some(software) = first_line.strip_prefix("SSH-") {
+        // SSH-2.0-OpenSSH_8.9p1 Ubuntu-3ubuntu0.1
+        let software = software.split_once('-').map(|(_, s)| s).unwrap_or("");
+        let software = software.split_whitespace().next().unwrap_or("");
+        let (product, version) = match software.split_once('_') {
+            Some((product, version)) => (product, Some(version)),
+            None => (software, None),
+        };
+
+        return Some(ServiceInfo {
+            service: Some("SSH".to_string()),
+            product: non_empty(product),
+            version: version.and_then(non_empty),
+            tls: false,
+        });
+    }
+
+    let service = if first_line.starts_with("220") {
+        let upper = first_line.to_uppercase();
+        if upper.contains("FTP") {
+            "FTP"
+        } else if upper.contains("SMTP") || upper.contains("MAIL") {
+            "SMTP"
+        } else {
+            match port {
+                21 => "FTP",
+                _ => "SMTP",
+            }
+        }
+    } else if first_line.starts_with("+OK") {
+        "POP3"
+    } else if first_line.starts_with("* OK") {
+        "IMAP"
+    } else {
+        // Unknown greeting: keep the port's usual name but still mine it for a product
+        let (product, version) = extract_product(first_line);
+        return Some(ServiceInfo {
+            service: well_known_service(port).map(str::to_string),
+            product,
+            version,
+            tls: false,
+        });
+    };
+
+    let (product, version) = extract_product(first_line);
+    Some(ServiceInfo {
+        service: Some(service.to_string()),
+        product,
+        version,
+        tls: false,
+    })
+}
+
+/// Recognises the MySQL/MariaDB initial handshake packet
+fn parse_mysql_handshake(greeting: &[u8]) -> Option<ServiceInfo> {
+    // 3-byte payload length, sequence id, protocol version 10, NUL-terminated server version
+    if greeting.len() < 6 || greeting[4] != 0x0a {
+        return None;
+    }
+
+    let version_bytes = &greeting[5..];
+    let end = version_bytes.iter().position(|&b| b == 0)?;
+    let version = std::str::from_utf8(&version_bytes[..end]).ok()?;
+
+    if version.is_empty() || !version.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let product = if version.contains("MariaDB") {
+        "MariaDB"
+    } else {
+        "MySQL"
+    };
+    let version = version.split('-').next().unwrap_or(version);
+
+    Some(ServiceInfo {
+        service: Some("MySQL".to_string()),
+        product: Some(product.to_string()),
+        version: non_empty(version),
+        tls: false,
+    })
+}
+
+/// Parses an HTTP response, taking product and version from the `Server` header
+fn parse_http_response(response: &[u8]) -> Option<ServiceInfo> {
+    let text = String::from_utf8_lossy(response);
+    if !text.starts_with("HTTP/") {
+        return None;
+    }
+
+    let server = text
+        .lines()
+        .skip(1)
+        .take_while(|line| !line.trim().is_empty())
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("server"))
+        .map(|(_, value)| value.trim().to_string());
+
+    let (product, version) = match server {
+        Some(server) => {
+            // "nginx/1.25.3", "Apache/2.4.57 (Debian)", "Werkzeug/3.0.1 Python/3.11.4"
+            let first = server.split_whitespace().next().unwrap_or("");
+            match first.split_once('/') {
+                Some((product, version)) => (non_empty(product), non_empty(version)),
+                None => (non_empty(&server), None),
+            }
+        }
+        None => (None, None),
+    };
+
+    Some(ServiceInfo {
+        service: Some("HTTP".to_string()),
+        product,
+        version,
+        tls: false,
+    })
+}
+
+/// Probes for Redis with `PING`, then asks `INFO server` for the version
+fn probe_redis(addr: &SocketAddr, timeout: Duration) -> Option<ServiceInfo> {
+    let reply = exchange(addr, timeout, Some(b"PING\r\n"), |buf| contains(buf, b"\r\n"))?;
+    let reply = String::from_utf8_lossy(&reply);
+
+    if reply.starts_with("+PONG") {
+        let version = exchange(addr, timeout, Some(b"INFO server\r\n"), |buf| {
+            contains(buf, b"redis_version:") && buf.ends_with(b"\r\n")
+        })
+        .and_then(|info| {
+            String::from_utf8_lossy(&info)
+                .lines()
+                .find_map(|line| line.strip_prefix("redis_version:").map(|v| v.trim().to_string()))
+        });
+
+        return Some(ServiceInfo {
+            service: Some("Redis".to_string()),
+            product: Some("Redis".to_string()),
+            version,
+            tls: false,
+        });
+    }
+
+    if reply.starts_with("-NOAUTH") || reply.starts_with("-DENIED") {
+        return Some(ServiceInfo {
+            service: Some("Redis".to_string()),
+            product: Some("Redis".to_string()),
+            ..ServiceInfo::default()
+        });
+    }
+
+    None
+}
+
+/// Whether a reply to our ClientHello is a TLS handshake or alert record
+fn is_tls_reply(response: &[u8]) -> bool {
+    response.len() >= 5 && matches!(response[0], 0x15 | 0x16) && response[1] == 0x03
+}
+
+/// Builds a minimal TLS 1.2 ClientHello record
+fn client_hello() -> Vec<u8> {
+    let cipher_suites: &[u8] = &[
+        0x13, 0x01, 0x13, 0x02, 0xc0, 0x2b, 0xc0, 0x2f, 0xc0, 0x2c, 0xc0, 0x30, 0x00, 0x9c,
+        0x00, 0x9d, 0x00, 0x2f, 0x00, 0x35,
+    ];
+
+    let mut extensions = Vec::new();
+    // supported_groups: x25519, secp256r1, secp384r1
+    extensions.extend_from_slice(&[0x00, 0x0a, 0x00, 0x08, 0x00, 0x06, 0x00, 0x1d, 0x00, 0x17, 0x00, 0x18]);
+    // ec_point_formats: uncompressed
+    extensions.extend_from_slice(&[0x00, 0x0b, 0x00, 0x02, 0x01, 0x00]);
+    // signature_algorithms
+    extensions.extend_from_slice(&[
+        0x00, 0x0d, 0x00, 0x0e, 0x00, 0x0c, 0x04, 0x03, 0x08, 0x04, 0x04, 0x01, 0x05, 0x03,
+        0x08, 0x05, 0x05, 0x01,
+    ]);
+
+    let mut body = Vec::new();
+    body.extend_from_slice(&[0x03, 0x03]); // client_version TLS 1.2
+    body.extend(std::iter::repeat_n(0x2a, 32)); // random
+    body.push(0x00); // session_id length
+    body.extend_from_slice(&(cipher_suites.len() as u16).to_be_bytes());
+    body.extend_from_slice(cipher_suites);
+    body.extend_from_slice(&[0x01, 0x00]); // compression: null
+    body.extend_from_slice(&(extensions.len() as u16).to_be_bytes());
+    body.extend_from_slice(&extensions);
+
+    let mut handshake = vec![0x01]; // ClientHello
+    handshake.extend_from_slice(&(body.len() as u32).to_be_bytes()[1..]);
+    handshake.extend_from_slice(&body);
+
+    let mut record = vec![0x16, 0x03, 0x01]; // handshake record, TLS 1.0 for compatibility
+    record.extend_from_slice(&(handshake.len() as u16).to_be_bytes());
+    record.extend_from_slice(&handshake);
+    record
+}
+
+/// Pulls a product name and version out of free-form banner text
+fn extract_product(text: &str) -> (Option<String>, Option<String>) {
+    let tokens: Vec<&str> = text
+        .split(|c: char| c.is_whitespace() || "()[],;".contains(c))
+        .filter(|t| !t.is_empty())
+        .collect();
+
+    // "name/1.2.3"
+    for token in &tokens {
+        if let Some((name, version)) = token.split_once('/') {
+            if starts_alphabetic(name) && version.starts_with(|c: char| c.is_ascii_digit()) {
+                return (Some(name.to_string()), Some(version.to_string()));
+            }
+        }
+    }
+
+    // "name 1.2.3"
+    for pair in tokens.windows(2) {
+        let (name, version) = (pair[0], pair[1]);
+        if starts_alphabetic(name)
+            && !name.contains('.')
+            && version.starts_with(|c: char| c.is_ascii_digit())
+            && version.contains('.')
+        {
+            return (Some(name.to_string()), Some(version.to_string()));
+        }
+    }
+
+    // Well-known product names without a version
+    let product = KNOWN_PRODUCTS
+        .iter()
+        .find(|known| tokens.iter().any(|t| t.eq_ignore_ascii_case(known)))
+        .map(|known| known.to_string());
+
+    (product, None)
+}
+
+/// Whether the string starts with an ASCII letter
+fn starts_alphabetic(s: &str) -> bool {
+    s.starts_with(|c: char| c.is_ascii_alphabetic())
+}
+
+/// Converts to an owned string unless empty
+fn non_empty(s: &str) -> Option<String> {
+    if s.is_empty() {
+        None
+    } else {
+        Some(s.to_string())
+    }
+}
+
+/// Whether `haystack` contains `needle`
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|w| w == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{Ipv4Addr, TcpListener};
+    use std::thread;
+
+    const TIMEOUT: Duration = Duration::from_millis(300);
+
+    /// Starts a local server that answers each connection with `respond(request)`
+    fn serve<F>(server_first: Option<&'static [u8]>, respond: F) -> u16
+    where
+        F: Fn(&[u8]) -> Option<Vec<u8>> + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { break };
+                if let Some(greeting) = server_first {
+                    let _ = stream.write_all(greeting);
+                    continue;
+                }
+
+                let _ = stream.set_read_timeout(Some(Duration::from_millis(100)));
+                let mut buf = [0u8; 1024];
+                let n = stream.read(&mut buf).unwrap_or(0);
+                if let Some(reply) = respond(&buf[..n]) {
+                    let _ = stream.write_all(&reply);
+                }
+            }
+        });
+
+        port
+    }
+
+    fn localhost() -> IpAddr {
+        IpAddr::V4(Ipv4Addr::LOCALHOST)
+    }
+
+    #[test]
+    fn test_probe_ssh_banner() {
+        let port = serve(Some(b"SSH-2.0-OpenSSH_9.6p1 Ubuntu-3ubuntu13\r\n"), |_| None);
+        let info = probe(localhost(), port, TIMEOUT);
+
+        assert_eq!(info.service.as_deref(), Some("SSH"));
+        assert_eq!(info.product.as_deref(), Some("OpenSSH"));
+        assert_eq!(info.version.as_deref(), Some("9.6p1"));
+    }
+
+    #[test]
+    fn test_probe_ftp_and_smtp_banners() {
+        let port = serve(Some(b"220 (vsFTPd 3.0.5)\r\n"), |_| None);
+        let info = probe(localhost(), port, TIMEOUT);
+        assert_eq!(info.service.as_deref(), Some("FTP"));
+        assert_eq!(info.product.as_deref(), Some("vsFTPd"));
+        assert_eq!(info.version.as_deref(), Some("3.0.5"));
+
+        let port = serve(Some(b"220 mail.example.com ESMTP Postfix (Ubuntu)\r\n"), |_| None);
+        let info = probe(localhost(), port, TIMEOUT);
+        assert_eq!(info.service.as_deref(), Some("SMTP"));
+        assert_eq!(info.product.as_deref(), Some("Postfix"));
+    }
+
+    #[test]
+    fn test_probe_http_server_header() {
+        let port = serve(None, |request| {
+            request
+                .starts_with(b"HEAD ")
+                .then(|| b"HTTP/1.1 200 OK\r\nServer: nginx/1.25.3\r\nContent-Length: 0\r\n\r\n".to_vec())
+        });
+        let info = probe(localhost(), port, TIMEOUT);
+
+        assert_eq!(info.service.as_deref(), Some("HTTP"));
+        assert_eq!(info.product.as_deref(), Some("nginx"));
+        assert_eq!(info.version.as_deref(), Some("1.25.3"));
+        assert!(!info.tls);
+    }
+
+    #[test]
+    fn test_probe_tls_handshake() {
+        let port = serve(None, |request| {
+            (request.first() == Some(&0x16)).then(|| vec![0x16, 0x03, 0x03, 0x00, 0x04, 0x02, 0x00, 0x00, 0x00])
+        });
+        let info = probe(localhost(), port, TIMEOUT);
+
+        assert!(info.tls);
+        assert_eq!(info.service.as_deref(), Some("TLS"));
+    }
+
+    #[test]
+    fn test_probe_redis() {
+        let port = serve(None, |request| {
+            if request.starts_with(b"PING") {
+                Some(b"+PONG\r\n".to_vec())
+            } else if request.starts_with(b"INFO") {
+                Some(b"$40\r\n# Server\r\nredis_version:7.2.4\r\nredis_mode:standalone\r\n".to_vec())
+            } else {
+                None
+            }
+        });
+        let info = probe(localhost(), port, TIMEOUT);
+
+        assert_eq!(info.service.as_deref(), Some("Redis"));
+        assert_eq!(info.version.as_deref(), Some("7.2.4"));
+    }
+
+    #[test]
+    fn test_parse_mysql_handshake() {
+        let mut packet = vec![0x4a, 0x00, 0x00, 0x00, 0x0a];
+        packet.extend_from_slice(b"8.0.36-0ubuntu0.22.04.1\0");
+        packet.extend_from_slice(&[0x08, 0x00, 0x00, 0x00]);
+
+        let info = parse_mysql_handshake(&packet).unwrap();
+        assert_eq!(info.product.as_deref(), Some("MySQL"));
+        assert_eq!(info.version.as_deref(), Some("8.0.36"));
+
+        assert!(parse_mysql_handshake(b"SSH-2.0-x\r\n").is_none());
+    }
+
+    #[test]
+    fn test_client_hello_lengths() {
+        let hello = client_hello();
+        let record_len = u16::from_be_bytes([hello[3], hello[4]]) as usize;
+        assert_eq!(record_len, hello.len() - 5);
+
+        let handshake_len = u32::from_be_bytes([0, hello[6], hello[7], hello[8]]) as usize;
+        assert_eq!(handshake_len, hello.len() - 9);
+    }
+}
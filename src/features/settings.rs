@@ -0,0 +1,587 @@
+use super::mode_config::ModeConfig;
+use super::process_presets::PresetSource;
+use crate::menu::MenuItem;
+use crate::ui_components::theme::Theme;
+use crate::utils::backup::BackupStore;
+use crate::utils::config::config_file_path;
+use crate::utils::{ModeError, Result};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Settings screen state machine
+#[derive(Debug, Clone)]
+pub enum SettingsState {
+    /// Browsing settings
+    Browsing { selected: usize },
+    /// Typing a new value for a numeric setting
+    Editing {
+        selected: usize,
+        input: String,
+        /// Validation problem from the last attempt
+        error: Option<String>,
+    },
+    /// Confirming the changes before writing `config.toml`
+    ConfirmingSave { selected: usize },
+    /// Confirming leaving with unsaved changes
+    ConfirmingDiscard { selected: usize },
+    /// Success state with message
+    Success { message: String },
+    /// Error state with message
+    Error { message: String },
+}
+
+/// One row of the Settings screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsField {
+    Theme,
+    ServiceDetection,
+    SaveToFile,
+    ExportFormat,
+    AllAddresses,
+    ScanTimeout,
+    ScanConcurrency,
+    GracePeriod,
+    ProcessPresets,
+    BackupKeep,
+    BackupMaxAge,
+    BackupCompress,
+    /// Whether a menu item is shown in the main menu
+    ShowMenuItem(MenuItem),
+}
+
+impl SettingsField {
+    /// Returns every field in display order
+    pub fn all() -> Vec<SettingsField> {
+        let mut fields = vec![
+            SettingsField::Theme,
+            SettingsField::ServiceDetection,
+            SettingsField::SaveToFile,
+            SettingsField::ExportFormat,
+            SettingsField::AllAddresses,
+            SettingsField::ScanTimeout,
+            SettingsField::ScanConcurrency,
+            SettingsField::GracePeriod,
+            SettingsField::ProcessPresets,
+            SettingsField::BackupKeep,
+            SettingsField::BackupMaxAge,
+            SettingsField::BackupCompress,
+        ];
+        // Settings itself stays visible so hidden items can be brought back
+        fields.extend(
+            MenuItem::all()
+                .into_iter()
+                .filter(|item| *item != MenuItem::Settings)
+                .map(SettingsField::ShowMenuItem),
+        );
+        fields
+    }
+
+    /// Returns the group the field is listed under
+    pub fn section(&self) -> &'static str {
+        match self {
+            SettingsField::Theme => "Appearance",
+            SettingsField::ServiceDetection
+            | SettingsField::SaveToFile
+            | SettingsField::ExportFormat
+            | SettingsField::AllAddresses
+            | SettingsField::ScanTimeout
+            | SettingsField::ScanConcurrency => "Port Scanner",
+            SettingsField::GracePeriod | SettingsField::ProcessPresets => "Process Manager",
+            SettingsField::BackupKeep | SettingsField::BackupMaxAge | SettingsField::BackupCompress => "Backups",
+            SettingsField::ShowMenuItem(_) => "Main Menu",
+        }
+    }
+
+    /// Returns the display name
+    pub fn name(&self) -> String {
+        match self {
+            SettingsField::Theme => "Theme".to_string(),
+            SettingsField::ServiceDetection => "Service Detection".to_string(),
+            SettingsField::SaveToFile => "Save Results to File".to_string(),
+            SettingsField::ExportFormat => "Export Format".to_string(),
+            SettingsField::AllAddresses => "All Resolved Addresses".to_string(),
+            SettingsField::ScanTimeout => "Timeout (ms)".to_string(),
+            SettingsField::ScanConcurrency => "Concurrency".to_string(),
+            SettingsField::GracePeriod => "Grace Period (ms)".to_string(),
+            SettingsField::ProcessPresets => "Presets".to_string(),
+            SettingsField::BackupKeep => "Keep Last".to_string(),
+            SettingsField::BackupMaxAge => "Max Age (days)".to_string(),
+            SettingsField::BackupCompress => "Compress Older".to_string(),
+            SettingsField::ShowMenuItem(item) => item.name().to_string(),
+        }
+    }
+
+    /// Returns the description shown for the selected field
+    pub fn description(&self) -> &'static str {
+        match self {
+            SettingsField::Theme => "Built-in theme, or a file in ~/.config/mode/themes/; applied when you leave Settings",
+            SettingsField::ServiceDetection => "Whether new scans start with service detection switched on",
+            SettingsField::SaveToFile => "Whether new scans start with saving results switched on",
+            SettingsField::ExportFormat => "Format preselected when saving scan results",
+            SettingsField::AllAddresses => "Scan every address a hostname resolves to by default",
+            SettingsField::ScanTimeout => "Connect timeout per port, 10-60000 ms",
+            SettingsField::ScanConcurrency => "Ports probed in parallel, 1-1000",
+            SettingsField::GracePeriod => "How long SIGTERM is given before escalating to SIGKILL, up to 60000 ms",
+            SettingsField::ProcessPresets => "Add [[process_presets]] tables to config.toml to define your own",
+            SettingsField::BackupKeep => "Newest backups kept per file, 0 keeps all of them",
+            SettingsField::BackupMaxAge => "Backups older than this are removed, 0 keeps them regardless of age",
+            SettingsField::BackupCompress => "Gzip every backup but the newest of each file",
+            SettingsField::ShowMenuItem(_) => "Whether the item is listed in the main menu",
+        }
+    }
+
+    /// Returns whether the value is typed in rather than toggled
+    pub fn is_numeric(&self) -> bool {
+        matches!(
+            self,
+            SettingsField::ScanTimeout
+                | SettingsField::ScanConcurrency
+                | SettingsField::GracePeriod
+                | SettingsField::BackupKeep
+                | SettingsField::BackupMaxAge
+        )
+    }
+
+    /// Returns whether the field can't be changed from the Settings screen
+    pub fn is_read_only(&self) -> bool {
+        matches!(self, SettingsField::ProcessPresets)
+    }
+
+    /// Formats the field's value in a config
+    pub fn value(&self, config: &ModeConfig) -> String {
+        let on_off = |enabled: bool| if enabled { "ON" } else { "OFF" }.to_string();
+        match self {
+            SettingsField::Theme => config.theme.clone(),
+            SettingsField::ServiceDetection => on_off(config.scanner.service_detection),
+            SettingsField::SaveToFile => on_off(config.scanner.save_to_file),
+            SettingsField::ExportFormat => config.scanner.export_format.name().to_string(),
+            SettingsField::AllAddresses => on_off(config.scanner.scan.all_addresses),
+            SettingsField::ScanTimeout => config.scanner.scan.timeout.as_millis().to_string(),
+            SettingsField::ScanConcurrency => config.scanner.scan.concurrency.to_string(),
+            SettingsField::GracePeriod => config.process.grace_period.as_millis().to_string(),
+            SettingsField::ProcessPresets => {
+                let custom = config
+                    .process
+                    .presets
+                    .iter()
+                    .filter(|p| p.source == PresetSource::Config)
+                    .count();
+                format!("{} ({} from config.toml)", config.process.presets.len(), custom)
+            }
+            SettingsField::BackupKeep => config.backups.keep.to_string(),
+            SettingsField::BackupMaxAge => config.backups.max_age_days.to_string(),
+            SettingsField::BackupCompress => on_off(config.backups.compress),
+            SettingsField::ShowMenuItem(item) => {
+                if config.hidden_menu_items.contains(item) {
+                    "Hidden".to_string()
+                } else {
+                    "Shown".to_string()
+                }
+            }
+        }
+    }
+
+    /// Flips a toggle or moves a choice to its next value
+    fn toggle(&self, config: &mut ModeConfig) {
+        match self {
+            SettingsField::Theme => {
+                let themes = Theme::available();
+                let index = themes.iter().position(|name| *name == config.theme).unwrap_or(0);
+                config.theme = themes[(index + 1) % themes.len()].clone();
+            }
+            SettingsField::ServiceDetection => {
+                config.scanner.service_detection = !config.scanner.service_detection;
+            }
+            SettingsField::SaveToFile => config.scanner.save_to_file = !config.scanner.save_to_file,
+            SettingsField::ExportFormat => {
+                config.scanner.export_format = config.scanner.export_format.next();
+            }
+            SettingsField::AllAddresses => {
+                config.scanner.scan.all_addresses = !config.scanner.scan.all_addresses;
+            }
+            SettingsField::BackupCompress => config.backups.compress = !config.backups.compress,
+            SettingsField::ShowMenuItem(item) => {
+                match config.hidden_menu_items.iter().position(|hidden| hidden == item) {
+                    Some(index) => {
+                        config.hidden_menu_items.remove(index);
+                    }
+                    None => config.hidden_menu_items.push(*item),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Sets a numeric field, leaving range checks to [`ModeConfig::validate`]
+    fn set_number(&self, config: &mut ModeConfig, number: u64) {
+        match self {
+            SettingsField::ScanTimeout => config.scanner.scan.timeout = Duration::from_millis(number),
+            SettingsField::ScanConcurrency => config.scanner.scan.concurrency = number as usize,
+            SettingsField::GracePeriod => config.process.grace_period = Duration::from_millis(number),
+            SettingsField::BackupKeep => config.backups.keep = number as usize,
+            SettingsField::BackupMaxAge => config.backups.max_age_days = number,
+            _ => {}
+        }
+    }
+}
+
+/// Settings feature
+#[derive(Debug)]
+pub struct Settings {
+    pub state: SettingsState,
+    /// `config.toml` the settings are saved to
+    path: Option<PathBuf>,
+    /// Settings as last saved (or as loaded)
+    saved: ModeConfig,
+    /// Settings being edited
+    config: ModeConfig,
+    /// Whether the settings were saved at least once
+    modified: bool,
+    /// Where `config.toml` is backed up before each save
+    backups: Option<BackupStore>,
+}
+
+impl Settings {
+    /// Creates a new Settings screen with the default settings
+    pub fn new() -> Self {
+        Self {
+            state: SettingsState::Browsing { selected: 0 },
+            path: None,
+            saved: ModeConfig::default(),
+            config: ModeConfig::default(),
+            modified: false,
+            backups: None,
+        }
+    }
+
+    /// Initializes the screen by loading `config.toml`
+    pub fn initialize(&mut self) -> Result<()> {
+        let path = config_file_path()?;
+        self.saved = ModeConfig::load_from(&path)?;
+        self.config = self.saved.clone();
+        self.path = Some(path);
+        self.backups = Some(BackupStore::open()?);
+        Ok(())
+    }
+
+    /// Returns the settings being edited
+    pub fn config(&self) -> &ModeConfig {
+        &self.config
+    }
+
+    /// Returns the settings as last saved
+    pub fn saved_config(&self) -> &ModeConfig {
+        &self.saved
+    }
+
+    /// Returns the field highlighted in the list, if any
+    pub fn selected_field(&self) -> Option<SettingsField> {
+        match self.state {
+            SettingsState::Browsing { selected } | SettingsState::Editing { selected, .. } => {
+                SettingsField::all().get(selected).copied()
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns whether a field differs from the saved settings
+    pub fn is_changed(&self, field: SettingsField) -> bool {
+        field.value(&self.config) != field.value(&self.saved)
+    }
+
+    /// Returns the fields that differ from the saved settings
+    pub fn changed_fields(&self) -> Vec<SettingsField> {
+        SettingsField::all().into_iter().filter(|field| self.is_changed(*field)).collect()
+    }
+
+    /// Returns whether there are unsaved changes
+    pub fn has_changes(&self) -> bool {
+        !self.changed_fields().is_empty()
+    }
+
+    /// Moves selection up
+    pub fn previous(&mut self) {
+        let total = SettingsField::all().len();
+        if let SettingsState::Browsing { selected } = &mut self.state {
+            *selected = if *selected == 0 { total - 1 } else { *selected - 1 };
+        }
+    }
+
+    /// Moves selection down
+    pub fn next(&mut self) {
+        let total = SettingsField::all().len();
+        if let SettingsState::Browsing { selected } = &mut self.state {
+            *selected = (*selected + 1) % total;
+        }
+    }
+
+    /// Toggles the selected field, or starts typing a value for numeric ones
+    pub fn activate(&mut self) {
+        let SettingsState::Browsing { selected } = self.state else {
+            return;
+        };
+        let Some(field) = self.selected_field() else {
+            return;
+        };
+
+        if field.is_numeric() {
+            self.state = SettingsState::Editing {
+                selected,
+                input: field.value(&self.config),
+                error: None,
+            };
+        } else {
+            field.toggle(&mut self.config);
+        }
+    }
+
+    /// Starts saving, asking for confirmation first
+    pub fn start_save(&mut self) {
+        if let SettingsState::Browsing { selected } = self.state {
+            if self.has_changes() {
+                self.state = SettingsState::ConfirmingSave { selected };
+            }
+        }
+    }
+
+    /// Asks before leaving if there are unsaved changes
+    ///
+    /// Returns true when it's fine to go back to the menu right away
+    pub fn request_exit(&mut self) -> bool {
+        let SettingsState::Browsing { selected } = self.state else {
+            return true;
+        };
+        if self.has_changes() {
+            self.state = SettingsState::ConfirmingDiscard { selected };
+            false
+        } else {
+            true
+        }
+    }
+
+    /// Handles input for the current state
+    pub fn handle_char(&mut self, c: char) {
+        if let SettingsState::Editing { input, .. } = &mut self.state {
+            if c.is_ascii_digit() {
+                input.push(c);
+            }
+        }
+    }
+
+    /// Handles backspace
+    pub fn handle_backspace(&mut self) {
+        if let SettingsState::Editing { input, .. } = &mut self.state {
+            input.pop();
+        }
+    }
+
+    /// Applies the typed value or saves the settings
+    pub fn advance(&mut self) {
+        match self.state.clone() {
+            SettingsState::Editing { selected, input, .. } => match self.apply_number(&input) {
+                Ok(()) => self.state = SettingsState::Browsing { selected },
+                Err(e) => {
+                    self.state = SettingsState::Editing {
+                        selected,
+                        input,
+                        error: Some(e.to_string()),
+                    };
+                }
+            },
+            SettingsState::ConfirmingSave { .. } => {
+                self.state = match self.save() {
+                    Ok(message) => SettingsState::Success { message },
+                    Err(e) => SettingsState::Error {
+                        message: e.to_string(),
+                    },
+                };
+            }
+            _ => {}
+        }
+    }
+
+    /// Validates and applies a typed value to the selected field
+    fn apply_number(&mut self, input: &str) -> Result<()> {
+        let field = self
+            .selected_field()
+            .ok_or_else(|| ModeError::InvalidInput("No setting selected".to_string()))?;
+        let number: u64 = input
+            .trim()
+            .parse()
+            .map_err(|_| ModeError::InvalidInput(format!("{} must be a whole number", field.name())))?;
+
+        let mut config = self.config.clone();
+        field.set_number(&mut config, number);
+        config.validate().map_err(ModeError::InvalidInput)?;
+        self.config = config;
+        Ok(())
+    }
+
+    /// Writes the settings to `config.toml`
+    fn save(&mut self) -> Result<String> {
+        let path = self
+            .path
+            .clone()
+            .ok_or_else(|| ModeError::Config("Config file path not set".to_string()))?;
+
+        let store = self
+            .backups
+            .as_ref()
+            .ok_or_else(|| ModeError::Config("backup store has not been opened".to_string()))?;
+
+        let changed = self.changed_fields().len();
+        let backup = self.config.save_to(&path, store)?;
+
+        // Reload so presets pick up the new default grace period exactly as on startup
+        self.saved = ModeConfig::load_from(&path)?;
+        self.config = self.saved.clone();
+        self.modified = true;
+
+        let mut message = format!("✓ Saved {} change(s) to {}", changed, path.display());
+        if let Some(backup) = backup {
+            message.push_str(&format!("\n\nBackup: {}", backup.display()));
+        }
+        Ok(message)
+    }
+
+    /// Goes back to the list, keeping the selection where it was
+    pub fn go_back(&mut self) {
+        let selected = match self.state {
+            SettingsState::Browsing { selected }
+            | SettingsState::Editing { selected, .. }
+            | SettingsState::ConfirmingSave { selected }
+            | SettingsState::ConfirmingDiscard { selected } => selected,
+            _ => 0,
+        };
+        self.state = SettingsState::Browsing { selected };
+    }
+
+    /// Returns whether the feature is done (success or error)
+    pub fn is_done(&self) -> bool {
+        matches!(
+            self.state,
+            SettingsState::Success { .. } | SettingsState::Error { .. }
+        )
+    }
+
+    /// Gets the prompt text for the current state
+    pub fn get_prompt(&self) -> String {
+        match &self.state {
+            SettingsState::Browsing { .. } => {
+                let path = self
+                    .path
+                    .as_ref()
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_else(|| "config.toml".to_string());
+                let changes = self.changed_fields().len();
+                if changes > 0 {
+                    format!("{} ({} unsaved change(s))", path, changes)
+                } else {
+                    path
+                }
+            }
+            SettingsState::Editing { error, .. } => {
+                let field = self.selected_field().map(|f| f.description()).unwrap_or_default();
+                match error {
+                    Some(error) => format!("{}\n\n{}", field, error),
+                    None => field.to_string(),
+                }
+            }
+            SettingsState::ConfirmingSave { .. } => "Save settings?".to_string(),
+            SettingsState::ConfirmingDiscard { .. } => "Discard unsaved settings?".to_string(),
+            SettingsState::Success { message } => message.clone(),
+            SettingsState::Error { message } => format!("Error: {}", message),
+        }
+    }
+
+    /// Returns the typed value while editing
+    pub fn get_input(&self) -> String {
+        match &self.state {
+            SettingsState::Editing { input, .. } => input.clone(),
+            _ => String::new(),
+        }
+    }
+
+    /// Gets the changes to confirm as (setting, "old → new") pairs
+    pub fn get_confirmation_data(&self) -> Option<Vec<(String, String)>> {
+        match &self.state {
+            SettingsState::ConfirmingSave { .. } => Some(
+                self.changed_fields()
+                    .into_iter()
+                    .map(|field| {
+                        (
+                            format!("{}: {}", field.section(), field.name()),
+                            format!("{} → {}", field.value(&self.saved), field.value(&self.config)),
+                        )
+                    })
+                    .collect(),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Returns whether settings were saved, so the app should apply them
+    pub fn is_modified(&self) -> bool {
+        self.modified
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::backup::BackupSettings;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_edit_validate_and_save() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let mut settings = Settings::new();
+        settings.path = Some(path.clone());
+        settings.backups = Some(BackupStore::new(dir.path().join("backups"), BackupSettings::default()));
+
+        let concurrency = SettingsField::all()
+            .iter()
+            .position(|f| *f == SettingsField::ScanConcurrency)
+            .unwrap();
+        settings.state = SettingsState::Browsing { selected: concurrency };
+
+        // Out-of-range values keep the user in the editor with the reason
+        settings.activate();
+        settings.handle_backspace();
+        settings.handle_backspace();
+        settings.handle_backspace();
+        settings.handle_char('0');
+        settings.advance();
+        assert!(matches!(&settings.state, SettingsState::Editing { error: Some(e), .. } if e.contains("concurrency")));
+        assert!(!settings.has_changes());
+
+        settings.handle_backspace();
+        for c in "6x4".chars() {
+            settings.handle_char(c);
+        }
+        settings.advance();
+        assert_eq!(settings.config().scanner.scan.concurrency, 64);
+        assert_eq!(settings.changed_fields(), vec![SettingsField::ScanConcurrency]);
+
+        // Toggling a menu item twice is no change at all
+        settings.state = SettingsState::Browsing {
+            selected: SettingsField::all().len() - 1,
+        };
+        settings.activate();
+        settings.activate();
+        assert_eq!(settings.changed_fields().len(), 1);
+
+        settings.start_save();
+        settings.advance();
+        assert!(matches!(settings.state, SettingsState::Success { .. }));
+        assert!(settings.is_modified() && !settings.has_changes());
+        assert_eq!(ModeConfig::load_from(&path).unwrap().scanner.scan.concurrency, 64);
+    }
+}
@@ -0,0 +1,610 @@
+use crate::utils::backup::BackupStore;
+use crate::utils::{expand_home, file_ops, ModeError, Result};
+use std::env;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+/// Indentation used for directives in blocks mode creates
+const DEFAULT_INDENT: &str = "    ";
+
+/// A `Host` or `Match` section with the raw lines that make it up
+///
+/// Lines are kept verbatim so comments, blank lines and directives mode
+/// doesn't know about survive a load/save round trip untouched.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostBlock {
+    /// Comment lines directly above the header, which move with the block
+    leading: Vec<String>,
+    header: String,
+    lines: Vec<String>,
+}
+
+impl HostBlock {
+    /// Creates an empty `Host` block for the given pattern
+    fn new(alias: &str) -> Self {
+        Self {
+            leading: Vec::new(),
+            header: format!("Host {}", alias),
+            lines: Vec::new(),
+        }
+    }
+
+    /// Returns whether this is a `Host` block rather than a `Match` block
+    pub fn is_host(&self) -> bool {
+        parse_directive(&self.header).is_some_and(|(key, _)| key.eq_ignore_ascii_case("host"))
+    }
+
+    /// Returns the patterns after the `Host` keyword as written
+    pub fn alias(&self) -> String {
+        parse_directive(&self.header).map(|(_, value)| value).unwrap_or_default()
+    }
+
+    /// Returns whether the block names a single host rather than a pattern
+    pub fn is_concrete(&self) -> bool {
+        let alias = self.alias();
+        !alias.is_empty() && !alias.contains(char::is_whitespace) && !alias.contains(['*', '?', '!'])
+    }
+
+    /// Returns whether any of the block's patterns match more than one host
+    pub fn is_wildcard(&self) -> bool {
+        self.alias().contains(['*', '?'])
+    }
+
+    /// Returns the first value of a directive (ssh uses the first one it sees)
+    pub fn get(&self, key: &str) -> Option<String> {
+        self.lines
+            .iter()
+            .filter_map(|line| parse_directive(line))
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, value)| value)
+    }
+
+    /// Sets a directive in place, adding it after the last directive if missing
+    ///
+    /// An unchanged value leaves the line exactly as written. An empty value
+    /// removes every occurrence of the directive.
+    pub fn set(&mut self, key: &str, value: &str) {
+        let is_key = |line: &str| parse_directive(line).is_some_and(|(k, _)| k.eq_ignore_ascii_case(key));
+
+        if value.is_empty() {
+            self.lines.retain(|line| !is_key(line));
+            return;
+        }
+        if self.get(key).as_deref() == Some(value) {
+            return;
+        }
+
+        let line = format!("{}{} {}", self.indent(), key, quote_value(value));
+        match self.lines.iter().position(|l| is_key(l)) {
+            Some(index) => self.lines[index] = line,
+            None => {
+                let insert_at = self
+                    .lines
+                    .iter()
+                    .rposition(|l| parse_directive(l).is_some())
+                    .map_or(0, |i| i + 1);
+                self.lines.insert(insert_at, line);
+            }
+        }
+    }
+
+    /// Renames a `Host` block, keeping the header's indentation
+    fn set_alias(&mut self, alias: &str) {
+        if self.alias() == alias {
+            return;
+        }
+        let indent: String = self.header.chars().take_while(|c| c.is_whitespace()).collect();
+        self.header = format!("{}Host {}", indent, alias);
+    }
+
+    /// Returns the indentation used by existing directives
+    fn indent(&self) -> String {
+        self.lines
+            .iter()
+            .find(|line| parse_directive(line).is_some())
+            .map(|line| line.chars().take_while(|c| c.is_whitespace()).collect())
+            .unwrap_or_else(|| DEFAULT_INDENT.to_string())
+    }
+}
+
+/// The fields mode edits on a `Host` block; empty strings mean unset
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HostEntry {
+    pub alias: String,
+    pub hostname: String,
+    pub user: String,
+    pub port: String,
+    pub identity_file: String,
+    pub proxy_jump: String,
+}
+
+impl HostEntry {
+    /// Labels of the editable fields, in form order (also the directive names)
+    pub const FIELDS: [&'static str; 6] = ["Host", "HostName", "User", "Port", "IdentityFile", "ProxyJump"];
+
+    /// Reads the editable fields from a block
+    pub fn from_block(block: &HostBlock) -> Self {
+        Self {
+            alias: block.alias(),
+            hostname: block.get("HostName").unwrap_or_default(),
+            user: block.get("User").unwrap_or_default(),
+            port: block.get("Port").unwrap_or_default(),
+            identity_file: block.get("IdentityFile").unwrap_or_default(),
+            proxy_jump: block.get("ProxyJump").unwrap_or_default(),
+        }
+    }
+
+    /// Returns a field by its index in [`HostEntry::FIELDS`]
+    pub fn field(&self, index: usize) -> &str {
+        match index {
+            0 => &self.alias,
+            1 => &self.hostname,
+            2 => &self.user,
+            3 => &self.port,
+            4 => &self.identity_file,
+            _ => &self.proxy_jump,
+        }
+    }
+
+    /// Returns a mutable field by its index in [`HostEntry::FIELDS`]
+    pub fn field_mut(&mut self, index: usize) -> &mut String {
+        match index {
+            0 => &mut self.alias,
+            1 => &mut self.hostname,
+            2 => &mut self.user,
+            3 => &mut self.port,
+            4 => &mut self.identity_file,
+            _ => &mut self.proxy_jump,
+        }
+    }
+
+    /// Validates every field, including the identity file on disk
+    pub fn validate(&self) -> Result<()> {
+        let alias = self.alias.trim();
+        if alias.is_empty() {
+            return Err(ModeError::InvalidInput("Host cannot be empty".to_string()));
+        }
+        if alias.contains(char::is_whitespace) {
+            return Err(ModeError::InvalidInput(
+                "Host must be a single name or pattern (no spaces)".to_string(),
+            ));
+        }
+        for (label, value) in [("HostName", &self.hostname), ("User", &self.user), ("ProxyJump", &self.proxy_jump)] {
+            if value.trim().contains(char::is_whitespace) {
+                return Err(ModeError::InvalidInput(format!("{} cannot contain spaces", label)));
+            }
+        }
+        let port = self.port.trim();
+        if !port.is_empty() && !matches!(port.parse::<u16>(), Ok(p) if p > 0) {
+            return Err(ModeError::InvalidInput(format!(
+                "Port must be a number between 1 and 65535, got '{}'",
+                port
+            )));
+        }
+        if !self.identity_file.trim().is_empty() {
+            check_identity_file(self.identity_file.trim())?;
+        }
+        Ok(())
+    }
+
+    /// Writes the fields into a block, leaving other directives alone
+    fn apply_to(&self, block: &mut HostBlock) {
+        block.set_alias(self.alias.trim());
+        block.set("HostName", self.hostname.trim());
+        block.set("User", self.user.trim());
+        block.set("Port", self.port.trim());
+        block.set("IdentityFile", self.identity_file.trim());
+        block.set("ProxyJump", self.proxy_jump.trim());
+    }
+}
+
+/// An OpenSSH client config file
+#[derive(Debug)]
+pub struct SshConfig {
+    path: PathBuf,
+    /// Global directives and comments before the first block
+    preamble: Vec<String>,
+    blocks: Vec<HostBlock>,
+}
+
+impl SshConfig {
+    /// Returns the location of the user's SSH config
+    pub fn file_path() -> Result<PathBuf> {
+        let home = env::var("HOME")
+            .map_err(|_| ModeError::Config("HOME environment variable not set".to_string()))?;
+        Ok(PathBuf::from(home).join(".ssh").join("config"))
+    }
+
+    /// Loads the user's SSH config
+    pub fn load() -> Result<Self> {
+        Self::load_from(Self::file_path()?)
+    }
+
+    /// Loads a config file, starting empty if it doesn't exist yet
+    pub fn load_from(path: PathBuf) -> Result<Self> {
+        let content = if path.exists() {
+            fs::read_to_string(&path)?
+        } else {
+            String::new()
+        };
+
+        let mut config = Self::parse(&content);
+        config.path = path;
+        Ok(config)
+    }
+
+    /// Splits config text into the preamble and its blocks
+    fn parse(content: &str) -> Self {
+        let mut preamble = Vec::new();
+        let mut blocks: Vec<HostBlock> = Vec::new();
+
+        for line in content.lines() {
+            let is_header = parse_directive(line).is_some_and(|(key, _)| {
+                key.eq_ignore_ascii_case("host") || key.eq_ignore_ascii_case("match")
+            });
+
+            if !is_header {
+                match blocks.last_mut() {
+                    Some(block) => block.lines.push(line.to_string()),
+                    None => preamble.push(line.to_string()),
+                }
+                continue;
+            }
+
+            // Comments directly above a header describe that block
+            let previous = match blocks.last_mut() {
+                Some(block) => &mut block.lines,
+                None => &mut preamble,
+            };
+            let comments = previous
+                .iter()
+                .rev()
+                .take_while(|l| l.trim_start().starts_with('#'))
+                .count();
+            let leading = previous.split_off(previous.len() - comments);
+
+            blocks.push(HostBlock {
+                leading,
+                header: line.to_string(),
+                lines: Vec::new(),
+            });
+        }
+
+        Self {
+            path: PathBuf::new(),
+            preamble,
+            blocks,
+        }
+    }
+
+    /// Returns the file the config was loaded from
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the `Host` blocks in file order (`Match` blocks are kept but not listed)
+    pub fn hosts(&self) -> Vec<&HostBlock> {
+        self.blocks.iter().filter(|b| b.is_host()).collect()
+    }
+
+    /// Looks up a `Host` block by its patterns as written
+    pub fn get(&self, alias: &str) -> Option<&HostBlock> {
+        self.blocks.iter().find(|b| b.is_host() && b.alias() == alias)
+    }
+
+    /// Adds a new `Host` block
+    ///
+    /// ssh keeps the first value it finds for each option, so the block goes
+    /// before the first wildcard `Host` or `Match` block; otherwise a `Host *`
+    /// further up would override what was just entered. With no such block
+    /// it is appended.
+    pub fn add_host(&mut self, entry: &HostEntry) -> Result<()> {
+        let alias = entry.alias.trim();
+        if self.get(alias).is_some() {
+            return Err(ModeError::InvalidInput(format!("Host '{}' already exists", alias)));
+        }
+
+        let index = self
+            .blocks
+            .iter()
+            .position(|b| !b.is_host() || b.is_wildcard())
+            .unwrap_or(self.blocks.len());
+
+        // Separate the new block from whatever comes before it
+        let previous = match index.checked_sub(1) {
+            Some(previous) => &mut self.blocks[previous].lines,
+            None => &mut self.preamble,
+        };
+        if previous.last().is_some_and(|line| !line.trim().is_empty()) {
+            previous.push(String::new());
+        }
+
+        let mut block = HostBlock::new(alias);
+        entry.apply_to(&mut block);
+        // ...and from the block that now follows it
+        if index < self.blocks.len() {
+            block.lines.push(String::new());
+        }
+        self.blocks.insert(index, block);
+        Ok(())
+    }
+
+    /// Updates a `Host` block in place, possibly renaming it
+    pub fn update_host(&mut self, alias: &str, entry: &HostEntry) -> Result<()> {
+        let new_alias = entry.alias.trim();
+        if new_alias != alias && self.get(new_alias).is_some() {
+            return Err(ModeError::InvalidInput(format!("Host '{}' already exists", new_alias)));
+        }
+
+        let block = self
+            .blocks
+            .iter_mut()
+            .find(|b| b.is_host() && b.alias() == alias)
+            .ok_or_else(|| not_found(alias))?;
+        entry.apply_to(block);
+        Ok(())
+    }
+
+    /// Removes a `Host` block along with the comments directly above it
+    pub fn remove_host(&mut self, alias: &str) -> Result<()> {
+        let index = self
+            .blocks
+            .iter()
+            .position(|b| b.is_host() && b.alias() == alias)
+            .ok_or_else(|| not_found(alias))?;
+        self.blocks.remove(index);
+        Ok(())
+    }
+
+    /// Renders the config back to text
+    pub fn render(&self) -> String {
+        let mut lines: Vec<&str> = self.preamble.iter().map(String::as_str).collect();
+        for block in &self.blocks {
+            lines.extend(block.leading.iter().map(String::as_str));
+            lines.push(&block.header);
+            lines.extend(block.lines.iter().map(String::as_str));
+        }
+
+        if lines.is_empty() {
+            String::new()
+        } else {
+            lines.join("\n") + "\n"
+        }
+    }
+
+    /// Writes the config atomically, backing up the previous version into `store`
+    ///
+    /// The file is kept private (0600) since ssh refuses configs others can write.
+    /// Returns the path to the backup file, if there was a file to back up
+    pub fn save(&self, store: &BackupStore) -> Result<Option<PathBuf>> {
+        let backup_path = if self.path.exists() {
+            Some(file_ops::create_backup(&self.path, store)?)
+        } else {
+            None
+        };
+
+        if let Some(dir) = self.path.parent() {
+            if !dir.exists() {
+                fs::create_dir_all(dir)?;
+                fs::set_permissions(dir, fs::Permissions::from_mode(0o700))?;
+            }
+        }
+        file_ops::write_atomic(&self.path, &self.render())?;
+        fs::set_permissions(&self.path, fs::Permissions::from_mode(0o600))?;
+
+        Ok(backup_path)
+    }
+}
+
+/// Checks that an identity file exists and is private enough for ssh to use it
+///
+/// Paths using ssh's `%` tokens can't be resolved here and are accepted as-is
+pub fn check_identity_file(path: &str) -> Result<()> {
+    if path.contains('%') {
+        return Ok(());
+    }
+
+    let resolved = expand_home(path);
+    let metadata = fs::metadata(&resolved)
+        .map_err(|_| ModeError::InvalidInput(format!("Identity file {} does not exist", path)))?;
+    if !metadata.is_file() {
+        return Err(ModeError::InvalidInput(format!("Identity file {} is not a file", path)));
+    }
+
+    let mode = metadata.permissions().mode() & 0o777;
+    if mode & 0o077 != 0 {
+        return Err(ModeError::InvalidInput(format!(
+            "Identity file {} has permissions {:04o}; ssh ignores keys others can read.\nFix with: chmod 600 {}",
+            path,
+            mode,
+            resolved.display()
+        )));
+    }
+    Ok(())
+}
+
+/// Splits a config line into its keyword and value
+///
+/// Accepts both `Key value` and `Key=value`; surrounding quotes are removed
+/// from the value. Blank lines and comments yield None.
+fn parse_directive(line: &str) -> Option<(String, String)> {
+    let trimmed = line.trim();
+    if trimmed.is_empty() || trimmed.starts_with('#') {
+        return None;
+    }
+
+    let split = trimmed.find(|c: char| c.is_whitespace() || c == '=')?;
+    let (key, rest) = trimmed.split_at(split);
+    let rest = rest.trim_start();
+    let value = rest.strip_prefix('=').unwrap_or(rest).trim();
+    let value = value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value);
+
+    Some((key.to_string(), value.to_string()))
+}
+
+/// Quotes a directive value if it contains whitespace
+fn quote_value(value: &str) -> String {
+    if value.contains(char::is_whitespace) {
+        format!("\"{}\"", value)
+    } else {
+        value.to_string()
+    }
+}
+
+fn not_found(alias: &str) -> ModeError {
+    ModeError::InvalidInput(format!("No host named '{}'", alias))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::backup::BackupSettings;
+    use tempfile::tempdir;
+
+    const SAMPLE: &str = "\
+# Global settings
+Include ~/.ssh/config.d/*
+ServerAliveInterval 60
+
+# Work box
+Host work
+    HostName work.example.com
+    User=deploy
+    # keep this comment
+    ForwardAgent yes
+
+Match host *.internal
+  User admin
+
+Host *
+  AddKeysToAgent yes
+";
+
+    #[test]
+    fn test_parse_and_render_round_trip() {
+        let config = SshConfig::parse(SAMPLE);
+        assert_eq!(config.render(), SAMPLE);
+
+        let hosts: Vec<String> = config.hosts().iter().map(|h| h.alias()).collect();
+        assert_eq!(hosts, vec!["work", "*"]);
+
+        let work = config.get("work").unwrap();
+        assert!(work.is_concrete());
+        assert!(!config.get("*").unwrap().is_concrete());
+        assert_eq!(work.get("user").as_deref(), Some("deploy"));
+        assert_eq!(work.get("ForwardAgent").as_deref(), Some("yes"));
+        assert_eq!(work.leading, vec!["# Work box".to_string()]);
+    }
+
+    #[test]
+    fn test_edit_preserves_comments_and_unknown_directives() {
+        let mut config = SshConfig::parse(SAMPLE);
+        let mut entry = HostEntry::from_block(config.get("work").unwrap());
+        entry.alias = "office".to_string();
+        entry.user = String::new();
+        entry.port = "2222".to_string();
+        config.update_host("work", &entry).unwrap();
+
+        // Untouched fields keep their original spelling
+        let mut unchanged = SshConfig::parse(SAMPLE);
+        let entry_as_is = HostEntry::from_block(unchanged.get("work").unwrap());
+        unchanged.update_host("work", &entry_as_is).unwrap();
+        assert_eq!(unchanged.render(), SAMPLE);
+
+        let rendered = config.render();
+        assert!(rendered.contains("# Work box\nHost office\n    HostName work.example.com\n"));
+        assert!(rendered.contains("    # keep this comment\n    ForwardAgent yes\n    Port 2222\n"));
+        assert!(!rendered.contains("deploy"));
+        assert!(rendered.contains("Match host *.internal\n  User admin\n"));
+
+        config.remove_host("office").unwrap();
+        let rendered = config.render();
+        assert!(!rendered.contains("Work box"));
+        assert!(rendered.starts_with("# Global settings\nInclude ~/.ssh/config.d/*\n"));
+    }
+
+    #[test]
+    fn test_add_host_and_save() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(".ssh").join("config");
+        let store = BackupStore::new(dir.path().join("backups"), BackupSettings::default());
+
+        let mut config = SshConfig::load_from(path.clone()).unwrap();
+        let entry = HostEntry {
+            alias: "pi".to_string(),
+            hostname: "192.168.1.20".to_string(),
+            user: "pi".to_string(),
+            ..HostEntry::default()
+        };
+        config.add_host(&entry).unwrap();
+        assert!(config.add_host(&entry).is_err());
+        assert_eq!(config.save(&store).unwrap(), None);
+
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "Host pi\n    HostName 192.168.1.20\n    User pi\n"
+        );
+        assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+
+        let mut config = SshConfig::load_from(path).unwrap();
+        config.add_host(&HostEntry { alias: "nas".to_string(), ..HostEntry::default() }).unwrap();
+        assert!(config.save(&store).unwrap().is_some());
+        assert!(config.render().ends_with("User pi\n\nHost nas\n"));
+    }
+
+    #[test]
+    fn test_add_host_goes_before_wildcard_and_match_blocks() {
+        let mut config = SshConfig::parse(SAMPLE);
+        let entry = HostEntry {
+            alias: "pi".to_string(),
+            user: "pi".to_string(),
+            ..HostEntry::default()
+        };
+        config.add_host(&entry).unwrap();
+
+        let hosts: Vec<String> = config.hosts().iter().map(|h| h.alias()).collect();
+        assert_eq!(hosts, vec!["work", "pi", "*"]);
+        assert!(config.render().contains(
+            "    ForwardAgent yes\n\nHost pi\n    User pi\n\nMatch host *.internal\n"
+        ));
+
+        // A config ending in `Host *` gets the block above it
+        let mut config = SshConfig::parse("Host work\n    User deploy\n\nHost *\n    User nobody\n");
+        config.add_host(&entry).unwrap();
+        assert_eq!(
+            config.render(),
+            "Host work\n    User deploy\n\nHost pi\n    User pi\n\nHost *\n    User nobody\n"
+        );
+    }
+
+    #[test]
+    fn test_validate_fields_and_identity_file() {
+        let dir = tempdir().unwrap();
+        let key = dir.path().join("id_ed25519");
+        fs::write(&key, "key").unwrap();
+        fs::set_permissions(&key, fs::Permissions::from_mode(0o644)).unwrap();
+
+        let mut entry = HostEntry {
+            alias: "box".to_string(),
+            identity_file: key.display().to_string(),
+            ..HostEntry::default()
+        };
+        assert!(entry.validate().unwrap_err().to_string().contains("chmod 600"));
+
+        fs::set_permissions(&key, fs::Permissions::from_mode(0o600)).unwrap();
+        assert!(entry.validate().is_ok());
+
+        entry.port = "70000".to_string();
+        assert!(entry.validate().is_err());
+        entry.port = "22".to_string();
+        entry.alias = "two words".to_string();
+        assert!(entry.validate().is_err());
+        entry.alias = "box".to_string();
+        entry.identity_file = dir.path().join("missing").display().to_string();
+        assert!(entry.validate().is_err());
+    }
+}
@@ -0,0 +1,366 @@
+use crate::features::ssh_config::{self, HostBlock, HostEntry, SshConfig};
+use crate::utils::backup::BackupStore;
+use crate::utils::{shell, ModeError, Result, ShellType};
+use std::path::PathBuf;
+
+/// SSH Config Manager state machine
+#[derive(Debug, Clone)]
+pub enum SshManagerState {
+    /// Browsing `Host` blocks
+    Listing { selected: usize },
+    /// Filling in the add/edit form
+    Editing { form: HostForm },
+    /// Confirming the form before writing the config
+    Confirming { form: HostForm },
+    /// Confirming removal of a host
+    ConfirmingDelete { alias: String },
+    /// Success state with message
+    Success { message: String },
+    /// Error state with message
+    Error { message: String },
+}
+
+/// Add/edit form for a `Host` block
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostForm {
+    /// Host being edited, or None when adding
+    pub original: Option<String>,
+    pub entry: HostEntry,
+    /// Index of the focused field in [`HostEntry::FIELDS`]
+    pub field: usize,
+    /// Validation problem from the last submit
+    pub error: Option<String>,
+}
+
+/// SSH Config Manager feature
+#[derive(Debug)]
+pub struct SshManager {
+    pub state: SshManagerState,
+    config: Option<SshConfig>,
+    /// `ssh` command for the host the user chose to connect to
+    exit_command: Option<String>,
+    /// Shell the exit command is written for
+    shell: ShellType,
+    /// Where `~/.ssh/config` is backed up before each write
+    backups: Option<BackupStore>,
+}
+
+impl SshManager {
+    /// Creates a new SSH Config Manager
+    pub fn new() -> Self {
+        Self {
+            state: SshManagerState::Listing { selected: 0 },
+            config: None,
+            exit_command: None,
+            shell: ShellType::Bash,
+            backups: None,
+        }
+    }
+
+    /// Initializes the manager by loading `~/.ssh/config`
+    pub fn initialize(&mut self) -> Result<()> {
+        self.config = Some(SshConfig::load()?);
+        self.backups = Some(BackupStore::open()?);
+        self.shell = shell::detect_shell().unwrap_or(ShellType::Bash);
+        Ok(())
+    }
+
+    /// Returns the `Host` blocks in file order
+    pub fn hosts(&self) -> Vec<&HostBlock> {
+        self.config.as_ref().map(|c| c.hosts()).unwrap_or_default()
+    }
+
+    /// Returns the host highlighted in the list, if any
+    pub fn selected_host(&self) -> Option<&HostBlock> {
+        match self.state {
+            SshManagerState::Listing { selected } => self.hosts().get(selected).copied(),
+            _ => None,
+        }
+    }
+
+    /// Returns to the list, focusing the named host if it still exists
+    pub fn show_list(&mut self, focus: Option<&str>) {
+        let selected = focus
+            .and_then(|alias| self.hosts().iter().position(|h| h.alias() == alias))
+            .unwrap_or(0);
+        self.state = SshManagerState::Listing { selected };
+    }
+
+    /// Moves selection up
+    pub fn previous(&mut self) {
+        let total = self.hosts().len();
+        if let SshManagerState::Listing { selected } = &mut self.state {
+            if total > 0 {
+                *selected = if *selected == 0 { total - 1 } else { *selected - 1 };
+            }
+        }
+    }
+
+    /// Moves selection down
+    pub fn next(&mut self) {
+        let total = self.hosts().len();
+        if let SshManagerState::Listing { selected } = &mut self.state {
+            if total > 0 {
+                *selected = (*selected + 1) % total;
+            }
+        }
+    }
+
+    /// Opens an empty form for a new host
+    pub fn start_add(&mut self) {
+        self.state = SshManagerState::Editing {
+            form: HostForm {
+                original: None,
+                entry: HostEntry::default(),
+                field: 0,
+                error: None,
+            },
+        };
+    }
+
+    /// Opens the form for the selected host
+    pub fn start_edit(&mut self) {
+        if let Some(host) = self.selected_host() {
+            let entry = HostEntry::from_block(host);
+            self.state = SshManagerState::Editing {
+                form: HostForm {
+                    original: Some(entry.alias.clone()),
+                    entry,
+                    field: 0,
+                    error: None,
+                },
+            };
+        }
+    }
+
+    /// Starts removing the selected host
+    pub fn start_delete(&mut self) {
+        if let Some(host) = self.selected_host() {
+            self.state = SshManagerState::ConfirmingDelete { alias: host.alias() };
+        }
+    }
+
+    /// Connects to the selected host
+    ///
+    /// Returns true when the app should exit so the shell wrapper can run `ssh`
+    pub fn connect(&mut self) -> bool {
+        let Some(host) = self.selected_host() else {
+            return false;
+        };
+
+        if !host.is_concrete() {
+            self.state = SshManagerState::Error {
+                message: format!(
+                    "'{}' is a pattern that applies to other hosts and can't be connected to directly",
+                    host.alias()
+                ),
+            };
+            return false;
+        }
+
+        self.exit_command = Some(format!("ssh {}", self.shell.quote(&host.alias())));
+        true
+    }
+
+    /// Moves focus to the next form field
+    pub fn next_field(&mut self) {
+        if let SshManagerState::Editing { form } = &mut self.state {
+            form.field = (form.field + 1) % HostEntry::FIELDS.len();
+        }
+    }
+
+    /// Moves focus to the previous form field
+    pub fn previous_field(&mut self) {
+        if let SshManagerState::Editing { form } = &mut self.state {
+            form.field = form.field.checked_sub(1).unwrap_or(HostEntry::FIELDS.len() - 1);
+        }
+    }
+
+    /// Handles input for the focused form field
+    pub fn handle_char(&mut self, c: char) {
+        if let SshManagerState::Editing { form } = &mut self.state {
+            form.entry.field_mut(form.field).push(c);
+        }
+    }
+
+    /// Handles backspace
+    pub fn handle_backspace(&mut self) {
+        if let SshManagerState::Editing { form } = &mut self.state {
+            form.entry.field_mut(form.field).pop();
+        }
+    }
+
+    /// Moves to the next field, or validates the form on the last one
+    pub fn advance(&mut self) {
+        match self.state.clone() {
+            SshManagerState::Editing { form } => {
+                if form.field + 1 < HostEntry::FIELDS.len() {
+                    self.next_field();
+                } else {
+                    self.submit_form();
+                }
+            }
+            SshManagerState::Confirming { form } => self.save_form(form),
+            SshManagerState::ConfirmingDelete { alias } => {
+                let result = self.with_config(|config| config.remove_host(&alias));
+                self.state = match result {
+                    Ok(backup) => SshManagerState::Success {
+                        message: with_backup(format!("✓ Host '{}' removed", alias), backup),
+                    },
+                    Err(e) => SshManagerState::Error {
+                        message: e.to_string(),
+                    },
+                };
+            }
+            _ => {}
+        }
+    }
+
+    /// Validates the form, keeping the user in it when something is wrong
+    pub fn submit_form(&mut self) {
+        let SshManagerState::Editing { mut form } = self.state.clone() else {
+            return;
+        };
+
+        match form.entry.validate() {
+            Ok(()) => {
+                form.error = None;
+                self.state = SshManagerState::Confirming { form };
+            }
+            Err(e) => {
+                form.error = Some(e.to_string());
+                // Focus the field that failed
+                if let Some(field) = error_field(&form.entry) {
+                    form.field = field;
+                }
+                self.state = SshManagerState::Editing { form };
+            }
+        }
+    }
+
+    /// Writes the confirmed form to the config
+    fn save_form(&mut self, form: HostForm) {
+        let alias = form.entry.alias.trim().to_string();
+        let result = self.with_config(|config| match &form.original {
+            Some(original) => config.update_host(original, &form.entry),
+            None => config.add_host(&form.entry),
+        });
+
+        self.state = match result {
+            Ok(backup) => {
+                let verb = if form.original.is_some() { "updated" } else { "added" };
+                SshManagerState::Success {
+                    message: with_backup(format!("✓ Host '{}' {}", alias, verb), backup),
+                }
+            }
+            Err(e) => SshManagerState::Error {
+                message: e.to_string(),
+            },
+        };
+    }
+
+    /// Applies a change to a freshly loaded config and saves it
+    ///
+    /// Reloading first means edits made outside mode since the list was
+    /// opened aren't overwritten. Returns the backup path, if any.
+    fn with_config(&mut self, op: impl FnOnce(&mut SshConfig) -> Result<()>) -> Result<Option<PathBuf>> {
+        let store = self
+            .backups
+            .as_ref()
+            .ok_or_else(|| ModeError::Config("backup store has not been opened".to_string()))?;
+        let mut config = SshConfig::load()?;
+        op(&mut config)?;
+        let backup = config.save(store)?;
+        self.config = Some(config);
+        Ok(backup)
+    }
+
+    /// Goes back to the previous state
+    pub fn go_back(&mut self) {
+        match self.state.clone() {
+            SshManagerState::Confirming { form } => {
+                self.state = SshManagerState::Editing { form };
+            }
+            SshManagerState::Editing { form } => self.show_list(form.original.as_deref()),
+            SshManagerState::ConfirmingDelete { alias } => self.show_list(Some(&alias)),
+            _ => self.show_list(None),
+        }
+    }
+
+    /// Returns whether the feature is done (success or error)
+    pub fn is_done(&self) -> bool {
+        matches!(
+            self.state,
+            SshManagerState::Success { .. } | SshManagerState::Error { .. }
+        )
+    }
+
+    /// Gets the prompt text for the current state
+    pub fn get_prompt(&self) -> String {
+        match &self.state {
+            SshManagerState::Listing { .. } => match &self.config {
+                Some(config) => format!("{} hosts in {}", self.hosts().len(), config.path().display()),
+                None => format!("{} hosts", self.hosts().len()),
+            },
+            SshManagerState::Editing { form } | SshManagerState::Confirming { form } => match &form.original {
+                Some(alias) => format!("Edit host '{}'", alias),
+                None => "Add host".to_string(),
+            },
+            SshManagerState::ConfirmingDelete { alias } => format!("Remove host '{}'?", alias),
+            SshManagerState::Success { message } => message.clone(),
+            SshManagerState::Error { message } => format!("Error: {}", message),
+        }
+    }
+
+    /// Gets the form fields to confirm as (label, value) pairs, skipping unset ones
+    pub fn get_confirmation_data(&self) -> Option<Vec<(&'static str, String)>> {
+        match &self.state {
+            SshManagerState::Confirming { form } => Some(
+                HostEntry::FIELDS
+                    .iter()
+                    .enumerate()
+                    .map(|(i, label)| (*label, form.entry.field(i).trim().to_string()))
+                    .filter(|(_, value)| !value.is_empty())
+                    .collect(),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Returns the `ssh` command for the host the user connected to
+    pub fn get_exit_command(&self) -> Option<String> {
+        self.exit_command.clone()
+    }
+}
+
+impl Default for SshManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Describes why a host's identity file can't be used, if it can't
+pub fn identity_problem(host: &HostBlock) -> Option<String> {
+    let identity_file = host.get("IdentityFile")?;
+    ssh_config::check_identity_file(&identity_file).err().map(|e| e.to_string())
+}
+
+/// Returns the index of the field that fails validation, for the field checks
+/// that can be pinned to one field
+fn error_field(entry: &HostEntry) -> Option<usize> {
+    (0..HostEntry::FIELDS.len()).find(|&i| {
+        let mut only = HostEntry {
+            alias: "x".to_string(),
+            ..HostEntry::default()
+        };
+        *only.field_mut(i) = entry.field(i).to_string();
+        only.validate().is_err()
+    })
+}
+
+fn with_backup(message: String, backup: Option<PathBuf>) -> String {
+    match backup {
+        Some(path) => format!("{}\n\nBackup: {}", message, path.display()),
+        None => message,
+    }
+}
@@ -0,0 +1,465 @@
+use crate::utils::procfs;
+use std::collections::{HashSet, VecDeque};
+use std::ffi::CString;
+use std::fs;
+use std::time::{Duration, Instant};
+
+/// How often CPU, memory and load are sampled
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How often mounted filesystems are re-read (statvfs can be slow on network mounts)
+const FS_REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Number of samples kept for the sparklines
+pub const HISTORY_LEN: usize = 120;
+
+/// Filesystem types that don't represent storage
+const PSEUDO_FILESYSTEMS: &[&str] = &[
+    "autofs", "binfmt_misc", "bpf", "cgroup", "cgroup2", "configfs", "debugfs", "devpts", "devtmpfs", "efivarfs",
+    "fusectl", "hugetlbfs", "mqueue", "nsfs", "proc", "pstore", "ramfs", "rpc_pipefs", "securityfs", "selinuxfs",
+    "squashfs", "sysfs", "tmpfs", "tracefs",
+];
+
+/// System Info state machine
+#[derive(Debug, Clone)]
+pub enum SystemInfoState {
+    /// Showing the live dashboard
+    Dashboard,
+    /// The system can't be read (e.g. no `/proc`)
+    Error { message: String },
+}
+
+/// Cumulative CPU time counters from one `/proc/stat` line
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CpuTimes {
+    pub total: u64,
+    /// Idle plus iowait
+    pub idle: u64,
+}
+
+impl CpuTimes {
+    /// Returns the busy percentage between an earlier sample and this one
+    pub fn usage_since(&self, earlier: &CpuTimes) -> f64 {
+        let total = self.total.saturating_sub(earlier.total);
+        let idle = self.idle.saturating_sub(earlier.idle);
+        if total == 0 {
+            0.0
+        } else {
+            (total.saturating_sub(idle) as f64 / total as f64 * 100.0).clamp(0.0, 100.0)
+        }
+    }
+}
+
+/// Memory figures from `/proc/meminfo`, in bytes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryInfo {
+    pub total: u64,
+    pub available: u64,
+    pub swap_total: u64,
+    pub swap_free: u64,
+}
+
+impl MemoryInfo {
+    /// Returns memory in use (total minus available)
+    pub fn used(&self) -> u64 {
+        self.total.saturating_sub(self.available)
+    }
+
+    /// Returns swap in use
+    pub fn swap_used(&self) -> u64 {
+        self.swap_total.saturating_sub(self.swap_free)
+    }
+}
+
+/// Load averages from `/proc/loadavg`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LoadAverage {
+    pub one: f64,
+    pub five: f64,
+    pub fifteen: f64,
+    pub running: u32,
+    pub total: u32,
+}
+
+/// Space on one mounted filesystem, in bytes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilesystemUsage {
+    pub mount_point: String,
+    pub device: String,
+    pub fs_type: String,
+    pub total: u64,
+    pub used: u64,
+    /// Space available to unprivileged users
+    pub available: u64,
+}
+
+impl FilesystemUsage {
+    /// Returns the fraction of usable space that is taken, like `df`
+    pub fn ratio(&self) -> f64 {
+        let usable = self.used + self.available;
+        if usable == 0 {
+            0.0
+        } else {
+            self.used as f64 / usable as f64
+        }
+    }
+}
+
+/// Which Windows Subsystem for Linux the system runs under
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WslVersion {
+    Wsl1,
+    Wsl2,
+}
+
+impl WslVersion {
+    /// Returns the display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            WslVersion::Wsl1 => "WSL 1",
+            WslVersion::Wsl2 => "WSL 2",
+        }
+    }
+}
+
+/// System Info dashboard feature
+#[derive(Debug)]
+pub struct SystemInfo {
+    pub state: SystemInfoState,
+    pub hostname: String,
+    pub kernel: String,
+    pub wsl: Option<WslVersion>,
+    /// Seconds since boot
+    pub uptime: u64,
+    pub load: LoadAverage,
+    pub memory: MemoryInfo,
+    pub filesystems: Vec<FilesystemUsage>,
+    /// Busy percentage of each core over the last interval
+    pub core_usage: Vec<f64>,
+    /// Overall busy percentage, oldest first
+    pub cpu_history: VecDeque<u64>,
+    /// Memory in use as a percentage, oldest first
+    pub memory_history: VecDeque<u64>,
+    /// Previous counters: the aggregate line first, then one per core
+    previous_cpu: Vec<CpuTimes>,
+    last_refresh: Option<Instant>,
+    last_fs_refresh: Option<Instant>,
+}
+
+impl SystemInfo {
+    /// Creates a new System Info dashboard
+    pub fn new() -> Self {
+        let kernel = read_trimmed("/proc/sys/kernel/osrelease");
+
+        Self {
+            state: SystemInfoState::Dashboard,
+            hostname: read_trimmed("/proc/sys/kernel/hostname"),
+            wsl: detect_wsl(&kernel, std::env::var_os("WSL_DISTRO_NAME").is_some()),
+            kernel,
+            uptime: 0,
+            load: LoadAverage::default(),
+            memory: MemoryInfo::default(),
+            filesystems: Vec::new(),
+            core_usage: Vec::new(),
+            cpu_history: VecDeque::with_capacity(HISTORY_LEN),
+            memory_history: VecDeque::with_capacity(HISTORY_LEN),
+            previous_cpu: Vec::new(),
+            last_refresh: None,
+            last_fs_refresh: None,
+        }
+    }
+
+    /// Takes the first sample so the dashboard isn't empty on open
+    pub fn initialize(&mut self) {
+        if fs::metadata("/proc/stat").is_err() {
+            self.state = SystemInfoState::Error {
+                message: "System Info reads /proc, which isn't available on this system".to_string(),
+            };
+            return;
+        }
+        self.refresh();
+    }
+
+    /// Samples again once the refresh interval has passed
+    pub fn tick(&mut self) {
+        let stale = match self.last_refresh {
+            Some(last) => last.elapsed() >= REFRESH_INTERVAL,
+            None => true,
+        };
+
+        if matches!(self.state, SystemInfoState::Dashboard) && stale {
+            self.refresh();
+        }
+    }
+
+    /// Re-reads every source, keeping values from sources that fail
+    pub fn refresh(&mut self) {
+        if let Ok(content) = fs::read_to_string("/proc/stat") {
+            let current = parse_cpu_times(&content);
+            if let (Some(now), Some(before)) = (current.first(), self.previous_cpu.first()) {
+                push_sample(&mut self.cpu_history, now.usage_since(before).round() as u64);
+            }
+            self.core_usage = current
+                .iter()
+                .skip(1)
+                .zip(self.previous_cpu.iter().skip(1))
+                .map(|(now, before)| now.usage_since(before))
+                .collect();
+            self.previous_cpu = current;
+        }
+
+        if let Ok(content) = fs::read_to_string("/proc/meminfo") {
+            self.memory = parse_meminfo(&content);
+            if self.memory.total > 0 {
+                let percent = self.memory.used() as f64 / self.memory.total as f64 * 100.0;
+                push_sample(&mut self.memory_history, percent.round() as u64);
+            }
+        }
+
+        if let Some(load) = fs::read_to_string("/proc/loadavg").ok().and_then(|c| parse_loadavg(&c)) {
+            self.load = load;
+        }
+        if let Some(uptime) = fs::read_to_string("/proc/uptime").ok().and_then(|c| parse_uptime(&c)) {
+            self.uptime = uptime;
+        }
+
+        let fs_stale = match self.last_fs_refresh {
+            Some(last) => last.elapsed() >= FS_REFRESH_INTERVAL,
+            None => true,
+        };
+        if fs_stale {
+            if let Ok(content) = fs::read_to_string("/proc/mounts") {
+                self.filesystems = parse_mounts(&content)
+                    .into_iter()
+                    .filter_map(|(device, mount_point, fs_type)| statvfs(device, mount_point, fs_type))
+                    .collect();
+            }
+            self.last_fs_refresh = Some(Instant::now());
+        }
+
+        self.last_refresh = Some(Instant::now());
+    }
+}
+
+impl Default for SystemInfo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Appends a sample, dropping the oldest once the history is full
+fn push_sample(history: &mut VecDeque<u64>, value: u64) {
+    if history.len() == HISTORY_LEN {
+        history.pop_front();
+    }
+    history.push_back(value);
+}
+
+/// Reads a one-line file, returning an empty string if it can't be read
+fn read_trimmed(path: &str) -> String {
+    fs::read_to_string(path).map(|s| s.trim().to_string()).unwrap_or_default()
+}
+
+/// Detects WSL from the kernel release (`...-microsoft-standard-WSL2`, `...-Microsoft`)
+pub fn detect_wsl(kernel: &str, has_distro_env: bool) -> Option<WslVersion> {
+    let lower = kernel.to_lowercase();
+    if lower.contains("wsl2") || lower.contains("microsoft-standard") {
+        Some(WslVersion::Wsl2)
+    } else if lower.contains("microsoft") || has_distro_env {
+        Some(WslVersion::Wsl1)
+    } else {
+        None
+    }
+}
+
+/// Parses the aggregate `cpu` line followed by each `cpuN` line of `/proc/stat`
+fn parse_cpu_times(content: &str) -> Vec<CpuTimes> {
+    content
+        .lines()
+        .filter(|line| line.starts_with("cpu"))
+        .map(|line| {
+            let values: Vec<u64> = line
+                .split_whitespace()
+                .skip(1)
+                .filter_map(|value| value.parse().ok())
+                .collect();
+            // user nice system idle iowait irq softirq steal (guest time is already in user)
+            let total = values.iter().take(8).sum();
+            let idle = values.get(3).copied().unwrap_or(0) + values.get(4).copied().unwrap_or(0);
+            CpuTimes { total, idle }
+        })
+        .collect()
+}
+
+/// Reads total, available and swap figures from `/proc/meminfo`
+fn parse_meminfo(content: &str) -> MemoryInfo {
+    let kb = |key| procfs::parse_meminfo_kb(content, key).unwrap_or(0) * 1024;
+    MemoryInfo {
+        total: kb("MemTotal"),
+        available: kb("MemAvailable"),
+        swap_total: kb("SwapTotal"),
+        swap_free: kb("SwapFree"),
+    }
+}
+
+/// Parses `/proc/loadavg` (`0.52 0.58 0.59 2/1234 56789`)
+fn parse_loadavg(content: &str) -> Option<LoadAverage> {
+    let mut fields = content.split_whitespace();
+    let one = fields.next()?.parse().ok()?;
+    let five = fields.next()?.parse().ok()?;
+    let fifteen = fields.next()?.parse().ok()?;
+    let (running, total) = fields.next()?.split_once('/')?;
+
+    Some(LoadAverage {
+        one,
+        five,
+        fifteen,
+        running: running.parse().ok()?,
+        total: total.parse().ok()?,
+    })
+}
+
+/// Parses whole seconds since boot from `/proc/uptime`
+fn parse_uptime(content: &str) -> Option<u64> {
+    let seconds: f64 = content.split_whitespace().next()?.parse().ok()?;
+    Some(seconds as u64)
+}
+
+/// Returns `(device, mount point, type)` for each storage mount in `/proc/mounts`
+///
+/// Pseudo filesystems are skipped, as are repeat mounts of the same device
+fn parse_mounts(content: &str) -> Vec<(String, String, String)> {
+    let mut seen = HashSet::new();
+
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let device = unescape_mount_field(fields.next()?);
+            let mount_point = unescape_mount_field(fields.next()?);
+            let fs_type = fields.next()?.to_string();
+            Some((device, mount_point, fs_type))
+        })
+        .filter(|(_, _, fs_type)| !PSEUDO_FILESYSTEMS.contains(&fs_type.as_str()))
+        .filter(|(device, _, _)| seen.insert(device.clone()))
+        .collect()
+}
+
+/// Decodes the octal escapes (`\040` for space) the kernel uses in `/proc/mounts`
+fn unescape_mount_field(field: &str) -> String {
+    let mut out = String::new();
+    let mut chars = field.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            let digits: String = chars.clone().take(3).collect();
+            if digits.len() == 3 {
+                if let Ok(code) = u8::from_str_radix(&digits, 8) {
+                    out.push(code as char);
+                    chars.nth(2);
+                    continue;
+                }
+            }
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Measures a mounted filesystem, skipping ones that report no size
+fn statvfs(device: String, mount_point: String, fs_type: String) -> Option<FilesystemUsage> {
+    let path = CString::new(mount_point.as_bytes()).ok()?;
+    // SAFETY: zeroed statvfs is a valid out-parameter and path is NUL-terminated
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+
+    let fragment = stat.f_frsize as u64;
+    let total = stat.f_blocks as u64 * fragment;
+    if total == 0 {
+        return None;
+    }
+
+    Some(FilesystemUsage {
+        mount_point,
+        device,
+        fs_type,
+        total,
+        used: (stat.f_blocks as u64).saturating_sub(stat.f_bfree as u64) * fragment,
+        available: stat.f_bavail as u64 * fragment,
+    })
+}
+
+/// Formats seconds since boot as `3d 4h 12m`
+pub fn format_uptime(seconds: u64) -> String {
+    let days = seconds / 86_400;
+    let hours = seconds % 86_400 / 3600;
+    let minutes = seconds % 3600 / 60;
+
+    if days > 0 {
+        format!("{}d {}h {}m", days, hours, minutes)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cpu_usage_between_samples() {
+        let before = parse_cpu_times("cpu  100 0 100 700 100 0 0 0 0 0\ncpu0 50 0 50 350 50 0 0 0 0 0\nintr 1 2\n");
+        let after = parse_cpu_times("cpu  200 0 200 1400 200 0 0 0 0 0\ncpu0 150 0 50 350 50 0 0 0 0 0\n");
+
+        assert_eq!(before.len(), 2);
+        assert_eq!(before[0], CpuTimes { total: 1000, idle: 800 });
+        assert!((after[0].usage_since(&before[0]) - 20.0).abs() < 1e-9);
+        assert!((after[1].usage_since(&before[1]) - 100.0).abs() < 1e-9);
+        assert_eq!(after[0].usage_since(&after[0]), 0.0);
+    }
+
+    #[test]
+    fn test_parse_meminfo_loadavg_and_uptime() {
+        let memory = parse_meminfo("MemTotal: 1000 kB\nMemFree: 100 kB\nMemAvailable: 250 kB\nSwapTotal: 0 kB\nSwapFree: 0 kB\n");
+        assert_eq!(memory.used(), 750 * 1024);
+        assert_eq!(memory.swap_used(), 0);
+
+        let load = parse_loadavg("0.52 0.58 0.59 2/1234 56789\n").unwrap();
+        assert_eq!((load.one, load.fifteen, load.running, load.total), (0.52, 0.59, 2, 1234));
+        assert!(parse_loadavg("garbage").is_none());
+
+        assert_eq!(parse_uptime("273849.12 1000.0\n"), Some(273849));
+        assert_eq!(format_uptime(273849), "3d 4h 4m");
+        assert_eq!(format_uptime(59), "0m");
+    }
+
+    #[test]
+    fn test_parse_mounts_skips_pseudo_and_repeated_devices() {
+        let mounts = "\
+proc /proc proc rw 0 0
+/dev/sda1 / ext4 rw 0 0
+tmpfs /run tmpfs rw 0 0
+/dev/sdb1 /mnt/my\\040disk ext4 rw 0 0
+/dev/sda1 /var/lib/docker ext4 rw 0 0
+";
+        let parsed = parse_mounts(mounts);
+        assert_eq!(
+            parsed,
+            vec![
+                ("/dev/sda1".to_string(), "/".to_string(), "ext4".to_string()),
+                ("/dev/sdb1".to_string(), "/mnt/my disk".to_string(), "ext4".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_detect_wsl() {
+        assert_eq!(detect_wsl("5.15.153.1-microsoft-standard-WSL2", false), Some(WslVersion::Wsl2));
+        assert_eq!(detect_wsl("4.4.0-19041-Microsoft", false), Some(WslVersion::Wsl1));
+        assert_eq!(detect_wsl("6.8.0-45-generic", false), None);
+        assert_eq!(detect_wsl("6.8.0-45-generic", true), Some(WslVersion::Wsl1));
+    }
+}
@@ -39,18 +39,15 @@ impl UsageViewer {
             // On WSL, use PowerShell to open browser
             // First try powershell.exe (WSL), then fallback to xdg-open (native Linux)
             Command::new("powershell.exe")
-                .args(&["Start-Process", url])
+                .args(["Start-Process", url])
                 .output()
                 .or_else(|_| Command::new("xdg-open").arg(url).output())
         } else if cfg!(target_os = "macos") {
             Command::new("open").arg(url).output()
         } else if cfg!(target_os = "windows") {
-            Command::new("cmd").args(&["/c", "start", url]).output()
+            Command::new("cmd").args(["/c", "start", url]).output()
         } else {
-            Err(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                "Unsupported platform",
-            ))
+            Err(std::io::Error::other("Unsupported platform"))
         };
 
         match result {
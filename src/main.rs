@@ -2,11 +2,40 @@ use crossterm::{
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use mode::{App, EventHandler};
+use mode::{
+    cli::{self, Command},
+    utils, App, EventHandler,
+};
 use ratatui::{backend::CrosstermBackend, Terminal};
-use std::{io, time::Duration};
+use std::{io, process, time::Duration};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Run headless subcommands without touching the terminal
+    match cli::parse_args(std::env::args().skip(1)) {
+        Ok(Command::Tui) => run_tui(),
+        Ok(command) => match cli::run(command) {
+            Ok(outcome) => {
+                if !outcome.output.is_empty() {
+                    println!("{}", outcome.output);
+                }
+                if let Some(exit_cmd) = &outcome.exit_command {
+                    let _ = utils::write_exit_command(exit_cmd);
+                }
+                Ok(())
+            }
+            Err(e) => {
+                eprintln!("mode: {}", e);
+                process::exit(e.exit_code());
+            }
+        },
+        Err(e) => {
+            eprintln!("mode: {}\n\n{}", e, cli::USAGE);
+            process::exit(e.exit_code());
+        }
+    }
+}
+
+fn run_tui() -> Result<(), Box<dyn std::error::Error>> {
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -33,6 +62,7 @@ fn run(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<(), Box<
 
     // Create event handler with 250ms tick rate
     let event_handler = EventHandler::new(Duration::from_millis(250));
+    app.set_event_sender(event_handler.sender());
 
     // Main event loop
     loop {
@@ -52,13 +82,7 @@ fn run(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<(), Box<
 
     // If there's an exit command, write it to a file for shell integration
     if let Some(exit_cmd) = &app.exit_command {
-        use std::io::Write;
-        if let Ok(home) = std::env::var("HOME") {
-            let cmd_file = format!("{}/.mode_exit_cmd", home);
-            if let Ok(mut file) = std::fs::File::create(&cmd_file) {
-                let _ = writeln!(file, "{}", exit_cmd);
-            }
-        }
+        let _ = utils::write_exit_command(exit_cmd);
     }
 
     Ok(())
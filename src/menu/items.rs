@@ -5,23 +5,25 @@ pub enum MenuItem {
     AliasManager,
     /// Process Manager - Active feature for killing lingering processes
     ProcessManager,
-    /// Bookmark - Active feature for temporary directory bookmarking
+    /// Bookmark - Active feature for named directory bookmarks
     Bookmark,
     /// Usage Viewer - Active feature for viewing Claude API usage
     UsageViewer,
     /// Scanner - Active feature for port scanning
     Scanner,
-    /// Environment Variables Manager - Coming soon
+    /// Environment Variables Manager - Active feature for persistent exports
     EnvManager,
-    /// PATH Manager - Coming soon
+    /// PATH Manager - Active feature for reordering and cleaning up PATH
     PathManager,
-    /// SSH Config Manager - Coming soon
+    /// SSH Config Manager - Active feature for editing ~/.ssh/config hosts
     SshManager,
-    /// Git Shortcuts - Coming soon
+    /// Git Shortcuts - Active feature for gitconfig aliases and templates
     GitShortcuts,
-    /// System Info Display - Coming soon
+    /// System Info Display - Active feature for a live resource dashboard
     SystemInfo,
-    /// Settings - Coming soon
+    /// Backups - Active feature for browsing, diffing and restoring backups
+    Backups,
+    /// Settings - Active feature for editing config.toml
     Settings,
 }
 
@@ -36,12 +38,12 @@ impl MenuItem {
             MenuItem::Bookmark,
             MenuItem::UsageViewer,
             MenuItem::Scanner,
-            // Coming soon
             MenuItem::EnvManager,
             MenuItem::PathManager,
             MenuItem::SshManager,
             MenuItem::GitShortcuts,
             MenuItem::SystemInfo,
+            MenuItem::Backups,
             MenuItem::Settings,
         ]
     }
@@ -51,38 +53,63 @@ impl MenuItem {
         match self {
             MenuItem::AliasManager => "Alias Manager",
             MenuItem::ProcessManager => "Process Manager",
-            MenuItem::Bookmark => "Bookmark Directory",
+            MenuItem::Bookmark => "Bookmarks",
             MenuItem::UsageViewer => "Usage Viewer",
             MenuItem::Scanner => "Port Scanner",
-            MenuItem::EnvManager => "Coming soon",
-            MenuItem::PathManager => "Coming soon",
-            MenuItem::SshManager => "Coming soon",
-            MenuItem::GitShortcuts => "Coming soon",
-            MenuItem::SystemInfo => "Coming soon",
-            MenuItem::Settings => "Coming soon",
+            MenuItem::EnvManager => "Env Variables",
+            MenuItem::PathManager => "PATH Manager",
+            MenuItem::SshManager => "SSH Hosts",
+            MenuItem::GitShortcuts => "Git Shortcuts",
+            MenuItem::SystemInfo => "System Info",
+            MenuItem::Backups => "Backups",
+            MenuItem::Settings => "Settings",
         }
     }
 
+    /// Returns the identifier used for the item in `config.toml`
+    pub fn slug(&self) -> &'static str {
+        match self {
+            MenuItem::AliasManager => "aliases",
+            MenuItem::ProcessManager => "processes",
+            MenuItem::Bookmark => "bookmarks",
+            MenuItem::UsageViewer => "usage",
+            MenuItem::Scanner => "scanner",
+            MenuItem::EnvManager => "env",
+            MenuItem::PathManager => "path",
+            MenuItem::SshManager => "ssh",
+            MenuItem::GitShortcuts => "git",
+            MenuItem::SystemInfo => "system-info",
+            MenuItem::Backups => "backups",
+            MenuItem::Settings => "settings",
+        }
+    }
+
+    /// Looks up a menu item by its config identifier
+    pub fn from_slug(slug: &str) -> Option<MenuItem> {
+        Self::all().into_iter().find(|item| item.slug() == slug)
+    }
+
     /// Returns the description of the menu item
     pub fn description(&self) -> &'static str {
         match self {
             MenuItem::AliasManager => "Create and manage shell aliases with validation and backups",
             MenuItem::ProcessManager => "Kill lingering dev servers (cursor, claude-code, vite, etc.)",
-            MenuItem::Bookmark => "Save and navigate to current directory using 'temp' alias",
+            MenuItem::Bookmark => "Save named directories and jump back to them instantly",
             MenuItem::UsageViewer => "Open Claude API usage dashboard in your browser",
             MenuItem::Scanner => "Scan network ports with service detection and custom ranges",
-            MenuItem::EnvManager => "View, edit, and manage environment variables",
-            MenuItem::PathManager => "Organize and clean up your PATH with validation",
-            MenuItem::SshManager => "Manage SSH host configurations and connection settings",
+            MenuItem::EnvManager => "Browse the environment and manage exports in your RC file",
+            MenuItem::PathManager => "Reorder and clean up PATH, flagging missing and shadowing entries",
+            MenuItem::SshManager => "Edit Host entries in ~/.ssh/config and connect to them",
             MenuItem::GitShortcuts => "Create git aliases and shortcuts with templates",
             MenuItem::SystemInfo => "Monitor CPU, memory, disk usage and system resources",
-            MenuItem::Settings => "Customize themes, shortcuts, and app preferences",
+            MenuItem::Backups => "Diff and restore the backups mode made of your config files",
+            MenuItem::Settings => "Theme, scanner defaults, backups and which tools the menu shows",
         }
     }
 
     /// Returns whether the menu item is active (implemented)
     pub fn is_active(&self) -> bool {
-        matches!(self, MenuItem::AliasManager | MenuItem::ProcessManager | MenuItem::Bookmark | MenuItem::UsageViewer | MenuItem::Scanner)
+        matches!(self, MenuItem::AliasManager | MenuItem::ProcessManager | MenuItem::Bookmark | MenuItem::UsageViewer | MenuItem::Scanner | MenuItem::EnvManager | MenuItem::PathManager | MenuItem::SshManager | MenuItem::GitShortcuts | MenuItem::SystemInfo | MenuItem::Backups | MenuItem::Settings)
     }
 
     /// Returns the total number of menu items
@@ -7,17 +7,37 @@ pub struct MenuState {
     selected: usize,
     /// Total number of menu items
     items_count: usize,
+    /// Items shown, in display order
+    items: Vec<MenuItem>,
 }
 
 impl MenuState {
     /// Creates a new menu state
     pub fn new() -> Self {
+        Self::with_hidden(&[])
+    }
+
+    /// Creates a menu state that leaves out the given items
+    ///
+    /// Settings is always shown so hidden items can be brought back.
+    pub fn with_hidden(hidden: &[MenuItem]) -> Self {
+        let items: Vec<MenuItem> = MenuItem::all()
+            .into_iter()
+            .filter(|item| *item == MenuItem::Settings || !hidden.contains(item))
+            .collect();
+
         Self {
             selected: 0,
-            items_count: MenuItem::count(),
+            items_count: items.len(),
+            items,
         }
     }
 
+    /// Returns the items shown, in display order
+    pub fn items(&self) -> &[MenuItem] {
+        &self.items
+    }
+
     /// Returns the currently selected index
     pub fn selected(&self) -> usize {
         self.selected
@@ -25,7 +45,7 @@ impl MenuState {
 
     /// Returns the currently selected menu item
     pub fn selected_item(&self) -> MenuItem {
-        MenuItem::all()[self.selected]
+        self.items[self.selected]
     }
 
     /// Moves selection up
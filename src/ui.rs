@@ -1,23 +1,25 @@
 use crate::{
     app::{ActiveFeature, App, AppState},
-    features::{AliasManagerState, BookmarkManagerState, PortState, ProcessAction, ProcessManagerState, ScanOption, ScanType, ScannerState, UsageViewerState},
+    features::{scanner::count_open_ports, ssh_manager, HostEntry, AliasManagerState, BackupsState, BookmarkManagerState, EnvManagerState, GitShortcutsState, PathManagerState, PortState, PresetSource, ProcessManager, ProcessManagerState, SortColumn, ScanOption, ScanType, ScannerState, SettingsField, SettingsState, SshManagerState, SystemInfoState, TemplateStatus, UsageViewerState},
     ui_components::{input_dialog, menu_view, theme::Theme},
 };
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph},
+    widgets::{Block, Borders, Cell, Gauge, LineGauge, List, ListItem, ListState, Paragraph, Row, Sparkline, Table, TableState},
     Frame,
 };
 
 /// Renders the UI based on application state
 pub fn render(frame: &mut Frame, app: &App) {
+    let theme = &app.theme;
+
     match &app.state {
         AppState::MainMenu => {
-            menu_view::render_menu(frame, frame.area(), &app.menu_state);
+            menu_view::render_menu(frame, theme, frame.area(), &app.menu_state, app.error_message.as_deref());
         }
         AppState::FeatureActive(feature) => {
-            render_feature(frame, feature);
+            render_feature(frame, theme, feature);
         }
         AppState::Exiting => {
             // Could show exit message, but app will close immediately
@@ -26,26 +28,48 @@ pub fn render(frame: &mut Frame, app: &App) {
 }
 
 /// Renders the active feature
-fn render_feature(frame: &mut Frame, feature: &ActiveFeature) {
+fn render_feature(frame: &mut Frame, theme: &Theme, feature: &ActiveFeature) {
     match feature {
         ActiveFeature::AliasManager(manager) => {
-            render_alias_manager(frame, manager);
+            render_alias_manager(frame, theme, manager);
         }
         ActiveFeature::ProcessManager(manager) => {
-            render_process_manager(frame, manager);
+            render_process_manager(frame, theme, manager);
         }
         ActiveFeature::BookmarkManager(manager) => {
-            render_bookmark_manager(frame, manager);
+            render_bookmark_manager(frame, theme, manager);
         }
         ActiveFeature::UsageViewer(viewer) => {
-            render_usage_viewer(frame, viewer);
+            render_usage_viewer(frame, theme, viewer);
         }
         ActiveFeature::Scanner(scanner) => {
-            render_scanner(frame, scanner);
+            render_scanner(frame, theme, scanner);
+        }
+        ActiveFeature::EnvManager(manager) => {
+            render_env_manager(frame, theme, manager);
+        }
+        ActiveFeature::PathManager(manager) => {
+            render_path_manager(frame, theme, manager);
+        }
+        ActiveFeature::SshManager(manager) => {
+            render_ssh_manager(frame, theme, manager);
+        }
+        ActiveFeature::GitShortcuts(shortcuts) => {
+            render_git_shortcuts(frame, theme, shortcuts);
+        }
+        ActiveFeature::SystemInfo(info) => {
+            render_system_info(frame, theme, info);
+        }
+        ActiveFeature::Backups(backups) => {
+            render_backups(frame, theme, backups);
+        }
+        ActiveFeature::Settings(settings) => {
+            render_settings(frame, theme, settings);
         }
         ActiveFeature::Placeholder(placeholder) => {
             input_dialog::render_message_dialog(
                 frame,
+                theme,
                 frame.area(),
                 "Feature Not Available",
                 &placeholder.get_message(),
@@ -55,12 +79,2013 @@ fn render_feature(frame: &mut Frame, feature: &ActiveFeature) {
     }
 }
 
-/// Renders the alias manager based on its state
-fn render_alias_manager(frame: &mut Frame, manager: &crate::features::AliasManager) {
-    let area = frame.area();
+/// Renders the alias manager based on its state
+fn render_alias_manager(frame: &mut Frame, theme: &Theme, manager: &crate::features::AliasManager) {
+    let area = frame.area();
+
+    match &manager.state {
+        AliasManagerState::Listing { selected } => {
+            let selected_idx = *selected;
+            let aliases = manager.aliases();
+
+            // Create layout with prompt at top, alias list in middle, help at bottom
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .margin(2)
+                .constraints([
+                    Constraint::Length(3), // Prompt
+                    Constraint::Min(10),   // Alias list
+                    Constraint::Length(3), // Help text
+                ])
+                .split(area);
+
+            // Render prompt
+            let prompt = manager.get_prompt();
+            let prompt_paragraph = Paragraph::new(prompt)
+                .alignment(Alignment::Center)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title("Alias Manager")
+                        .title_style(theme.title())
+                        .border_style(theme.border()),
+                );
+            frame.render_widget(prompt_paragraph, chunks[0]);
+
+            let list_block = Block::default()
+                .borders(Borders::ALL)
+                .title("Aliases")
+                .title_style(theme.title())
+                .border_style(theme.border());
+
+            if aliases.is_empty() {
+                let empty = Paragraph::new("No aliases found. Press 'a' to create one.")
+                    .alignment(Alignment::Center)
+                    .style(theme.dim())
+                    .block(list_block);
+                frame.render_widget(empty, chunks[1]);
+            } else {
+                let name_width = aliases.iter().map(|a| a.name.len()).max().unwrap_or(0);
+
+                // Render alias list
+                let alias_items: Vec<ListItem> = aliases
+                    .iter()
+                    .enumerate()
+                    .map(|(i, alias)| {
+                        let is_selected = i == selected_idx;
+                        let prefix = if is_selected { "▸ " } else { "  " };
+
+                        let name_style = if is_selected {
+                            theme.menu_item_selected()
+                        } else {
+                            theme.menu_item_active()
+                        };
+
+                        let mut spans = vec![
+                            Span::styled(prefix, theme.accent()),
+                            Span::styled(format!("{:<width$}", alias.name, width = name_width), name_style),
+                            Span::styled("  = ", theme.dim()),
+                            Span::styled(alias.command.clone(), theme.text()),
+                        ];
+                        if alias.managed {
+                            spans.push(Span::styled("  [mode]", theme.dim()));
+                        }
+
+                        ListItem::new(Line::from(spans))
+                    })
+                    .collect();
+
+                let mut list_state = ListState::default();
+                list_state.select(Some(selected_idx));
+
+                let alias_list = List::new(alias_items).block(list_block);
+                frame.render_stateful_widget(alias_list, chunks[1], &mut list_state);
+            }
+
+            // Render help text
+            let help_text = Line::from(vec![
+                Span::styled("↑/↓", theme.accent()),
+                Span::styled(": Navigate  ", theme.help()),
+                Span::styled("a", theme.accent()),
+                Span::styled(": Add  ", theme.help()),
+                Span::styled("e", theme.accent()),
+                Span::styled(": Edit  ", theme.help()),
+                Span::styled("r", theme.accent()),
+                Span::styled(": Rename  ", theme.help()),
+                Span::styled("d", theme.accent()),
+                Span::styled(": Delete  ", theme.help()),
+                Span::styled("ESC", theme.accent()),
+                Span::styled(": Back", theme.help()),
+            ]);
+            let help_paragraph = Paragraph::new(help_text)
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::NONE));
+            frame.render_widget(help_paragraph, chunks[2]);
+        }
+        AliasManagerState::EnteringName { .. }
+        | AliasManagerState::EnteringCommand { .. }
+        | AliasManagerState::EditingCommand { .. }
+        | AliasManagerState::Renaming { .. } => {
+            // Create layout with dialog and help text
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(10),   // Dialog
+                    Constraint::Length(3), // Help text
+                ])
+                .split(area);
+
+            let prompt = manager.get_prompt();
+            let input = manager.get_input();
+            let cursor_pos = input.len();
+
+            input_dialog::render_input_dialog(
+                frame,
+                theme,
+                chunks[0],
+                "Alias Manager",
+                &prompt,
+                &input,
+                cursor_pos,
+                false,
+            );
+
+            // Render help text
+            let help_text = Line::from(vec![
+                Span::styled("Enter", theme.accent()),
+                Span::styled(": Continue  ", theme.help()),
+                Span::styled("ESC", theme.accent()),
+                Span::styled(": Cancel", theme.help()),
+            ]);
+            let help_paragraph = Paragraph::new(help_text)
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::NONE));
+            frame.render_widget(help_paragraph, chunks[1]);
+        }
+        AliasManagerState::Confirming { .. } => {
+            // Create layout with dialog and help text
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(10),   // Dialog
+                    Constraint::Length(3), // Help text
+                ])
+                .split(area);
+
+            if let Some((name, command)) = manager.get_confirmation_data() {
+                let key_info = vec![
+                    ("Alias Name", name.as_str()),
+                    ("Command", command.as_str()),
+                ];
+
+                input_dialog::render_confirmation_dialog(
+                    frame,
+                    theme,
+                    chunks[0],
+                    "Confirm Alias Creation",
+                    "Create New Alias",
+                    key_info,
+                    "Do you want to create this alias?",
+                );
+            }
+
+            // Render help text
+            let help_text = Line::from(vec![
+                Span::styled("Y", theme.accent()),
+                Span::styled(": Confirm  ", theme.help()),
+                Span::styled("N", theme.accent()),
+                Span::styled(": Cancel", theme.help()),
+            ]);
+            let help_paragraph = Paragraph::new(help_text)
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::NONE));
+            frame.render_widget(help_paragraph, chunks[1]);
+        }
+        AliasManagerState::ConfirmingDelete { .. } => {
+            // Create layout with dialog and help text
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(10),   // Dialog
+                    Constraint::Length(3), // Help text
+                ])
+                .split(area);
+
+            if let Some((name, command)) = manager.get_confirmation_data() {
+                let key_info = vec![
+                    ("Alias Name", name.as_str()),
+                    ("Command", command.as_str()),
+                ];
+
+                input_dialog::render_confirmation_dialog(
+                    frame,
+                    theme,
+                    chunks[0],
+                    "Confirm Alias Deletion",
+                    "Delete Alias",
+                    key_info,
+                    "Do you want to delete this alias? A backup will be created first.",
+                );
+            }
+
+            // Render help text
+            let help_text = Line::from(vec![
+                Span::styled("Y", theme.accent()),
+                Span::styled(": Delete  ", theme.help()),
+                Span::styled("N", theme.accent()),
+                Span::styled(": Cancel", theme.help()),
+            ]);
+            let help_paragraph = Paragraph::new(help_text)
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::NONE));
+            frame.render_widget(help_paragraph, chunks[1]);
+        }
+        AliasManagerState::Processing => {
+            let message = manager.get_prompt();
+            input_dialog::render_message_dialog(frame, theme, area, "Alias Manager", &message, false);
+        }
+        AliasManagerState::Success { .. } => {
+            // Create layout with dialog and help text
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(10),   // Dialog
+                    Constraint::Length(3), // Help text
+                ])
+                .split(area);
+
+            let message = manager.get_prompt();
+
+            input_dialog::render_message_dialog(
+                frame,
+                theme,
+                chunks[0],
+                "Success",
+                &message,
+                false,
+            );
+
+            // Render help text
+            let help_text = Line::from(vec![
+                Span::styled("Enter", theme.accent()),
+                Span::styled(" or ", theme.help()),
+                Span::styled("ESC", theme.accent()),
+                Span::styled(": Continue", theme.help()),
+            ]);
+            let help_paragraph = Paragraph::new(help_text)
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::NONE));
+            frame.render_widget(help_paragraph, chunks[1]);
+        }
+        AliasManagerState::Error { .. } => {
+            // Create layout with dialog and help text
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(10),   // Dialog
+                    Constraint::Length(3), // Help text
+                ])
+                .split(area);
+
+            let message = manager.get_prompt();
+
+            input_dialog::render_message_dialog(
+                frame,
+                theme,
+                chunks[0],
+                "Error",
+                &message,
+                true,
+            );
+
+            // Render help text
+            let help_text = Line::from(vec![
+                Span::styled("Enter", theme.accent()),
+                Span::styled(" or ", theme.help()),
+                Span::styled("ESC", theme.accent()),
+                Span::styled(": Continue", theme.help()),
+            ]);
+            let help_paragraph = Paragraph::new(help_text)
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::NONE));
+            frame.render_widget(help_paragraph, chunks[1]);
+        }
+    }
+}
+
+/// Renders the process manager based on its state
+fn render_process_manager(frame: &mut Frame, theme: &Theme, manager: &crate::features::ProcessManager) {
+    let area = frame.area();
+
+    match &manager.state {
+        ProcessManagerState::SelectingAction { selected } => {
+            // The process browser comes first, followed by the presets
+            let mut actions = vec![
+                (ProcessManager::BROWSE_NAME, ProcessManager::BROWSE_DESCRIPTION, false),
+                (ProcessManager::PORT_NAME, ProcessManager::PORT_DESCRIPTION, false),
+            ];
+            actions.extend(manager.actions().iter().map(|action| {
+                (
+                    action.name.as_str(),
+                    action.description.as_str(),
+                    action.source == PresetSource::Config,
+                )
+            }));
+            let selected_idx = *selected;
+
+            // Create layout with prompt at top, menu in middle, help at bottom
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .margin(2)
+                .constraints([
+                    Constraint::Length(3), // Prompt
+                    Constraint::Min(10),   // Action list
+                    Constraint::Length(3), // Help text
+                ])
+                .split(area);
+
+            // Render prompt
+            let prompt = manager.get_prompt();
+            let prompt_paragraph = Paragraph::new(prompt)
+                .alignment(Alignment::Center)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title("Process Manager")
+                        .title_style(theme.title())
+                        .border_style(theme.border()),
+                );
+            frame.render_widget(prompt_paragraph, chunks[0]);
+
+            // Render action list
+            let action_items: Vec<ListItem> = actions
+                .iter()
+                .enumerate()
+                .map(|(i, (name, description, custom))| {
+                    let is_selected = i == selected_idx;
+                    let prefix = if is_selected { "▸ " } else { "  " };
+
+                    let title_style = if is_selected {
+                        theme.menu_item_selected()
+                    } else {
+                        theme.menu_item_active()
+                    };
+
+                    let mut title_spans = vec![Span::styled(format!("{}{}", prefix, name), title_style)];
+                    if *custom {
+                        title_spans.push(Span::styled("  (config)", theme.dim()));
+                    }
+                    let title_line = Line::from(title_spans);
+
+                    let desc_line = Line::from(Span::styled(
+                        format!("    {}", description),
+                        theme.dim()
+                    ));
+
+                    ListItem::new(vec![title_line, desc_line])
+                })
+                .collect();
+
+            let action_list = List::new(action_items).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Select Action")
+                    .title_style(theme.title())
+                    .border_style(theme.border()),
+            );
+            frame.render_widget(action_list, chunks[1]);
+
+            // Render help text
+            let help_text = Line::from(vec![
+                Span::styled("↑/↓", theme.accent()),
+                Span::styled(": Navigate  ", theme.help()),
+                Span::styled("Enter", theme.accent()),
+                Span::styled(": Select  ", theme.help()),
+                Span::styled("ESC", theme.accent()),
+                Span::styled(": Cancel", theme.help()),
+            ]);
+            let help_paragraph = Paragraph::new(help_text)
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::NONE));
+            frame.render_widget(help_paragraph, chunks[2]);
+        }
+        ProcessManagerState::Browsing { selected } | ProcessManagerState::Filtering { selected } => {
+            render_process_browser(frame, theme, manager, *selected);
+        }
+        ProcessManagerState::EnteringPorts { input, error } => {
+            // Create layout with dialog and help text
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(10),   // Dialog
+                    Constraint::Length(3), // Help text
+                ])
+                .split(area);
+
+            input_dialog::render_input_dialog(
+                frame,
+                theme,
+                chunks[0],
+                "Kill by Port",
+                &manager.get_prompt(),
+                input,
+                input.len(),
+                error.is_some(),
+            );
+
+            // Render help text
+            let help_text = Line::from(vec![
+                Span::styled("Enter", theme.accent()),
+                Span::styled(": Find Listeners  ", theme.help()),
+                Span::styled("ESC", theme.accent()),
+                Span::styled(": Back", theme.help()),
+            ]);
+            let help_paragraph = Paragraph::new(help_text)
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::NONE));
+            frame.render_widget(help_paragraph, chunks[1]);
+        }
+        ProcessManagerState::ConfirmingKill { signal, .. } => {
+            // Create layout with dialog and help text
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(10),   // Dialog
+                    Constraint::Length(3), // Help text
+                ])
+                .split(area);
+
+            let targets = manager.kill_targets();
+            let count = format!("{} process(es)", targets.len());
+            let names = targets
+                .iter()
+                .map(|process| format!("{} {}", process.pid, process.name))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let signal_label = format!(
+                "{} ({})",
+                manager.kill_strategy(*signal).describe(),
+                signal.description()
+            );
+            let key_info = vec![
+                ("Processes", count.as_str()),
+                ("Targets", names.as_str()),
+                ("Signal", signal_label.as_str()),
+            ];
+
+            input_dialog::render_confirmation_dialog(
+                frame,
+                theme,
+                chunks[0],
+                "Confirm Kill",
+                "Kill Selected Processes",
+                key_info,
+                &manager.get_prompt(),
+            );
+
+            // Render help text
+            let help_text = Line::from(vec![
+                Span::styled("←/→", theme.accent()),
+                Span::styled(": Switch Signal  ", theme.help()),
+                Span::styled("Y", theme.accent()),
+                Span::styled(": Confirm  ", theme.help()),
+                Span::styled("N", theme.accent()),
+                Span::styled(": Cancel", theme.help()),
+            ]);
+            let help_paragraph = Paragraph::new(help_text)
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::NONE));
+            frame.render_widget(help_paragraph, chunks[1]);
+        }
+        ProcessManagerState::Confirming { action, selected } => {
+            // Create layout with summary at top, matches in middle, help at bottom
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .margin(1)
+                .constraints([
+                    Constraint::Length(5), // Summary
+                    Constraint::Min(8),    // Matching processes
+                    Constraint::Length(2), // Help text
+                ])
+                .split(area);
+
+            // Render summary with the patterns being matched
+            let patterns = manager
+                .get_confirmation_data()
+                .map(|(_, patterns)| patterns)
+                .unwrap_or_default();
+            let summary = vec![
+                Line::from(Span::styled(manager.get_prompt(), theme.text())),
+                Line::from(vec![
+                    Span::styled("Patterns: ", theme.dim()),
+                    Span::styled(patterns, theme.secondary()),
+                ]),
+                Line::from(vec![
+                    Span::styled("Stop with: ", theme.dim()),
+                    Span::styled(action.strategy().describe(), theme.secondary()),
+                ]),
+            ];
+            let summary_paragraph = Paragraph::new(summary)
+                .alignment(Alignment::Center)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title("Review Processes")
+                        .title_style(theme.title())
+                        .border_style(theme.border()),
+                );
+            frame.render_widget(summary_paragraph, chunks[0]);
+
+            // Render matching processes
+            let preview = manager.preview();
+            let rows: Vec<Row> = preview
+                .iter()
+                .map(|process| {
+                    let checked = manager.is_checked(process.pid);
+                    let style = if checked { theme.accent() } else { theme.dim() };
+                    Row::new(vec![
+                        Cell::from(if checked { "[x]" } else { "[ ]" }),
+                        Cell::from(process.pid.to_string()),
+                        Cell::from(process.user.clone()),
+                        Cell::from(process.ports_label()),
+                        Cell::from(process.cmdline.clone()),
+                    ])
+                    .style(style)
+                })
+                .collect();
+            let header = Row::new(vec!["", "PID", "USER", "PORTS", "COMMAND"]).style(theme.title());
+            let widths = [
+                Constraint::Length(4),
+                Constraint::Length(8),
+                Constraint::Length(10),
+                Constraint::Length(12),
+                Constraint::Min(20),
+            ];
+            let table = Table::new(rows, widths)
+                .header(header)
+                .highlight_style(theme.menu_item_selected())
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title("Matching Processes")
+                        .title_style(theme.title())
+                        .border_style(theme.border()),
+                );
+
+            let mut table_state = TableState::default();
+            if !preview.is_empty() {
+                table_state.select(Some(*selected));
+            }
+            frame.render_stateful_widget(table, chunks[1], &mut table_state);
+
+            // Render help text
+            let help_text = Line::from(vec![
+                Span::styled("↑/↓", theme.accent()),
+                Span::styled(": Navigate  ", theme.help()),
+                Span::styled("Space", theme.accent()),
+                Span::styled(": Check/Uncheck  ", theme.help()),
+                Span::styled("a", theme.accent()),
+                Span::styled(": All  ", theme.help()),
+                Span::styled("Y", theme.accent()),
+                Span::styled(": Kill Checked  ", theme.help()),
+                Span::styled("N", theme.accent()),
+                Span::styled(": Cancel", theme.help()),
+            ]);
+            let help_paragraph = Paragraph::new(help_text)
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::NONE));
+            frame.render_widget(help_paragraph, chunks[2]);
+        }
+        ProcessManagerState::Processing { .. } => {
+            let message = manager.get_prompt();
+            input_dialog::render_message_dialog(frame, theme, area, "Process Manager", &message, false);
+        }
+        ProcessManagerState::Success { .. } => {
+            let message = manager.get_prompt();
+            input_dialog::render_message_dialog(frame, theme, area, "Success", &message, false);
+        }
+        ProcessManagerState::Error { .. } => {
+            let message = manager.get_prompt();
+            input_dialog::render_message_dialog(frame, theme, area, "Error", &message, true);
+        }
+    }
+}
+
+/// Renders the live process table used by the process browser
+fn render_process_browser(frame: &mut Frame, theme: &Theme, manager: &crate::features::ProcessManager, selected: usize) {
+    let filtering = matches!(manager.state, ProcessManagerState::Filtering { .. });
+
+    // Create layout with summary and filter at top, table in middle, help at bottom
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3), // Summary
+            Constraint::Length(3), // Filter
+            Constraint::Min(8),    // Process table
+            Constraint::Length(2), // Help text
+        ])
+        .split(frame.area());
+
+    // Render summary
+    let prompt_paragraph = Paragraph::new(manager.get_prompt())
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Process Browser")
+                .title_style(theme.title())
+                .border_style(theme.border()),
+        );
+    frame.render_widget(prompt_paragraph, chunks[0]);
+
+    // Render filter input
+    let filter_text = if filtering {
+        format!("{}_", manager.filter())
+    } else if manager.filter().is_empty() {
+        "press / to filter by pid, user, command or :port".to_string()
+    } else {
+        manager.filter().to_string()
+    };
+    let filter_style = if filtering {
+        theme.input_focused()
+    } else if manager.filter().is_empty() {
+        theme.dim()
+    } else {
+        theme.input()
+    };
+    let filter_paragraph = Paragraph::new(Span::styled(filter_text, filter_style)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Filter")
+            .title_style(theme.title())
+            .border_style(if filtering { theme.accent() } else { theme.border() }),
+    );
+    frame.render_widget(filter_paragraph, chunks[1]);
+
+    // Render process table
+    let (sort_column, descending) = manager.sort_order();
+    let arrow = if descending { " ▼" } else { " ▲" };
+    let header_label = |column: SortColumn| {
+        if column == sort_column {
+            format!("{}{}", column.name(), arrow)
+        } else {
+            column.name().to_string()
+        }
+    };
+    let header = Row::new(vec![
+        Cell::from(""),
+        Cell::from(header_label(SortColumn::Pid)),
+        Cell::from(header_label(SortColumn::User)),
+        Cell::from(header_label(SortColumn::Cpu)),
+        Cell::from(header_label(SortColumn::Memory)),
+        Cell::from("PORTS"),
+        Cell::from(header_label(SortColumn::Command)),
+    ])
+    .style(theme.title());
+
+    let processes = manager.visible_processes();
+    let rows: Vec<Row> = processes
+        .iter()
+        .map(|process| {
+            let marked = manager.is_marked(process.pid);
+            let style = if marked { theme.accent() } else { theme.text() };
+            Row::new(vec![
+                Cell::from(if marked { "✓" } else { " " }),
+                Cell::from(process.pid.to_string()),
+                Cell::from(process.user.clone()),
+                Cell::from(format!("{:.1}", process.cpu_percent)),
+                Cell::from(format_memory(process.memory)),
+                Cell::from(process.ports_label()),
+                Cell::from(process.cmdline.clone()),
+            ])
+            .style(style)
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Length(2),
+        Constraint::Length(8),
+        Constraint::Length(10),
+        Constraint::Length(7),
+        Constraint::Length(8),
+        Constraint::Length(12),
+        Constraint::Min(20),
+    ];
+    let table = Table::new(rows, widths)
+        .header(header)
+        .highlight_style(theme.menu_item_selected())
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Processes")
+                .title_style(theme.title())
+                .border_style(theme.border()),
+        );
+
+    let mut table_state = TableState::default();
+    if !processes.is_empty() {
+        table_state.select(Some(selected));
+    }
+    frame.render_stateful_widget(table, chunks[2], &mut table_state);
+
+    // Render help text
+    let help_text = if filtering {
+        Line::from(vec![
+            Span::styled("Type", theme.accent()),
+            Span::styled(": Filter  ", theme.help()),
+            Span::styled("Enter", theme.accent()),
+            Span::styled(": Apply  ", theme.help()),
+            Span::styled("ESC", theme.accent()),
+            Span::styled(": Clear", theme.help()),
+        ])
+    } else {
+        Line::from(vec![
+            Span::styled("Space", theme.accent()),
+            Span::styled(": Mark  ", theme.help()),
+            Span::styled("a", theme.accent()),
+            Span::styled(": Mark All  ", theme.help()),
+            Span::styled("Enter", theme.accent()),
+            Span::styled(": Kill  ", theme.help()),
+            Span::styled("/", theme.accent()),
+            Span::styled(": Filter  ", theme.help()),
+            Span::styled("p", theme.accent()),
+            Span::styled(": Preset  ", theme.help()),
+            Span::styled("s/o", theme.accent()),
+            Span::styled(": Sort/Order  ", theme.help()),
+            Span::styled("r", theme.accent()),
+            Span::styled(": Refresh  ", theme.help()),
+            Span::styled("ESC", theme.accent()),
+            Span::styled(": Back", theme.help()),
+        ])
+    };
+    let help_paragraph = Paragraph::new(help_text)
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::NONE));
+    frame.render_widget(help_paragraph, chunks[3]);
+}
+
+/// Formats a byte count with a binary unit suffix
+fn format_memory(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "K", "M", "G", "T"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{}{}", bytes, UNITS[0])
+    } else {
+        format!("{:.1}{}", value, UNITS[unit])
+    }
+}
+
+/// Renders the bookmark manager based on its state
+fn render_bookmark_manager(frame: &mut Frame, theme: &Theme, manager: &crate::features::BookmarkManager) {
+    let area = frame.area();
+
+    match &manager.state {
+        BookmarkManagerState::Listing { selected } => {
+            let selected_idx = *selected;
+            let bookmarks = manager.bookmarks();
+
+            // Create layout with prompt at top, bookmark list in middle, help at bottom
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .margin(2)
+                .constraints([
+                    Constraint::Length(3), // Prompt
+                    Constraint::Min(10),   // Bookmark list
+                    Constraint::Length(3), // Help text
+                ])
+                .split(area);
+
+            // Render prompt
+            let prompt = manager.get_prompt();
+            let prompt_paragraph = Paragraph::new(prompt)
+                .alignment(Alignment::Center)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title("Bookmarks")
+                        .title_style(theme.title())
+                        .border_style(theme.border()),
+                );
+            frame.render_widget(prompt_paragraph, chunks[0]);
+
+            let list_block = Block::default()
+                .borders(Borders::ALL)
+                .title("Saved Directories")
+                .title_style(theme.title())
+                .border_style(theme.border());
+
+            if bookmarks.is_empty() {
+                let empty = Paragraph::new("No bookmarks yet. Press 'a' to bookmark the current directory.")
+                    .alignment(Alignment::Center)
+                    .style(theme.dim())
+                    .block(list_block);
+                frame.render_widget(empty, chunks[1]);
+            } else {
+                let name_width = bookmarks.iter().map(|b| b.name.len()).max().unwrap_or(0);
+
+                // Render bookmark list
+                let bookmark_items: Vec<ListItem> = bookmarks
+                    .iter()
+                    .enumerate()
+                    .map(|(i, bookmark)| {
+                        let is_selected = i == selected_idx;
+                        let prefix = if is_selected { "▸ " } else { "  " };
+
+                        let name_style = if is_selected {
+                            theme.menu_item_selected()
+                        } else {
+                            theme.menu_item_active()
+                        };
+
+                        let mut spans = vec![
+                            Span::styled(prefix, theme.accent()),
+                            Span::styled(format!("{:<width$}", bookmark.name, width = name_width), name_style),
+                            Span::styled("  → ", theme.dim()),
+                            Span::styled(bookmark.path.display().to_string(), theme.text()),
+                        ];
+                        if !bookmark.exists() {
+                            spans.push(Span::styled("  [missing]", theme.error()));
+                        }
+
+                        ListItem::new(Line::from(spans))
+                    })
+                    .collect();
+
+                let mut list_state = ListState::default();
+                list_state.select(Some(selected_idx));
+
+                let bookmark_list = List::new(bookmark_items).block(list_block);
+                frame.render_stateful_widget(bookmark_list, chunks[1], &mut list_state);
+            }
+
+            // Render help text
+            let help_text = Line::from(vec![
+                Span::styled("↑/↓", theme.accent()),
+                Span::styled(": Navigate  ", theme.help()),
+                Span::styled("Enter", theme.accent()),
+                Span::styled(": Jump  ", theme.help()),
+                Span::styled("a", theme.accent()),
+                Span::styled(": Add Current Dir  ", theme.help()),
+                Span::styled("r", theme.accent()),
+                Span::styled(": Rename  ", theme.help()),
+                Span::styled("d", theme.accent()),
+                Span::styled(": Delete  ", theme.help()),
+                Span::styled("Tab", theme.accent()),
+                Span::styled(": Frequent  ", theme.help()),
+                Span::styled("ESC", theme.accent()),
+                Span::styled(": Back", theme.help()),
+            ]);
+            let help_paragraph = Paragraph::new(help_text)
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::NONE));
+            frame.render_widget(help_paragraph, chunks[2]);
+        }
+        BookmarkManagerState::Frequent { selected, query } => {
+            let selected_idx = *selected;
+            let dirs = manager.frequent_dirs();
+
+            // Create layout with filter at top, directory list in middle, help at bottom
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .margin(2)
+                .constraints([
+                    Constraint::Length(3), // Filter
+                    Constraint::Min(10),   // Directory list
+                    Constraint::Length(3), // Help text
+                ])
+                .split(area);
+
+            // Render the filter as a prompt line with a cursor
+            let filter_line = Line::from(vec![
+                Span::styled("› ", theme.accent()),
+                Span::styled(query.clone(), theme.input_focused()),
+                Span::styled(" ", theme.cursor()),
+            ]);
+            let filter_paragraph = Paragraph::new(filter_line).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Jump — type to filter")
+                    .title_style(theme.title())
+                    .border_style(theme.border()),
+            );
+            frame.render_widget(filter_paragraph, chunks[0]);
+
+            let list_block = Block::default()
+                .borders(Borders::ALL)
+                .title(manager.get_prompt())
+                .title_style(theme.title())
+                .border_style(theme.border());
+
+            if dirs.is_empty() {
+                let message = if query.is_empty() {
+                    "No visited directories yet. The mode shell wrapper records them as you cd around."
+                } else {
+                    "No visited directory matches the filter."
+                };
+                let empty = Paragraph::new(message)
+                    .alignment(Alignment::Center)
+                    .style(theme.dim())
+                    .block(list_block);
+                frame.render_widget(empty, chunks[1]);
+            } else {
+                let now = crate::features::frecency::now();
+
+                // Render ranked directory list
+                let dir_items: Vec<ListItem> = dirs
+                    .iter()
+                    .enumerate()
+                    .map(|(i, dir)| {
+                        let is_selected = i == selected_idx;
+                        let prefix = if is_selected { "▸ " } else { "  " };
+
+                        let path_style = if is_selected {
+                            theme.menu_item_selected()
+                        } else {
+                            theme.menu_item_active()
+                        };
+
+                        ListItem::new(Line::from(vec![
+                            Span::styled(prefix, theme.accent()),
+                            Span::styled(format!("{:>6.1}  ", dir.score(now)), theme.dim()),
+                            Span::styled(dir.path.display().to_string(), path_style),
+                        ]))
+                    })
+                    .collect();
+
+                let mut list_state = ListState::default();
+                list_state.select(Some(selected_idx));
+
+                let dir_list = List::new(dir_items).block(list_block);
+                frame.render_stateful_widget(dir_list, chunks[1], &mut list_state);
+            }
+
+            // Render help text
+            let help_text = Line::from(vec![
+                Span::styled("↑/↓", theme.accent()),
+                Span::styled(": Navigate  ", theme.help()),
+                Span::styled("Enter", theme.accent()),
+                Span::styled(": Jump  ", theme.help()),
+                Span::styled("Del", theme.accent()),
+                Span::styled(": Forget  ", theme.help()),
+                Span::styled("Tab", theme.accent()),
+                Span::styled(": Bookmarks  ", theme.help()),
+                Span::styled("ESC", theme.accent()),
+                Span::styled(": Clear/Back", theme.help()),
+            ]);
+            let help_paragraph = Paragraph::new(help_text)
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::NONE));
+            frame.render_widget(help_paragraph, chunks[2]);
+        }
+        BookmarkManagerState::Adding { .. } | BookmarkManagerState::Renaming { .. } => {
+            // Create layout with dialog and help text
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(10),   // Dialog
+                    Constraint::Length(3), // Help text
+                ])
+                .split(area);
+
+            let prompt = manager.get_prompt();
+            let input = manager.get_input();
+            let cursor_pos = input.len();
+
+            input_dialog::render_input_dialog(
+                frame,
+                theme,
+                chunks[0],
+                "Bookmarks",
+                &prompt,
+                &input,
+                cursor_pos,
+                false,
+            );
+
+            // Render help text
+            let help_text = Line::from(vec![
+                Span::styled("Enter", theme.accent()),
+                Span::styled(": Save  ", theme.help()),
+                Span::styled("ESC", theme.accent()),
+                Span::styled(": Cancel", theme.help()),
+            ]);
+            let help_paragraph = Paragraph::new(help_text)
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::NONE));
+            frame.render_widget(help_paragraph, chunks[1]);
+        }
+        BookmarkManagerState::ConfirmingDelete { .. } => {
+            // Create layout with dialog and help text
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(10),   // Dialog
+                    Constraint::Length(3), // Help text
+                ])
+                .split(area);
+
+            if let Some((name, path)) = manager.get_confirmation_data() {
+                let key_info = vec![
+                    ("Bookmark", name.as_str()),
+                    ("Directory", path.as_str()),
+                ];
+
+                input_dialog::render_confirmation_dialog(
+                    frame,
+                    theme,
+                    chunks[0],
+                    "Confirm Bookmark Deletion",
+                    "Delete Bookmark",
+                    key_info,
+                    "Do you want to delete this bookmark? The directory itself is not touched.",
+                );
+            }
+
+            // Render help text
+            let help_text = Line::from(vec![
+                Span::styled("Y", theme.accent()),
+                Span::styled(": Delete  ", theme.help()),
+                Span::styled("N", theme.accent()),
+                Span::styled(": Cancel", theme.help()),
+            ]);
+            let help_paragraph = Paragraph::new(help_text)
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::NONE));
+            frame.render_widget(help_paragraph, chunks[1]);
+        }
+        BookmarkManagerState::Success { .. } | BookmarkManagerState::Error { .. } => {
+            let is_error = matches!(manager.state, BookmarkManagerState::Error { .. });
+
+            // Create layout with dialog and help text
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(10),   // Dialog
+                    Constraint::Length(3), // Help text
+                ])
+                .split(area);
+
+            let message = manager.get_prompt();
+            let title = if is_error { "Error" } else { "Success" };
+            input_dialog::render_message_dialog(frame, theme, chunks[0], title, &message, is_error);
+
+            // Render help text
+            let help_text = Line::from(vec![
+                Span::styled("Enter", theme.accent()),
+                Span::styled(" or ", theme.help()),
+                Span::styled("ESC", theme.accent()),
+                Span::styled(": Continue", theme.help()),
+            ]);
+            let help_paragraph = Paragraph::new(help_text)
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::NONE));
+            frame.render_widget(help_paragraph, chunks[1]);
+        }
+    }
+}
+
+/// Renders the environment variables manager based on its state
+fn render_env_manager(frame: &mut Frame, theme: &Theme, manager: &crate::features::EnvManager) {
+    let area = frame.area();
+
+    match &manager.state {
+        EnvManagerState::Listing { selected } | EnvManagerState::Filtering { selected } => {
+            render_env_list(frame, theme, manager, *selected);
+        }
+        EnvManagerState::EnteringName { .. }
+        | EnvManagerState::EnteringValue { .. }
+        | EnvManagerState::EditingValue { .. } => {
+            // Create layout with dialog and help text
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(10),   // Dialog
+                    Constraint::Length(3), // Help text
+                ])
+                .split(area);
+
+            let prompt = manager.get_prompt();
+            let input = manager.get_input();
+            let cursor_pos = input.len();
+
+            input_dialog::render_input_dialog(
+                frame,
+                theme,
+                chunks[0],
+                "Environment Variables",
+                &prompt,
+                &input,
+                cursor_pos,
+                false,
+            );
+
+            // Render help text
+            let help_text = Line::from(vec![
+                Span::styled("Enter", theme.accent()),
+                Span::styled(": Continue  ", theme.help()),
+                Span::styled("ESC", theme.accent()),
+                Span::styled(": Cancel", theme.help()),
+            ]);
+            let help_paragraph = Paragraph::new(help_text)
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::NONE));
+            frame.render_widget(help_paragraph, chunks[1]);
+        }
+        EnvManagerState::Confirming { .. } | EnvManagerState::ConfirmingUnset { .. } => {
+            let unsetting = matches!(manager.state, EnvManagerState::ConfirmingUnset { .. });
+
+            // Create layout with dialog and help text
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(10),   // Dialog
+                    Constraint::Length(3), // Help text
+                ])
+                .split(area);
+
+            if let Some((name, value)) = manager.get_confirmation_data() {
+                let key_info = vec![
+                    ("Variable", name.as_str()),
+                    ("Value", value.as_str()),
+                ];
+
+                if unsetting {
+                    input_dialog::render_confirmation_dialog(
+                        frame,
+                        theme,
+                        chunks[0],
+                        "Confirm Unset",
+                        "Remove Export",
+                        key_info,
+                        "Remove this export from your RC file and unset it? A backup will be created first.",
+                    );
+                } else {
+                    input_dialog::render_confirmation_dialog(
+                        frame,
+                        theme,
+                        chunks[0],
+                        "Confirm Export",
+                        "Add Persistent Variable",
+                        key_info,
+                        "Add this export to your RC file?",
+                    );
+                }
+            }
+
+            // Render help text
+            let help_text = Line::from(vec![
+                Span::styled("Y", theme.accent()),
+                Span::styled(if unsetting { ": Unset  " } else { ": Confirm  " }, theme.help()),
+                Span::styled("N", theme.accent()),
+                Span::styled(": Cancel", theme.help()),
+            ]);
+            let help_paragraph = Paragraph::new(help_text)
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::NONE));
+            frame.render_widget(help_paragraph, chunks[1]);
+        }
+        EnvManagerState::Success { .. } | EnvManagerState::Error { .. } => {
+            let is_error = matches!(manager.state, EnvManagerState::Error { .. });
+
+            // Create layout with dialog and help text
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(10),   // Dialog
+                    Constraint::Length(3), // Help text
+                ])
+                .split(area);
+
+            let message = manager.get_prompt();
+            let title = if is_error { "Error" } else { "Success" };
+            input_dialog::render_message_dialog(frame, theme, chunks[0], title, &message, is_error);
+
+            // Render help text
+            let help_text = Line::from(vec![
+                Span::styled("Enter", theme.accent()),
+                Span::styled(" or ", theme.help()),
+                Span::styled("ESC", theme.accent()),
+                Span::styled(": Continue", theme.help()),
+            ]);
+            let help_paragraph = Paragraph::new(help_text)
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::NONE));
+            frame.render_widget(help_paragraph, chunks[1]);
+        }
+    }
+}
+
+/// Renders the variable table used by the environment variables manager
+fn render_env_list(frame: &mut Frame, theme: &Theme, manager: &crate::features::EnvManager, selected: usize) {
+    let filtering = matches!(manager.state, EnvManagerState::Filtering { .. });
+
+    // Create layout with summary and filter at top, table in middle, help at bottom
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3), // Summary
+            Constraint::Length(3), // Filter
+            Constraint::Min(8),    // Variable table
+            Constraint::Length(2), // Help text
+        ])
+        .split(frame.area());
+
+    // Render summary
+    let prompt_paragraph = Paragraph::new(manager.get_prompt())
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Environment Variables")
+                .title_style(theme.title())
+                .border_style(theme.border()),
+        );
+    frame.render_widget(prompt_paragraph, chunks[0]);
+
+    // Render filter input
+    let filter_text = if filtering {
+        format!("{}_", manager.filter())
+    } else if manager.filter().is_empty() {
+        "press / to filter by name or value".to_string()
+    } else {
+        manager.filter().to_string()
+    };
+    let filter_style = if filtering {
+        theme.input_focused()
+    } else if manager.filter().is_empty() {
+        theme.dim()
+    } else {
+        theme.input()
+    };
+    let filter_paragraph = Paragraph::new(Span::styled(filter_text, filter_style)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Filter")
+            .title_style(theme.title())
+            .border_style(if filtering { theme.accent() } else { theme.border() }),
+    );
+    frame.render_widget(filter_paragraph, chunks[1]);
+
+    // Render variable table
+    let header = Row::new(vec![Cell::from("NAME"), Cell::from("SOURCE"), Cell::from("VALUE")]).style(theme.title());
+
+    let vars = manager.visible_vars();
+    let rows: Vec<Row> = vars
+        .iter()
+        .map(|var| {
+            let source = match (var.in_rc(), var.managed, var.value.is_some()) {
+                (true, true, _) => "rc [mode]",
+                (true, false, true) => "rc",
+                (true, false, false) => "rc (not loaded)",
+                (false, _, _) => "inherited",
+            };
+            let style = if var.in_rc() { theme.text() } else { theme.dim() };
+
+            // Show the RC text when it differs from the live value (e.g. $HOME references)
+            let mut value = vec![Span::raw(var.display_value().to_string())];
+            if let (Some(live), Some(rc)) = (&var.value, &var.rc_value) {
+                if live != rc {
+                    value.push(Span::styled(format!("  (rc: {})", rc), theme.dim()));
+                }
+            }
+
+            Row::new(vec![
+                Cell::from(var.name.clone()),
+                Cell::from(source),
+                Cell::from(Line::from(value)),
+            ])
+            .style(style)
+        })
+        .collect();
+
+    let name_width = vars.iter().map(|v| v.name.len()).max().unwrap_or(4).clamp(4, 32) as u16;
+    let widths = [
+        Constraint::Length(name_width),
+        Constraint::Length(16),
+        Constraint::Min(20),
+    ];
+    let table = Table::new(rows, widths)
+        .header(header)
+        .highlight_style(theme.menu_item_selected())
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Variables")
+                .title_style(theme.title())
+                .border_style(theme.border()),
+        );
+
+    let mut table_state = TableState::default();
+    if !vars.is_empty() {
+        table_state.select(Some(selected));
+    }
+    frame.render_stateful_widget(table, chunks[2], &mut table_state);
+
+    // Render help text
+    let help_text = if filtering {
+        Line::from(vec![
+            Span::styled("Type", theme.accent()),
+            Span::styled(": Filter  ", theme.help()),
+            Span::styled("Enter", theme.accent()),
+            Span::styled(": Apply  ", theme.help()),
+            Span::styled("ESC", theme.accent()),
+            Span::styled(": Clear", theme.help()),
+        ])
+    } else {
+        Line::from(vec![
+            Span::styled("↑/↓", theme.accent()),
+            Span::styled(": Navigate  ", theme.help()),
+            Span::styled("/", theme.accent()),
+            Span::styled(": Filter  ", theme.help()),
+            Span::styled("a", theme.accent()),
+            Span::styled(": Add  ", theme.help()),
+            Span::styled("e", theme.accent()),
+            Span::styled(": Edit/Persist  ", theme.help()),
+            Span::styled("d", theme.accent()),
+            Span::styled(": Unset  ", theme.help()),
+            Span::styled("ESC", theme.accent()),
+            Span::styled(": Back", theme.help()),
+        ])
+    };
+    let help_paragraph = Paragraph::new(help_text)
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::NONE));
+    frame.render_widget(help_paragraph, chunks[3]);
+}
+
+/// Renders the PATH manager based on its state
+fn render_path_manager(frame: &mut Frame, theme: &Theme, manager: &crate::features::PathManager) {
+    let area = frame.area();
+
+    match &manager.state {
+        PathManagerState::Editing { selected } => {
+            render_path_list(frame, theme, manager, *selected);
+        }
+        PathManagerState::Adding { .. } => {
+            // Create layout with dialog and help text
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(10),   // Dialog
+                    Constraint::Length(3), // Help text
+                ])
+                .split(area);
+
+            let prompt = manager.get_prompt();
+            let input = manager.get_input();
+            let cursor_pos = input.len();
+
+            input_dialog::render_input_dialog(frame, theme, chunks[0], "Add to PATH", &prompt, &input, cursor_pos, false);
+
+            // Render help text
+            let help_text = Line::from(vec![
+                Span::styled("Enter", theme.accent()),
+                Span::styled(": Add  ", theme.help()),
+                Span::styled("ESC", theme.accent()),
+                Span::styled(": Cancel", theme.help()),
+            ]);
+            let help_paragraph = Paragraph::new(help_text)
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::NONE));
+            frame.render_widget(help_paragraph, chunks[1]);
+        }
+        PathManagerState::ConfirmingSave { .. } | PathManagerState::ConfirmingDiscard { .. } => {
+            let saving = matches!(manager.state, PathManagerState::ConfirmingSave { .. });
+
+            // Create layout with dialog and help text
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(10),   // Dialog
+                    Constraint::Length(3), // Help text
+                ])
+                .split(area);
+
+            if saving {
+                if let Some((rc_file, entries)) = manager.get_confirmation_data() {
+                    let key_info = vec![("RC file", rc_file.as_str()), ("PATH", entries.as_str())];
+                    input_dialog::render_confirmation_dialog(
+                        frame,
+                        theme,
+                        chunks[0],
+                        "Confirm Save",
+                        "Save PATH",
+                        key_info,
+                        "Replace the mode PATH block in your RC file? A backup will be created first.",
+                    );
+                }
+            } else {
+                input_dialog::render_message_dialog(frame, theme, chunks[0], "Unsaved Changes", &manager.get_prompt(), false);
+            }
+
+            // Render help text
+            let help_text = Line::from(vec![
+                Span::styled("Y", theme.accent()),
+                Span::styled(if saving { ": Save  " } else { ": Discard  " }, theme.help()),
+                Span::styled("N", theme.accent()),
+                Span::styled(if saving { ": Cancel" } else { ": Keep editing" }, theme.help()),
+            ]);
+            let help_paragraph = Paragraph::new(help_text)
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::NONE));
+            frame.render_widget(help_paragraph, chunks[1]);
+        }
+        PathManagerState::Success { .. } | PathManagerState::Error { .. } => {
+            let is_error = matches!(manager.state, PathManagerState::Error { .. });
+
+            // Create layout with dialog and help text
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(10),   // Dialog
+                    Constraint::Length(3), // Help text
+                ])
+                .split(area);
+
+            let message = manager.get_prompt();
+            let title = if is_error { "Error" } else { "Success" };
+            input_dialog::render_message_dialog(frame, theme, chunks[0], title, &message, is_error);
+
+            // Render help text
+            let help_text = Line::from(vec![
+                Span::styled("Enter", theme.accent()),
+                Span::styled(" or ", theme.help()),
+                Span::styled("ESC", theme.accent()),
+                Span::styled(": Continue", theme.help()),
+            ]);
+            let help_paragraph = Paragraph::new(help_text)
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::NONE));
+            frame.render_widget(help_paragraph, chunks[1]);
+        }
+    }
+}
+
+/// Renders the PATH entries table with the problems found for each entry
+fn render_path_list(frame: &mut Frame, theme: &Theme, manager: &crate::features::PathManager, selected: usize) {
+    // Create layout with summary at top, entries in middle, details and help at bottom
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3), // Summary
+            Constraint::Min(8),    // Entry table
+            Constraint::Length(3), // Details
+            Constraint::Length(2), // Help text
+        ])
+        .split(frame.area());
+
+    // Render summary
+    let prompt_paragraph = Paragraph::new(manager.get_prompt())
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("PATH Manager")
+                .title_style(theme.title())
+                .border_style(theme.border()),
+        );
+    frame.render_widget(prompt_paragraph, chunks[0]);
+
+    // Render entry table
+    let header = Row::new(vec![Cell::from("#"), Cell::from("DIRECTORY"), Cell::from("STATUS")]).style(theme.title());
+
+    let entries = manager.entries();
+    let rows: Vec<Row> = entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let (status, style) = if entry.dir.is_empty() {
+                ("empty (current directory)".to_string(), theme.error())
+            } else if !entry.exists {
+                ("missing".to_string(), theme.error())
+            } else if let Some(first) = entry.duplicate_of {
+                (format!("duplicate of #{}", first + 1), theme.dim())
+            } else if !entry.shadows.is_empty() {
+                (format!("shadows {}", entry.shadows.len()), theme.accent())
+            } else {
+                ("ok".to_string(), theme.success())
+            };
+            let dir = if entry.dir.is_empty() { "(empty)".to_string() } else { entry.dir.clone() };
+
+            Row::new(vec![
+                Cell::from((i + 1).to_string()),
+                Cell::from(dir),
+                Cell::from(status).style(style),
+            ])
+            .style(if entry.is_redundant() { theme.dim() } else { theme.text() })
+        })
+        .collect();
+
+    let widths = [Constraint::Length(3), Constraint::Min(20), Constraint::Length(26)];
+    let table = Table::new(rows, widths)
+        .header(header)
+        .highlight_style(theme.menu_item_selected())
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Entries (first match wins)")
+                .title_style(theme.title())
+                .border_style(theme.border()),
+        );
+
+    let mut table_state = TableState::default();
+    if !entries.is_empty() {
+        table_state.select(Some(selected));
+    }
+    frame.render_stateful_widget(table, chunks[1], &mut table_state);
+
+    // Render details for the selected entry
+    let details = match manager.selected_entry() {
+        Some(entry) if !entry.shadows.is_empty() => Span::styled(
+            format!("Hides later copies of: {}", entry.shadows.join(", ")),
+            theme.text(),
+        ),
+        Some(entry) if entry.is_redundant() => Span::styled("Press c to drop missing and duplicate entries", theme.dim()),
+        Some(_) => Span::styled("No conflicts with later entries", theme.dim()),
+        None => Span::styled("PATH is empty", theme.dim()),
+    };
+    let details_paragraph = Paragraph::new(details).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Details")
+            .title_style(theme.title())
+            .border_style(theme.border()),
+    );
+    frame.render_widget(details_paragraph, chunks[2]);
+
+    // Render help text
+    let help_text = Line::from(vec![
+        Span::styled("↑/↓", theme.accent()),
+        Span::styled(": Navigate  ", theme.help()),
+        Span::styled("K/J", theme.accent()),
+        Span::styled(": Move  ", theme.help()),
+        Span::styled("a", theme.accent()),
+        Span::styled(": Add  ", theme.help()),
+        Span::styled("d", theme.accent()),
+        Span::styled(": Remove  ", theme.help()),
+        Span::styled("c", theme.accent()),
+        Span::styled(": Clean up  ", theme.help()),
+        Span::styled("s", theme.accent()),
+        Span::styled(": Save  ", theme.help()),
+        Span::styled("ESC", theme.accent()),
+        Span::styled(": Back", theme.help()),
+    ]);
+    let help_paragraph = Paragraph::new(help_text)
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::NONE));
+    frame.render_widget(help_paragraph, chunks[3]);
+}
+
+/// Renders the SSH config manager based on its state
+fn render_ssh_manager(frame: &mut Frame, theme: &Theme, manager: &crate::features::SshManager) {
+    let area = frame.area();
+
+    match &manager.state {
+        SshManagerState::Listing { selected } => {
+            render_ssh_host_list(frame, theme, manager, *selected);
+        }
+        SshManagerState::Editing { form } => {
+            // Create layout with form and help text
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(10),   // Form
+                    Constraint::Length(3), // Help text
+                ])
+                .split(area);
+
+            let fields: Vec<(&str, &str)> = HostEntry::FIELDS
+                .iter()
+                .enumerate()
+                .map(|(i, label)| (*label, form.entry.field(i)))
+                .collect();
+            input_dialog::render_form_dialog(
+                frame,
+                theme,
+                chunks[0],
+                &manager.get_prompt(),
+                fields,
+                form.field,
+                form.error.as_deref(),
+            );
+
+            // Render help text
+            let help_text = Line::from(vec![
+                Span::styled("Tab/↑/↓", theme.accent()),
+                Span::styled(": Field  ", theme.help()),
+                Span::styled("Enter", theme.accent()),
+                Span::styled(": Next  ", theme.help()),
+                Span::styled("Ctrl+S", theme.accent()),
+                Span::styled(": Save  ", theme.help()),
+                Span::styled("ESC", theme.accent()),
+                Span::styled(": Cancel", theme.help()),
+            ]);
+            let help_paragraph = Paragraph::new(help_text)
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::NONE));
+            frame.render_widget(help_paragraph, chunks[1]);
+        }
+        SshManagerState::Confirming { .. } | SshManagerState::ConfirmingDelete { .. } => {
+            let deleting = matches!(manager.state, SshManagerState::ConfirmingDelete { .. });
+
+            // Create layout with dialog and help text
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(10),   // Dialog
+                    Constraint::Length(3), // Help text
+                ])
+                .split(area);
+
+            if deleting {
+                input_dialog::render_message_dialog(frame, theme, chunks[0], "Confirm Remove", &manager.get_prompt(), true);
+            } else if let Some(fields) = manager.get_confirmation_data() {
+                let key_info = fields.iter().map(|(label, value)| (*label, value.as_str())).collect();
+                input_dialog::render_confirmation_dialog(
+                    frame,
+                    theme,
+                    chunks[0],
+                    "Confirm Host",
+                    &manager.get_prompt(),
+                    key_info,
+                    "Write this host to ~/.ssh/config? A backup will be created first.",
+                );
+            }
+
+            // Render help text
+            let help_text = Line::from(vec![
+                Span::styled("Y", theme.accent()),
+                Span::styled(if deleting { ": Remove  " } else { ": Save  " }, theme.help()),
+                Span::styled("N", theme.accent()),
+                Span::styled(": Cancel", theme.help()),
+            ]);
+            let help_paragraph = Paragraph::new(help_text)
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::NONE));
+            frame.render_widget(help_paragraph, chunks[1]);
+        }
+        SshManagerState::Success { .. } | SshManagerState::Error { .. } => {
+            let is_error = matches!(manager.state, SshManagerState::Error { .. });
+
+            // Create layout with dialog and help text
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(10),   // Dialog
+                    Constraint::Length(3), // Help text
+                ])
+                .split(area);
+
+            let message = manager.get_prompt();
+            let title = if is_error { "Error" } else { "Success" };
+            input_dialog::render_message_dialog(frame, theme, chunks[0], title, &message, is_error);
+
+            // Render help text
+            let help_text = Line::from(vec![
+                Span::styled("Enter", theme.accent()),
+                Span::styled(" or ", theme.help()),
+                Span::styled("ESC", theme.accent()),
+                Span::styled(": Continue", theme.help()),
+            ]);
+            let help_paragraph = Paragraph::new(help_text)
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::NONE));
+            frame.render_widget(help_paragraph, chunks[1]);
+        }
+    }
+}
+
+/// Renders the host table used by the SSH config manager
+fn render_ssh_host_list(frame: &mut Frame, theme: &Theme, manager: &crate::features::SshManager, selected: usize) {
+    // Create layout with summary at top, hosts in middle, details and help at bottom
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3), // Summary
+            Constraint::Min(8),    // Host table
+            Constraint::Length(4), // Details
+            Constraint::Length(2), // Help text
+        ])
+        .split(frame.area());
+
+    // Render summary
+    let prompt_paragraph = Paragraph::new(manager.get_prompt())
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("SSH Hosts")
+                .title_style(theme.title())
+                .border_style(theme.border()),
+        );
+    frame.render_widget(prompt_paragraph, chunks[0]);
+
+    // Render host table
+    let header = Row::new(vec![
+        Cell::from("HOST"),
+        Cell::from("HOSTNAME"),
+        Cell::from("USER"),
+        Cell::from("PORT"),
+        Cell::from("NOTES"),
+    ])
+    .style(theme.title());
+
+    let hosts = manager.hosts();
+    let rows: Vec<Row> = hosts
+        .iter()
+        .map(|host| {
+            let (notes, notes_style) = if !host.is_concrete() {
+                ("pattern".to_string(), theme.dim())
+            } else if ssh_manager::identity_problem(host).is_some() {
+                ("key problem".to_string(), theme.error())
+            } else {
+                (String::new(), theme.dim())
+            };
+
+            Row::new(vec![
+                Cell::from(host.alias()),
+                Cell::from(host.get("HostName").unwrap_or_default()),
+                Cell::from(host.get("User").unwrap_or_default()),
+                Cell::from(host.get("Port").unwrap_or_default()),
+                Cell::from(notes).style(notes_style),
+            ])
+            .style(if host.is_concrete() { theme.text() } else { theme.dim() })
+        })
+        .collect();
+
+    let alias_width = hosts.iter().map(|h| h.alias().len()).max().unwrap_or(4).clamp(4, 32) as u16;
+    let widths = [
+        Constraint::Length(alias_width),
+        Constraint::Min(16),
+        Constraint::Length(12),
+        Constraint::Length(6),
+        Constraint::Length(12),
+    ];
+    let table = Table::new(rows, widths)
+        .header(header)
+        .highlight_style(theme.menu_item_selected())
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Hosts")
+                .title_style(theme.title())
+                .border_style(theme.border()),
+        );
+
+    let mut table_state = TableState::default();
+    if !hosts.is_empty() {
+        table_state.select(Some(selected));
+    }
+    frame.render_stateful_widget(table, chunks[1], &mut table_state);
+
+    // Render details for the selected host
+    let details = match manager.selected_host() {
+        Some(host) => {
+            let mut lines = vec![Line::from(vec![
+                Span::styled("IdentityFile: ", theme.dim()),
+                Span::styled(host.get("IdentityFile").unwrap_or_else(|| "(default)".to_string()), theme.text()),
+                Span::styled("  ProxyJump: ", theme.dim()),
+                Span::styled(host.get("ProxyJump").unwrap_or_else(|| "(none)".to_string()), theme.text()),
+            ])];
+            if let Some(problem) = ssh_manager::identity_problem(host) {
+                lines.push(Line::from(Span::styled(problem.replace('\n', " "), theme.error())));
+            }
+            lines
+        }
+        None => vec![Line::from(Span::styled("No hosts yet — press a to add one", theme.dim()))],
+    };
+    let details_paragraph = Paragraph::new(details).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Details")
+            .title_style(theme.title())
+            .border_style(theme.border()),
+    );
+    frame.render_widget(details_paragraph, chunks[2]);
+
+    // Render help text
+    let help_text = Line::from(vec![
+        Span::styled("↑/↓", theme.accent()),
+        Span::styled(": Navigate  ", theme.help()),
+        Span::styled("Enter", theme.accent()),
+        Span::styled(": Connect  ", theme.help()),
+        Span::styled("a", theme.accent()),
+        Span::styled(": Add  ", theme.help()),
+        Span::styled("e", theme.accent()),
+        Span::styled(": Edit  ", theme.help()),
+        Span::styled("d", theme.accent()),
+        Span::styled(": Remove  ", theme.help()),
+        Span::styled("ESC", theme.accent()),
+        Span::styled(": Back", theme.help()),
+    ]);
+    let help_paragraph = Paragraph::new(help_text)
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::NONE));
+    frame.render_widget(help_paragraph, chunks[3]);
+}
+
+/// Renders git shortcuts based on its state
+fn render_git_shortcuts(frame: &mut Frame, theme: &Theme, shortcuts: &crate::features::GitShortcuts) {
+    let area = frame.area();
+
+    match &shortcuts.state {
+        GitShortcutsState::Listing { selected } => {
+            let selected_idx = *selected;
+            let aliases = shortcuts.aliases();
+
+            // Create layout with prompt at top, alias list in middle, help at bottom
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .margin(2)
+                .constraints([
+                    Constraint::Length(3), // Prompt
+                    Constraint::Min(10),   // Alias list
+                    Constraint::Length(3), // Help text
+                ])
+                .split(area);
+
+            // Render prompt
+            let prompt_paragraph = Paragraph::new(shortcuts.get_prompt())
+                .alignment(Alignment::Center)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title("Git Shortcuts")
+                        .title_style(theme.title())
+                        .border_style(theme.border()),
+                );
+            frame.render_widget(prompt_paragraph, chunks[0]);
+
+            let list_block = Block::default()
+                .borders(Borders::ALL)
+                .title(format!("[alias] ({})", shortcuts.scope().name()))
+                .title_style(theme.title())
+                .border_style(theme.border());
+
+            if aliases.is_empty() {
+                let empty = Paragraph::new("No git aliases yet. Press 'a' to create one or 't' to pick from templates.")
+                    .alignment(Alignment::Center)
+                    .style(theme.dim())
+                    .block(list_block);
+                frame.render_widget(empty, chunks[1]);
+            } else {
+                let name_width = aliases.iter().map(|a| a.name.len()).max().unwrap_or(0);
+
+                // Render alias list
+                let alias_items: Vec<ListItem> = aliases
+                    .iter()
+                    .enumerate()
+                    .map(|(i, alias)| {
+                        let is_selected = i == selected_idx;
+                        let prefix = if is_selected { "▸ " } else { "  " };
+
+                        let name_style = if is_selected {
+                            theme.menu_item_selected()
+                        } else {
+                            theme.menu_item_active()
+                        };
+
+                        ListItem::new(Line::from(vec![
+                            Span::styled(prefix, theme.accent()),
+                            Span::styled(format!("{:<width$}", alias.name, width = name_width), name_style),
+                            Span::styled("  = ", theme.dim()),
+                            Span::styled(alias.command.clone(), theme.text()),
+                        ]))
+                    })
+                    .collect();
+
+                let mut list_state = ListState::default();
+                list_state.select(Some(selected_idx));
+
+                let alias_list = List::new(alias_items).block(list_block);
+                frame.render_stateful_widget(alias_list, chunks[1], &mut list_state);
+            }
+
+            // Render help text
+            let help_text = Line::from(vec![
+                Span::styled("↑/↓", theme.accent()),
+                Span::styled(": Navigate  ", theme.help()),
+                Span::styled("a", theme.accent()),
+                Span::styled(": Add  ", theme.help()),
+                Span::styled("e", theme.accent()),
+                Span::styled(": Edit  ", theme.help()),
+                Span::styled("d", theme.accent()),
+                Span::styled(": Delete  ", theme.help()),
+                Span::styled("t", theme.accent()),
+                Span::styled(": Templates  ", theme.help()),
+                Span::styled("g", theme.accent()),
+                Span::styled(": Global/Local  ", theme.help()),
+                Span::styled("ESC", theme.accent()),
+                Span::styled(": Back", theme.help()),
+            ]);
+            let help_paragraph = Paragraph::new(help_text)
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::NONE));
+            frame.render_widget(help_paragraph, chunks[2]);
+        }
+        GitShortcutsState::Templates { selected, checked } => {
+            let selected_idx = *selected;
+
+            // Create layout with prompt at top, template list in middle, help at bottom
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .margin(2)
+                .constraints([
+                    Constraint::Length(3), // Prompt
+                    Constraint::Min(10),   // Template list
+                    Constraint::Length(3), // Help text
+                ])
+                .split(area);
+
+            // Render prompt
+            let prompt_paragraph = Paragraph::new(shortcuts.get_prompt())
+                .alignment(Alignment::Center)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title("Git Shortcuts")
+                        .title_style(theme.title())
+                        .border_style(theme.border()),
+                );
+            frame.render_widget(prompt_paragraph, chunks[0]);
+
+            let templates = crate::features::git_config::TEMPLATES;
+            let name_width = templates.iter().map(|t| t.name.len()).max().unwrap_or(0);
+            let command_width = templates.iter().map(|t| t.command.len()).max().unwrap_or(0);
 
-    match &manager.state {
-        AliasManagerState::EnteringName { .. } | AliasManagerState::EnteringCommand { .. } => {
+            // Render template list
+            let template_items: Vec<ListItem> = templates
+                .iter()
+                .enumerate()
+                .map(|(i, template)| {
+                    let is_selected = i == selected_idx;
+                    let prefix = if is_selected { "▸ " } else { "  " };
+                    let checkbox = if checked.get(i).copied().unwrap_or(false) { "[x] " } else { "[ ] " };
+
+                    let name_style = if is_selected {
+                        theme.menu_item_selected()
+                    } else {
+                        theme.menu_item_active()
+                    };
+
+                    let mut spans = vec![
+                        Span::styled(prefix, theme.accent()),
+                        Span::styled(checkbox, theme.accent()),
+                        Span::styled(format!("{:<width$}", template.name, width = name_width), name_style),
+                        Span::styled("  ", theme.dim()),
+                        Span::styled(format!("{:<width$}", template.command, width = command_width), theme.text()),
+                        Span::styled(format!("  {}", template.description), theme.dim()),
+                    ];
+                    match shortcuts.template_status(template) {
+                        TemplateStatus::New => {}
+                        TemplateStatus::Installed => spans.push(Span::styled("  [installed]", theme.success())),
+                        TemplateStatus::Conflicts => spans.push(Span::styled("  [replaces yours]", theme.error())),
+                    }
+
+                    ListItem::new(Line::from(spans))
+                })
+                .collect();
+
+            let mut list_state = ListState::default();
+            list_state.select(Some(selected_idx));
+
+            let template_list = List::new(template_items).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Templates")
+                    .title_style(theme.title())
+                    .border_style(theme.border()),
+            );
+            frame.render_stateful_widget(template_list, chunks[1], &mut list_state);
+
+            // Render help text
+            let help_text = Line::from(vec![
+                Span::styled("↑/↓", theme.accent()),
+                Span::styled(": Navigate  ", theme.help()),
+                Span::styled("Space", theme.accent()),
+                Span::styled(": Toggle  ", theme.help()),
+                Span::styled("Enter", theme.accent()),
+                Span::styled(": Install checked  ", theme.help()),
+                Span::styled("ESC", theme.accent()),
+                Span::styled(": Back", theme.help()),
+            ]);
+            let help_paragraph = Paragraph::new(help_text)
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::NONE));
+            frame.render_widget(help_paragraph, chunks[2]);
+        }
+        GitShortcutsState::EnteringName { .. }
+        | GitShortcutsState::EnteringCommand { .. }
+        | GitShortcutsState::EditingCommand { .. } => {
             // Create layout with dialog and help text
             let chunks = Layout::default()
                 .direction(Direction::Vertical)
@@ -70,33 +2095,25 @@ fn render_alias_manager(frame: &mut Frame, manager: &crate::features::AliasManag
                 ])
                 .split(area);
 
-            let prompt = manager.get_prompt();
-            let input = manager.get_input();
+            let prompt = shortcuts.get_prompt();
+            let input = shortcuts.get_input();
             let cursor_pos = input.len();
 
-            input_dialog::render_input_dialog(
-                frame,
-                chunks[0],
-                "Alias Manager",
-                &prompt,
-                &input,
-                cursor_pos,
-                false,
-            );
+            input_dialog::render_input_dialog(frame, theme, chunks[0], "Git Shortcuts", &prompt, &input, cursor_pos, false);
 
             // Render help text
             let help_text = Line::from(vec![
-                Span::styled("Enter", Theme::accent()),
-                Span::styled(": Continue  ", Theme::help()),
-                Span::styled("ESC", Theme::accent()),
-                Span::styled(": Cancel", Theme::help()),
+                Span::styled("Enter", theme.accent()),
+                Span::styled(": Continue  ", theme.help()),
+                Span::styled("ESC", theme.accent()),
+                Span::styled(": Cancel", theme.help()),
             ]);
             let help_paragraph = Paragraph::new(help_text)
                 .alignment(Alignment::Center)
                 .block(Block::default().borders(Borders::NONE));
             frame.render_widget(help_paragraph, chunks[1]);
         }
-        AliasManagerState::Confirming { .. } => {
+        GitShortcutsState::ConfirmingDelete { name, command } => {
             // Create layout with dialog and help text
             let chunks = Layout::default()
                 .direction(Direction::Vertical)
@@ -106,44 +2123,30 @@ fn render_alias_manager(frame: &mut Frame, manager: &crate::features::AliasManag
                 ])
                 .split(area);
 
-            if let Some((name, command)) = manager.get_confirmation_data() {
-                let key_info = vec![
-                    ("Alias Name", name.as_str()),
-                    ("Command", command.as_str()),
-                ];
-
-                input_dialog::render_confirmation_dialog(
-                    frame,
-                    chunks[0],
-                    "Confirm Alias Creation",
-                    "Create New Alias",
-                    key_info,
-                    "Do you want to create this alias?",
-                );
-            }
+            let key_info = vec![("Alias", name.as_str()), ("Command", command.as_str())];
+            input_dialog::render_confirmation_dialog(
+                frame,
+                theme,
+                chunks[0],
+                "Confirm Delete",
+                "Delete Git Alias",
+                key_info,
+                "Remove this alias from your git config? A backup will be created first.",
+            );
 
             // Render help text
             let help_text = Line::from(vec![
-                Span::styled("Y", Theme::accent()),
-                Span::styled(": Confirm  ", Theme::help()),
-                Span::styled("N", Theme::accent()),
-                Span::styled(": Cancel", Theme::help()),
+                Span::styled("Y", theme.accent()),
+                Span::styled(": Delete  ", theme.help()),
+                Span::styled("N", theme.accent()),
+                Span::styled(": Cancel", theme.help()),
             ]);
             let help_paragraph = Paragraph::new(help_text)
                 .alignment(Alignment::Center)
                 .block(Block::default().borders(Borders::NONE));
             frame.render_widget(help_paragraph, chunks[1]);
         }
-        AliasManagerState::Processing => {
-            input_dialog::render_message_dialog(
-                frame,
-                area,
-                "Alias Manager",
-                "Creating alias...",
-                false,
-            );
-        }
-        AliasManagerState::Success { .. } => {
+        GitShortcutsState::ConfirmingTemplates { .. } => {
             // Create layout with dialog and help text
             let chunks = Layout::default()
                 .direction(Direction::Vertical)
@@ -153,29 +2156,33 @@ fn render_alias_manager(frame: &mut Frame, manager: &crate::features::AliasManag
                 ])
                 .split(area);
 
-            let message = manager.get_prompt();
-
-            input_dialog::render_message_dialog(
+            let templates = shortcuts.checked_templates();
+            let key_info = templates.iter().map(|t| (t.name, t.command)).collect();
+            input_dialog::render_confirmation_dialog(
                 frame,
+                theme,
                 chunks[0],
-                "Success",
-                &message,
-                false,
+                "Confirm Templates",
+                &shortcuts.get_prompt(),
+                key_info,
+                "Existing aliases with these names are replaced. A backup will be created first.",
             );
 
             // Render help text
             let help_text = Line::from(vec![
-                Span::styled("Enter", Theme::accent()),
-                Span::styled(" or ", Theme::help()),
-                Span::styled("ESC", Theme::accent()),
-                Span::styled(": Continue", Theme::help()),
+                Span::styled("Y", theme.accent()),
+                Span::styled(": Install  ", theme.help()),
+                Span::styled("N", theme.accent()),
+                Span::styled(": Cancel", theme.help()),
             ]);
             let help_paragraph = Paragraph::new(help_text)
                 .alignment(Alignment::Center)
                 .block(Block::default().borders(Borders::NONE));
             frame.render_widget(help_paragraph, chunks[1]);
         }
-        AliasManagerState::Error { .. } => {
+        GitShortcutsState::Success { .. } | GitShortcutsState::Error { .. } => {
+            let is_error = matches!(shortcuts.state, GitShortcutsState::Error { .. });
+
             // Create layout with dialog and help text
             let chunks = Layout::default()
                 .direction(Direction::Vertical)
@@ -185,22 +2192,16 @@ fn render_alias_manager(frame: &mut Frame, manager: &crate::features::AliasManag
                 ])
                 .split(area);
 
-            let message = manager.get_prompt();
-
-            input_dialog::render_message_dialog(
-                frame,
-                chunks[0],
-                "Error",
-                &message,
-                true,
-            );
+            let message = shortcuts.get_prompt();
+            let title = if is_error { "Error" } else { "Success" };
+            input_dialog::render_message_dialog(frame, theme, chunks[0], title, &message, is_error);
 
             // Render help text
             let help_text = Line::from(vec![
-                Span::styled("Enter", Theme::accent()),
-                Span::styled(" or ", Theme::help()),
-                Span::styled("ESC", Theme::accent()),
-                Span::styled(": Continue", Theme::help()),
+                Span::styled("Enter", theme.accent()),
+                Span::styled(" or ", theme.help()),
+                Span::styled("ESC", theme.accent()),
+                Span::styled(": Continue", theme.help()),
             ]);
             let help_paragraph = Paragraph::new(help_text)
                 .alignment(Alignment::Center)
@@ -210,91 +2211,46 @@ fn render_alias_manager(frame: &mut Frame, manager: &crate::features::AliasManag
     }
 }
 
-/// Renders the process manager based on its state
-fn render_process_manager(frame: &mut Frame, manager: &crate::features::ProcessManager) {
+/// Renders the settings screen based on its state
+fn render_settings(frame: &mut Frame, theme: &Theme, settings: &crate::features::Settings) {
     let area = frame.area();
 
-    match &manager.state {
-        ProcessManagerState::SelectingAction { selected } => {
-            let actions = ProcessAction::all();
-            let selected_idx = *selected;
-
-            // Create layout with prompt at top, menu in middle, help at bottom
+    match &settings.state {
+        SettingsState::Browsing { selected } => {
+            render_settings_list(frame, theme, settings, *selected);
+        }
+        SettingsState::Editing { error, .. } => {
+            // Create layout with dialog and help text
             let chunks = Layout::default()
                 .direction(Direction::Vertical)
-                .margin(2)
                 .constraints([
-                    Constraint::Length(3), // Prompt
-                    Constraint::Min(10),   // Action list
+                    Constraint::Min(10),   // Dialog
                     Constraint::Length(3), // Help text
                 ])
                 .split(area);
 
-            // Render prompt
-            let prompt = manager.get_prompt();
-            let prompt_paragraph = Paragraph::new(prompt)
-                .alignment(Alignment::Center)
-                .block(
-                    Block::default()
-                        .borders(Borders::ALL)
-                        .title("Process Manager")
-                        .title_style(Theme::title())
-                        .border_style(Theme::border()),
-                );
-            frame.render_widget(prompt_paragraph, chunks[0]);
-
-            // Render action list
-            let action_items: Vec<ListItem> = actions
-                .iter()
-                .enumerate()
-                .map(|(i, action)| {
-                    let is_selected = i == selected_idx;
-                    let prefix = if is_selected { "▸ " } else { "  " };
-
-                    let title_style = if is_selected {
-                        Theme::menu_item_selected()
-                    } else {
-                        Theme::menu_item_active()
-                    };
-
-                    let title_line = Line::from(Span::styled(
-                        format!("{}{}", prefix, action.name()),
-                        title_style
-                    ));
-
-                    let desc_line = Line::from(Span::styled(
-                        format!("    {}", action.description()),
-                        Theme::dim()
-                    ));
-
-                    ListItem::new(vec![title_line, desc_line])
-                })
-                .collect();
+            let title = settings.selected_field().map(|f| f.name()).unwrap_or_default();
+            let prompt = settings.get_prompt();
+            let input = settings.get_input();
+            let cursor_pos = input.len();
 
-            let action_list = List::new(action_items).block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title("Select Action")
-                    .title_style(Theme::title())
-                    .border_style(Theme::border()),
-            );
-            frame.render_widget(action_list, chunks[1]);
+            input_dialog::render_input_dialog(frame, theme, chunks[0], &title, &prompt, &input, cursor_pos, error.is_some());
 
             // Render help text
             let help_text = Line::from(vec![
-                Span::styled("↑/↓", Theme::accent()),
-                Span::styled(": Navigate  ", Theme::help()),
-                Span::styled("Enter", Theme::accent()),
-                Span::styled(": Select  ", Theme::help()),
-                Span::styled("ESC", Theme::accent()),
-                Span::styled(": Cancel", Theme::help()),
+                Span::styled("Enter", theme.accent()),
+                Span::styled(": Apply  ", theme.help()),
+                Span::styled("ESC", theme.accent()),
+                Span::styled(": Cancel", theme.help()),
             ]);
             let help_paragraph = Paragraph::new(help_text)
                 .alignment(Alignment::Center)
                 .block(Block::default().borders(Borders::NONE));
-            frame.render_widget(help_paragraph, chunks[2]);
+            frame.render_widget(help_paragraph, chunks[1]);
         }
-        ProcessManagerState::Confirming { .. } => {
+        SettingsState::ConfirmingSave { .. } | SettingsState::ConfirmingDiscard { .. } => {
+            let saving = matches!(settings.state, SettingsState::ConfirmingSave { .. });
+
             // Create layout with dialog and help text
             let chunks = Layout::default()
                 .direction(Direction::Vertical)
@@ -304,55 +2260,39 @@ fn render_process_manager(frame: &mut Frame, manager: &crate::features::ProcessM
                 ])
                 .split(area);
 
-            if let Some((action_name, description)) = manager.get_confirmation_data() {
-                let key_info = vec![
-                    ("Action", action_name.as_str()),
-                    ("Target", description.as_str()),
-                ];
-
-                input_dialog::render_confirmation_dialog(
-                    frame,
-                    chunks[0],
-                    "Confirm Process Action",
-                    "Kill Processes",
-                    key_info,
-                    "Are you sure you want to proceed?",
-                );
+            if saving {
+                if let Some(changes) = settings.get_confirmation_data() {
+                    let key_info: Vec<(&str, &str)> =
+                        changes.iter().map(|(name, change)| (name.as_str(), change.as_str())).collect();
+                    input_dialog::render_confirmation_dialog(
+                        frame,
+                        theme,
+                        chunks[0],
+                        "Confirm Save",
+                        "Save Settings",
+                        key_info,
+                        "Write these changes to config.toml? A backup will be created first.",
+                    );
+                }
+            } else {
+                input_dialog::render_message_dialog(frame, theme, chunks[0], "Unsaved Changes", &settings.get_prompt(), false);
             }
 
             // Render help text
             let help_text = Line::from(vec![
-                Span::styled("Y", Theme::accent()),
-                Span::styled(": Confirm  ", Theme::help()),
-                Span::styled("N", Theme::accent()),
-                Span::styled(": Cancel", Theme::help()),
+                Span::styled("Y", theme.accent()),
+                Span::styled(if saving { ": Save  " } else { ": Discard  " }, theme.help()),
+                Span::styled("N", theme.accent()),
+                Span::styled(if saving { ": Cancel" } else { ": Keep editing" }, theme.help()),
             ]);
             let help_paragraph = Paragraph::new(help_text)
                 .alignment(Alignment::Center)
                 .block(Block::default().borders(Borders::NONE));
             frame.render_widget(help_paragraph, chunks[1]);
         }
-        ProcessManagerState::Processing { .. } => {
-            let message = manager.get_prompt();
-            input_dialog::render_message_dialog(frame, area, "Process Manager", &message, false);
-        }
-        ProcessManagerState::Success { .. } => {
-            let message = manager.get_prompt();
-            input_dialog::render_message_dialog(frame, area, "Success", &message, false);
-        }
-        ProcessManagerState::Error { .. } => {
-            let message = manager.get_prompt();
-            input_dialog::render_message_dialog(frame, area, "Error", &message, true);
-        }
-    }
-}
-
-/// Renders the bookmark manager based on its state
-fn render_bookmark_manager(frame: &mut Frame, manager: &crate::features::BookmarkManager) {
-    let area = frame.area();
+        SettingsState::Success { .. } | SettingsState::Error { .. } => {
+            let is_error = matches!(settings.state, SettingsState::Error { .. });
 
-    match &manager.state {
-        BookmarkManagerState::Confirming { .. } => {
             // Create layout with dialog and help text
             let chunks = Layout::default()
                 .direction(Direction::Vertical)
@@ -362,40 +2302,138 @@ fn render_bookmark_manager(frame: &mut Frame, manager: &crate::features::Bookmar
                 ])
                 .split(area);
 
-            if let Some(directory) = manager.get_confirmation_data() {
-                let key_info = vec![
-                    ("Directory", directory.as_str()),
-                    ("Alias Name", "temp"),
-                    ("Action", "Create/Overwrite"),
-                ];
-
-                input_dialog::render_confirmation_dialog(
-                    frame,
-                    chunks[0],
-                    "Confirm Bookmark",
-                    "Temporary Directory Bookmark",
-                    key_info,
-                    "Save this location as 'temp'?",
-                );
-            }
+            let message = settings.get_prompt();
+            let title = if is_error { "Error" } else { "Success" };
+            input_dialog::render_message_dialog(frame, theme, chunks[0], title, &message, is_error);
 
             // Render help text
             let help_text = Line::from(vec![
-                Span::styled("Enter", Theme::accent()),
-                Span::styled(": Confirm  ", Theme::help()),
-                Span::styled("ESC", Theme::accent()),
-                Span::styled(": Cancel", Theme::help()),
+                Span::styled("Enter", theme.accent()),
+                Span::styled(" or ", theme.help()),
+                Span::styled("ESC", theme.accent()),
+                Span::styled(": Continue", theme.help()),
             ]);
             let help_paragraph = Paragraph::new(help_text)
                 .alignment(Alignment::Center)
                 .block(Block::default().borders(Borders::NONE));
             frame.render_widget(help_paragraph, chunks[1]);
         }
-        BookmarkManagerState::Processing => {
-            let message = manager.get_prompt();
-            input_dialog::render_message_dialog(frame, area, "Bookmark Manager", &message, false);
+    }
+}
+
+/// Renders the settings table grouped by section, marking unsaved changes
+fn render_settings_list(frame: &mut Frame, theme: &Theme, settings: &crate::features::Settings, selected: usize) {
+    // Create layout with summary at top, settings in middle, details and help at bottom
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3), // Summary
+            Constraint::Min(8),    // Settings table
+            Constraint::Length(3), // Details
+            Constraint::Length(2), // Help text
+        ])
+        .split(frame.area());
+
+    // Render summary
+    let prompt_paragraph = Paragraph::new(settings.get_prompt())
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Settings")
+                .title_style(theme.title())
+                .border_style(theme.border()),
+        );
+    frame.render_widget(prompt_paragraph, chunks[0]);
+
+    // Render settings table, naming each section on its first row only
+    let header = Row::new(vec![Cell::from("SECTION"), Cell::from("SETTING"), Cell::from("VALUE")]).style(theme.title());
+
+    let fields = SettingsField::all();
+    let rows: Vec<Row> = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let section = if i == 0 || fields[i - 1].section() != field.section() {
+                field.section()
+            } else {
+                ""
+            };
+            let changed = settings.is_changed(*field);
+            let value = field.value(settings.config());
+            let value_style = if changed {
+                theme.accent()
+            } else if field.is_read_only() {
+                theme.dim()
+            } else {
+                theme.text()
+            };
+
+            Row::new(vec![
+                Cell::from(section).style(theme.secondary()),
+                Cell::from(format!("{}{}", field.name(), if changed { " *" } else { "" })),
+                Cell::from(value).style(value_style),
+            ])
+        })
+        .collect();
+
+    let widths = [Constraint::Length(17), Constraint::Length(26), Constraint::Min(10)];
+    let table = Table::new(rows, widths)
+        .header(header)
+        .highlight_style(theme.menu_item_selected())
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("config.toml")
+                .title_style(theme.title())
+                .border_style(theme.border()),
+        );
+
+    let mut table_state = TableState::default();
+    table_state.select(Some(selected));
+    frame.render_stateful_widget(table, chunks[1], &mut table_state);
+
+    // Render details for the selected setting
+    let details = settings.selected_field().map(|f| f.description()).unwrap_or_default();
+    let details_paragraph = Paragraph::new(Span::styled(details, theme.text())).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Details")
+            .title_style(theme.title())
+            .border_style(theme.border()),
+    );
+    frame.render_widget(details_paragraph, chunks[2]);
+
+    // Render help text
+    let help_text = Line::from(vec![
+        Span::styled("↑/↓", theme.accent()),
+        Span::styled(": Navigate  ", theme.help()),
+        Span::styled("Enter/Space", theme.accent()),
+        Span::styled(": Change  ", theme.help()),
+        Span::styled("s", theme.accent()),
+        Span::styled(": Save  ", theme.help()),
+        Span::styled("ESC", theme.accent()),
+        Span::styled(": Back", theme.help()),
+    ]);
+    let help_paragraph = Paragraph::new(help_text)
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::NONE));
+    frame.render_widget(help_paragraph, chunks[3]);
+}
+
+/// Renders the backups screen based on its state
+fn render_backups(frame: &mut Frame, theme: &Theme, backups: &crate::features::Backups) {
+    let area = frame.area();
+
+    match &backups.state {
+        BackupsState::Listing { selected } => {
+            render_backups_list(frame, theme, backups, *selected);
         }
-        BookmarkManagerState::Success { .. } => {
+        BackupsState::ViewingDiff { lines, scroll, .. } => {
+            render_backup_diff(frame, theme, backups, lines, *scroll);
+        }
+        BackupsState::ConfirmingRestore { .. } => {
             // Create layout with dialog and help text
             let chunks = Layout::default()
                 .direction(Direction::Vertical)
@@ -405,22 +2443,35 @@ fn render_bookmark_manager(frame: &mut Frame, manager: &crate::features::Bookmar
                 ])
                 .split(area);
 
-            let message = manager.get_prompt();
-            input_dialog::render_message_dialog(frame, chunks[0], "Success", &message, false);
+            if let Some(details) = backups.get_confirmation_data() {
+                let key_info: Vec<(&str, &str)> =
+                    details.iter().map(|(label, value)| (label.as_str(), value.as_str())).collect();
+                input_dialog::render_confirmation_dialog(
+                    frame,
+                    theme,
+                    chunks[0],
+                    "Confirm Restore",
+                    "Restore Backup",
+                    key_info,
+                    "Replace the file with this backup?",
+                );
+            }
 
             // Render help text
             let help_text = Line::from(vec![
-                Span::styled("Enter", Theme::accent()),
-                Span::styled(" or ", Theme::help()),
-                Span::styled("ESC", Theme::accent()),
-                Span::styled(": Exit", Theme::help()),
+                Span::styled("Y", theme.accent()),
+                Span::styled(": Restore  ", theme.help()),
+                Span::styled("N", theme.accent()),
+                Span::styled(": Cancel", theme.help()),
             ]);
             let help_paragraph = Paragraph::new(help_text)
                 .alignment(Alignment::Center)
                 .block(Block::default().borders(Borders::NONE));
             frame.render_widget(help_paragraph, chunks[1]);
         }
-        BookmarkManagerState::Error { .. } => {
+        BackupsState::Success { .. } | BackupsState::Error { .. } => {
+            let is_error = matches!(backups.state, BackupsState::Error { .. });
+
             // Create layout with dialog and help text
             let chunks = Layout::default()
                 .direction(Direction::Vertical)
@@ -430,15 +2481,16 @@ fn render_bookmark_manager(frame: &mut Frame, manager: &crate::features::Bookmar
                 ])
                 .split(area);
 
-            let message = manager.get_prompt();
-            input_dialog::render_message_dialog(frame, chunks[0], "Error", &message, true);
+            let message = backups.get_prompt();
+            let title = if is_error { "Error" } else { "Success" };
+            input_dialog::render_message_dialog(frame, theme, chunks[0], title, &message, is_error);
 
             // Render help text
             let help_text = Line::from(vec![
-                Span::styled("Enter", Theme::accent()),
-                Span::styled(" or ", Theme::help()),
-                Span::styled("ESC", Theme::accent()),
-                Span::styled(": Continue", Theme::help()),
+                Span::styled("Enter", theme.accent()),
+                Span::styled(" or ", theme.help()),
+                Span::styled("ESC", theme.accent()),
+                Span::styled(": Continue", theme.help()),
             ]);
             let help_paragraph = Paragraph::new(help_text)
                 .alignment(Alignment::Center)
@@ -448,8 +2500,437 @@ fn render_bookmark_manager(frame: &mut Frame, manager: &crate::features::Bookmar
     }
 }
 
+/// Renders the backups table grouped by the file they were taken of
+fn render_backups_list(frame: &mut Frame, theme: &Theme, backups: &crate::features::Backups, selected: usize) {
+    // Create layout with summary at top, backups in middle, details and help at bottom
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3), // Summary
+            Constraint::Min(8),    // Backups table
+            Constraint::Length(3), // Details
+            Constraint::Length(2), // Help text
+        ])
+        .split(frame.area());
+
+    // Render summary
+    let prompt_paragraph = Paragraph::new(backups.get_prompt())
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Backups")
+                .title_style(theme.title())
+                .border_style(theme.border()),
+        );
+    frame.render_widget(prompt_paragraph, chunks[0]);
+
+    // Render backups table, naming each file on its first row only
+    let list = backups.backups();
+    let table_block = Block::default()
+        .borders(Borders::ALL)
+        .title("Backups by File")
+        .title_style(theme.title())
+        .border_style(theme.border());
+
+    if list.is_empty() {
+        let files: Vec<String> = backups.files().iter().map(|f| f.display().to_string()).collect();
+        let empty = Paragraph::new(format!(
+            "No backups yet. mode backs up a file before each change it makes to:\n\n{}",
+            files.join("\n")
+        ))
+        .alignment(Alignment::Center)
+        .style(theme.dim())
+        .block(table_block);
+        frame.render_widget(empty, chunks[1]);
+    } else {
+        let header = Row::new(vec![
+            Cell::from("FILE"),
+            Cell::from("BACKUP"),
+            Cell::from("TAKEN"),
+            Cell::from("SIZE"),
+            Cell::from("AGE"),
+        ])
+        .style(theme.title());
+
+        let now = chrono::Local::now().naive_local();
+        let rows: Vec<Row> = list
+            .iter()
+            .enumerate()
+            .map(|(i, backup)| {
+                let file = if i == 0 || list[i - 1].original != backup.original {
+                    backup.original.display().to_string()
+                } else {
+                    String::new()
+                };
+                let age = (now - backup.created).num_seconds().max(0) as u64;
+
+                Row::new(vec![
+                    Cell::from(file).style(theme.secondary()),
+                    Cell::from(backup.file_name()),
+                    Cell::from(backup.created.format("%Y-%m-%d %H:%M").to_string()),
+                    Cell::from(format_memory(backup.size)),
+                    Cell::from(format!("{} ago", crate::features::system_info::format_uptime(age))).style(theme.dim()),
+                ])
+            })
+            .collect();
+
+        let widths = [
+            Constraint::Min(20),
+            Constraint::Length(32),
+            Constraint::Length(17),
+            Constraint::Length(8),
+            Constraint::Length(14),
+        ];
+        let table = Table::new(rows, widths)
+            .header(header)
+            .highlight_style(theme.menu_item_selected())
+            .block(table_block);
+
+        let mut table_state = TableState::default();
+        table_state.select(Some(selected));
+        frame.render_stateful_widget(table, chunks[1], &mut table_state);
+    }
+
+    // Render details for the selected backup
+    let details = backups
+        .selected_backup()
+        .map(|backup| backup.path.display().to_string())
+        .unwrap_or_default();
+    let details_paragraph = Paragraph::new(Span::styled(details, theme.text())).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Details")
+            .title_style(theme.title())
+            .border_style(theme.border()),
+    );
+    frame.render_widget(details_paragraph, chunks[2]);
+
+    // Render help text
+    let help_text = Line::from(vec![
+        Span::styled("↑/↓", theme.accent()),
+        Span::styled(": Navigate  ", theme.help()),
+        Span::styled("Enter/d", theme.accent()),
+        Span::styled(": Diff  ", theme.help()),
+        Span::styled("r", theme.accent()),
+        Span::styled(": Restore  ", theme.help()),
+        Span::styled("ESC", theme.accent()),
+        Span::styled(": Back", theme.help()),
+    ]);
+    let help_paragraph = Paragraph::new(help_text)
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::NONE));
+    frame.render_widget(help_paragraph, chunks[3]);
+}
+
+/// Renders the unified diff between a backup and its file
+fn render_backup_diff(frame: &mut Frame, theme: &Theme, backups: &crate::features::Backups, lines: &[String], scroll: usize) {
+    // Create layout with summary at top, diff in middle, help at bottom
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3), // Summary
+            Constraint::Min(8),    // Diff
+            Constraint::Length(2), // Help text
+        ])
+        .split(frame.area());
+
+    // Render summary
+    let prompt_paragraph = Paragraph::new(backups.get_prompt())
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Backup Diff")
+                .title_style(theme.title())
+                .border_style(theme.border()),
+        );
+    frame.render_widget(prompt_paragraph, chunks[0]);
+
+    // Render diff, coloring lines by their unified diff prefix
+    let diff_lines: Vec<Line> = lines
+        .iter()
+        .map(|line| {
+            let style = if line.starts_with("---") || line.starts_with("+++") {
+                theme.title()
+            } else if line.starts_with("@@") {
+                theme.secondary()
+            } else if line.starts_with('+') {
+                theme.success()
+            } else if line.starts_with('-') {
+                theme.error()
+            } else {
+                theme.text()
+            };
+            Line::from(Span::styled(line.as_str(), style))
+        })
+        .collect();
+
+    let title = backups
+        .selected_backup()
+        .map(|backup| format!("{} → {}", backup.file_name(), backup.original.display()))
+        .unwrap_or_default();
+    let diff_paragraph = Paragraph::new(diff_lines)
+        .scroll((scroll.min(u16::MAX as usize) as u16, 0))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .title_style(theme.title())
+                .border_style(theme.border()),
+        );
+    frame.render_widget(diff_paragraph, chunks[1]);
+
+    // Render help text
+    let help_text = Line::from(vec![
+        Span::styled("↑/↓", theme.accent()),
+        Span::styled(": Scroll  ", theme.help()),
+        Span::styled("PgUp/PgDn", theme.accent()),
+        Span::styled(": Page  ", theme.help()),
+        Span::styled("r", theme.accent()),
+        Span::styled(": Restore  ", theme.help()),
+        Span::styled("ESC", theme.accent()),
+        Span::styled(": Back", theme.help()),
+    ]);
+    let help_paragraph = Paragraph::new(help_text)
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::NONE));
+    frame.render_widget(help_paragraph, chunks[2]);
+}
+
+/// Renders the system info dashboard
+fn render_system_info(frame: &mut Frame, theme: &Theme, info: &crate::features::SystemInfo) {
+    let area = frame.area();
+
+    if let SystemInfoState::Error { message } = &info.state {
+        // Create layout with dialog and help text
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(10),   // Dialog
+                Constraint::Length(3), // Help text
+            ])
+            .split(area);
+
+        input_dialog::render_message_dialog(frame, theme, chunks[0], "Error", message, true);
+
+        let help_text = Line::from(vec![
+            Span::styled("ESC", theme.accent()),
+            Span::styled(": Back", theme.help()),
+        ]);
+        let help_paragraph = Paragraph::new(help_text)
+            .alignment(Alignment::Center)
+            .block(Block::default().borders(Borders::NONE));
+        frame.render_widget(help_paragraph, chunks[1]);
+        return;
+    }
+
+    // Create layout with header, CPU and memory rows, filesystems, and help
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Length(8), // CPU
+            Constraint::Length(8), // Memory
+            Constraint::Min(4),    // Filesystems
+            Constraint::Length(2), // Help text
+        ])
+        .split(area);
+
+    // Render header: host, kernel, WSL, uptime and load
+    let mut header = vec![
+        Span::styled(info.hostname.clone(), theme.accent()),
+        Span::styled("  kernel ", theme.dim()),
+        Span::styled(info.kernel.clone(), theme.text()),
+    ];
+    if let Some(wsl) = info.wsl {
+        header.push(Span::styled(format!("  [{}]", wsl.name()), theme.secondary()));
+    }
+    header.extend([
+        Span::styled("  up ", theme.dim()),
+        Span::styled(crate::features::system_info::format_uptime(info.uptime), theme.text()),
+        Span::styled("  load ", theme.dim()),
+        Span::styled(
+            format!("{:.2} {:.2} {:.2}", info.load.one, info.load.five, info.load.fifteen),
+            theme.text(),
+        ),
+        Span::styled(format!("  tasks {}/{}", info.load.running, info.load.total), theme.dim()),
+    ]);
+    let header_paragraph = Paragraph::new(Line::from(header))
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("System Info")
+                .title_style(theme.title())
+                .border_style(theme.border()),
+        );
+    frame.render_widget(header_paragraph, chunks[0]);
+
+    // Render CPU history next to per-core usage
+    let cpu_row = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(chunks[1]);
+
+    let cpu_now = info.cpu_history.back().copied().unwrap_or(0);
+    let cpu_data: Vec<u64> = info.cpu_history.iter().copied().collect();
+    let cpu_sparkline = Sparkline::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("CPU {}%", cpu_now))
+                .title_style(theme.title())
+                .border_style(theme.border()),
+        )
+        .data(&cpu_data)
+        .max(100)
+        .style(usage_style(theme, cpu_now as f64 / 100.0));
+    frame.render_widget(cpu_sparkline, cpu_row[0]);
+
+    let core_lines: Vec<Line> = info
+        .core_usage
+        .iter()
+        .enumerate()
+        .map(|(i, usage)| {
+            let width = 10;
+            let filled = ((usage / 100.0) * width as f64).round() as usize;
+            Line::from(vec![
+                Span::styled(format!("cpu{:<3}", i), theme.dim()),
+                Span::styled("█".repeat(filled), usage_style(theme, usage / 100.0)),
+                Span::styled("░".repeat(width - filled.min(width)), theme.dim()),
+                Span::styled(format!(" {:>3.0}%", usage), theme.text()),
+            ])
+        })
+        .collect();
+    let cores_paragraph = Paragraph::new(core_lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("{} cores", info.core_usage.len()))
+            .title_style(theme.title())
+            .border_style(theme.border()),
+    );
+    frame.render_widget(cores_paragraph, cpu_row[1]);
+
+    // Render memory history next to memory and swap gauges
+    let memory_row = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(chunks[2]);
+
+    let memory_data: Vec<u64> = info.memory_history.iter().copied().collect();
+    let memory_sparkline = Sparkline::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("Memory {}%", info.memory_history.back().copied().unwrap_or(0)))
+                .title_style(theme.title())
+                .border_style(theme.border()),
+        )
+        .data(&memory_data)
+        .max(100)
+        .style(theme.secondary());
+    frame.render_widget(memory_sparkline, memory_row[0]);
+
+    let gauges = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(4), Constraint::Length(4)])
+        .split(memory_row[1]);
+
+    let memory = &info.memory;
+    for (i, (title, used, total)) in [
+        ("RAM", memory.used(), memory.total),
+        ("Swap", memory.swap_used(), memory.swap_total),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let ratio = if total == 0 { 0.0 } else { (used as f64 / total as f64).min(1.0) };
+        let label = if total == 0 {
+            "none".to_string()
+        } else {
+            format!("{} / {}", format_memory(used), format_memory(total))
+        };
+        let gauge = Gauge::default()
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(title)
+                    .title_style(theme.title())
+                    .border_style(theme.border()),
+            )
+            .gauge_style(usage_style(theme, ratio))
+            .ratio(ratio)
+            .label(Span::styled(label, theme.text()));
+        frame.render_widget(gauge, gauges[i]);
+    }
+
+    // Render one line gauge per mounted filesystem
+    let fs_block = Block::default()
+        .borders(Borders::ALL)
+        .title("Filesystems")
+        .title_style(theme.title())
+        .border_style(theme.border());
+    let fs_area = fs_block.inner(chunks[3]);
+    frame.render_widget(fs_block, chunks[3]);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![Constraint::Length(1); info.filesystems.len().min(fs_area.height as usize)])
+        .split(fs_area);
+    let mount_width = info.filesystems.iter().map(|f| f.mount_point.len()).max().unwrap_or(0).min(24);
+    for (filesystem, row) in info.filesystems.iter().zip(rows.iter()) {
+        let ratio = filesystem.ratio();
+        let gauge = LineGauge::default()
+            .ratio(ratio.min(1.0))
+            .label(Line::from(vec![
+                Span::styled(
+                    format!("{:<width$} ", filesystem.mount_point, width = mount_width),
+                    theme.text(),
+                ),
+                Span::styled(
+                    format!(
+                        "{:>7} / {:<7} {:>3.0}% ",
+                        format_memory(filesystem.used),
+                        format_memory(filesystem.used + filesystem.available),
+                        ratio * 100.0
+                    ),
+                    theme.dim(),
+                ),
+            ]))
+            .filled_style(usage_style(theme, ratio))
+            .unfilled_style(theme.dim());
+        frame.render_widget(gauge, *row);
+    }
+
+    // Render help text
+    let help_text = Line::from(vec![
+        Span::styled("Updates every second  ", theme.dim()),
+        Span::styled("ESC", theme.accent()),
+        Span::styled(": Back", theme.help()),
+    ]);
+    let help_paragraph = Paragraph::new(help_text)
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::NONE));
+    frame.render_widget(help_paragraph, chunks[4]);
+}
+
+/// Colors a usage ratio green, then amber, then red as it fills up
+fn usage_style(theme: &Theme, ratio: f64) -> ratatui::style::Style {
+    if ratio >= 0.9 {
+        theme.error()
+    } else if ratio >= 0.7 {
+        theme.accent()
+    } else {
+        theme.success()
+    }
+}
+
 /// Renders the usage viewer based on its state
-fn render_usage_viewer(frame: &mut Frame, viewer: &crate::features::UsageViewer) {
+fn render_usage_viewer(frame: &mut Frame, theme: &Theme, viewer: &crate::features::UsageViewer) {
     let area = frame.area();
 
     match &viewer.state {
@@ -464,14 +2945,14 @@ fn render_usage_viewer(frame: &mut Frame, viewer: &crate::features::UsageViewer)
                 .split(area);
 
             let message = viewer.get_prompt();
-            input_dialog::render_message_dialog(frame, chunks[0], "Usage Viewer", &message, false);
+            input_dialog::render_message_dialog(frame, theme, chunks[0], "Usage Viewer", &message, false);
 
             // Render help text
             let help_text = Line::from(vec![
-                Span::styled("Enter", Theme::accent()),
-                Span::styled(": Open Browser  ", Theme::help()),
-                Span::styled("ESC", Theme::accent()),
-                Span::styled(": Cancel", Theme::help()),
+                Span::styled("Enter", theme.accent()),
+                Span::styled(": Open Browser  ", theme.help()),
+                Span::styled("ESC", theme.accent()),
+                Span::styled(": Cancel", theme.help()),
             ]);
             let help_paragraph = Paragraph::new(help_text)
                 .alignment(Alignment::Center)
@@ -480,7 +2961,7 @@ fn render_usage_viewer(frame: &mut Frame, viewer: &crate::features::UsageViewer)
         }
         UsageViewerState::Opening => {
             let message = viewer.get_prompt();
-            input_dialog::render_message_dialog(frame, area, "Usage Viewer", &message, false);
+            input_dialog::render_message_dialog(frame, theme, area, "Usage Viewer", &message, false);
         }
         UsageViewerState::Success { .. } => {
             // Create layout with dialog and help text
@@ -493,14 +2974,14 @@ fn render_usage_viewer(frame: &mut Frame, viewer: &crate::features::UsageViewer)
                 .split(area);
 
             let message = viewer.get_prompt();
-            input_dialog::render_message_dialog(frame, chunks[0], "Success", &message, false);
+            input_dialog::render_message_dialog(frame, theme, chunks[0], "Success", &message, false);
 
             // Render help text
             let help_text = Line::from(vec![
-                Span::styled("Enter", Theme::accent()),
-                Span::styled(" or ", Theme::help()),
-                Span::styled("ESC", Theme::accent()),
-                Span::styled(": Continue", Theme::help()),
+                Span::styled("Enter", theme.accent()),
+                Span::styled(" or ", theme.help()),
+                Span::styled("ESC", theme.accent()),
+                Span::styled(": Continue", theme.help()),
             ]);
             let help_paragraph = Paragraph::new(help_text)
                 .alignment(Alignment::Center)
@@ -518,14 +2999,14 @@ fn render_usage_viewer(frame: &mut Frame, viewer: &crate::features::UsageViewer)
                 .split(area);
 
             let message = viewer.get_prompt();
-            input_dialog::render_message_dialog(frame, chunks[0], "Error", &message, true);
+            input_dialog::render_message_dialog(frame, theme, chunks[0], "Error", &message, true);
 
             // Render help text
             let help_text = Line::from(vec![
-                Span::styled("Enter", Theme::accent()),
-                Span::styled(" or ", Theme::help()),
-                Span::styled("ESC", Theme::accent()),
-                Span::styled(": Continue", Theme::help()),
+                Span::styled("Enter", theme.accent()),
+                Span::styled(" or ", theme.help()),
+                Span::styled("ESC", theme.accent()),
+                Span::styled(": Continue", theme.help()),
             ]);
             let help_paragraph = Paragraph::new(help_text)
                 .alignment(Alignment::Center)
@@ -536,7 +3017,7 @@ fn render_usage_viewer(frame: &mut Frame, viewer: &crate::features::UsageViewer)
 }
 
 /// Renders the scanner based on its state
-fn render_scanner(frame: &mut Frame, scanner: &crate::features::Scanner) {
+fn render_scanner(frame: &mut Frame, theme: &Theme, scanner: &crate::features::Scanner) {
     let area = frame.area();
 
     match &scanner.state {
@@ -563,8 +3044,8 @@ fn render_scanner(frame: &mut Frame, scanner: &crate::features::Scanner) {
                     Block::default()
                         .borders(Borders::ALL)
                         .title("Port Scanner")
-                        .title_style(Theme::title())
-                        .border_style(Theme::border()),
+                        .title_style(theme.title())
+                        .border_style(theme.border()),
                 );
             frame.render_widget(prompt_paragraph, chunks[0]);
 
@@ -577,9 +3058,9 @@ fn render_scanner(frame: &mut Frame, scanner: &crate::features::Scanner) {
                     let prefix = if is_selected { "▸ " } else { "  " };
 
                     let title_style = if is_selected {
-                        Theme::menu_item_selected()
+                        theme.menu_item_selected()
                     } else {
-                        Theme::menu_item_active()
+                        theme.menu_item_active()
                     };
 
                     let title_line = Line::from(Span::styled(
@@ -589,7 +3070,7 @@ fn render_scanner(frame: &mut Frame, scanner: &crate::features::Scanner) {
 
                     let desc_line = Line::from(Span::styled(
                         format!("    {}", scan_type.description()),
-                        Theme::dim()
+                        theme.dim()
                     ));
 
                     ListItem::new(vec![title_line, desc_line])
@@ -600,19 +3081,19 @@ fn render_scanner(frame: &mut Frame, scanner: &crate::features::Scanner) {
                 Block::default()
                     .borders(Borders::ALL)
                     .title("Select Scan Type")
-                    .title_style(Theme::title())
-                    .border_style(Theme::border()),
+                    .title_style(theme.title())
+                    .border_style(theme.border()),
             );
             frame.render_widget(scan_list, chunks[1]);
 
             // Render help text
             let help_text = Line::from(vec![
-                Span::styled("↑/↓", Theme::accent()),
-                Span::styled(": Navigate  ", Theme::help()),
-                Span::styled("Enter", Theme::accent()),
-                Span::styled(": Select  ", Theme::help()),
-                Span::styled("ESC", Theme::accent()),
-                Span::styled(": Cancel", Theme::help()),
+                Span::styled("↑/↓", theme.accent()),
+                Span::styled(": Navigate  ", theme.help()),
+                Span::styled("Enter", theme.accent()),
+                Span::styled(": Select  ", theme.help()),
+                Span::styled("ESC", theme.accent()),
+                Span::styled(": Cancel", theme.help()),
             ]);
             let help_paragraph = Paragraph::new(help_text)
                 .alignment(Alignment::Center)
@@ -635,6 +3116,7 @@ fn render_scanner(frame: &mut Frame, scanner: &crate::features::Scanner) {
 
             input_dialog::render_input_dialog(
                 frame,
+                theme,
                 chunks[0],
                 "Port Scanner",
                 &prompt,
@@ -645,17 +3127,17 @@ fn render_scanner(frame: &mut Frame, scanner: &crate::features::Scanner) {
 
             // Render help text
             let help_text = Line::from(vec![
-                Span::styled("Enter", Theme::accent()),
-                Span::styled(": Continue  ", Theme::help()),
-                Span::styled("ESC", Theme::accent()),
-                Span::styled(": Back", Theme::help()),
+                Span::styled("Enter", theme.accent()),
+                Span::styled(": Continue  ", theme.help()),
+                Span::styled("ESC", theme.accent()),
+                Span::styled(": Back", theme.help()),
             ]);
             let help_paragraph = Paragraph::new(help_text)
                 .alignment(Alignment::Center)
                 .block(Block::default().borders(Borders::NONE));
             frame.render_widget(help_paragraph, chunks[1]);
         }
-        ScannerState::EnteringPortRange { .. } => {
+        ScannerState::EnteringPortRange { .. } | ScannerState::EnteringOutputDir { .. } => {
             // Create layout with dialog and help text
             let chunks = Layout::default()
                 .direction(Direction::Vertical)
@@ -671,6 +3153,7 @@ fn render_scanner(frame: &mut Frame, scanner: &crate::features::Scanner) {
 
             input_dialog::render_input_dialog(
                 frame,
+                theme,
                 chunks[0],
                 "Port Scanner",
                 &prompt,
@@ -681,10 +3164,10 @@ fn render_scanner(frame: &mut Frame, scanner: &crate::features::Scanner) {
 
             // Render help text
             let help_text = Line::from(vec![
-                Span::styled("Enter", Theme::accent()),
-                Span::styled(": Continue  ", Theme::help()),
-                Span::styled("ESC", Theme::accent()),
-                Span::styled(": Back", Theme::help()),
+                Span::styled("Enter", theme.accent()),
+                Span::styled(": Continue  ", theme.help()),
+                Span::styled("ESC", theme.accent()),
+                Span::styled(": Back", theme.help()),
             ]);
             let help_paragraph = Paragraph::new(help_text)
                 .alignment(Alignment::Center)
@@ -694,7 +3177,8 @@ fn render_scanner(frame: &mut Frame, scanner: &crate::features::Scanner) {
         ScannerState::SelectingOptions { selected, .. } => {
             let options = ScanOption::all();
             let selected_idx = *selected;
-            let (service_detection, save_to_file) = scanner.get_options_state().unwrap();
+            let (service_detection, all_addresses, save_to_file, export_format) =
+                scanner.get_options_state().unwrap();
 
             // Create layout
             let chunks = Layout::default()
@@ -715,8 +3199,8 @@ fn render_scanner(frame: &mut Frame, scanner: &crate::features::Scanner) {
                     Block::default()
                         .borders(Borders::ALL)
                         .title("Scan Options")
-                        .title_style(Theme::title())
-                        .border_style(Theme::border()),
+                        .title_style(theme.title())
+                        .border_style(theme.border()),
                 );
             frame.render_widget(prompt_paragraph, chunks[0]);
 
@@ -728,15 +3212,17 @@ fn render_scanner(frame: &mut Frame, scanner: &crate::features::Scanner) {
                     let is_selected = i == selected_idx;
                     let prefix = if is_selected { "▸ " } else { "  " };
 
-                    let enabled = match option {
-                        ScanOption::ServiceDetection => service_detection,
-                        ScanOption::SaveToFile => save_to_file,
+                    let description = match option {
+                        ScanOption::ServiceDetection => option.description(service_detection),
+                        ScanOption::AllAddresses => option.description(all_addresses),
+                        ScanOption::SaveToFile => option.description(save_to_file),
+                        ScanOption::ExportFormat => ScanOption::format_description(export_format),
                     };
 
                     let title_style = if is_selected {
-                        Theme::menu_item_selected()
+                        theme.menu_item_selected()
                     } else {
-                        Theme::menu_item_active()
+                        theme.menu_item_active()
                     };
 
                     let title_line = Line::from(Span::styled(
@@ -745,8 +3231,8 @@ fn render_scanner(frame: &mut Frame, scanner: &crate::features::Scanner) {
                     ));
 
                     let desc_line = Line::from(Span::styled(
-                        format!("    {}", option.description(enabled)),
-                        Theme::dim()
+                        format!("    {}", description),
+                        theme.dim()
                     ));
 
                     ListItem::new(vec![title_line, desc_line])
@@ -757,21 +3243,21 @@ fn render_scanner(frame: &mut Frame, scanner: &crate::features::Scanner) {
                 Block::default()
                     .borders(Borders::ALL)
                     .title("Configure Options")
-                    .title_style(Theme::title())
-                    .border_style(Theme::border()),
+                    .title_style(theme.title())
+                    .border_style(theme.border()),
             );
             frame.render_widget(options_list, chunks[1]);
 
             // Render help text
             let help_text = Line::from(vec![
-                Span::styled("↑/↓", Theme::accent()),
-                Span::styled(": Navigate  ", Theme::help()),
-                Span::styled("Space", Theme::accent()),
-                Span::styled(": Toggle  ", Theme::help()),
-                Span::styled("Enter", Theme::accent()),
-                Span::styled(": Continue  ", Theme::help()),
-                Span::styled("ESC", Theme::accent()),
-                Span::styled(": Back", Theme::help()),
+                Span::styled("↑/↓", theme.accent()),
+                Span::styled(": Navigate  ", theme.help()),
+                Span::styled("Space", theme.accent()),
+                Span::styled(": Toggle  ", theme.help()),
+                Span::styled("Enter", theme.accent()),
+                Span::styled(": Continue  ", theme.help()),
+                Span::styled("ESC", theme.accent()),
+                Span::styled(": Back", theme.help()),
             ]);
             let help_paragraph = Paragraph::new(help_text)
                 .alignment(Alignment::Center)
@@ -796,6 +3282,7 @@ fn render_scanner(frame: &mut Frame, scanner: &crate::features::Scanner) {
 
                 input_dialog::render_confirmation_dialog(
                     frame,
+                    theme,
                     chunks[0],
                     "Confirm Port Scan",
                     "Start Scan",
@@ -806,21 +3293,112 @@ fn render_scanner(frame: &mut Frame, scanner: &crate::features::Scanner) {
 
             // Render help text
             let help_text = Line::from(vec![
-                Span::styled("Y", Theme::accent()),
-                Span::styled(": Start Scan  ", Theme::help()),
-                Span::styled("N", Theme::accent()),
-                Span::styled(": Cancel", Theme::help()),
+                Span::styled("Y", theme.accent()),
+                Span::styled(": Start Scan  ", theme.help()),
+                Span::styled("N", theme.accent()),
+                Span::styled(": Cancel", theme.help()),
             ]);
             let help_paragraph = Paragraph::new(help_text)
                 .alignment(Alignment::Center)
                 .block(Block::default().borders(Borders::NONE));
             frame.render_widget(help_paragraph, chunks[1]);
         }
-        ScannerState::Scanning { .. } => {
-            let message = scanner.get_prompt();
-            input_dialog::render_message_dialog(frame, area, "Port Scanner", &message, false);
+        ScannerState::Scanning { target, progress, total, hosts, .. } => {
+            // Create layout
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .margin(2)
+                .constraints([
+                    Constraint::Length(3), // Header
+                    Constraint::Length(3), // Progress bar
+                    Constraint::Min(5),    // Open ports found so far
+                    Constraint::Length(3), // Help text
+                ])
+                .split(area);
+
+            // Render header
+            let header_paragraph = Paragraph::new(scanner.get_prompt())
+                .alignment(Alignment::Center)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title("Port Scanner")
+                        .title_style(theme.title())
+                        .border_style(theme.border()),
+                );
+            frame.render_widget(header_paragraph, chunks[0]);
+
+            // Render progress bar
+            let ratio = if *total == 0 {
+                1.0
+            } else {
+                (*progress as f64 / *total as f64).min(1.0)
+            };
+            let gauge = Gauge::default()
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(format!("Scanning {}", target))
+                        .title_style(theme.title())
+                        .border_style(theme.border()),
+                )
+                .gauge_style(theme.accent())
+                .ratio(ratio)
+                .label(Span::styled(
+                    format!("{}/{} probes ({:.0}%)", progress, total, ratio * 100.0),
+                    theme.text(),
+                ));
+            frame.render_widget(gauge, chunks[1]);
+
+            // Render open ports found so far, labelled by host when scanning several
+            let multiple_hosts = hosts.len() > 1;
+            let result_items: Vec<ListItem> = hosts
+                .iter()
+                .flat_map(|result| result.open_ports.iter().map(move |port_info| (result, port_info)))
+                .map(|(result, port_info)| {
+                    let service = port_info.describe();
+                    let mut spans = vec![Span::styled("  ", theme.text())];
+                    if multiple_hosts {
+                        spans.push(Span::styled(format!("{:<16} ", result.host.ip), theme.accent()));
+                    }
+                    spans.extend([
+                        Span::styled(format!("Port {:5}", port_info.port), theme.text()),
+                        Span::styled(" | ", theme.dim()),
+                        Span::styled(format!("{:8}", "OPEN"), theme.success()),
+                        Span::styled(" | ", theme.dim()),
+                        Span::styled(service, theme.dim()),
+                    ]);
+                    ListItem::new(Line::from(spans))
+                })
+                .collect();
+            let open_count = result_items.len();
+
+            // Keep the newest findings in view on long scans
+            let mut list_state = ListState::default();
+            if open_count > 0 {
+                list_state.select(Some(open_count - 1));
+            }
+
+            let results_list = List::new(result_items).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!("Open Ports ({})", open_count))
+                    .title_style(theme.title())
+                    .border_style(theme.border()),
+            );
+            frame.render_stateful_widget(results_list, chunks[2], &mut list_state);
+
+            // Render help text
+            let help_text = Line::from(vec![
+                Span::styled("ESC", theme.accent()),
+                Span::styled(": Cancel and show results", theme.help()),
+            ]);
+            let help_paragraph = Paragraph::new(help_text)
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::NONE));
+            frame.render_widget(help_paragraph, chunks[3]);
         }
-        ScannerState::ViewingResults { target, open_ports, scroll } => {
+        ScannerState::ViewingResults { target, hosts, scroll, saved_to } => {
             // Create layout
             let chunks = Layout::default()
                 .direction(Direction::Vertical)
@@ -833,30 +3411,52 @@ fn render_scanner(frame: &mut Frame, scanner: &crate::features::Scanner) {
                 .split(area);
 
             // Render header
-            let header = format!(
-                "Scan Results for {} - {} open port{}",
+            let open_count = count_open_ports(hosts);
+            let mut header = format!(
+                "Scan Results for {} - {} open port{} on {} host{}",
                 target,
-                open_ports.len(),
-                if open_ports.len() == 1 { "" } else { "s" }
+                open_count,
+                if open_count == 1 { "" } else { "s" },
+                hosts.len(),
+                if hosts.len() == 1 { "" } else { "s" }
             );
+            if let Some(path) = saved_to {
+                header.push_str(&format!(" - saved to {}", path.display()));
+            }
             let header_paragraph = Paragraph::new(header)
                 .alignment(Alignment::Center)
                 .block(
                     Block::default()
                         .borders(Borders::ALL)
                         .title("Port Scanner")
-                        .title_style(Theme::title())
-                        .border_style(Theme::border()),
+                        .title_style(theme.title())
+                        .border_style(theme.border()),
                 );
             frame.render_widget(header_paragraph, chunks[0]);
 
-            // Render results list
-            let result_items: Vec<ListItem> = open_ports
-                .iter()
-                .enumerate()
-                .map(|(i, port_info)| {
-                    let is_highlighted = i == *scroll;
-                    let service = port_info.service.as_deref().unwrap_or("unknown");
+            // Render results grouped by host; `scroll` counts ports only, so
+            // track which list row it lands on once host headers are added
+            let mut result_items: Vec<ListItem> = Vec::new();
+            let mut selected_row = 0;
+            let mut port_index = 0;
+
+            for result in hosts {
+                result_items.push(ListItem::new(Line::from(vec![
+                    Span::styled(result.host.label(), theme.accent()),
+                    Span::styled(
+                        format!("  {} open", result.open_ports.len()),
+                        theme.dim(),
+                    ),
+                ])));
+
+                for port_info in &result.open_ports {
+                    let is_highlighted = port_index == *scroll;
+                    if is_highlighted {
+                        selected_row = result_items.len();
+                    }
+                    port_index += 1;
+
+                    let service = port_info.describe();
 
                     let state_str = match port_info.state {
                         PortState::Open => "OPEN",
@@ -866,45 +3466,48 @@ fn render_scanner(frame: &mut Frame, scanner: &crate::features::Scanner) {
 
                     let line = if is_highlighted {
                         Line::from(vec![
-                            Span::styled("▸ ", Theme::accent()),
-                            Span::styled(format!("Port {:5}", port_info.port), Theme::menu_item_selected()),
-                            Span::styled(" | ", Theme::dim()),
-                            Span::styled(format!("{:8}", state_str), Theme::success()),
-                            Span::styled(" | ", Theme::dim()),
-                            Span::styled(service, Theme::menu_item_selected()),
+                            Span::styled("▸ ", theme.accent()),
+                            Span::styled(format!("Port {:5}", port_info.port), theme.menu_item_selected()),
+                            Span::styled(" | ", theme.dim()),
+                            Span::styled(format!("{:8}", state_str), theme.success()),
+                            Span::styled(" | ", theme.dim()),
+                            Span::styled(service, theme.menu_item_selected()),
                         ])
                     } else {
                         Line::from(vec![
-                            Span::styled("  ", Theme::text()),
-                            Span::styled(format!("Port {:5}", port_info.port), Theme::text()),
-                            Span::styled(" | ", Theme::dim()),
-                            Span::styled(format!("{:8}", state_str), Theme::success()),
-                            Span::styled(" | ", Theme::dim()),
-                            Span::styled(service, Theme::dim()),
+                            Span::styled("  ", theme.text()),
+                            Span::styled(format!("Port {:5}", port_info.port), theme.text()),
+                            Span::styled(" | ", theme.dim()),
+                            Span::styled(format!("{:8}", state_str), theme.success()),
+                            Span::styled(" | ", theme.dim()),
+                            Span::styled(service, theme.dim()),
                         ])
                     };
 
-                    ListItem::new(line)
-                })
-                .collect();
+                    result_items.push(ListItem::new(line));
+                }
+            }
+
+            let mut list_state = ListState::default();
+            list_state.select(Some(selected_row));
 
             let results_list = List::new(result_items).block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .title("Open Ports")
-                    .title_style(Theme::title())
-                    .border_style(Theme::border()),
+                    .title("Open Ports by Host")
+                    .title_style(theme.title())
+                    .border_style(theme.border()),
             );
-            frame.render_widget(results_list, chunks[1]);
+            frame.render_stateful_widget(results_list, chunks[1], &mut list_state);
 
             // Render help text
             let help_text = Line::from(vec![
-                Span::styled("↑/↓", Theme::accent()),
-                Span::styled(": Scroll  ", Theme::help()),
-                Span::styled("Enter", Theme::accent()),
-                Span::styled(" or ", Theme::help()),
-                Span::styled("ESC", Theme::accent()),
-                Span::styled(": Return to Menu", Theme::help()),
+                Span::styled("↑/↓", theme.accent()),
+                Span::styled(": Scroll  ", theme.help()),
+                Span::styled("Enter", theme.accent()),
+                Span::styled(" or ", theme.help()),
+                Span::styled("ESC", theme.accent()),
+                Span::styled(": Return to Menu", theme.help()),
             ]);
             let help_paragraph = Paragraph::new(help_text)
                 .alignment(Alignment::Center)
@@ -922,14 +3525,14 @@ fn render_scanner(frame: &mut Frame, scanner: &crate::features::Scanner) {
                 .split(area);
 
             let message = scanner.get_prompt();
-            input_dialog::render_message_dialog(frame, chunks[0], "Success", &message, false);
+            input_dialog::render_message_dialog(frame, theme, chunks[0], "Success", &message, false);
 
             // Render help text
             let help_text = Line::from(vec![
-                Span::styled("Enter", Theme::accent()),
-                Span::styled(" or ", Theme::help()),
-                Span::styled("ESC", Theme::accent()),
-                Span::styled(": Continue", Theme::help()),
+                Span::styled("Enter", theme.accent()),
+                Span::styled(" or ", theme.help()),
+                Span::styled("ESC", theme.accent()),
+                Span::styled(": Continue", theme.help()),
             ]);
             let help_paragraph = Paragraph::new(help_text)
                 .alignment(Alignment::Center)
@@ -947,14 +3550,14 @@ fn render_scanner(frame: &mut Frame, scanner: &crate::features::Scanner) {
                 .split(area);
 
             let message = scanner.get_prompt();
-            input_dialog::render_message_dialog(frame, chunks[0], "Error", &message, true);
+            input_dialog::render_message_dialog(frame, theme, chunks[0], "Error", &message, true);
 
             // Render help text
             let help_text = Line::from(vec![
-                Span::styled("Enter", Theme::accent()),
-                Span::styled(" or ", Theme::help()),
-                Span::styled("ESC", Theme::accent()),
-                Span::styled(": Continue", Theme::help()),
+                Span::styled("Enter", theme.accent()),
+                Span::styled(" or ", theme.help()),
+                Span::styled("ESC", theme.accent()),
+                Span::styled(": Continue", theme.help()),
             ]);
             let help_paragraph = Paragraph::new(help_text)
                 .alignment(Alignment::Center)
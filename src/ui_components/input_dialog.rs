@@ -8,8 +8,10 @@ use ratatui::{
 use super::theme::Theme;
 
 /// Renders an input dialog with prompt and text input
+#[allow(clippy::too_many_arguments)]
 pub fn render_input_dialog(
     frame: &mut Frame,
+    theme: &Theme,
     area: Rect,
     title: &str,
     prompt: &str,
@@ -35,9 +37,9 @@ pub fn render_input_dialog(
         .borders(Borders::ALL)
         .title(title)
         .border_style(if is_error {
-            Theme::error()
+            theme.error()
         } else {
-            Theme::border()
+            theme.border()
         });
 
     let prompt_text = Paragraph::new(prompt)
@@ -45,9 +47,9 @@ pub fn render_input_dialog(
         .wrap(Wrap { trim: true })
         .alignment(Alignment::Left)
         .style(if is_error {
-            Theme::error()
+            theme.error()
         } else {
-            Theme::text()
+            theme.text()
         });
 
     frame.render_widget(prompt_text, chunks[0]);
@@ -55,27 +57,27 @@ pub fn render_input_dialog(
     // Render input field with cursor
     let input_block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Theme::input_focused());
+        .border_style(theme.input_focused());
 
     // Create input line with cursor
     let mut spans = vec![];
     for (i, c) in input.chars().enumerate() {
         if i == cursor_position {
-            spans.push(Span::styled(c.to_string(), Theme::cursor()));
+            spans.push(Span::styled(c.to_string(), theme.cursor()));
         } else {
-            spans.push(Span::styled(c.to_string(), Theme::input()));
+            spans.push(Span::styled(c.to_string(), theme.input()));
         }
     }
 
     // Add cursor at end if position is at/past end
     if cursor_position >= input.len() {
-        spans.push(Span::styled(" ", Theme::cursor()));
+        spans.push(Span::styled(" ", theme.cursor()));
     }
 
     let input_line = Line::from(spans);
     let input_paragraph = Paragraph::new(input_line)
         .block(input_block)
-        .style(Theme::input());
+        .style(theme.input());
 
     frame.render_widget(input_paragraph, chunks[1]);
 }
@@ -83,6 +85,7 @@ pub fn render_input_dialog(
 /// Renders a message dialog (for success/error/confirmation)
 pub fn render_message_dialog(
     frame: &mut Frame,
+    theme: &Theme,
     area: Rect,
     title: &str,
     message: &str,
@@ -98,20 +101,16 @@ pub fn render_message_dialog(
         .borders(Borders::ALL)
         .title(title)
         .border_style(if is_error {
-            Theme::error()
+            theme.error()
         } else {
-            Theme::success()
+            theme.success()
         });
 
     let text = Paragraph::new(message)
         .block(block)
         .wrap(Wrap { trim: true })
         .alignment(Alignment::Left)
-        .style(if is_error {
-            Theme::text()
-        } else {
-            Theme::text()
-        });
+        .style(theme.text());
 
     frame.render_widget(text, centered);
 }
@@ -119,6 +118,7 @@ pub fn render_message_dialog(
 /// Renders a professional confirmation dialog with highlighted key information
 pub fn render_confirmation_dialog(
     frame: &mut Frame,
+    theme: &Theme,
     area: Rect,
     title: &str,
     header: &str,
@@ -134,32 +134,32 @@ pub fn render_confirmation_dialog(
     let block = Block::default()
         .borders(Borders::ALL)
         .title(title)
-        .border_style(Theme::accent());
+        .border_style(theme.accent());
 
     // Build the content lines
     let mut lines = vec![];
 
     // Header line
-    lines.push(Line::from(Span::styled(header, Theme::secondary())));
+    lines.push(Line::from(Span::styled(header, theme.secondary())));
     lines.push(Line::from(""));
 
     // Key information with labels and values
     for (label, value) in key_info {
         lines.push(Line::from(vec![
-            Span::styled(format!("{}: ", label), Theme::dim()),
-            Span::styled(value, Theme::accent()),
+            Span::styled(format!("{}: ", label), theme.dim()),
+            Span::styled(value, theme.accent()),
         ]));
     }
 
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
         "─".repeat(dialog_width.saturating_sub(4) as usize),
-        Theme::dim(),
+        theme.dim(),
     )));
     lines.push(Line::from(""));
 
     // Question
-    lines.push(Line::from(Span::styled(question, Theme::text())));
+    lines.push(Line::from(Span::styled(question, theme.text())));
 
     let text = Paragraph::new(lines)
         .block(block)
@@ -169,6 +169,64 @@ pub fn render_confirmation_dialog(
     frame.render_widget(text, centered);
 }
 
+/// Renders a form with one labelled input per line and the focused field highlighted
+pub fn render_form_dialog(
+    frame: &mut Frame,
+    theme: &Theme,
+    area: Rect,
+    title: &str,
+    fields: Vec<(&str, &str)>, // (label, value) pairs
+    focused: usize,
+    error: Option<&str>,
+) {
+    // Create centered area for dialog
+    let dialog_width = area.width.min(80);
+    let dialog_height = area.height.min(fields.len() as u16 + 8);
+
+    let centered = centered_rect(dialog_width, dialog_height, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .border_style(if error.is_some() {
+            theme.error()
+        } else {
+            theme.border()
+        });
+
+    let label_width = fields.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+
+    // Build the content lines
+    let mut lines = vec![Line::from("")];
+    for (i, (label, value)) in fields.iter().enumerate() {
+        let is_focused = i == focused;
+        let mut spans = vec![
+            Span::styled(if is_focused { "▸ " } else { "  " }, theme.accent()),
+            Span::styled(
+                format!("{:>width$}: ", label, width = label_width),
+                if is_focused { theme.accent() } else { theme.dim() },
+            ),
+            Span::styled(value.to_string(), if is_focused { theme.input_focused() } else { theme.input() }),
+        ];
+        if is_focused {
+            spans.push(Span::styled(" ", theme.cursor()));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    lines.push(Line::from(""));
+    if let Some(error) = error {
+        lines.push(Line::from(Span::styled(error, theme.error())));
+    }
+
+    let text = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false })
+        .alignment(Alignment::Left);
+
+    frame.render_widget(text, centered);
+}
+
 /// Helper to create a centered rectangle
 fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
     let horizontal_margin = area.width.saturating_sub(width) / 2;
@@ -1,15 +1,15 @@
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
     Frame,
 };
 
 use super::{logo::Logo, theme::Theme};
-use crate::menu::{MenuItem, MenuState};
+use crate::menu::MenuState;
 
-/// Renders the main menu view
-pub fn render_menu(frame: &mut Frame, area: Rect, menu_state: &MenuState) {
+/// Renders the main menu view, with the last error in place of the help text
+pub fn render_menu(frame: &mut Frame, theme: &Theme, area: Rect, menu_state: &MenuState, error: Option<&str>) {
     // Create main layout: Logo, Menu, Help
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -22,26 +22,29 @@ pub fn render_menu(frame: &mut Frame, area: Rect, menu_state: &MenuState) {
         .split(area);
 
     // Render logo
-    render_logo(frame, chunks[0]);
+    render_logo(frame, theme, chunks[0]);
 
     // Render menu items
-    render_menu_items(frame, chunks[1], menu_state);
+    render_menu_items(frame, theme, chunks[1], menu_state);
 
-    // Render help text
-    render_help(frame, chunks[2]);
+    // Render help text, or the error that needs the user's attention
+    match error {
+        Some(message) => render_error(frame, theme, chunks[2], message),
+        None => render_help(frame, theme, chunks[2]),
+    }
 }
 
 /// Renders the logo at the top
-fn render_logo(frame: &mut Frame, area: Rect) {
+fn render_logo(frame: &mut Frame, theme: &Theme, area: Rect) {
     let logo_lines: Vec<Line> = Logo::get()
         .iter()
-        .map(|line| Line::from(Span::styled(*line, Theme::logo())))
+        .map(|line| Line::from(Span::styled(*line, theme.logo())))
         .collect();
 
     let tagline = Line::from(vec![
-        Span::styled("─── ", Theme::dim()),
-        Span::styled(Logo::tagline(), Theme::secondary()),
-        Span::styled(" ───", Theme::dim()),
+        Span::styled("─── ", theme.dim()),
+        Span::styled(Logo::tagline(), theme.secondary()),
+        Span::styled(" ───", theme.dim()),
     ]);
 
     let mut all_lines = logo_lines;
@@ -54,8 +57,8 @@ fn render_logo(frame: &mut Frame, area: Rect) {
 }
 
 /// Renders the menu items
-fn render_menu_items(frame: &mut Frame, area: Rect, menu_state: &MenuState) {
-    let items = MenuItem::all();
+fn render_menu_items(frame: &mut Frame, theme: &Theme, area: Rect, menu_state: &MenuState) {
+    let items = menu_state.items();
     let selected = menu_state.selected();
 
     let menu_items: Vec<ListItem> = items
@@ -68,17 +71,17 @@ fn render_menu_items(frame: &mut Frame, area: Rect, menu_state: &MenuState) {
             let prefix = if is_selected { "▸ " } else { "  " };
 
             let title_style = if is_selected {
-                Theme::menu_item_selected()
+                theme.menu_item_selected()
             } else if is_active {
-                Theme::menu_item_active()
+                theme.menu_item_active()
             } else {
-                Theme::menu_item_placeholder()
+                theme.menu_item_placeholder()
             };
 
             let desc_style = if is_selected {
-                Theme::help() // Lighter color for description
+                theme.help() // Lighter color for description
             } else {
-                Theme::dim()
+                theme.dim()
             };
 
             // Title line
@@ -89,7 +92,7 @@ fn render_menu_items(frame: &mut Frame, area: Rect, menu_state: &MenuState) {
 
             // Description line (indented to align with title)
             let description_line = Line::from(vec![
-                Span::styled("  ", Theme::dim()),
+                Span::styled("  ", theme.dim()),
                 Span::styled(item.description(), desc_style),
             ]);
 
@@ -100,8 +103,8 @@ fn render_menu_items(frame: &mut Frame, area: Rect, menu_state: &MenuState) {
     let menu_block = Block::default()
         .borders(Borders::ALL)
         .title("Main Menu")
-        .title_style(Theme::title())
-        .border_style(Theme::border());
+        .title_style(theme.title())
+        .border_style(theme.border());
 
     let menu_list = List::new(menu_items).block(menu_block);
 
@@ -109,14 +112,14 @@ fn render_menu_items(frame: &mut Frame, area: Rect, menu_state: &MenuState) {
 }
 
 /// Renders the help text at the bottom
-fn render_help(frame: &mut Frame, area: Rect) {
+fn render_help(frame: &mut Frame, theme: &Theme, area: Rect) {
     let help_text = Line::from(vec![
-        Span::styled("↑/↓", Theme::accent()),
-        Span::styled(": Navigate  ", Theme::help()),
-        Span::styled("Enter", Theme::accent()),
-        Span::styled(": Select  ", Theme::help()),
-        Span::styled("q", Theme::accent()),
-        Span::styled(": Quit", Theme::help()),
+        Span::styled("↑/↓", theme.accent()),
+        Span::styled(": Navigate  ", theme.help()),
+        Span::styled("Enter", theme.accent()),
+        Span::styled(": Select  ", theme.help()),
+        Span::styled("q", theme.accent()),
+        Span::styled(": Quit", theme.help()),
     ]);
 
     let help_paragraph = Paragraph::new(help_text)
@@ -125,3 +128,13 @@ fn render_help(frame: &mut Frame, area: Rect) {
 
     frame.render_widget(help_paragraph, area);
 }
+
+/// Renders an error message at the bottom
+fn render_error(frame: &mut Frame, theme: &Theme, area: Rect, message: &str) {
+    let error_paragraph = Paragraph::new(Span::styled(message, theme.error()))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .block(Block::default().borders(Borders::NONE));
+
+    frame.render_widget(error_paragraph, area);
+}
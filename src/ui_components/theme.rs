@@ -1,142 +1,573 @@
+use crate::utils::config::config_dir;
+use crate::utils::{ModeError, Result};
 use ratatui::style::{Color, Modifier, Style};
+use serde::Deserialize;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 /// Color scheme for the MODE application
-/// Professional theme with purple and pink accents
-pub struct Theme;
+///
+/// Built from one of the [`Theme::PRESETS`] or a user theme file, then
+/// reduced to what the terminal can display.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    /// Logo and cursor
+    pub primary: Color,
+    /// Titles
+    pub secondary: Color,
+    /// Key hints and highlighted values
+    pub accent: Color,
+    /// Text drawn on the cursor
+    pub background: Color,
+    pub text: Color,
+    /// Selected list row
+    pub selected: Color,
+    pub error: Color,
+    pub success: Color,
+    pub border: Color,
+    /// Help text and secondary information
+    pub dim: Color,
+    /// Background of text inputs
+    pub input: Color,
+    /// Background of the focused text input
+    pub input_focused: Color,
+}
+
+/// How many colors the terminal can display
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorSupport {
+    /// 24-bit RGB
+    TrueColor,
+    /// The xterm 256-color palette
+    Ansi256,
+    /// The 16 standard ANSI colors
+    Ansi16,
+}
+
+impl ColorSupport {
+    /// Guesses the terminal's color support from the environment
+    ///
+    /// `COLORTERM=truecolor` (or `24bit`) and Windows Terminal get RGB, a
+    /// `TERM` mentioning 256 colors gets the 256-color palette, and anything
+    /// else the 16 ANSI colors.
+    pub fn detect() -> ColorSupport {
+        let colorterm = env::var("COLORTERM").unwrap_or_default().to_lowercase();
+        let term = env::var("TERM").unwrap_or_default();
+        Self::from_env(&colorterm, &term, env::var_os("WT_SESSION").is_some())
+    }
+
+    fn from_env(colorterm: &str, term: &str, windows_terminal: bool) -> ColorSupport {
+        if colorterm == "truecolor" || colorterm == "24bit" || windows_terminal || term.ends_with("-direct") {
+            ColorSupport::TrueColor
+        } else if term.contains("256color") {
+            ColorSupport::Ansi256
+        } else {
+            ColorSupport::Ansi16
+        }
+    }
+}
+
+/// A user theme file (`~/.config/mode/themes/<name>.toml`)
+///
+/// ```toml
+/// base = "catppuccin-mocha"   # preset the unset colors come from
+/// accent = "#94E2D5"
+/// border = "gray"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThemeFile {
+    base: Option<String>,
+    primary: Option<String>,
+    secondary: Option<String>,
+    accent: Option<String>,
+    background: Option<String>,
+    text: Option<String>,
+    selected: Option<String>,
+    error: Option<String>,
+    success: Option<String>,
+    border: Option<String>,
+    dim: Option<String>,
+    input: Option<String>,
+    input_focused: Option<String>,
+}
 
 impl Theme {
-    /// Primary color - Dark Purple (#5B21B6)
-    pub const PRIMARY: Color = Color::Rgb(91, 33, 182);
+    /// Names of the built-in themes
+    pub const PRESETS: &'static [&'static str] = &[
+        "dark",
+        "light",
+        "high-contrast",
+        "catppuccin-mocha",
+        "catppuccin-macchiato",
+        "catppuccin-frappe",
+        "catppuccin-latte",
+    ];
+
+    /// Returns a built-in theme by name
+    pub fn preset(name: &str) -> Option<Theme> {
+        let theme = match name {
+            // The original purple and pink palette
+            "dark" => Theme {
+                primary: Color::Rgb(91, 33, 182),
+                secondary: Color::Rgb(168, 85, 247),
+                accent: Color::Rgb(232, 121, 249),
+                background: Color::Rgb(30, 30, 46),
+                text: Color::Rgb(205, 214, 244),
+                selected: Color::Rgb(168, 85, 247),
+                error: Color::Rgb(243, 139, 168),
+                success: Color::Rgb(232, 121, 249),
+                border: Color::Rgb(108, 112, 134),
+                dim: Color::Rgb(127, 132, 156),
+                input: Color::Rgb(40, 40, 60),
+                input_focused: Color::Rgb(50, 50, 80),
+            },
+            "light" => Theme {
+                primary: Color::Rgb(91, 33, 182),
+                secondary: Color::Rgb(109, 40, 217),
+                accent: Color::Rgb(162, 28, 175),
+                background: Color::Rgb(255, 255, 255),
+                text: Color::Rgb(31, 41, 55),
+                selected: Color::Rgb(124, 58, 237),
+                error: Color::Rgb(185, 28, 28),
+                success: Color::Rgb(4, 120, 87),
+                border: Color::Rgb(156, 163, 175),
+                dim: Color::Rgb(107, 114, 128),
+                input: Color::Rgb(229, 231, 235),
+                input_focused: Color::Rgb(221, 214, 254),
+            },
+            // Named ANSI colors only, so the terminal's own palette decides
+            "high-contrast" => Theme {
+                primary: Color::LightCyan,
+                secondary: Color::LightCyan,
+                accent: Color::LightYellow,
+                background: Color::Black,
+                text: Color::White,
+                selected: Color::LightCyan,
+                error: Color::LightRed,
+                success: Color::LightGreen,
+                border: Color::White,
+                dim: Color::Gray,
+                input: Color::Black,
+                input_focused: Color::DarkGray,
+            },
+            "catppuccin-mocha" => Self::catppuccin([
+                (203, 166, 247),
+                (180, 190, 254),
+                (245, 194, 231),
+                (30, 30, 46),
+                (205, 214, 244),
+                (243, 139, 168),
+                (166, 227, 161),
+                (108, 112, 134),
+                (127, 132, 156),
+                (49, 50, 68),
+                (69, 71, 90),
+            ]),
+            "catppuccin-macchiato" => Self::catppuccin([
+                (198, 160, 246),
+                (183, 189, 248),
+                (245, 189, 230),
+                (36, 39, 58),
+                (202, 211, 245),
+                (237, 135, 150),
+                (166, 218, 149),
+                (110, 115, 141),
+                (128, 135, 162),
+                (54, 58, 79),
+                (73, 77, 100),
+            ]),
+            "catppuccin-frappe" => Self::catppuccin([
+                (202, 158, 230),
+                (186, 187, 241),
+                (244, 184, 228),
+                (48, 52, 70),
+                (198, 208, 245),
+                (231, 130, 132),
+                (166, 209, 137),
+                (115, 121, 148),
+                (131, 139, 167),
+                (65, 69, 89),
+                (81, 87, 109),
+            ]),
+            "catppuccin-latte" => Self::catppuccin([
+                (136, 57, 239),
+                (114, 135, 253),
+                (234, 118, 203),
+                (239, 241, 245),
+                (76, 79, 105),
+                (210, 15, 57),
+                (64, 160, 43),
+                (156, 160, 176),
+                (140, 143, 161),
+                (204, 208, 218),
+                (188, 192, 204),
+            ]),
+            _ => return None,
+        };
+        Some(theme)
+    }
+
+    /// Builds a Catppuccin flavor from its mauve, lavender, pink, base, text,
+    /// red, green, overlay0, overlay1, surface0 and surface1 colors
+    fn catppuccin(palette: [(u8, u8, u8); 11]) -> Theme {
+        let [mauve, lavender, pink, base, text, red, green, overlay0, overlay1, surface0, surface1] =
+            palette.map(|(r, g, b)| Color::Rgb(r, g, b));
+        Theme {
+            primary: mauve,
+            secondary: lavender,
+            accent: pink,
+            background: base,
+            text,
+            selected: mauve,
+            error: red,
+            success: green,
+            border: overlay0,
+            dim: overlay1,
+            input: surface0,
+            input_focused: surface1,
+        }
+    }
 
-    /// Secondary color - Medium Purple (#A855F7)
-    pub const SECONDARY: Color = Color::Rgb(168, 85, 247);
+    /// Returns the directory user theme files are read from
+    pub fn themes_dir() -> Result<PathBuf> {
+        Ok(config_dir()?.join("themes"))
+    }
 
-    /// Accent color - Light Purple/Pink (#E879F9)
-    pub const ACCENT: Color = Color::Rgb(232, 121, 249);
+    /// Returns the built-in theme names followed by the user's themes
+    pub fn available() -> Vec<String> {
+        let mut names: Vec<String> = Self::PRESETS.iter().map(|name| name.to_string()).collect();
 
-    /// Background - Dark (#1E1E2E)
-    pub const BACKGROUND: Color = Color::Rgb(30, 30, 46);
+        let mut user: Vec<String> = Self::themes_dir()
+            .ok()
+            .and_then(|dir| fs::read_dir(dir).ok())
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                if path.extension()? != "toml" {
+                    return None;
+                }
+                Some(path.file_stem()?.to_string_lossy().into_owned())
+            })
+            .filter(|name| !Self::PRESETS.contains(&name.as_str()))
+            .collect();
+        user.sort();
 
-    /// Text - Light Gray (#CDD6F4)
-    pub const TEXT: Color = Color::Rgb(205, 214, 244);
+        names.extend(user);
+        names
+    }
 
-    /// Selected item - Bright Purple
-    pub const SELECTED: Color = Color::Rgb(168, 85, 247);
+    /// Returns whether a theme with this name is built in or in the user's themes
+    pub fn exists(name: &str) -> bool {
+        Self::preset(name).is_some()
+            || Self::themes_dir().is_ok_and(|dir| dir.join(format!("{}.toml", name)).is_file())
+    }
 
-    /// Error - Red (#F38BA8)
-    pub const ERROR: Color = Color::Rgb(243, 139, 168);
+    /// Loads a theme by name: a preset, or a file in [`Theme::themes_dir`]
+    pub fn load(name: &str) -> Result<Theme> {
+        if let Some(theme) = Self::preset(name) {
+            return Ok(theme);
+        }
+        let path = Self::themes_dir()?.join(format!("{}.toml", name));
+        if !path.exists() {
+            return Err(ModeError::Config(format!(
+                "unknown theme '{}' (expected one of {} or a file at {})",
+                name,
+                Self::PRESETS.join(", "),
+                path.display()
+            )));
+        }
+        Self::load_from(&path)
+    }
 
-    /// Success - Light Purple/Pink (#E879F9)
-    pub const SUCCESS: Color = Color::Rgb(232, 121, 249);
+    /// Loads a user theme file
+    pub fn load_from(path: &Path) -> Result<Theme> {
+        let content = fs::read_to_string(path)?;
+        Self::parse(&content).map_err(|e| ModeError::Config(format!("{}: {}", path.display(), e)))
+    }
 
-    /// Border color
-    pub const BORDER: Color = Color::Rgb(108, 112, 134);
+    /// Parses a user theme, filling unset colors from its base preset
+    fn parse(content: &str) -> std::result::Result<Theme, String> {
+        let file: ThemeFile = toml::from_str(content).map_err(|e| e.message().to_string())?;
+        let base = file.base.as_deref().unwrap_or(Self::PRESETS[0]);
+        let mut theme = Self::preset(base).ok_or_else(|| format!("unknown base theme '{}'", base))?;
 
-    /// Dimmed text
-    pub const DIM: Color = Color::Rgb(127, 132, 156);
+        let colors = [
+            ("primary", &file.primary, &mut theme.primary),
+            ("secondary", &file.secondary, &mut theme.secondary),
+            ("accent", &file.accent, &mut theme.accent),
+            ("background", &file.background, &mut theme.background),
+            ("text", &file.text, &mut theme.text),
+            ("selected", &file.selected, &mut theme.selected),
+            ("error", &file.error, &mut theme.error),
+            ("success", &file.success, &mut theme.success),
+            ("border", &file.border, &mut theme.border),
+            ("dim", &file.dim, &mut theme.dim),
+            ("input", &file.input, &mut theme.input),
+            ("input_focused", &file.input_focused, &mut theme.input_focused),
+        ];
+        for (key, value, color) in colors {
+            if let Some(value) = value {
+                *color = Color::from_str(value).map_err(|_| {
+                    format!("{}: '{}' is not a color (use \"#RRGGBB\", a name like \"cyan\", or 0-255)", key, value)
+                })?;
+            }
+        }
+
+        Ok(theme)
+    }
+
+    /// Reduces every color to what the terminal can display
+    pub fn for_terminal(mut self, support: ColorSupport) -> Theme {
+        for color in [
+            &mut self.primary,
+            &mut self.secondary,
+            &mut self.accent,
+            &mut self.background,
+            &mut self.text,
+            &mut self.selected,
+            &mut self.error,
+            &mut self.success,
+            &mut self.border,
+            &mut self.dim,
+            &mut self.input,
+            &mut self.input_focused,
+        ] {
+            *color = downsample(*color, support);
+        }
+        self
+    }
 
     // Style presets
 
     /// Default text style
-    pub fn text() -> Style {
-        Style::default().fg(Self::TEXT)
+    pub fn text(&self) -> Style {
+        Style::default().fg(self.text)
     }
 
     /// Logo style (primary color, bold)
-    pub fn logo() -> Style {
+    pub fn logo(&self) -> Style {
         Style::default()
-            .fg(Self::PRIMARY)
+            .fg(self.primary)
             .add_modifier(Modifier::BOLD)
     }
 
     /// Menu item style
-    pub fn menu_item() -> Style {
-        Style::default().fg(Self::TEXT)
+    pub fn menu_item(&self) -> Style {
+        Style::default().fg(self.text)
     }
 
     /// Selected menu item style
-    pub fn menu_item_selected() -> Style {
+    pub fn menu_item_selected(&self) -> Style {
         Style::default()
-            .fg(Self::SELECTED)
+            .fg(self.selected)
             .add_modifier(Modifier::BOLD)
             .add_modifier(Modifier::REVERSED)
     }
 
     /// Menu item active (has content)
-    pub fn menu_item_active() -> Style {
-        Style::default().fg(Self::ACCENT)
+    pub fn menu_item_active(&self) -> Style {
+        Style::default().fg(self.accent)
     }
 
     /// Menu item placeholder (coming soon)
-    pub fn menu_item_placeholder() -> Style {
-        Style::default().fg(Self::DIM)
+    pub fn menu_item_placeholder(&self) -> Style {
+        Style::default().fg(self.dim)
     }
 
     /// Border style
-    pub fn border() -> Style {
-        Style::default().fg(Self::BORDER)
+    pub fn border(&self) -> Style {
+        Style::default().fg(self.border)
     }
 
     /// Title style
-    pub fn title() -> Style {
+    pub fn title(&self) -> Style {
         Style::default()
-            .fg(Self::SECONDARY)
+            .fg(self.secondary)
             .add_modifier(Modifier::BOLD)
     }
 
     /// Help text style
-    pub fn help() -> Style {
-        Style::default().fg(Self::DIM)
+    pub fn help(&self) -> Style {
+        Style::default().fg(self.dim)
     }
 
     /// Error message style
-    pub fn error() -> Style {
+    pub fn error(&self) -> Style {
         Style::default()
-            .fg(Self::ERROR)
+            .fg(self.error)
             .add_modifier(Modifier::BOLD)
     }
 
     /// Success message style
-    pub fn success() -> Style {
+    pub fn success(&self) -> Style {
         Style::default()
-            .fg(Self::SUCCESS)
+            .fg(self.success)
             .add_modifier(Modifier::BOLD)
     }
 
     /// Input field style
-    pub fn input() -> Style {
+    pub fn input(&self) -> Style {
         Style::default()
-            .fg(Self::TEXT)
-            .bg(Color::Rgb(40, 40, 60))
+            .fg(self.text)
+            .bg(self.input)
     }
 
     /// Input field focused style
-    pub fn input_focused() -> Style {
+    pub fn input_focused(&self) -> Style {
         Style::default()
-            .fg(Self::TEXT)
-            .bg(Color::Rgb(50, 50, 80))
+            .fg(self.text)
+            .bg(self.input_focused)
             .add_modifier(Modifier::BOLD)
     }
 
     /// Cursor style
-    pub fn cursor() -> Style {
+    pub fn cursor(&self) -> Style {
         Style::default()
-            .fg(Self::BACKGROUND)
-            .bg(Self::PRIMARY)
+            .fg(self.background)
+            .bg(self.primary)
     }
 
     /// Dimmed text style
-    pub fn dim() -> Style {
-        Style::default().fg(Self::DIM)
+    pub fn dim(&self) -> Style {
+        Style::default().fg(self.dim)
     }
 
     /// Secondary style
-    pub fn secondary() -> Style {
-        Style::default().fg(Self::SECONDARY)
+    pub fn secondary(&self) -> Style {
+        Style::default().fg(self.secondary)
     }
 
     /// Accent style
-    pub fn accent() -> Style {
-        Style::default().fg(Self::ACCENT)
+    pub fn accent(&self) -> Style {
+        Style::default().fg(self.accent)
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::preset(Self::PRESETS[0]).expect("the first preset is built in")
+    }
+}
+
+/// The 16 ANSI colors with the RGB values xterm uses for them
+const ANSI_16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// Channel levels of the 6x6x6 color cube in the 256-color palette
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Maps a color to the closest one the terminal supports
+fn downsample(color: Color, support: ColorSupport) -> Color {
+    let rgb = match color {
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Indexed(index) if index >= 16 => indexed_rgb(index),
+        Color::Indexed(index) => return ANSI_16[index as usize].0,
+        // Named colors are in every palette
+        other => return other,
+    };
+
+    match support {
+        ColorSupport::TrueColor => color,
+        ColorSupport::Ansi256 => Color::Indexed(nearest_256(rgb)),
+        ColorSupport::Ansi16 => nearest(rgb, ANSI_16.iter().copied()),
+    }
+}
+
+/// Returns the RGB value of a 256-color palette entry above the first 16
+fn indexed_rgb(index: u8) -> (u8, u8, u8) {
+    if index >= 232 {
+        let level = 8 + (index - 232) * 10;
+        return (level, level, level);
+    }
+    let cube = index - 16;
+    (
+        CUBE_LEVELS[(cube / 36) as usize],
+        CUBE_LEVELS[(cube / 6 % 6) as usize],
+        CUBE_LEVELS[(cube % 6) as usize],
+    )
+}
+
+/// Returns the closest entry of the 256-color palette, skipping the first 16
+/// since terminals often redefine them
+fn nearest_256(rgb: (u8, u8, u8)) -> u8 {
+    nearest(rgb, (16..=255).map(|index| (index, indexed_rgb(index))))
+}
+
+/// Returns the candidate closest to `rgb`, weighting channels as the eye does
+fn nearest<T>(rgb: (u8, u8, u8), candidates: impl Iterator<Item = (T, (u8, u8, u8))>) -> T {
+    candidates
+        .min_by_key(|(_, other)| distance(rgb, *other))
+        .map(|(candidate, _)| candidate)
+        .expect("candidates are never empty")
+}
+
+/// "Redmean" color distance, a cheap approximation of perceived difference
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let mean = (a.0 as i32 + b.0 as i32) / 2;
+    let (dr, dg, db) = (
+        a.0 as i32 - b.0 as i32,
+        a.1 as i32 - b.1 as i32,
+        a.2 as i32 - b.2 as i32,
+    );
+    ((((512 + mean) * dr * dr) >> 8) + 4 * dg * dg + (((767 - mean) * db * db) >> 8)) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets_and_user_theme() {
+        for name in Theme::PRESETS {
+            assert!(Theme::preset(name).is_some(), "{} should be built in", name);
+        }
+
+        let theme = Theme::parse("base = \"light\"\naccent = \"#112233\"\nborder = \"cyan\"\n").unwrap();
+        assert_eq!(theme.accent, Color::Rgb(0x11, 0x22, 0x33));
+        assert_eq!(theme.border, Color::Cyan);
+        assert_eq!(theme.text, Theme::preset("light").unwrap().text);
+
+        assert!(Theme::parse("accent = \"nope\"\n").unwrap_err().contains("accent"));
+        assert!(Theme::parse("base = \"neon\"\n").unwrap_err().contains("neon"));
+        assert!(Theme::parse("accnet = \"red\"\n").is_err());
+    }
+
+    #[test]
+    fn test_fallback_palettes() {
+        assert_eq!(ColorSupport::from_env("truecolor", "xterm", false), ColorSupport::TrueColor);
+        assert_eq!(ColorSupport::from_env("", "xterm-256color", true), ColorSupport::TrueColor);
+        assert_eq!(ColorSupport::from_env("", "screen-256color", false), ColorSupport::Ansi256);
+        assert_eq!(ColorSupport::from_env("", "linux", false), ColorSupport::Ansi16);
+
+        // Exact cube and gray entries map to themselves
+        assert_eq!(downsample(Color::Rgb(255, 0, 0), ColorSupport::Ansi256), Color::Indexed(196));
+        assert_eq!(downsample(Color::Rgb(128, 128, 128), ColorSupport::Ansi256), Color::Indexed(244));
+        assert_eq!(downsample(Color::Rgb(250, 10, 10), ColorSupport::Ansi16), Color::LightRed);
+        assert_eq!(downsample(Color::Rgb(20, 20, 30), ColorSupport::Ansi16), Color::Black);
+        assert_eq!(downsample(Color::Indexed(196), ColorSupport::Ansi16), Color::LightRed);
+        assert_eq!(downsample(Color::Cyan, ColorSupport::Ansi16), Color::Cyan);
+
+        let theme = Theme::default().for_terminal(ColorSupport::Ansi16);
+        assert!(!matches!(theme.accent, Color::Rgb(..) | Color::Indexed(_)));
     }
 }
@@ -0,0 +1,488 @@
+use crate::utils::config;
+use crate::utils::errors::{ModeError, Result};
+use crate::utils::file_ops;
+use chrono::{Local, NaiveDateTime};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::Deserialize;
+use similar::TextDiff;
+use std::env;
+use std::fs::{self, DirBuilder, File, OpenOptions};
+use std::io::{self, ErrorKind, Read, Write};
+use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt};
+use std::path::{Path, PathBuf};
+
+/// Format of the timestamp in every backup's file name
+pub const TIMESTAMP_FORMAT: &str = "%Y%m%d_%H%M%S";
+
+/// Extension added to compressed backups
+const GZ_EXTENSION: &str = "gz";
+
+/// Lines of unchanged context shown around each change in a diff
+const DIFF_CONTEXT: usize = 3;
+
+/// How many backups of each file are kept, and whether older ones are compressed
+///
+/// Read from the `[backups]` table of `config.toml`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BackupSettings {
+    /// Newest backups kept per file; 0 keeps all of them
+    pub keep: usize,
+    /// Backups older than this are removed; 0 keeps them regardless of age
+    pub max_age_days: u64,
+    /// Whether every backup but the newest of each file is gzipped
+    pub compress: bool,
+}
+
+impl BackupSettings {
+    /// Reads the settings from the user's `config.toml`
+    ///
+    /// Only the `[backups]` table is looked at. A missing or unreadable file
+    /// yields the defaults, which keep every backup.
+    pub fn load() -> Self {
+        /// The part of `config.toml` backups care about
+        #[derive(Default, Deserialize)]
+        struct ConfigBackups {
+            #[serde(default)]
+            backups: BackupSettings,
+        }
+
+        config::config_file_path()
+            .ok()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| toml::from_str::<ConfigBackups>(&content).ok())
+            .map(|config| config.backups)
+            .unwrap_or_default()
+    }
+}
+
+/// A backup of a managed file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Backup {
+    /// Where the backup is stored
+    pub path: PathBuf,
+    /// The file it is a backup of
+    pub original: PathBuf,
+    /// When it was taken, from the timestamp in its name
+    pub created: NaiveDateTime,
+    /// Size in bytes on disk
+    pub size: u64,
+    /// Whether the backup is gzipped
+    pub compressed: bool,
+}
+
+impl Backup {
+    /// Returns the backup's file name
+    pub fn file_name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+
+    /// Reads the backed-up content, decompressing it if needed
+    pub fn read(&self) -> Result<String> {
+        let read_error =
+            |e: io::Error| ModeError::FileOperation(format!("Failed to read {}: {}", self.path.display(), e));
+
+        let file = File::open(&self.path).map_err(read_error)?;
+        let mut content = String::new();
+        if self.compressed {
+            GzDecoder::new(file).read_to_string(&mut content).map_err(read_error)?;
+        } else {
+            io::BufReader::new(file).read_to_string(&mut content).map_err(read_error)?;
+        }
+        Ok(content)
+    }
+}
+
+/// Where backups are kept: one directory per managed file
+///
+/// A file's directory is named after its absolute path with `/` replaced by
+/// `%` (as Vim names undo files), and each backup inside is named
+/// `<file name>.<timestamp>`, with `-2`, `-3`, ... added when several are
+/// taken within the same second and `.gz` once compressed.
+#[derive(Debug, Clone)]
+pub struct BackupStore {
+    /// Directory holding the per-file directories
+    root: PathBuf,
+    /// Retention applied after every backup
+    settings: BackupSettings,
+}
+
+impl BackupStore {
+    /// Creates a store at `root` that prunes with the given settings
+    pub fn new(root: PathBuf, settings: BackupSettings) -> Self {
+        Self { root, settings }
+    }
+
+    /// Opens the user's store in `~/.local/state/mode/backups` with the
+    /// settings from `config.toml`
+    pub fn open() -> Result<Self> {
+        Ok(Self::new(config::state_dir()?.join("backups"), BackupSettings::load()))
+    }
+
+    /// Returns the directory the store keeps its backups in
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Returns the directory holding the backups of `file_path`
+    fn dir_for(&self, file_path: &Path) -> Result<PathBuf> {
+        let absolute = if file_path.is_absolute() {
+            file_path.to_path_buf()
+        } else {
+            env::current_dir()?.join(file_path)
+        };
+        Ok(self.root.join(absolute.to_string_lossy().replace('/', "%")))
+    }
+
+    /// Copies a file into the store, then applies the retention settings
+    ///
+    /// Backups of the same file written by older versions of mode next to it
+    /// are moved into the store as well.
+    ///
+    /// Returns the path to the new backup
+    pub fn back_up(&self, file_path: &Path) -> Result<PathBuf> {
+        let timestamp = Local::now().format(TIMESTAMP_FORMAT).to_string();
+        let backup_path = self.copy_in(file_path, &timestamp).map_err(|e| {
+            ModeError::BackupFailed(format!("Failed to back up {}: {}", file_path.display(), e))
+        })?;
+
+        self.adopt_legacy(file_path)?;
+        self.prune(file_path)?;
+        Ok(backup_path)
+    }
+
+    /// Copies a file into its directory under a name no other backup has
+    fn copy_in(&self, file_path: &Path, timestamp: &str) -> Result<PathBuf> {
+        let mut source = File::open(file_path)?;
+        let (path, mut target) = self.create_unique(file_path, timestamp)?;
+        io::copy(&mut source, &mut target)?;
+        target.flush()?;
+        Ok(path)
+    }
+
+    /// Creates a new, private backup file for `file_path` taken at `timestamp`
+    ///
+    /// The file is created exclusively, so backups taken in the same second,
+    /// even by two mode processes, never overwrite each other
+    fn create_unique(&self, file_path: &Path, timestamp: &str) -> Result<(PathBuf, File)> {
+        let dir = self.dir_for(file_path)?;
+        DirBuilder::new().recursive(true).mode(0o700).create(&dir)?;
+        let name = file_name(file_path);
+
+        for sequence in 1.. {
+            let path = dir.join(backup_name(&name, timestamp, sequence));
+            match OpenOptions::new().write(true).create_new(true).mode(0o600).open(&path) {
+                Ok(file) => return Ok((path, file)),
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e.into()),
+            }
+        }
+        unreachable!("backup sequence numbers ran out")
+    }
+
+    /// Lists the backups of a file, newest first
+    ///
+    /// Files in its directory that aren't named like its backups are ignored
+    pub fn list(&self, file_path: &Path) -> Result<Vec<Backup>> {
+        let dir = self.dir_for(file_path)?;
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let prefix = format!("{}.", file_name(file_path));
+        let mut backups = Vec::new();
+        for entry in entries {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let Some((created, sequence, compressed)) = name.strip_prefix(&prefix).and_then(parse_suffix) else {
+                continue;
+            };
+
+            let metadata = entry.metadata()?;
+            if !metadata.is_file() {
+                continue;
+            }
+
+            let backup = Backup {
+                path: entry.path(),
+                original: file_path.to_path_buf(),
+                created,
+                size: metadata.len(),
+                compressed,
+            };
+            backups.push((sequence, backup));
+        }
+
+        backups.sort_by(|(a_seq, a), (b_seq, b)| b.created.cmp(&a.created).then(b_seq.cmp(a_seq)));
+        Ok(backups.into_iter().map(|(_, backup)| backup).collect())
+    }
+
+    /// Moves backups older versions of mode left next to a file into the store
+    ///
+    /// Those were named `.bashrc.backup.<timestamp>` (or `config.backup.<timestamp>`
+    /// for `config.toml`). Returns how many were moved.
+    pub fn adopt_legacy(&self, file_path: &Path) -> Result<usize> {
+        let legacy_prefix = file_path.with_extension("backup.");
+        let (Some(dir), Some(legacy_prefix)) = (file_path.parent(), legacy_prefix.file_name()) else {
+            return Ok(0);
+        };
+        let legacy_prefix = legacy_prefix.to_string_lossy().into_owned();
+
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut moved = 0;
+        for entry in entries {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let Some(timestamp) = name.strip_prefix(&legacy_prefix) else {
+                continue;
+            };
+            if NaiveDateTime::parse_from_str(timestamp, TIMESTAMP_FORMAT).is_err() || !entry.file_type()?.is_file() {
+                continue;
+            }
+
+            let legacy = entry.path();
+            let (target, mut file) = self.create_unique(file_path, timestamp)?;
+            if fs::rename(&legacy, &target).is_err() {
+                // Across filesystems: copy into the reserved file instead
+                io::copy(&mut File::open(&legacy)?, &mut file)?;
+                fs::remove_file(&legacy)?;
+            }
+            moved += 1;
+        }
+        Ok(moved)
+    }
+
+    /// Applies the retention settings to the backups of a file
+    ///
+    /// Backups beyond the newest `keep`, or older than `max_age_days`, are
+    /// removed; with `compress` on, the survivors other than the newest are
+    /// gzipped. Returns the paths of the removed backups.
+    pub fn prune(&self, file_path: &Path) -> Result<Vec<PathBuf>> {
+        let BackupSettings { keep, max_age_days, compress } = self.settings;
+        let now = Local::now().naive_local();
+
+        let mut removed = Vec::new();
+        let mut kept = 0;
+        for (index, backup) in self.list(file_path)?.into_iter().enumerate() {
+            let too_many = keep > 0 && index >= keep;
+            let too_old = max_age_days > 0 && (now - backup.created).num_days() >= max_age_days as i64;
+
+            if too_many || too_old {
+                fs::remove_file(&backup.path)?;
+                removed.push(backup.path);
+                continue;
+            }
+
+            if compress && kept > 0 && !backup.compressed {
+                compress_backup(&backup)?;
+            }
+            kept += 1;
+        }
+        Ok(removed)
+    }
+
+    /// Puts a backup's content back in place of its file
+    ///
+    /// The current file is backed up first, so a restore can itself be undone.
+    ///
+    /// Returns the path of that backup, or None if the file no longer existed
+    pub fn restore(&self, backup: &Backup) -> Result<Option<PathBuf>> {
+        // Read first: pruning after backing up the current file may remove or compress it
+        let content = backup.read()?;
+
+        let current_backup = if backup.original.exists() {
+            Some(self.back_up(&backup.original)?)
+        } else {
+            None
+        };
+
+        file_ops::write_atomic(&backup.original, &content)?;
+        Ok(current_backup)
+    }
+}
+
+/// Returns a file's name, used as the prefix of its backups
+fn file_name(file_path: &Path) -> String {
+    file_path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Builds a backup's name from the file's name, timestamp and sequence number
+fn backup_name(name: &str, timestamp: &str, sequence: u32) -> String {
+    if sequence == 1 {
+        format!("{}.{}", name, timestamp)
+    } else {
+        format!("{}.{}-{}", name, timestamp, sequence)
+    }
+}
+
+/// Parses what follows `<file name>.` in a backup's name
+///
+/// Returns the timestamp, sequence number and whether it is compressed
+fn parse_suffix(suffix: &str) -> Option<(NaiveDateTime, u32, bool)> {
+    let (suffix, compressed) = match suffix.strip_suffix(&format!(".{}", GZ_EXTENSION)) {
+        Some(suffix) => (suffix, true),
+        None => (suffix, false),
+    };
+    let (timestamp, sequence) = match suffix.split_once('-') {
+        Some((timestamp, sequence)) => (timestamp, sequence.parse().ok()?),
+        None => (suffix, 1),
+    };
+
+    let created = NaiveDateTime::parse_from_str(timestamp, TIMESTAMP_FORMAT).ok()?;
+    Some((created, sequence, compressed))
+}
+
+/// Replaces a backup with a gzipped copy
+fn compress_backup(backup: &Backup) -> Result<()> {
+    let mut compressed_path = backup.path.clone().into_os_string();
+    compressed_path.push(format!(".{}", GZ_EXTENSION));
+    let compressed_path = PathBuf::from(compressed_path);
+
+    let target = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(&compressed_path)?;
+    let mut encoder = GzEncoder::new(target, Compression::default());
+    io::copy(&mut File::open(&backup.path)?, &mut encoder)?;
+    encoder.finish()?.sync_all()?;
+
+    fs::remove_file(&backup.path)?;
+    Ok(())
+}
+
+/// Returns a unified diff from a backup to the current state of its file
+///
+/// Lines only in the backup are marked `-` and lines only in the current file
+/// `+`; a missing current file diffs as empty. Returns an empty string when
+/// they are identical.
+pub fn diff_against_current(backup: &Backup) -> Result<String> {
+    let old = backup.read()?;
+    let new = match fs::read_to_string(&backup.original) {
+        Ok(content) => content,
+        Err(e) if e.kind() == ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
+
+    Ok(TextDiff::from_lines(&old, &new)
+        .unified_diff()
+        .context_radius(DIFF_CONTEXT)
+        .header(&backup.path.display().to_string(), &backup.original.display().to_string())
+        .to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_backups_never_collide() {
+        let dir = tempdir().unwrap();
+        let store = BackupStore::new(dir.path().join("store"), BackupSettings::default());
+        let rc = dir.path().join(".bashrc");
+
+        for version in 1..=3 {
+            fs::write(&rc, format!("version {}\n", version)).unwrap();
+            store.back_up(&rc).unwrap();
+        }
+
+        // Same-second backups get sequence numbers and list newest first
+        let backups = store.list(&rc).unwrap();
+        let contents: Vec<String> = backups.iter().map(|b| b.read().unwrap()).collect();
+        assert_eq!(contents, ["version 3\n", "version 2\n", "version 1\n"]);
+        assert!(backups.iter().all(|b| b.path.parent() == Some(store.dir_for(&rc).unwrap().as_path())));
+        assert!(store.dir_for(&rc).unwrap().ends_with(rc.to_string_lossy().replace('/', "%")));
+
+        // Files named alike in other directories have their own backups
+        let other = dir.path().join("other");
+        fs::create_dir(&other).unwrap();
+        assert!(store.list(&other.join(".bashrc")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_prune_and_compress() {
+        let dir = tempdir().unwrap();
+        let rc = dir.path().join(".bashrc");
+        fs::write(&rc, "alias a=b\n").unwrap();
+
+        let keep_all = BackupStore::new(dir.path().join("store"), BackupSettings::default());
+        let backup_dir = keep_all.dir_for(&rc).unwrap();
+        fs::create_dir_all(&backup_dir).unwrap();
+        let old = Local::now().naive_local() - chrono::Duration::days(40);
+        fs::write(backup_dir.join(format!(".bashrc.{}", old.format(TIMESTAMP_FORMAT))), "ancient\n").unwrap();
+        for timestamp in ["20990101_000000", "20990102_000000", "20990103_000000"] {
+            fs::write(backup_dir.join(format!(".bashrc.{}", timestamp)), timestamp).unwrap();
+        }
+        assert!(keep_all.prune(&rc).unwrap().is_empty());
+
+        let settings = BackupSettings { keep: 0, max_age_days: 30, compress: true };
+        let store = BackupStore::new(keep_all.root().to_path_buf(), settings);
+        assert_eq!(store.prune(&rc).unwrap().len(), 1);
+
+        let backups = store.list(&rc).unwrap();
+        let compressed: Vec<bool> = backups.iter().map(|b| b.compressed).collect();
+        assert_eq!(compressed, [false, true, true]);
+        assert_eq!(backups[1].file_name(), ".bashrc.20990102_000000.gz");
+        assert_eq!(backups[1].read().unwrap(), "20990102_000000");
+
+        let settings = BackupSettings { keep: 1, ..settings };
+        let store = BackupStore::new(keep_all.root().to_path_buf(), settings);
+        assert_eq!(store.prune(&rc).unwrap().len(), 2);
+        assert_eq!(store.list(&rc).unwrap()[0].file_name(), ".bashrc.20990103_000000");
+    }
+
+    #[test]
+    fn test_legacy_backups_move_into_store() {
+        let dir = tempdir().unwrap();
+        let store = BackupStore::new(dir.path().join("store"), BackupSettings::default());
+        let config = dir.path().join("config.toml");
+        fs::write(&config, "theme = \"dark\"\n").unwrap();
+        fs::write(dir.path().join("config.backup.20240101_120000"), "old\n").unwrap();
+        fs::write(dir.path().join("config.backup.mine"), "not mode's\n").unwrap();
+
+        assert_eq!(store.adopt_legacy(&config).unwrap(), 1);
+        assert!(!dir.path().join("config.backup.20240101_120000").exists());
+        assert!(dir.path().join("config.backup.mine").exists());
+
+        let backups = store.list(&config).unwrap();
+        assert_eq!(backups.len(), 1);
+        assert_eq!(backups[0].file_name(), "config.toml.20240101_120000");
+        assert_eq!(backups[0].read().unwrap(), "old\n");
+    }
+
+    #[test]
+    fn test_diff_and_restore() {
+        let dir = tempdir().unwrap();
+        let store = BackupStore::new(dir.path().join("store"), BackupSettings::default());
+        let rc = dir.path().join(".bashrc");
+        fs::write(&rc, "alias a=b\n").unwrap();
+        store.back_up(&rc).unwrap();
+        fs::write(&rc, "alias a=b\nalias c=d\n").unwrap();
+        let backup = store.list(&rc).unwrap().remove(0);
+
+        let diff = diff_against_current(&backup).unwrap();
+        assert!(diff.contains("+alias c=d\n"));
+        assert!(!diff.contains("-alias a=b"));
+
+        let before_restore = store.restore(&backup).unwrap().unwrap();
+        assert_eq!(fs::read_to_string(&rc).unwrap(), "alias a=b\n");
+        assert_eq!(fs::read_to_string(before_restore).unwrap(), "alias a=b\nalias c=d\n");
+        assert!(diff_against_current(&backup).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,47 @@
+use crate::utils::errors::{ModeError, Result};
+use std::env;
+use std::path::PathBuf;
+
+/// Returns the directory holding mode's configuration
+///
+/// Uses `$XDG_CONFIG_HOME/mode` when set, otherwise `~/.config/mode`.
+pub fn config_dir() -> Result<PathBuf> {
+    if let Some(dir) = env::var_os("XDG_CONFIG_HOME").filter(|dir| !dir.is_empty()) {
+        return Ok(PathBuf::from(dir).join("mode"));
+    }
+
+    let home = env::var("HOME")
+        .map_err(|_| ModeError::Config("HOME environment variable not set".to_string()))?;
+    Ok(PathBuf::from(home).join(".config").join("mode"))
+}
+
+/// Returns the path of the main configuration file (`config.toml`)
+pub fn config_file_path() -> Result<PathBuf> {
+    Ok(config_dir()?.join("config.toml"))
+}
+
+/// Returns the directory holding data mode manages on the user's behalf
+///
+/// Uses `$XDG_DATA_HOME/mode` when set, otherwise `~/.local/share/mode`.
+pub fn data_dir() -> Result<PathBuf> {
+    if let Some(dir) = env::var_os("XDG_DATA_HOME").filter(|dir| !dir.is_empty()) {
+        return Ok(PathBuf::from(dir).join("mode"));
+    }
+
+    let home = env::var("HOME")
+        .map_err(|_| ModeError::Config("HOME environment variable not set".to_string()))?;
+    Ok(PathBuf::from(home).join(".local").join("share").join("mode"))
+}
+
+/// Returns the directory holding state mode keeps between runs, like backups
+///
+/// Uses `$XDG_STATE_HOME/mode` when set, otherwise `~/.local/state/mode`.
+pub fn state_dir() -> Result<PathBuf> {
+    if let Some(dir) = env::var_os("XDG_STATE_HOME").filter(|dir| !dir.is_empty()) {
+        return Ok(PathBuf::from(dir).join("mode"));
+    }
+
+    let home = env::var("HOME")
+        .map_err(|_| ModeError::Config("HOME environment variable not set".to_string()))?;
+    Ok(PathBuf::from(home).join(".local").join("state").join("mode"))
+}
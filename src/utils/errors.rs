@@ -43,6 +43,14 @@ pub enum ModeError {
     #[error("Terminal error: {0}")]
     Terminal(String),
 
+    /// Configuration file could not be read or is invalid
+    #[error("Invalid configuration: {0}")]
+    Config(String),
+
+    /// A rewritten RC file failed the shell's syntax check and was not saved
+    #[error("RC file failed the syntax check: {0}")]
+    SyntaxCheck(String),
+
     /// Generic error
     #[error("{0}")]
     Generic(String),
@@ -50,3 +58,29 @@ pub enum ModeError {
 
 /// Result type for MODE operations
 pub type Result<T> = std::result::Result<T, ModeError>;
+
+impl ModeError {
+    /// Returns the process exit code used when this error ends a CLI command
+    ///
+    /// Codes are stable so scripts can react to specific failures:
+    /// 1 generic, 2 invalid input, 3 invalid alias name, 4 duplicate alias,
+    /// 5 shell detection, 6 RC file missing, 7 RC file not writable,
+    /// 8 backup or file operation failure, 9 IO error, 10 terminal error,
+    /// 11 invalid configuration, 12 RC file syntax check failure
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ModeError::Generic(_) => 1,
+            ModeError::InvalidInput(_) => 2,
+            ModeError::InvalidAliasName(_) => 3,
+            ModeError::DuplicateAlias(_) => 4,
+            ModeError::ShellDetection(_) => 5,
+            ModeError::RcFileNotFound(_) => 6,
+            ModeError::RcFileNotWritable(_) => 7,
+            ModeError::BackupFailed(_) | ModeError::FileOperation(_) => 8,
+            ModeError::Io(_) => 9,
+            ModeError::Terminal(_) => 10,
+            ModeError::Config(_) => 11,
+            ModeError::SyntaxCheck(_) => 12,
+        }
+    }
+}
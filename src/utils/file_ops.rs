@@ -1,26 +1,20 @@
+use crate::utils::backup::BackupStore;
 use crate::utils::errors::{ModeError, Result};
-use chrono::Local;
+use crate::utils::rc_block::RcFile;
+use crate::utils::shell::{self, ShellType};
 use std::fs;
-use std::io::{Read, Write};
+use std::io::Write;
 use std::path::{Path, PathBuf};
 use tempfile::NamedTempFile;
 
-/// Creates a timestamped backup of the given file
+/// Creates a timestamped backup of the given file in a backup store
+///
+/// Older backups of the file are then pruned (and compressed) as the
+/// store's settings say.
 ///
 /// Returns the path to the backup file
-pub fn create_backup(file_path: &Path) -> Result<PathBuf> {
-    let timestamp = Local::now().format("%Y%m%d_%H%M%S");
-    let backup_path = file_path.with_extension(format!("backup.{}", timestamp));
-
-    fs::copy(file_path, &backup_path).map_err(|e| {
-        ModeError::BackupFailed(format!(
-            "Failed to create backup at {}: {}",
-            backup_path.display(),
-            e
-        ))
-    })?;
-
-    Ok(backup_path)
+pub fn create_backup(file_path: &Path, store: &BackupStore) -> Result<PathBuf> {
+    store.back_up(file_path)
 }
 
 /// Checks if an alias already exists in the RC file
@@ -29,70 +23,549 @@ pub fn create_backup(file_path: &Path) -> Result<PathBuf> {
 pub fn check_duplicate_alias(rc_file: &Path, alias_name: &str) -> Result<bool> {
     let content = fs::read_to_string(rc_file)?;
 
-    // Look for lines like: alias name= or alias name='
-    let alias_pattern = format!("alias {}=", alias_name);
-    let alias_pattern_quoted = format!("alias {}", alias_name);
+    Ok(content
+        .lines()
+        .any(|line| parse_alias_line(line).is_some_and(|(name, _)| name == alias_name)))
+}
 
-    for line in content.lines() {
-        let trimmed = line.trim();
-        if trimmed.starts_with(&alias_pattern) || trimmed.starts_with(&alias_pattern_quoted) {
-            return Ok(true);
+/// An alias definition found in an RC file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AliasEntry {
+    /// Alias name
+    pub name: String,
+    /// Command the alias expands to (unquoted)
+    pub command: String,
+    /// Zero-based line number of the definition
+    pub line: usize,
+    /// Whether the alias lives in mode's block (or under a legacy "Added by mode" comment)
+    pub managed: bool,
+}
+
+/// Parses a single alias definition line
+///
+/// Understands `alias name=value` as well as fish's `alias name value` and
+/// `abbr -a name value`. Returns the alias name and the unquoted command, or
+/// None if the line is not a simple alias definition
+pub(crate) fn parse_alias_line(line: &str) -> Option<(String, String)> {
+    let trimmed = line.trim();
+
+    if let Some(rest) = trimmed.strip_prefix("alias ") {
+        let rest = rest.trim_start();
+        if let Some((name, value)) = rest.split_once('=') {
+            if !name.is_empty() && !name.contains(char::is_whitespace) {
+                return Some((name.to_string(), unquote_shell_value(value.trim_end())));
+            }
         }
+        return parse_fish_definition(split_fish_words(rest), &["-s", "--save"]);
     }
 
-    Ok(false)
+    let rest = trimmed.strip_prefix("abbr ")?;
+    let mut words = split_fish_words(rest);
+    // `--position` takes a value, which would otherwise be read as the name
+    if let Some(idx) = words.iter().position(|w| w == "-p" || w == "--position") {
+        words.drain(idx..(idx + 2).min(words.len()));
+    }
+    parse_fish_definition(words, &["-a", "--add", "-g", "--global", "-U", "--universal"])
 }
 
-/// Removes an alias from the RC file if it exists
+/// Splits fish `[FLAGS] NAME VALUE...` words into the name and the joined value
 ///
-/// This function removes all lines that define the specified alias
-pub fn remove_alias(rc_file: &Path, alias_name: &str) -> Result<()> {
-    // Read current content
-    let content = fs::read_to_string(rc_file)?;
+/// Returns None when a flag outside `allowed_flags` is present, as it turns the
+/// line into something other than a definition (`abbr --erase`, `abbr --list`)
+fn parse_fish_definition(words: Vec<String>, allowed_flags: &[&str]) -> Option<(String, String)> {
+    let mut words = words.into_iter().skip_while(|w| allowed_flags.contains(&w.as_str()));
+    let name = words.next()?;
+    if name.starts_with('-') {
+        return None;
+    }
 
-    // Filter out lines that define this alias
-    let alias_pattern = format!("alias {}=", alias_name);
-    let mut new_lines = Vec::new();
-    let mut skip_next_comment = false;
+    let value = words.collect::<Vec<_>>().join(" ");
+    if value.is_empty() {
+        return None;
+    }
 
-    for line in content.lines() {
-        let trimmed = line.trim();
+    Some((name, value))
+}
 
-        // Check if this is the "Added by mode" comment for this alias
-        if trimmed.starts_with("# Added by mode") {
-            skip_next_comment = true;
-            continue;
+/// Splits a fish command line into unquoted words
+///
+/// Handles single quotes (where only `\\` and `\'` are escapes), double quotes
+/// with backslash escapes, and backslash escapes in unquoted words. An unquoted
+/// `#` starts a comment and ends the line
+fn split_fish_words(line: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            ' ' | '\t' => {
+                words.extend(word.take());
+                continue;
+            }
+            '#' if word.is_none() => break,
+            _ => {}
         }
 
-        // Check if this line defines the alias we want to remove
-        if trimmed.starts_with(&alias_pattern) {
-            skip_next_comment = false;
-            continue;
+        let current = word.get_or_insert_with(String::new);
+        match c {
+            '\'' => {
+                while let Some(inner) = chars.next() {
+                    match inner {
+                        '\'' => break,
+                        '\\' if matches!(chars.peek(), Some('\\' | '\'')) => current.extend(chars.next()),
+                        _ => current.push(inner),
+                    }
+                }
+            }
+            '"' => {
+                while let Some(inner) = chars.next() {
+                    match inner {
+                        '"' => break,
+                        '\\' if matches!(chars.peek(), Some('"' | '\\' | '$')) => current.extend(chars.next()),
+                        _ => current.push(inner),
+                    }
+                }
+            }
+            '\\' => current.extend(chars.next()),
+            _ => current.push(c),
         }
+    }
+
+    words.extend(word);
+    words
+}
 
-        // If we skipped a comment but the next line wasn't the alias, keep the comment
-        if skip_next_comment && !trimmed.starts_with(&alias_pattern) {
-            skip_next_comment = false;
+/// Removes shell quoting from an alias value
+///
+/// Handles single quotes (including the `'\''` escape idiom), double quotes
+/// with backslash escapes, and unquoted words
+fn unquote_shell_value(value: &str) -> String {
+    let mut result = String::new();
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                for inner in chars.by_ref() {
+                    if inner == '\'' {
+                        break;
+                    }
+                    result.push(inner);
+                }
+            }
+            '"' => {
+                while let Some(inner) = chars.next() {
+                    match inner {
+                        '"' => break,
+                        '\\' => match chars.peek() {
+                            Some(&next) if matches!(next, '"' | '\\' | '$' | '`') => {
+                                result.push(next);
+                                chars.next();
+                            }
+                            _ => result.push(inner),
+                        },
+                        _ => result.push(inner),
+                    }
+                }
+            }
+            '\\' => {
+                if let Some(next) = chars.next() {
+                    result.push(next);
+                }
+            }
+            // An unquoted blank or comment ends the value
+            ' ' | '\t' | '#' => break,
+            _ => result.push(c),
         }
+    }
+
+    result
+}
+
+/// Lists all aliases defined in the RC file
+///
+/// Includes both mode-managed aliases and hand-written ones, in file order
+pub fn list_aliases(rc_file: &Path) -> Result<Vec<AliasEntry>> {
+    Ok(list_definitions(rc_file, parse_alias_line)?
+        .into_iter()
+        .map(|(line, managed, (name, command))| AliasEntry {
+            name,
+            command,
+            line,
+            managed,
+        })
+        .collect())
+}
+
+/// Parses every line of the RC file with `parse`, in file order
+///
+/// Each match comes with its zero-based line number and whether it is
+/// managed: inside mode's block, or under a legacy "Added by mode" comment
+fn list_definitions<T>(rc_file: &Path, parse: impl Fn(&str) -> Option<T>) -> Result<Vec<(usize, bool, T)>> {
+    let rc = RcFile::load(rc_file)?;
+    let block = rc.block_range().unwrap_or_default();
+    let mut definitions = Vec::new();
+    let mut previous_was_mode_comment = false;
+
+    for (idx, line) in rc.lines().iter().enumerate() {
+        let trimmed = line.trim();
 
-        if !skip_next_comment {
-            new_lines.push(line);
+        if let Some(definition) = parse(trimmed) {
+            definitions.push((idx, block.contains(&idx) || previous_was_mode_comment, definition));
         }
+
+        previous_was_mode_comment = trimmed.starts_with("# Added by mode");
+    }
+
+    Ok(definitions)
+}
+
+/// Loads an RC file for editing, moving legacy mode entries into mode's block
+fn load_for_edit(rc_file: &Path) -> Result<RcFile> {
+    let mut rc = RcFile::load(rc_file)?;
+    rc.migrate_legacy()?;
+    Ok(rc)
+}
+
+/// Removes an alias from the RC file if it exists
+///
+/// This function removes all lines that define the specified alias, wherever
+/// they are in the file. Legacy mode entries are moved into mode's block first
+pub fn remove_alias(rc_file: &Path, shell: ShellType, alias_name: &str) -> Result<()> {
+    let new_lines = remove_definitions(rc_file, |line| is_alias_definition(line, alias_name))?;
+
+    let staged = stage_rc_file(rc_file, shell, &new_lines)?;
+    persist_temp_file(staged, rc_file)
+}
+
+/// Returns whether a line defines the named alias
+fn is_alias_definition(line: &str, alias_name: &str) -> bool {
+    matches!(parse_alias_line(line), Some((name, _)) if name == alias_name)
+}
+
+/// Returns the file's lines without those matching `is_definition`
+///
+/// Legacy mode entries are moved into mode's block first; a block that only
+/// existed to receive them and ends up empty is left out again.
+fn remove_definitions(rc_file: &Path, is_definition: impl Fn(&str) -> bool) -> Result<Vec<String>> {
+    let mut rc = RcFile::load(rc_file)?;
+    let had_block = rc.block_range().is_some();
+    rc.migrate_legacy()?;
+    rc.retain(|line| !is_definition(line.trim()));
+    if !had_block {
+        rc.remove_empty_block();
+    }
+    Ok(rc.lines())
+}
+
+/// Removes an alias from the RC file after creating a backup
+///
+/// Returns the path to the backup file
+pub fn delete_alias(rc_file: &Path, shell: ShellType, store: &BackupStore, alias_name: &str) -> Result<PathBuf> {
+    if !list_aliases(rc_file)?.iter().any(|a| a.name == alias_name) {
+        return Err(ModeError::InvalidInput(format!(
+            "Alias '{}' not found in RC file",
+            alias_name
+        )));
     }
 
-    // Write back to file atomically
-    let rc_dir = rc_file.parent().ok_or_else(|| {
+    let new_lines = remove_definitions(rc_file, |line| is_alias_definition(line, alias_name))?;
+    rewrite_rc_file(rc_file, shell, store, &new_lines)
+}
+
+/// Formats the line defining an alias in the given shell's syntax
+///
+/// The command is quoted as a single literal word, so quotes, `$` and
+/// backslashes in it survive exactly as entered
+pub fn alias_line(shell: ShellType, name: &str, command: &str) -> String {
+    match shell {
+        ShellType::Bash | ShellType::Zsh => format!("alias {}={}", name, shell.quote(command)),
+        ShellType::Fish => format!("alias {} {}", name, shell.quote(command)),
+    }
+}
+
+/// Rewrites an existing alias in place after creating a backup
+///
+/// Every definition of `old_name` is replaced with a definition of `new_name`
+/// in the shell's syntax, keeping its indentation and position in the file.
+/// Fish abbreviations stay abbreviations. Renaming onto a name that is already
+/// defined is rejected.
+///
+/// Returns the path to the backup file
+pub fn update_alias(
+    rc_file: &Path,
+    shell: ShellType,
+    store: &BackupStore,
+    old_name: &str,
+    new_name: &str,
+    command: &str,
+) -> Result<PathBuf> {
+    if old_name != new_name && list_aliases(rc_file)?.iter().any(|a| a.name == new_name) {
+        return Err(ModeError::DuplicateAlias(new_name.to_string()));
+    }
+
+    let mut found = false;
+    let new_lines: Vec<String> = load_for_edit(rc_file)?
+        .lines()
+        .into_iter()
+        .map(|line| match parse_alias_line(&line) {
+            Some((name, _)) if name == old_name => {
+                found = true;
+                let indent = &line[..line.len() - line.trim_start().len()];
+                if line.trim_start().starts_with("abbr ") {
+                    format!("{}abbr -a {} {}", indent, new_name, shell.quote(command))
+                } else {
+                    format!("{}{}", indent, alias_line(shell, new_name, command))
+                }
+            }
+            _ => line,
+        })
+        .collect();
+
+    if !found {
+        return Err(ModeError::InvalidInput(format!(
+            "Alias '{}' not found in RC file",
+            old_name
+        )));
+    }
+
+    rewrite_rc_file(rc_file, shell, store, &new_lines)
+}
+
+/// An `export NAME=value` (or fish `set -gx NAME value`) line found in an RC file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportEntry {
+    /// Variable name
+    pub name: String,
+    /// Value as written in the RC file (unquoted, references like `$HOME` unexpanded)
+    pub value: String,
+    /// Zero-based line number of the definition
+    pub line: usize,
+    /// Whether the export lives in mode's block (or under a legacy "Added by mode" comment)
+    pub managed: bool,
+}
+
+/// Returns whether a string is a valid environment variable name
+pub fn is_valid_var_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Formats an `export NAME="value"` line, or `set -gx NAME "value"` for fish
+///
+/// The value is double-quoted so `$VAR` references still expand when the
+/// shell starts (see `ShellType::quote_expanding`)
+pub fn export_line(shell: ShellType, name: &str, value: &str) -> String {
+    match shell {
+        ShellType::Bash | ShellType::Zsh => format!("export {}={}", name, shell.quote_expanding(value)),
+        ShellType::Fish => format!("set -gx {} {}", name, shell.quote_expanding(value)),
+    }
+}
+
+/// Parses a single `export NAME=value` or fish `set -x NAME value` line
+pub(crate) fn parse_export_line(line: &str) -> Option<(String, String)> {
+    let trimmed = line.trim();
+
+    if let Some(rest) = trimmed.strip_prefix("set ") {
+        return parse_fish_set(split_fish_words(rest));
+    }
+
+    let rest = trimmed.strip_prefix("export ")?.trim_start();
+    let (name, value) = rest.split_once('=')?;
+
+    if !is_valid_var_name(name) {
+        return None;
+    }
+
+    Some((name.to_string(), unquote_shell_value(value.trim_end())))
+}
+
+/// Parses the words of a fish `set` command that exports a variable
+///
+/// Only scope and export flags are accepted; erasing, querying, appending and
+/// unexported assignments are not definitions. List values are joined with spaces
+fn parse_fish_set(words: Vec<String>) -> Option<(String, String)> {
+    let mut exported = false;
+    let mut words = words.into_iter().peekable();
+
+    while let Some(flag) = words.next_if(|w| w.starts_with('-')) {
+        match flag.as_str() {
+            "--export" => exported = true,
+            "--global" | "--universal" | "--local" => {}
+            short if !short.starts_with("--") && short[1..].chars().all(|c| "gUlx".contains(c)) => {
+                exported |= short.contains('x');
+            }
+            _ => return None,
+        }
+    }
+
+    let name = words.next()?;
+    if !exported || !is_valid_var_name(&name) {
+        return None;
+    }
+
+    Some((name, words.collect::<Vec<_>>().join(" ")))
+}
+
+/// Lists all `export NAME=value` lines in the RC file, in file order
+pub fn list_exports(rc_file: &Path) -> Result<Vec<ExportEntry>> {
+    Ok(list_definitions(rc_file, parse_export_line)?
+        .into_iter()
+        .map(|(line, managed, (name, value))| ExportEntry {
+            name,
+            value,
+            line,
+            managed,
+        })
+        .collect())
+}
+
+/// Adds an export line in the shell's syntax to mode's block in the RC file
+///
+/// Returns the path to the RC file backup
+pub fn append_export(
+    rc_file: &Path,
+    shell: ShellType,
+    store: &BackupStore,
+    name: &str,
+    value: &str,
+) -> Result<PathBuf> {
+    if list_exports(rc_file)?.iter().any(|e| e.name == name) {
+        return Err(ModeError::InvalidInput(format!(
+            "'{}' is already exported in RC file",
+            name
+        )));
+    }
+
+    set_managed_definition(rc_file, shell, store, &export_line(shell, name, value))
+}
+
+/// Rewrites every export of a variable in place after creating a backup
+///
+/// Returns the path to the backup file
+pub fn update_export(
+    rc_file: &Path,
+    shell: ShellType,
+    store: &BackupStore,
+    name: &str,
+    value: &str,
+) -> Result<PathBuf> {
+    let mut found = false;
+    let new_lines: Vec<String> = load_for_edit(rc_file)?
+        .lines()
+        .into_iter()
+        .map(|line| match parse_export_line(&line) {
+            Some((existing, _)) if existing == name => {
+                found = true;
+                let indent = &line[..line.len() - line.trim_start().len()];
+                format!("{}{}", indent, export_line(shell, name, value))
+            }
+            _ => line,
+        })
+        .collect();
+
+    if !found {
+        return Err(ModeError::InvalidInput(format!(
+            "'{}' is not exported in RC file",
+            name
+        )));
+    }
+
+    rewrite_rc_file(rc_file, shell, store, &new_lines)
+}
+
+/// Removes every export of a variable from the RC file after creating a backup
+///
+/// Returns the path to the backup file
+pub fn delete_export(rc_file: &Path, shell: ShellType, store: &BackupStore, name: &str) -> Result<PathBuf> {
+    if !list_exports(rc_file)?.iter().any(|e| e.name == name) {
+        return Err(ModeError::InvalidInput(format!(
+            "'{}' is not exported in RC file",
+            name
+        )));
+    }
+
+    let new_lines = remove_definitions(rc_file, |line| {
+        matches!(parse_export_line(line), Some((existing, _)) if existing == name)
+    })?;
+    rewrite_rc_file(rc_file, shell, store, &new_lines)
+}
+
+/// Adds or replaces a definition in mode's block after creating a backup
+///
+/// The line replaces the block's existing definition of the same alias or
+/// variable; otherwise it is appended to the block, which is created at the
+/// end of the file if needed. Legacy mode entries are moved into the block
+/// in the same write.
+///
+/// Returns the path to the backup file
+pub fn set_managed_definition(
+    rc_file: &Path,
+    shell: ShellType,
+    store: &BackupStore,
+    definition: &str,
+) -> Result<PathBuf> {
+    let mut rc = load_for_edit(rc_file)?;
+    rc.set_definition(definition)?;
+    rewrite_rc_file(rc_file, shell, store, &rc.lines())
+}
+
+/// Replaces an RC file's lines after a syntax check and a backup
+///
+/// The new content is staged next to the RC file and parsed by the shell
+/// first; if that fails, no backup is made and the original is untouched.
+///
+/// Returns the path to the backup file
+fn rewrite_rc_file<S: AsRef<str>>(
+    rc_file: &Path,
+    shell: ShellType,
+    store: &BackupStore,
+    lines: &[S],
+) -> Result<PathBuf> {
+    let staged = stage_rc_file(rc_file, shell, lines)?;
+    let backup_path = create_backup(rc_file, store)?;
+    persist_temp_file(staged, rc_file)?;
+
+    Ok(backup_path)
+}
+
+/// Writes candidate RC file lines to a temp file and checks they parse
+fn stage_rc_file<S: AsRef<str>>(rc_file: &Path, shell: ShellType, lines: &[S]) -> Result<NamedTempFile> {
+    let staged = write_temp_lines(rc_file, lines)?;
+
+    shell::check_syntax(shell, staged.path()).map_err(|e| match e {
+        ModeError::SyntaxCheck(message) => ModeError::SyntaxCheck(format!(
+            "{} left unchanged; {}",
+            rc_file.display(),
+            message.replace(&staged.path().display().to_string(), &rc_file.display().to_string())
+        )),
+        other => other,
+    })?;
+
+    Ok(staged)
+}
+
+/// Writes lines to a file atomically via a temp file in the same directory
+fn write_lines_atomically<S: AsRef<str>>(file_path: &Path, lines: &[S]) -> Result<()> {
+    let temp_file = write_temp_lines(file_path, lines)?;
+    persist_temp_file(temp_file, file_path)
+}
+
+/// Writes lines to a new temp file in the same directory as `file_path`
+fn write_temp_lines<S: AsRef<str>>(file_path: &Path, lines: &[S]) -> Result<NamedTempFile> {
+    let dir = file_path.parent().ok_or_else(|| {
         ModeError::FileOperation("Could not determine RC file directory".to_string())
     })?;
 
-    let mut temp_file = NamedTempFile::new_in(rc_dir).map_err(|e| {
+    let mut temp_file = NamedTempFile::new_in(dir).map_err(|e| {
         ModeError::FileOperation(format!("Failed to create temporary file: {}", e))
     })?;
 
-    // Write filtered content
-    for line in new_lines {
+    for line in lines {
         temp_file
-            .write_all(line.as_bytes())
+            .write_all(line.as_ref().as_bytes())
             .map_err(|e| ModeError::FileOperation(format!("Failed to write to temp file: {}", e)))?;
         temp_file
             .write_all(b"\n")
@@ -103,11 +576,15 @@ pub fn remove_alias(rc_file: &Path, alias_name: &str) -> Result<()> {
         .flush()
         .map_err(|e| ModeError::FileOperation(format!("Failed to flush temp file: {}", e)))?;
 
-    // Atomically replace the original file
-    temp_file.persist(rc_file).map_err(|e| {
+    Ok(temp_file)
+}
+
+/// Atomically replaces `file_path` with a written temp file
+fn persist_temp_file(temp_file: NamedTempFile, file_path: &Path) -> Result<()> {
+    temp_file.persist(file_path).map_err(|e| {
         ModeError::FileOperation(format!(
             "Failed to persist temp file to {}: {}",
-            rc_file.display(),
+            file_path.display(),
             e
         ))
     })?;
@@ -118,75 +595,77 @@ pub fn remove_alias(rc_file: &Path, alias_name: &str) -> Result<()> {
 /// Safely appends an alias to the RC file using atomic file operations
 ///
 /// Steps:
-/// 1. Create a backup of the RC file
-/// 2. Read current content
-/// 3. Create a temporary file
-/// 4. Write original content + new alias to temp file
+/// 1. Read current content, moving legacy mode entries into mode's block
+/// 2. Add the alias to the block and write the result to a temporary file
+/// 3. Check the temporary file with the shell's syntax check
+/// 4. Create a backup of the RC file
 /// 5. Atomically rename temp file to original
-pub fn append_alias(rc_file: &Path, alias_name: &str, command: &str) -> Result<PathBuf> {
-    // Create backup first
-    let backup_path = create_backup(rc_file)?;
-
-    // Read current content
-    let mut current_content = String::new();
-    fs::File::open(rc_file)?.read_to_string(&mut current_content)?;
-
-    // Ensure file ends with newline
-    if !current_content.is_empty() && !current_content.ends_with('\n') {
-        current_content.push('\n');
-    }
+pub fn append_alias(
+    rc_file: &Path,
+    shell: ShellType,
+    store: &BackupStore,
+    alias_name: &str,
+    command: &str,
+) -> Result<PathBuf> {
+    set_managed_definition(rc_file, shell, store, &alias_line(shell, alias_name, command))
+}
 
-    // Create the alias entry
-    let timestamp = Local::now().format("%Y-%m-%d %H:%M:%S");
-    let alias_entry = format!(
-        "\n# Added by mode on {}\nalias {}='{}'\n",
-        timestamp, alias_name, command
-    );
+/// Replaces a file's lines atomically after creating a backup
+///
+/// Returns the path to the backup file
+pub fn rewrite_with_backup<S: AsRef<str>>(file_path: &Path, store: &BackupStore, lines: &[S]) -> Result<PathBuf> {
+    let backup_path = create_backup(file_path, store)?;
+    write_lines_atomically(file_path, lines)?;
+    Ok(backup_path)
+}
 
-    // Create temp file in the same directory for atomic rename
-    let rc_dir = rc_file.parent().ok_or_else(|| {
-        ModeError::FileOperation("Could not determine RC file directory".to_string())
+/// Replaces a file's content atomically, creating its directory if needed
+///
+/// Used for files mode owns outright, which need no backup
+pub fn write_atomic(path: &Path, content: &str) -> Result<()> {
+    let dir = path.parent().ok_or_else(|| {
+        ModeError::FileOperation(format!("Could not determine directory of {}", path.display()))
     })?;
+    fs::create_dir_all(dir)?;
 
-    let mut temp_file = NamedTempFile::new_in(rc_dir).map_err(|e| {
+    let mut temp_file = NamedTempFile::new_in(dir).map_err(|e| {
         ModeError::FileOperation(format!("Failed to create temporary file: {}", e))
     })?;
 
-    // Write combined content to temp file
     temp_file
-        .write_all(current_content.as_bytes())
+        .write_all(content.as_bytes())
         .map_err(|e| ModeError::FileOperation(format!("Failed to write to temp file: {}", e)))?;
-
-    temp_file
-        .write_all(alias_entry.as_bytes())
-        .map_err(|e| ModeError::FileOperation(format!("Failed to write alias to temp file: {}", e)))?;
-
-    // Sync to disk
     temp_file
         .flush()
         .map_err(|e| ModeError::FileOperation(format!("Failed to flush temp file: {}", e)))?;
 
-    // Atomically replace the original file
-    temp_file.persist(rc_file).map_err(|e| {
+    temp_file.persist(path).map_err(|e| {
         ModeError::FileOperation(format!(
             "Failed to persist temp file to {}: {}",
-            rc_file.display(),
+            path.display(),
             e
         ))
     })?;
 
-    Ok(backup_path)
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::backup::BackupSettings;
     use std::io::Write;
     use tempfile::tempdir;
 
+    /// Opens a backup store inside a test's temp directory
+    fn store_in(dir: &Path) -> BackupStore {
+        BackupStore::new(dir.join("backups"), BackupSettings::default())
+    }
+
     #[test]
     fn test_create_backup() {
         let dir = tempdir().unwrap();
+        let store = store_in(dir.path());
         let file_path = dir.path().join("test.txt");
 
         // Create test file
@@ -194,7 +673,7 @@ mod tests {
         file.write_all(b"test content").unwrap();
 
         // Create backup
-        let backup_path = create_backup(&file_path).unwrap();
+        let backup_path = create_backup(&file_path, &store).unwrap();
 
         // Verify backup exists and has same content
         assert!(backup_path.exists());
@@ -221,6 +700,7 @@ mod tests {
     #[test]
     fn test_append_alias() {
         let dir = tempdir().unwrap();
+        let store = store_in(dir.path());
         let file_path = dir.path().join(".bashrc");
 
         // Create test RC file
@@ -230,15 +710,264 @@ mod tests {
         drop(file);
 
         // Append new alias
-        let backup_path = append_alias(&file_path, "gs", "git status").unwrap();
+        let backup_path = append_alias(&file_path, ShellType::Bash, &store, "gs", "git status").unwrap();
 
         // Verify backup was created
         assert!(backup_path.exists());
 
         // Verify new content
         let content = fs::read_to_string(&file_path).unwrap();
-        assert!(content.contains("alias gs='git status'"));
-        assert!(content.contains("Added by mode"));
+        assert!(content.contains("\n# >>> mode >>>\nalias gs='git status'\n# <<< mode <<<\n"));
         assert!(content.contains("alias ll='ls -la'"));
     }
+
+    #[test]
+    fn test_list_aliases() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join(".bashrc");
+
+        fs::write(
+            &file_path,
+            "alias ll='ls -la'\n\n# Added by mode on 2024-01-01 00:00:00\nalias gs='git status'\n  alias gl=\"git log --oneline\"\nexport FOO=bar\n",
+        )
+        .unwrap();
+
+        let aliases = list_aliases(&file_path).unwrap();
+        assert_eq!(aliases.len(), 3);
+
+        assert_eq!(aliases[0].name, "ll");
+        assert_eq!(aliases[0].command, "ls -la");
+        assert!(!aliases[0].managed);
+
+        assert_eq!(aliases[1].name, "gs");
+        assert_eq!(aliases[1].line, 3);
+        assert!(aliases[1].managed);
+
+        assert_eq!(aliases[2].name, "gl");
+        assert_eq!(aliases[2].command, "git log --oneline");
+        assert!(!aliases[2].managed);
+    }
+
+    #[test]
+    fn test_unquote_shell_value() {
+        assert_eq!(unquote_shell_value("'ls -la'"), "ls -la");
+        assert_eq!(unquote_shell_value("ls"), "ls");
+        assert_eq!(unquote_shell_value(r"'echo '\''hi'\'''"), "echo 'hi'");
+        assert_eq!(unquote_shell_value(r#""echo \"x\"""#), r#"echo "x""#);
+        assert_eq!(unquote_shell_value("ls # trailing comment"), "ls");
+    }
+
+    #[test]
+    fn test_remove_alias_moves_other_mode_entries_into_block() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join(".bashrc");
+
+        fs::write(
+            &file_path,
+            "# Added by mode on 2024-01-01 00:00:00\nalias a='one'\n\n# Added by mode on 2024-01-02 00:00:00\nalias b='two'\n",
+        )
+        .unwrap();
+
+        remove_alias(&file_path, ShellType::Bash, "a").unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&file_path).unwrap(),
+            "# >>> mode >>>\nalias b='two'\n# <<< mode <<<\n"
+        );
+    }
+
+    #[test]
+    fn test_update_alias() {
+        let dir = tempdir().unwrap();
+        let store = store_in(dir.path());
+        let file_path = dir.path().join(".bashrc");
+
+        fs::write(&file_path, "alias ll='ls -la'\nalias gs='git status'\n").unwrap();
+
+        let backup_path = update_alias(&file_path, ShellType::Bash, &store, "ll", "la", "ls -A").unwrap();
+        assert!(backup_path.exists());
+
+        let content = fs::read_to_string(&file_path).unwrap();
+        assert_eq!(content, "alias la='ls -A'\nalias gs='git status'\n");
+
+        // Renaming onto an existing alias is rejected
+        assert!(update_alias(&file_path, ShellType::Bash, &store, "la", "gs", "ls").is_err());
+        // Unknown aliases are rejected
+        assert!(update_alias(&file_path, ShellType::Bash, &store, "nope", "nope", "ls").is_err());
+    }
+
+    #[test]
+    fn test_alias_quoting_round_trip() {
+        let dir = tempdir().unwrap();
+        let store = store_in(dir.path());
+        let file_path = dir.path().join(".bashrc");
+        fs::write(&file_path, "").unwrap();
+
+        let command = r#"git log --format='%h' | grep "$1" \"#;
+        append_alias(&file_path, ShellType::Bash, &store, "gl", command).unwrap();
+
+        let content = fs::read_to_string(&file_path).unwrap();
+        assert!(content.contains(r#"alias gl='git log --format='\''%h'\'' | grep "$1" \'"#));
+        assert_eq!(list_aliases(&file_path).unwrap()[0].command, command);
+    }
+
+    #[test]
+    fn test_failed_syntax_check_leaves_file_untouched() {
+        let dir = tempdir().unwrap();
+        let store = store_in(dir.path());
+        let file_path = dir.path().join(".bashrc");
+        fs::write(&file_path, "alias ll='ls -la'\n").unwrap();
+
+        let result = set_managed_definition(&file_path, ShellType::Bash, &store, "alias q='echo it's'");
+        assert!(matches!(result, Err(ModeError::SyntaxCheck(_))));
+
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), "alias ll='ls -la'\n");
+        // Neither a backup nor the staged candidate is left behind
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_fish_definitions() {
+        let dir = tempdir().unwrap();
+        let store = store_in(dir.path());
+        let file_path = dir.path().join("mode.fish");
+
+        fs::write(
+            &file_path,
+            "alias ll 'ls -la'\nabbr -a gs git status\nabbr --erase gs\nset -gx EDITOR nvim\nset -l TMP x\nset --erase EDITOR\n",
+        )
+        .unwrap();
+
+        let aliases: Vec<(String, String)> = list_aliases(&file_path)
+            .unwrap()
+            .into_iter()
+            .map(|a| (a.name, a.command))
+            .collect();
+        assert_eq!(
+            aliases,
+            vec![("ll".to_string(), "ls -la".to_string()), ("gs".to_string(), "git status".to_string())]
+        );
+
+        let exports = list_exports(&file_path).unwrap();
+        assert_eq!(exports.len(), 1);
+        assert_eq!(exports[0].value, "nvim");
+
+        // Abbreviations stay abbreviations and quoting survives a round trip
+        update_alias(&file_path, ShellType::Fish, &store, "gs", "gs", "git log --format='%h'").unwrap();
+        append_alias(&file_path, ShellType::Fish, &store, "hi", r"echo it's a \ test").unwrap();
+        append_export(&file_path, ShellType::Fish, &store, "GREETING", r#"say "hi" to $USER"#).unwrap();
+
+        let content = fs::read_to_string(&file_path).unwrap();
+        assert!(content.contains(r"abbr -a gs 'git log --format=\'%h\''"));
+        assert!(content.contains(r#"set -gx GREETING "say \"hi\" to $USER""#));
+
+        let aliases = list_aliases(&file_path).unwrap();
+        assert_eq!(aliases[1].command, "git log --format='%h'");
+        assert_eq!(aliases[2].command, r"echo it's a \ test");
+        assert!(aliases[2].managed);
+        assert_eq!(list_exports(&file_path).unwrap()[1].value, r#"say "hi" to $USER"#);
+    }
+
+    #[test]
+    fn test_export_round_trip() {
+        let dir = tempdir().unwrap();
+        let store = store_in(dir.path());
+        let file_path = dir.path().join(".bashrc");
+
+        fs::write(
+            &file_path,
+            "export EDITOR=vim\n  export GOPATH=\"$HOME/go\"\nexport 1BAD=x\nalias ll='ls'\n",
+        )
+        .unwrap();
+
+        let exports = list_exports(&file_path).unwrap();
+        let names: Vec<&str> = exports.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["EDITOR", "GOPATH"]);
+        assert_eq!(exports[1].value, "$HOME/go");
+
+        append_export(&file_path, ShellType::Bash, &store, "GREETING", r#"say "hi""#).unwrap();
+        assert!(append_export(&file_path, ShellType::Bash, &store, "EDITOR", "nano").is_err());
+        let added = list_exports(&file_path).unwrap();
+        assert!(added[2].managed);
+        assert_eq!(added[2].value, r#"say "hi""#);
+
+        update_export(&file_path, ShellType::Bash, &store, "GOPATH", "/opt/go").unwrap();
+        assert!(fs::read_to_string(&file_path).unwrap().contains("  export GOPATH=\"/opt/go\"\n"));
+
+        delete_export(&file_path, ShellType::Bash, &store, "GREETING").unwrap();
+        let content = fs::read_to_string(&file_path).unwrap();
+        assert!(!content.contains("GREETING"));
+        assert!(!content.contains("Added by mode"));
+        assert!(delete_export(&file_path, ShellType::Bash, &store, "GREETING").is_err());
+    }
+
+    #[test]
+    fn test_managed_definitions_share_one_block() {
+        let dir = tempdir().unwrap();
+        let store = store_in(dir.path());
+        let file_path = dir.path().join(".bashrc");
+        fs::write(&file_path, "# top\n").unwrap();
+
+        set_managed_definition(&file_path, ShellType::Bash, &store, "export PATH=/a").unwrap();
+        fs::write(
+            &file_path,
+            fs::read_to_string(&file_path).unwrap() + "alias ll='ls'\n",
+        )
+        .unwrap();
+
+        append_alias(&file_path, ShellType::Bash, &store, "gs", "git status").unwrap();
+        let backup_path = set_managed_definition(&file_path, ShellType::Bash, &store, "export PATH=/b").unwrap();
+        assert!(backup_path.exists());
+        assert_eq!(
+            fs::read_to_string(&file_path).unwrap(),
+            "# top\n\n# >>> mode >>>\nexport PATH=/b\nalias gs='git status'\n# <<< mode <<<\nalias ll='ls'\n"
+        );
+
+        let aliases = list_aliases(&file_path).unwrap();
+        assert!(aliases[0].managed);
+        assert!(!aliases[1].managed);
+
+        // An unterminated block is reported rather than guessed at
+        fs::write(&file_path, "# >>> mode >>>\nexport PATH=/x\n").unwrap();
+        assert!(set_managed_definition(&file_path, ShellType::Bash, &store, "export PATH=/y").is_err());
+    }
+
+    #[test]
+    fn test_legacy_entries_move_into_block_on_next_write() {
+        let dir = tempdir().unwrap();
+        let store = store_in(dir.path());
+        let file_path = dir.path().join(".bashrc");
+        fs::write(
+            &file_path,
+            "# top\n\n# Added by mode on 2024-01-01 00:00:00\nalias gs='git status'\n\n# >>> mode path >>>\nexport PATH=/a\n# <<< mode path <<<\n",
+        )
+        .unwrap();
+
+        // Legacy entries count as managed before they are moved
+        assert!(list_aliases(&file_path).unwrap()[0].managed);
+
+        append_export(&file_path, ShellType::Bash, &store, "EDITOR", "nvim").unwrap();
+        assert_eq!(
+            fs::read_to_string(&file_path).unwrap(),
+            "# top\n\n# >>> mode >>>\nalias gs='git status'\nexport PATH=/a\nexport EDITOR=\"nvim\"\n# <<< mode <<<\n"
+        );
+    }
+
+    #[test]
+    fn test_delete_alias() {
+        let dir = tempdir().unwrap();
+        let store = store_in(dir.path());
+        let file_path = dir.path().join(".bashrc");
+
+        fs::write(&file_path, "alias ll='ls -la'\nalias gs='git status'\n").unwrap();
+
+        let backup_path = delete_alias(&file_path, ShellType::Bash, &store, "ll").unwrap();
+        assert_eq!(
+            fs::read_to_string(&backup_path).unwrap(),
+            "alias ll='ls -la'\nalias gs='git status'\n"
+        );
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), "alias gs='git status'\n");
+
+        assert!(delete_alias(&file_path, ShellType::Bash, &store, "ll").is_err());
+    }
 }
@@ -1,6 +1,10 @@
+pub mod backup;
+pub mod config;
 pub mod errors;
 pub mod file_ops;
+pub mod procfs;
+pub mod rc_block;
 pub mod shell;
 
 pub use errors::{ModeError, Result};
-pub use shell::{detect_shell, get_rc_file_path, validate_alias_name, ShellType};
+pub use shell::{detect_shell, expand_home, get_rc_file_path, validate_alias_name, write_exit_command, ShellType};
@@ -0,0 +1,521 @@
+use std::collections::HashMap;
+use std::fs;
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+
+/// Signals that can be sent to a process
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Signal {
+    /// Ask the process to terminate (SIGTERM)
+    Term,
+    /// Terminate the process immediately (SIGKILL)
+    Kill,
+}
+
+impl Signal {
+    /// Returns all signals offered to the user
+    pub fn all() -> Vec<Signal> {
+        vec![Signal::Term, Signal::Kill]
+    }
+
+    /// Returns the conventional signal name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Signal::Term => "SIGTERM",
+            Signal::Kill => "SIGKILL",
+        }
+    }
+
+    /// Returns a short description of the signal
+    pub fn description(&self) -> &'static str {
+        match self {
+            Signal::Term => "Graceful: lets the process clean up",
+            Signal::Kill => "Forceful: cannot be caught or ignored",
+        }
+    }
+
+    /// Parses a signal name such as `SIGTERM`, `term`, `KILL` or `9`
+    pub fn from_name(name: &str) -> Option<Signal> {
+        let name = name.trim().to_uppercase();
+        match name.strip_prefix("SIG").unwrap_or(&name) {
+            "TERM" | "15" => Some(Signal::Term),
+            "KILL" | "9" => Some(Signal::Kill),
+            _ => None,
+        }
+    }
+
+    /// Returns the other signal (used to toggle the choice)
+    pub fn toggle(&self) -> Signal {
+        match self {
+            Signal::Term => Signal::Kill,
+            Signal::Kill => Signal::Term,
+        }
+    }
+
+    /// Returns the raw signal number
+    fn number(&self) -> libc::c_int {
+        match self {
+            Signal::Term => libc::SIGTERM,
+            Signal::Kill => libc::SIGKILL,
+        }
+    }
+}
+
+/// Why a signal could not be delivered
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignalError {
+    /// The process no longer exists
+    NotFound,
+    /// The process belongs to another user
+    PermissionDenied,
+    /// Any other failure reported by kill(2)
+    Other(String),
+}
+
+impl std::fmt::Display for SignalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SignalError::NotFound => write!(f, "already gone"),
+            SignalError::PermissionDenied => write!(f, "permission denied"),
+            SignalError::Other(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+/// Sends a signal to a single process
+pub fn send_signal(pid: u32, signal: Signal) -> std::result::Result<(), SignalError> {
+    // SAFETY: kill(2) has no memory-safety preconditions
+    let result = unsafe { libc::kill(pid as libc::pid_t, signal.number()) };
+    if result == 0 {
+        return Ok(());
+    }
+
+    let error = std::io::Error::last_os_error();
+    match error.raw_os_error() {
+        Some(libc::ESRCH) => Err(SignalError::NotFound),
+        Some(libc::EPERM) => Err(SignalError::PermissionDenied),
+        _ => Err(SignalError::Other(error.to_string())),
+    }
+}
+
+/// A snapshot of one running process
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    pub ppid: u32,
+    pub user: String,
+    /// Executable name as reported by the kernel (`comm`)
+    pub name: String,
+    /// Base name of the program in argv[0]
+    pub exe: String,
+    /// Full command line with arguments joined by spaces
+    pub cmdline: String,
+    /// CPU usage relative to a single core since the previous sample
+    pub cpu_percent: f32,
+    /// Resident memory in bytes
+    pub memory: u64,
+    /// Resident memory as a share of total system memory
+    pub memory_percent: f32,
+    /// TCP ports this process is listening on
+    pub listening_ports: Vec<u16>,
+    /// Start time after boot in clock ticks; with the PID this identifies
+    /// the process even if the PID is later reused
+    pub start_ticks: u64,
+}
+
+impl ProcessInfo {
+    /// Returns whether the process matches a free-text filter
+    ///
+    /// Matches case-insensitively against the PID, user, name, command line
+    /// and listening ports (`:3000` or `3000`).
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return true;
+        }
+
+        let port_query = query.trim_start_matches(':');
+        self.pid.to_string() == query
+            || self.user.to_lowercase().contains(&query)
+            || self.name.to_lowercase().contains(&query)
+            || self.cmdline.to_lowercase().contains(&query)
+            || self.listening_ports.iter().any(|port| port.to_string() == port_query)
+    }
+
+    /// Returns the listening ports formatted for display
+    pub fn ports_label(&self) -> String {
+        self.listening_ports
+            .iter()
+            .map(|port| port.to_string())
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+/// Fields of `/proc/<pid>/stat` used by the sampler
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct StatFields {
+    name: String,
+    /// One-letter state such as `R`, `S` or `Z`
+    state: char,
+    ppid: u32,
+    /// utime + stime in clock ticks
+    cpu_ticks: u64,
+    /// Start time after boot in clock ticks
+    start_ticks: u64,
+    /// Resident set size in pages
+    rss_pages: u64,
+}
+
+/// Reads processes from `/proc`, remembering CPU counters between samples
+#[derive(Debug, Default)]
+pub struct ProcessSampler {
+    previous_ticks: HashMap<u32, u64>,
+    previous_total: u64,
+    users: HashMap<u32, String>,
+}
+
+impl ProcessSampler {
+    /// Creates a new sampler
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Takes a snapshot of all user-space processes
+    ///
+    /// CPU usage is measured against the previous call; the first sample
+    /// falls back to each process's average over its lifetime.
+    pub fn sample(&mut self) -> Vec<ProcessInfo> {
+        if self.users.is_empty() {
+            self.users = fs::read_to_string("/etc/passwd")
+                .map(|content| parse_passwd(&content))
+                .unwrap_or_default();
+        }
+
+        let stat = fs::read_to_string("/proc/stat").unwrap_or_default();
+        let total = parse_total_ticks(&stat);
+        let cpus = stat
+            .lines()
+            .filter(|line| line.starts_with("cpu") && !line.starts_with("cpu "))
+            .count()
+            .max(1) as f32;
+        let total_delta = total.saturating_sub(self.previous_total);
+
+        let clock_ticks = sysconf(libc::_SC_CLK_TCK).unwrap_or(100) as f32;
+        let page_size = sysconf(libc::_SC_PAGESIZE).unwrap_or(4096);
+        let uptime = fs::read_to_string("/proc/uptime")
+            .ok()
+            .and_then(|content| content.split_whitespace().next()?.parse::<f32>().ok())
+            .unwrap_or(0.0);
+        let memory_total = fs::read_to_string("/proc/meminfo")
+            .ok()
+            .and_then(|content| parse_meminfo_kb(&content, "MemTotal"))
+            .unwrap_or(0)
+            * 1024;
+
+        let sockets = listening_sockets();
+        let mut ticks = HashMap::new();
+        let mut processes = Vec::new();
+
+        for pid in list_pids() {
+            let dir = Path::new("/proc").join(pid.to_string());
+            let Some(stat) = fs::read_to_string(dir.join("stat")).ok().and_then(|s| parse_stat(&s)) else {
+                continue;
+            };
+            let raw_cmdline = fs::read(dir.join("cmdline")).unwrap_or_default();
+            let cmdline = parse_cmdline(&raw_cmdline);
+            // Kernel threads and zombies have no command line and cannot be managed
+            if cmdline.is_empty() {
+                continue;
+            }
+
+            let uid = fs::metadata(&dir).map(|meta| meta.uid()).unwrap_or(0);
+            let user = self
+                .users
+                .get(&uid)
+                .cloned()
+                .unwrap_or_else(|| uid.to_string());
+
+            let cpu_percent = match self.previous_ticks.get(&pid) {
+                Some(previous) if total_delta > 0 => {
+                    stat.cpu_ticks.saturating_sub(*previous) as f32 / total_delta as f32 * 100.0 * cpus
+                }
+                _ => {
+                    let elapsed = uptime - stat.start_ticks as f32 / clock_ticks;
+                    if elapsed > 0.0 {
+                        stat.cpu_ticks as f32 / clock_ticks / elapsed * 100.0
+                    } else {
+                        0.0
+                    }
+                }
+            };
+            ticks.insert(pid, stat.cpu_ticks);
+
+            let memory = stat.rss_pages * page_size;
+            let memory_percent = if memory_total > 0 {
+                memory as f32 / memory_total as f32 * 100.0
+            } else {
+                0.0
+            };
+
+            let mut listening_ports: Vec<u16> = socket_inodes(pid)
+                .iter()
+                .filter_map(|inode| sockets.get(inode).copied())
+                .collect();
+            listening_ports.sort_unstable();
+            listening_ports.dedup();
+
+            processes.push(ProcessInfo {
+                pid,
+                ppid: stat.ppid,
+                user,
+                name: stat.name,
+                exe: parse_exe(&raw_cmdline),
+                cmdline,
+                cpu_percent,
+                memory,
+                memory_percent,
+                listening_ports,
+                start_ticks: stat.start_ticks,
+            });
+        }
+
+        self.previous_ticks = ticks;
+        self.previous_total = total;
+        processes
+    }
+}
+
+/// Returns the numeric entries of `/proc`
+fn list_pids() -> Vec<u32> {
+    fs::read_dir("/proc")
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok()?.file_name().to_str()?.parse().ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Maps the inode of every listening TCP socket to its local port
+///
+/// Reads both `/proc/net/tcp` and `/proc/net/tcp6`.
+pub fn listening_sockets() -> HashMap<u64, u16> {
+    ["/proc/net/tcp", "/proc/net/tcp6"]
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .flat_map(|content| parse_net_tcp(&content))
+        .collect()
+}
+
+/// Returns the socket inodes held open by a process
+///
+/// Processes owned by other users yield an empty list unless running as root.
+pub fn socket_inodes(pid: u32) -> Vec<u64> {
+    let Ok(entries) = fs::read_dir(format!("/proc/{}/fd", pid)) else {
+        return Vec::new();
+    };
+
+    entries
+        .filter_map(|entry| {
+            let target = fs::read_link(entry.ok()?.path()).ok()?;
+            parse_socket_link(target.to_str()?)
+        })
+        .collect()
+}
+
+/// Returns the start time of a process in clock ticks after boot
+pub fn start_ticks(pid: u32) -> Option<u64> {
+    read_stat(pid).map(|stat| stat.start_ticks)
+}
+
+/// Returns whether the process that started at `start_ticks` is still running
+///
+/// Zombies count as gone, as does a different process that reused the PID.
+pub fn is_alive(pid: u32, start_ticks: u64) -> bool {
+    read_stat(pid).is_some_and(|stat| stat.start_ticks == start_ticks && !matches!(stat.state, 'Z' | 'X'))
+}
+
+/// Reads and parses `/proc/<pid>/stat`
+fn read_stat(pid: u32) -> Option<StatFields> {
+    parse_stat(&fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?)
+}
+
+/// Parses `/proc/<pid>/stat`
+///
+/// The command name may contain spaces and parentheses, so fields are
+/// located relative to the last closing parenthesis.
+fn parse_stat(content: &str) -> Option<StatFields> {
+    let open = content.find('(')?;
+    let close = content.rfind(')')?;
+    let name = content.get(open + 1..close)?.to_string();
+    let fields: Vec<&str> = content.get(close + 1..)?.split_whitespace().collect();
+
+    // Indices are offset by 3 from the field numbers in proc(5)
+    let field = |index: usize| -> Option<u64> { fields.get(index)?.parse().ok() };
+    Some(StatFields {
+        name,
+        state: fields.first()?.chars().next()?,
+        ppid: field(1)? as u32,
+        cpu_ticks: field(11)? + field(12)?,
+        start_ticks: field(19)?,
+        rss_pages: field(21)?,
+    })
+}
+
+/// Joins the NUL-separated arguments of `/proc/<pid>/cmdline`
+fn parse_cmdline(raw: &[u8]) -> String {
+    raw.split(|byte| *byte == 0)
+        .filter(|arg| !arg.is_empty())
+        .map(|arg| String::from_utf8_lossy(arg).into_owned())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Returns the base name of argv[0] from `/proc/<pid>/cmdline`
+fn parse_exe(raw: &[u8]) -> String {
+    let argv0 = raw.split(|byte| *byte == 0).next().unwrap_or_default();
+    let argv0 = String::from_utf8_lossy(argv0);
+    // Some programs rewrite argv[0] with their arguments ("nginx: worker process")
+    let program = argv0.split_whitespace().next().unwrap_or_default();
+    program.rsplit('/').next().unwrap_or_default().to_string()
+}
+
+/// Sums the aggregate `cpu` line of `/proc/stat`
+fn parse_total_ticks(content: &str) -> u64 {
+    content
+        .lines()
+        .find(|line| line.starts_with("cpu "))
+        .map(|line| {
+            line.split_whitespace()
+                .skip(1)
+                .filter_map(|value| value.parse::<u64>().ok())
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+/// Reads a kB value such as `MemTotal` from `/proc/meminfo`
+pub fn parse_meminfo_kb(content: &str, key: &str) -> Option<u64> {
+    content.lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        if name != key {
+            return None;
+        }
+        value.split_whitespace().next()?.parse().ok()
+    })
+}
+
+/// Maps UIDs to user names from `/etc/passwd`
+fn parse_passwd(content: &str) -> HashMap<u32, String> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split(':');
+            let name = fields.next()?;
+            let uid = fields.nth(1)?.parse().ok()?;
+            Some((uid, name.to_string()))
+        })
+        .collect()
+}
+
+/// Extracts `(inode, port)` for every listening socket in `/proc/net/tcp{,6}`
+fn parse_net_tcp(content: &str) -> Vec<(u64, u16)> {
+    const TCP_LISTEN: &str = "0A";
+
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.get(3) != Some(&TCP_LISTEN) {
+                return None;
+            }
+            let (_, port) = fields.get(1)?.rsplit_once(':')?;
+            let port = u16::from_str_radix(port, 16).ok()?;
+            let inode = fields.get(9)?.parse().ok()?;
+            Some((inode, port))
+        })
+        .collect()
+}
+
+/// Extracts the inode from an fd link such as `socket:[12345]`
+fn parse_socket_link(target: &str) -> Option<u64> {
+    target
+        .strip_prefix("socket:[")?
+        .strip_suffix(']')?
+        .parse()
+        .ok()
+}
+
+/// Reads a positive sysconf value
+fn sysconf(name: libc::c_int) -> Option<u64> {
+    // SAFETY: sysconf has no memory-safety preconditions
+    let value = unsafe { libc::sysconf(name) };
+    (value > 0).then_some(value as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    #[test]
+    fn test_parse_stat_handles_spaces_in_name() {
+        let content = "1234 (tmux: server) S 1 1234 1234 0 -1 4194560 1 0 0 0 \
+                       25 5 0 0 20 0 1 0 4200 10000000 512 18446744073709551615";
+        let stat = parse_stat(content).unwrap();
+        assert_eq!(stat.name, "tmux: server");
+        assert_eq!(stat.state, 'S');
+        assert_eq!(stat.ppid, 1);
+        assert_eq!(stat.cpu_ticks, 30);
+        assert_eq!(stat.start_ticks, 4200);
+        assert_eq!(stat.rss_pages, 512);
+    }
+
+    #[test]
+    fn test_parse_net_tcp_keeps_listening_sockets() {
+        let content = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode\n\
+            0: 00000000:0BB8 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 4242 1\n\
+            1: 0100007F:9C40 0100007F:0BB8 01 00000000:00000000 00:00000000 00000000  1000        0 4343 1\n";
+        assert_eq!(parse_net_tcp(content), vec![(4242, 3000)]);
+    }
+
+    #[test]
+    fn test_parse_helpers() {
+        assert_eq!(parse_cmdline(b"node\0server.js\0--port\0x\0"), "node server.js --port x");
+        assert_eq!(parse_exe(b"/usr/bin/python3\0-m\0uvicorn\0"), "python3");
+        assert_eq!(Signal::from_name("sigkill"), Some(Signal::Kill));
+        assert_eq!(Signal::from_name("HUP"), None);
+        assert_eq!(parse_socket_link("socket:[987]"), Some(987));
+        assert_eq!(parse_socket_link("/dev/null"), None);
+        assert_eq!(parse_total_ticks("cpu  10 20 30 40\ncpu0 5 10 15 20\n"), 100);
+        assert_eq!(parse_meminfo_kb("MemTotal:       16000 kB\nMemFree: 1 kB\n", "MemTotal"), Some(16000));
+        assert_eq!(parse_passwd("root:x:0:0:root:/root:/bin/bash\n").get(&0).map(String::as_str), Some("root"));
+    }
+
+    #[test]
+    fn test_sample_includes_current_process_and_its_ports() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let mut sampler = ProcessSampler::new();
+        let processes = sampler.sample();
+        let current = processes
+            .iter()
+            .find(|process| process.pid == std::process::id())
+            .expect("current process should be listed");
+
+        assert!(current.listening_ports.contains(&port));
+        assert!(current.matches(&format!(":{}", port)));
+        assert!(current.memory > 0);
+        assert!(is_alive(current.pid, current.start_ticks));
+        assert!(!is_alive(current.pid, current.start_ticks + 1));
+    }
+
+    #[test]
+    fn test_send_signal_reports_missing_process() {
+        // PIDs are capped well below this value on Linux
+        assert_eq!(send_signal(u32::MAX / 2, Signal::Term), Err(SignalError::NotFound));
+    }
+}
@@ -0,0 +1,330 @@
+use crate::utils::errors::{ModeError, Result};
+use crate::utils::file_ops;
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+
+/// Line opening the block that holds everything mode writes to an RC file
+pub const BLOCK_START: &str = "# >>> mode >>>";
+
+/// Line closing mode's block
+pub const BLOCK_END: &str = "# <<< mode <<<";
+
+/// Comment older versions of mode wrote above each appended definition
+const LEGACY_COMMENT: &str = "# Added by mode";
+
+/// Markers of the PATH block older versions of mode wrote
+const LEGACY_PATH_START: &str = "# >>> mode path >>>";
+const LEGACY_PATH_END: &str = "# <<< mode path <<<";
+
+/// What a definition line defines, so a new value replaces the old line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DefinitionKey {
+    Alias(String),
+    /// An exported variable; `fish_add_path` lines older versions wrote count as `PATH`
+    Export(String),
+}
+
+impl DefinitionKey {
+    /// Returns what a line defines, or None for comments and other code
+    pub fn of(line: &str) -> Option<Self> {
+        let trimmed = line.trim();
+        if trimmed.starts_with("fish_add_path ") {
+            return Some(DefinitionKey::Export("PATH".to_string()));
+        }
+
+        file_ops::parse_alias_line(trimmed)
+            .map(|(name, _)| DefinitionKey::Alias(name))
+            .or_else(|| file_ops::parse_export_line(trimmed).map(|(name, _)| DefinitionKey::Export(name)))
+    }
+}
+
+/// An RC file split around mode's block
+///
+/// Lines are kept verbatim, so `serialize` reproduces the parsed content
+/// exactly; only the methods that edit the block change anything.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RcFile {
+    /// Lines before the block (every line when there is no block)
+    before: Vec<String>,
+    /// Lines between the block markers, if the file has a block
+    block: Option<Vec<String>>,
+    /// Lines after the block
+    after: Vec<String>,
+    /// Whether the content ended with a newline
+    trailing_newline: bool,
+}
+
+impl RcFile {
+    /// Reads and parses an RC file
+    pub fn load(path: &Path) -> Result<Self> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    /// Splits RC file content around mode's block
+    ///
+    /// A start marker without a matching end marker is an error, as guessing
+    /// where the block ends could swallow the user's own lines
+    pub fn parse(content: &str) -> Result<Self> {
+        let lines: Vec<String> = content.lines().map(|line| line.to_string()).collect();
+        let trailing_newline = content.ends_with('\n');
+
+        let Some(start) = lines.iter().position(|line| line.trim() == BLOCK_START) else {
+            return Ok(Self {
+                before: lines,
+                block: None,
+                after: Vec::new(),
+                trailing_newline,
+            });
+        };
+
+        let end = lines[start..]
+            .iter()
+            .position(|line| line.trim() == BLOCK_END)
+            .map(|offset| start + offset)
+            .ok_or_else(|| {
+                ModeError::FileOperation(format!("'{}' has no matching '{}' line", BLOCK_START, BLOCK_END))
+            })?;
+
+        Ok(Self {
+            before: lines[..start].to_vec(),
+            block: Some(lines[start + 1..end].to_vec()),
+            after: lines[end + 1..].to_vec(),
+            trailing_newline,
+        })
+    }
+
+    /// Returns every line of the file, block markers included
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = self.before.clone();
+        if let Some(block) = &self.block {
+            lines.push(BLOCK_START.to_string());
+            lines.extend(block.iter().cloned());
+            lines.push(BLOCK_END.to_string());
+        }
+        lines.extend(self.after.iter().cloned());
+        lines
+    }
+
+    /// Joins the lines back into file content
+    pub fn serialize(&self) -> String {
+        let mut content = self.lines().join("\n");
+        if self.trailing_newline {
+            content.push('\n');
+        }
+        content
+    }
+
+    /// Returns the zero-based line numbers of the block's body, if there is a block
+    pub fn block_range(&self) -> Option<Range<usize>> {
+        let start = self.before.len() + 1;
+        self.block.as_ref().map(|block| start..start + block.len())
+    }
+
+    /// Returns the block's body lines (empty when there is no block)
+    pub fn block_lines(&self) -> &[String] {
+        self.block.as_deref().unwrap_or_default()
+    }
+
+    /// Adds a definition to the block, replacing the block's line for the same name
+    ///
+    /// The block is appended to the end of the file if there isn't one yet
+    pub fn set_definition(&mut self, line: &str) -> Result<()> {
+        let key = DefinitionKey::of(line)
+            .ok_or_else(|| ModeError::InvalidInput(format!("'{}' is not an alias or export", line)))?;
+
+        let block = self.block_mut();
+        match block.iter_mut().find(|existing| DefinitionKey::of(existing).as_ref() == Some(&key)) {
+            Some(existing) => *existing = line.to_string(),
+            None => block.push(line.to_string()),
+        }
+        Ok(())
+    }
+
+    /// Keeps only the lines, in and out of the block, for which `keep` is true
+    pub fn retain(&mut self, keep: impl Fn(&str) -> bool) {
+        self.before.retain(|line| keep(line));
+        if let Some(block) = &mut self.block {
+            block.retain(|line| keep(line));
+        }
+        self.after.retain(|line| keep(line));
+    }
+
+    /// Drops the block if it has nothing in it, along with the blank line
+    /// that separated it from the surrounding lines
+    pub fn remove_empty_block(&mut self) {
+        if !self.block.as_ref().is_some_and(|block| block.is_empty()) {
+            return;
+        }
+        self.block = None;
+
+        let is_blank = |line: &String| line.trim().is_empty();
+        if self.before.last().is_some_and(is_blank) && self.after.first().filter(|line| !is_blank(line)).is_none() {
+            self.before.pop();
+        }
+        let after = std::mem::take(&mut self.after);
+        self.before.extend(after);
+    }
+
+    /// Returns the block's lines, creating an empty block at the end of the file
+    fn block_mut(&mut self) -> &mut Vec<String> {
+        if self.block.is_none() {
+            if self.before.last().is_some_and(|line| !line.trim().is_empty()) {
+                self.before.push(String::new());
+            }
+            self.trailing_newline = true;
+        }
+        self.block.get_or_insert_with(Vec::new)
+    }
+
+    /// Moves definitions written by older versions of mode into the block
+    ///
+    /// Those versions appended each definition under an `# Added by mode on ...`
+    /// comment and kept PATH in a separate `# >>> mode path >>>` block. The
+    /// comments, markers and the blank line mode put before each are dropped.
+    ///
+    /// Without a block yet, the block takes the place of the first legacy
+    /// entry, so the user's own lines after it still run after mode's (a
+    /// later PATH change isn't overwritten by the old PATH assignment).
+    ///
+    /// Returns whether anything was moved
+    pub fn migrate_legacy(&mut self) -> Result<bool> {
+        let (mut moved, first) = take_legacy(&mut self.before)?;
+        if let (None, Some(index)) = (&self.block, first) {
+            self.after = self.before.split_off(index);
+            if self.before.last().is_some_and(|line| !line.trim().is_empty()) {
+                self.before.push(String::new());
+            }
+            if self.after.first().is_some_and(|line| !line.trim().is_empty()) {
+                self.after.insert(0, String::new());
+            }
+            if self.after.is_empty() {
+                self.trailing_newline = true;
+            }
+            self.block = Some(Vec::new());
+        }
+        moved.extend(take_legacy(&mut self.after)?.0);
+
+        for line in &moved {
+            self.set_definition(line)?;
+        }
+        Ok(!moved.is_empty())
+    }
+}
+
+/// Removes legacy definitions from a run of lines, returning them in order
+/// along with the index of the remaining line the first one stood before
+fn take_legacy(lines: &mut Vec<String>) -> Result<(Vec<String>, Option<usize>)> {
+    let mut moved = Vec::new();
+    let mut first = None;
+    let mut kept: Vec<String> = Vec::with_capacity(lines.len());
+    let mut rest = std::mem::take(lines).into_iter().peekable();
+
+    while let Some(line) = rest.next() {
+        let trimmed = line.trim();
+
+        let taken = if trimmed.starts_with(LEGACY_COMMENT)
+            && rest.peek().is_some_and(|next| DefinitionKey::of(next).is_some())
+        {
+            moved.extend(rest.next().map(|definition| definition.trim().to_string()));
+            true
+        } else if trimmed == LEGACY_PATH_START {
+            loop {
+                match rest.next() {
+                    Some(body) if body.trim() == LEGACY_PATH_END => break,
+                    Some(body) if DefinitionKey::of(&body).is_some() => moved.push(body.trim().to_string()),
+                    Some(_) => {}
+                    None => {
+                        return Err(ModeError::FileOperation(format!(
+                            "'{}' has no matching '{}' line",
+                            LEGACY_PATH_START, LEGACY_PATH_END
+                        )))
+                    }
+                }
+            }
+            true
+        } else {
+            false
+        };
+
+        if taken {
+            if kept.last().is_some_and(|previous| previous.trim().is_empty()) {
+                kept.pop();
+            }
+            first.get_or_insert(kept.len());
+        } else {
+            kept.push(line);
+        }
+    }
+
+    *lines = kept;
+    Ok((moved, first))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        for content in [
+            "",
+            "alias ll='ls -la'",
+            "# bashrc\n\n# >>> mode >>>\nalias gs='git status'\n# note\nexport EDITOR=nvim\n# <<< mode <<<\necho after\n",
+            "# >>> mode >>>\n# <<< mode <<<",
+        ] {
+            let rc = RcFile::parse(content).unwrap();
+            assert_eq!(rc.serialize(), content);
+        }
+
+        assert!(RcFile::parse("# >>> mode >>>\nalias a=b\n").is_err());
+    }
+
+    #[test]
+    fn test_set_definition() {
+        let mut rc = RcFile::parse("# bashrc\n").unwrap();
+        rc.set_definition("alias gs='git status'").unwrap();
+        rc.set_definition("export EDITOR=vim").unwrap();
+        rc.set_definition("export EDITOR=nvim").unwrap();
+        rc.set_definition("export PATH=/bin").unwrap();
+
+        assert_eq!(
+            rc.serialize(),
+            "# bashrc\n\n# >>> mode >>>\nalias gs='git status'\nexport EDITOR=nvim\nexport PATH=/bin\n# <<< mode <<<\n"
+        );
+        assert_eq!(rc.block_range(), Some(3..6));
+        assert!(rc.set_definition("echo hi").is_err());
+    }
+
+    #[test]
+    fn test_migrate_legacy() {
+        let content = "# bashrc\nalias ll='ls -la'\n\n# Added by mode on 2024-01-01 00:00:00\nalias gs='git status'\n\n\
+            # >>> mode path >>>\nexport PATH='/usr/bin'\n# <<< mode path <<<\n\n\
+            # Added by mode on 2024-01-02 00:00:00\nexport EDITOR=\"vim\"\n# Added by mode on 2024-01-03 00:00:00\necho kept\n";
+
+        let mut rc = RcFile::parse(content).unwrap();
+        assert!(rc.migrate_legacy().unwrap());
+        assert_eq!(
+            rc.serialize(),
+            "# bashrc\nalias ll='ls -la'\n\n\
+            # >>> mode >>>\nalias gs='git status'\nexport PATH='/usr/bin'\nexport EDITOR=\"vim\"\n# <<< mode <<<\n\n\
+            # Added by mode on 2024-01-03 00:00:00\necho kept\n"
+        );
+
+        // Nothing left to move the second time
+        assert!(!rc.migrate_legacy().unwrap());
+    }
+
+    #[test]
+    fn test_migrate_legacy_keeps_later_path_changes_last() {
+        let content = "# >>> mode path >>>\nexport PATH='/usr/bin:/bin'\n# <<< mode path <<<\n\
+            export PATH=\"$HOME/.cargo/bin:$PATH\"\n";
+
+        let mut rc = RcFile::parse(content).unwrap();
+        assert!(rc.migrate_legacy().unwrap());
+        assert_eq!(
+            rc.serialize(),
+            "# >>> mode >>>\nexport PATH='/usr/bin:/bin'\n# <<< mode <<<\n\nexport PATH=\"$HOME/.cargo/bin:$PATH\"\n"
+        );
+    }
+}
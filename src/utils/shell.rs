@@ -1,12 +1,16 @@
 use crate::utils::errors::{ModeError, Result};
 use std::env;
-use std::path::PathBuf;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
 
 /// Supported shell types
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ShellType {
     Bash,
     Zsh,
+    Fish,
 }
 
 impl ShellType {
@@ -15,14 +19,60 @@ impl ShellType {
         match self {
             ShellType::Bash => "bash",
             ShellType::Zsh => "zsh",
+            ShellType::Fish => "fish",
         }
     }
 
     /// Returns the RC file name for this shell
+    ///
+    /// Bash and zsh read theirs from the home directory; fish's lives in
+    /// its `conf.d` directory, which fish sources at startup
     pub fn rc_file_name(&self) -> &'static str {
         match self {
             ShellType::Bash => ".bashrc",
             ShellType::Zsh => ".zshrc",
+            ShellType::Fish => "mode.fish",
+        }
+    }
+
+    /// Quotes a value as a single literal word in this shell's syntax
+    ///
+    /// Every RC writer goes through this (or `quote_expanding`), so commands
+    /// containing quotes, backslashes or `$` come back exactly as entered
+    pub fn quote(&self, value: &str) -> String {
+        match self {
+            ShellType::Bash | ShellType::Zsh => quote(value),
+            ShellType::Fish => quote_fish(value),
+        }
+    }
+
+    /// Double-quotes a value so `$VAR` references in it still expand
+    ///
+    /// Quotes and backslashes are escaped, as are backticks outside fish
+    /// (where they aren't special)
+    pub fn quote_expanding(&self, value: &str) -> String {
+        let special: &[char] = match self {
+            ShellType::Bash | ShellType::Zsh => &['"', '\\', '`'],
+            ShellType::Fish => &['"', '\\'],
+        };
+
+        let mut quoted = String::with_capacity(value.len() + 2);
+        quoted.push('"');
+        for c in value.chars() {
+            if special.contains(&c) {
+                quoted.push('\\');
+            }
+            quoted.push(c);
+        }
+        quoted.push('"');
+        quoted
+    }
+
+    /// Returns the arguments that make this shell parse a file without running it
+    fn syntax_check_args(&self) -> &'static [&'static str] {
+        match self {
+            ShellType::Bash | ShellType::Zsh => &["-n"],
+            ShellType::Fish => &["--no-execute"],
         }
     }
 }
@@ -38,9 +88,11 @@ pub fn detect_shell() -> Result<ShellType> {
         Ok(ShellType::Bash)
     } else if shell_path.contains("zsh") {
         Ok(ShellType::Zsh)
+    } else if shell_path.contains("fish") {
+        Ok(ShellType::Fish)
     } else {
         Err(ModeError::ShellDetection(format!(
-            "Unsupported shell: {}. Only bash and zsh are supported.",
+            "Unsupported shell: {}. Only bash, zsh and fish are supported.",
             shell_path
         )))
     }
@@ -48,12 +100,27 @@ pub fn detect_shell() -> Result<ShellType> {
 
 /// Gets the path to the RC file for the given shell type
 ///
-/// Returns the full path to the shell's RC file (e.g., ~/.bashrc or ~/.zshrc)
+/// Returns the full path to the shell's RC file (e.g., ~/.bashrc or ~/.zshrc).
+/// For fish this is `~/.config/fish/conf.d/mode.fish`, which mode owns and
+/// creates on first use.
 pub fn get_rc_file_path(shell: ShellType) -> Result<PathBuf> {
     let home = env::var("HOME")
         .map_err(|_| ModeError::ShellDetection("HOME environment variable not set".to_string()))?;
 
-    let rc_path = PathBuf::from(home).join(shell.rc_file_name());
+    let rc_path = match shell {
+        ShellType::Bash | ShellType::Zsh => PathBuf::from(home).join(shell.rc_file_name()),
+        ShellType::Fish => {
+            let config_home = env::var_os("XDG_CONFIG_HOME")
+                .filter(|dir| !dir.is_empty())
+                .map(PathBuf::from)
+                .unwrap_or_else(|| PathBuf::from(home).join(".config"));
+            let rc_path = config_home.join("fish").join("conf.d").join(shell.rc_file_name());
+            if !rc_path.exists() {
+                create_fish_rc_file(&rc_path)?;
+            }
+            rc_path
+        }
+    };
 
     // Check if file exists
     if !rc_path.exists() {
@@ -77,6 +144,104 @@ pub fn get_rc_file_path(shell: ShellType) -> Result<PathBuf> {
     Ok(rc_path)
 }
 
+/// Creates mode's fish configuration file in `conf.d`
+fn create_fish_rc_file(rc_path: &Path) -> Result<()> {
+    if let Some(dir) = rc_path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(rc_path, "# Aliases, variables and PATH managed by mode\n")?;
+    Ok(())
+}
+
+/// Writes a command for the shell wrapper to evaluate after mode exits
+///
+/// The `mode` shell function (see `mode-wrapper.sh` and its zsh and fish
+/// counterparts) reads `~/.mode_exit_cmd`
+/// once the binary returns and runs it in the user's live shell
+pub fn write_exit_command(command: &str) -> Result<()> {
+    let home = env::var("HOME")
+        .map_err(|_| ModeError::ShellDetection("HOME environment variable not set".to_string()))?;
+
+    fs::write(PathBuf::from(home).join(".mode_exit_cmd"), format!("{}\n", command))?;
+
+    Ok(())
+}
+
+/// Expands a leading `~` to the user's home directory
+///
+/// Paths without a leading `~` (or when `HOME` is unset) are returned as-is
+pub fn expand_home(path: &str) -> PathBuf {
+    let home = env::var("HOME").ok();
+
+    match (path, home) {
+        ("~", Some(home)) => PathBuf::from(home),
+        (path, Some(home)) if path.starts_with("~/") => PathBuf::from(home).join(&path[2..]),
+        (path, _) => PathBuf::from(path),
+    }
+}
+
+/// Returns whether a value can be used as a shell word without quoting
+fn is_safe_word(value: &str) -> bool {
+    let is_safe = |c: char| c.is_ascii_alphanumeric() || "/._-+=:@%,".contains(c);
+    !value.is_empty() && value.chars().all(is_safe)
+}
+
+/// Quotes a value for use as a single word in a POSIX shell command
+///
+/// Values made only of safe characters are returned unchanged
+pub fn quote(value: &str) -> String {
+    if is_safe_word(value) {
+        return value.to_string();
+    }
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// Quotes a value for use as a single word in a fish command
+///
+/// Values made only of safe characters are returned unchanged. Fish single
+/// quotes treat a backslash before `\` or `'` as an escape, so both are
+/// escaped rather than using the POSIX `'\''` idiom
+pub fn quote_fish(value: &str) -> String {
+    if is_safe_word(value) {
+        return value.to_string();
+    }
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+/// Parses a file with the shell without running it (`bash -n`, `zsh -n`,
+/// `fish --no-execute`)
+///
+/// Passes when the shell isn't installed, as there is nothing to check with.
+/// On failure the error carries the shell's first complaint
+pub fn check_syntax(shell: ShellType, path: &Path) -> Result<()> {
+    let output = match Command::new(shell.name())
+        .args(shell.syntax_check_args())
+        .arg(path)
+        .output()
+    {
+        Ok(output) => output,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e.into()),
+    };
+
+    if output.status.success() {
+        return Ok(());
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let complaint = stderr.lines().find(|line| !line.trim().is_empty()).unwrap_or("");
+    Err(ModeError::SyntaxCheck(format!(
+        "{} reported: {}",
+        shell.name(),
+        complaint.trim()
+    )))
+}
+
+/// Builds the command that takes the given shell to a directory
+pub fn cd_command(shell: ShellType, dir: &Path) -> String {
+    format!("cd {}", shell.quote(&dir.display().to_string()))
+}
+
 /// Validates an alias name
 ///
 /// Alias names must:
@@ -91,7 +256,7 @@ pub fn validate_alias_name(name: &str) -> Result<()> {
         ));
     }
 
-    if name.chars().nth(0).unwrap().is_ascii_digit() {
+    if name.chars().next().unwrap().is_ascii_digit() {
         return Err(ModeError::InvalidAliasName(
             "Alias name cannot start with a digit".to_string(),
         ));
@@ -141,11 +306,58 @@ mod tests {
         assert!(validate_alias_name("if").is_err());
     }
 
+    #[test]
+    fn test_quote() {
+        assert_eq!(quote("/home/me/src"), "/home/me/src");
+        assert_eq!(quote("/home/me/my src"), "'/home/me/my src'");
+        assert_eq!(quote("it's"), r"'it'\''s'");
+        assert_eq!(quote("$HOME"), "'$HOME'");
+        assert_eq!(quote(""), "''");
+    }
+
+    #[test]
+    fn test_quote_fish() {
+        assert_eq!(quote_fish("ls"), "ls");
+        assert_eq!(quote_fish("ls -la"), "'ls -la'");
+        assert_eq!(quote_fish("it's"), r"'it\'s'");
+        assert_eq!(quote_fish(r"a\b"), r"'a\\b'");
+        assert_eq!(quote_fish("$HOME"), "'$HOME'");
+    }
+
+    #[test]
+    fn test_cd_command() {
+        let dir = Path::new(r"/tmp/a\b c");
+        assert_eq!(cd_command(ShellType::Bash, dir), r"cd '/tmp/a\b c'");
+        assert_eq!(cd_command(ShellType::Fish, dir), r"cd '/tmp/a\\b c'");
+    }
+
+    #[test]
+    fn test_quote_expanding() {
+        assert_eq!(ShellType::Bash.quote_expanding("$HOME/go"), "\"$HOME/go\"");
+        assert_eq!(ShellType::Zsh.quote_expanding(r#"say "hi" `x`"#), r#""say \"hi\" \`x\`""#);
+        assert_eq!(ShellType::Fish.quote_expanding(r"a\b `x`"), r#""a\\b `x`""#);
+    }
+
+    #[test]
+    fn test_check_syntax() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".bashrc");
+
+        fs::write(&path, format!("alias q={}\n", ShellType::Bash.quote("git log --format='%h'"))).unwrap();
+        assert!(check_syntax(ShellType::Bash, &path).is_ok());
+
+        // An unterminated quote, as raw interpolation of `echo it's` produced
+        fs::write(&path, "alias q='echo it's'\n").unwrap();
+        assert!(matches!(check_syntax(ShellType::Bash, &path), Err(ModeError::SyntaxCheck(_))));
+    }
+
     #[test]
     fn test_shell_type() {
         assert_eq!(ShellType::Bash.name(), "bash");
         assert_eq!(ShellType::Zsh.name(), "zsh");
         assert_eq!(ShellType::Bash.rc_file_name(), ".bashrc");
         assert_eq!(ShellType::Zsh.rc_file_name(), ".zshrc");
+        assert_eq!(ShellType::Fish.name(), "fish");
+        assert_eq!(ShellType::Fish.rc_file_name(), "mode.fish");
     }
 }
@@ -0,0 +1,400 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::tempdir;
+
+/// Builds a `mode` command with an isolated HOME containing an empty .bashrc
+fn mode_in(home: &std::path::Path) -> Command {
+    let mut cmd = Command::cargo_bin("mode").unwrap();
+    cmd.env("HOME", home)
+        .env("SHELL", "/bin/bash")
+        .env_remove("XDG_CONFIG_HOME")
+        .env_remove("XDG_DATA_HOME")
+        .env_remove("XDG_STATE_HOME");
+    cmd
+}
+
+#[test]
+fn test_version() {
+    let home = tempdir().unwrap();
+    mode_in(home.path())
+        .arg("--version")
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("mode "));
+}
+
+#[test]
+fn test_unknown_command_exits_with_usage_error() {
+    let home = tempdir().unwrap();
+    mode_in(home.path())
+        .arg("frobnicate")
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("Usage: mode"));
+}
+
+#[test]
+fn test_alias_add_list_and_remove() {
+    let home = tempdir().unwrap();
+    let rc_file = home.path().join(".bashrc");
+    fs::write(&rc_file, "# my bashrc\n").unwrap();
+
+    mode_in(home.path())
+        .args(["alias", "add", "ll", "ls -la"])
+        .assert()
+        .success();
+    assert!(fs::read_to_string(&rc_file).unwrap().contains("alias ll='ls -la'"));
+
+    // The shell wrapper picks up the reload command
+    let exit_cmd = fs::read_to_string(home.path().join(".mode_exit_cmd")).unwrap();
+    assert!(exit_cmd.starts_with("source "));
+
+    mode_in(home.path())
+        .args(["alias", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("ll\tls -la"));
+
+    // Duplicates map to their own exit code
+    mode_in(home.path())
+        .args(["alias", "add", "ll", "ls"])
+        .assert()
+        .code(4);
+
+    mode_in(home.path())
+        .args(["alias", "rm", "ll"])
+        .assert()
+        .success();
+    assert!(!fs::read_to_string(&rc_file).unwrap().contains("alias ll="));
+}
+
+#[test]
+fn test_backups_go_to_state_dir_and_are_pruned() {
+    let home = tempdir().unwrap();
+    let rc_file = home.path().join(".bashrc");
+    fs::write(&rc_file, "# my bashrc\n").unwrap();
+    fs::create_dir_all(home.path().join(".config/mode")).unwrap();
+    fs::write(home.path().join(".config/mode/config.toml"), "[backups]\nkeep = 2\n").unwrap();
+
+    for name in ["a", "b", "c"] {
+        mode_in(home.path()).args(["alias", "add", name, "ls"]).assert().success();
+    }
+
+    // Nothing is left next to the rc file; the store keeps the newest two
+    let store = home.path().join(".local/state/mode/backups");
+    let backup_dir = store.join(rc_file.to_string_lossy().replace('/', "%"));
+    let left_in_home = fs::read_dir(home.path())
+        .unwrap()
+        .filter(|entry| entry.as_ref().unwrap().file_name().to_string_lossy().contains(".backup."))
+        .count();
+    assert_eq!(left_in_home, 0);
+    assert_eq!(fs::read_dir(&backup_dir).unwrap().count(), 2);
+}
+
+#[test]
+fn test_alias_add_rejects_invalid_name() {
+    let home = tempdir().unwrap();
+    fs::write(home.path().join(".bashrc"), "").unwrap();
+
+    mode_in(home.path())
+        .args(["alias", "add", "my-alias", "ls"])
+        .assert()
+        .code(3);
+}
+
+#[test]
+fn test_alias_without_rc_file() {
+    let home = tempdir().unwrap();
+
+    mode_in(home.path())
+        .args(["alias", "list"])
+        .assert()
+        .code(6);
+}
+
+#[test]
+fn test_bookmark_add_go_and_remove() {
+    let home = tempdir().unwrap();
+    let project = home.path().join("my project");
+    fs::create_dir(&project).unwrap();
+    let rc_file = home.path().join(".bashrc");
+    fs::write(&rc_file, "# my bashrc\n").unwrap();
+
+    mode_in(home.path())
+        .current_dir(&project)
+        .args(["bookmark"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("as 'my_project'"));
+
+    // Bookmarks live in mode's own file and leave the RC file alone
+    assert!(home.path().join(".local/share/mode/bookmarks.toml").exists());
+    assert_eq!(fs::read_to_string(&rc_file).unwrap(), "# my bashrc\n");
+
+    mode_in(home.path())
+        .args(["bookmark", "rename", "my_project", "proj"])
+        .assert()
+        .success();
+    mode_in(home.path())
+        .args(["bookmark", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("proj\t"));
+
+    // Jumping hands a cd to the shell wrapper
+    mode_in(home.path())
+        .args(["bookmark", "go", "proj"])
+        .assert()
+        .success();
+    let exit_cmd = fs::read_to_string(home.path().join(".mode_exit_cmd")).unwrap();
+    assert_eq!(exit_cmd.trim(), format!("cd '{}'", project.display()));
+
+    mode_in(home.path())
+        .args(["bookmark", "rm", "proj"])
+        .assert()
+        .success();
+    mode_in(home.path())
+        .args(["bookmark", "go", "proj"])
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn test_bookmark_imports_legacy_temp_alias() {
+    let home = tempdir().unwrap();
+    let rc_file = home.path().join(".bashrc");
+    fs::write(
+        &rc_file,
+        "# my bashrc\n\n# Added by mode on 2024-01-01 00:00:00\nalias temp='cd \"/srv/old\"'\n",
+    )
+    .unwrap();
+
+    mode_in(home.path())
+        .args(["bookmark", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("temp\t/srv/old"));
+    assert!(!fs::read_to_string(&rc_file).unwrap().contains("alias temp"));
+}
+
+#[test]
+fn test_bookmark_list_without_rc_file() {
+    let home = tempdir().unwrap();
+
+    mode_in(home.path()).args(["bookmark", "list"]).assert().success();
+    // The import is attempted once; the store file marks it done
+    assert!(home.path().join(".local/share/mode/bookmarks.toml").exists());
+}
+
+#[test]
+fn test_visit_then_jump_to_best_match() {
+    let home = tempdir().unwrap();
+    let api = home.path().join("src").join("api");
+    let docs = home.path().join("src").join("docs");
+    fs::create_dir_all(&api).unwrap();
+    fs::create_dir_all(&docs).unwrap();
+
+    for dir in [&api, &docs, &docs, home.path()] {
+        mode_in(home.path()).arg("visit").arg(dir).assert().success();
+    }
+    let history = fs::read_to_string(home.path().join(".local/share/mode/dirs.tsv")).unwrap();
+    assert_eq!(history.lines().count(), 2, "HOME is not recorded");
+
+    mode_in(home.path())
+        .args(["jump", "src"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("docs"));
+    mode_in(home.path()).args(["jump", "api"]).assert().success();
+    let exit_cmd = fs::read_to_string(home.path().join(".mode_exit_cmd")).unwrap();
+    assert_eq!(exit_cmd.trim(), format!("cd {}", api.display()));
+
+    mode_in(home.path()).args(["jump", "nothing-here"]).assert().code(1);
+}
+
+#[test]
+fn test_env_set_list_and_unset() {
+    let home = tempdir().unwrap();
+    let rc_file = home.path().join(".bashrc");
+    fs::write(&rc_file, "export EDITOR=vim\n").unwrap();
+
+    mode_in(home.path())
+        .args(["env", "set", "GOPATH", "$HOME/go"])
+        .assert()
+        .success();
+    assert!(fs::read_to_string(&rc_file).unwrap().contains("export GOPATH=\"$HOME/go\""));
+    let exit_cmd = fs::read_to_string(home.path().join(".mode_exit_cmd")).unwrap();
+    assert_eq!(exit_cmd.trim(), "export GOPATH=\"$HOME/go\"");
+
+    mode_in(home.path())
+        .args(["env", "list"])
+        .assert()
+        .success()
+        .stdout("EDITOR\tvim\nGOPATH\t$HOME/go\n");
+
+    mode_in(home.path()).args(["env", "unset", "EDITOR"]).assert().success();
+    let exit_cmd = fs::read_to_string(home.path().join(".mode_exit_cmd")).unwrap();
+    assert_eq!(exit_cmd.trim(), "unset EDITOR");
+
+    mode_in(home.path()).args(["env", "set", "1BAD", "x"]).assert().code(2);
+}
+
+#[test]
+fn test_fish_writes_to_conf_d() {
+    let home = tempdir().unwrap();
+    let rc_file = home.path().join(".config/fish/conf.d/mode.fish");
+
+    mode_in(home.path())
+        .env("SHELL", "/usr/bin/fish")
+        .args(["alias", "add", "ll", "ls -la"])
+        .assert()
+        .success();
+    assert!(fs::read_to_string(&rc_file).unwrap().contains("alias ll 'ls -la'"));
+
+    mode_in(home.path())
+        .env("SHELL", "/usr/bin/fish")
+        .args(["env", "set", "EDITOR", "nvim"])
+        .assert()
+        .success();
+    let exit_cmd = fs::read_to_string(home.path().join(".mode_exit_cmd")).unwrap();
+    assert_eq!(exit_cmd.trim(), "set -gx EDITOR \"nvim\"");
+
+    mode_in(home.path())
+        .env("SHELL", "/usr/bin/fish")
+        .args(["alias", "rm", "ll"])
+        .assert()
+        .success();
+    let exit_cmd = fs::read_to_string(home.path().join(".mode_exit_cmd")).unwrap();
+    assert!(exit_cmd.starts_with("functions --erase ll 2>/dev/null; abbr --erase ll"));
+}
+
+#[test]
+fn test_scan_exports_csv_to_output_dir() {
+    let home = tempdir().unwrap();
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let reports = home.path().join("reports");
+
+    mode_in(home.path())
+        .args(["scan", "127.0.0.1", "--ports", &port.to_string(), "--format", "csv", "--output"])
+        .arg(&reports)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Results saved to"));
+
+    let saved: Vec<_> = fs::read_dir(&reports).unwrap().map(|e| e.unwrap().path()).collect();
+    assert_eq!(saved.len(), 1);
+    assert_eq!(saved[0].extension().and_then(|e| e.to_str()), Some("csv"));
+
+    let csv = fs::read_to_string(&saved[0]).unwrap();
+    assert!(csv.contains(&format!("127.0.0.1,,{},tcp,open", port)));
+}
+
+#[test]
+fn test_scan_groups_output_by_host() {
+    let home = tempdir().unwrap();
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    mode_in(home.path())
+        .args(["scan", "127.0.0.1-2,localhost", "--ports", &port.to_string(), "--json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"hosts\""))
+        .stdout(predicate::str::contains("\"ip\": \"127.0.0.2\""));
+
+    mode_in(home.path())
+        .args(["scan", "10.0.0.0/8"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("Too many hosts"));
+}
+
+#[test]
+fn test_scan_uses_scanner_settings_from_config() {
+    let home = tempdir().unwrap();
+    let config_dir = home.path().join(".config/mode");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(config_dir.join("config.toml"), "[scanner]\nconcurrency = 7\ntimeout_ms = 250\n").unwrap();
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port().to_string();
+
+    mode_in(home.path())
+        .args(["scan", "127.0.0.1", "--ports", &port, "--json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"concurrency\": 7"))
+        .stdout(predicate::str::contains("\"timeout_ms\": 250"));
+
+    mode_in(home.path())
+        .args(["scan", "127.0.0.1", "--ports", &port, "--json", "--timeout", "300"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"timeout_ms\": 300"));
+}
+
+#[test]
+fn test_kill_runs_preset_from_config() {
+    let home = tempdir().unwrap();
+    let config_dir = home.path().join(".config/mode");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        "[[process_presets]]\nname = \"Sleepers\"\ncmdline = [\"^sleep 4242\\\\.5$\"]\n",
+    )
+    .unwrap();
+
+    let mut sleeper = std::process::Command::new("sleep").arg("4242.5").spawn().unwrap();
+
+    mode_in(home.path())
+        .args(["kill", "sleepers"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Sleepers: stopped 1 process(es)"))
+        .stdout(predicate::str::contains("terminated"));
+    assert!(sleeper.wait().unwrap().code().is_none());
+
+    mode_in(home.path())
+        .args(["kill", "everything"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("sleepers"));
+
+    fs::write(config_dir.join("config.toml"), "[[process_presets]]\nname = \"Empty\"\n").unwrap();
+    mode_in(home.path())
+        .args(["kill", "cursor"])
+        .assert()
+        .code(11)
+        .stderr(predicate::str::contains("at least one of cmdline"));
+}
+
+#[test]
+fn test_kill_port_stops_the_listener() {
+    use std::io::{BufRead, BufReader};
+    use std::process::Stdio;
+
+    let home = tempdir().unwrap();
+    let mut server = std::process::Command::new("python3")
+        .args([
+            "-c",
+            "import socket, time\n\
+             s = socket.socket()\n\
+             s.bind(('127.0.0.1', 0))\n\
+             s.listen()\n\
+             print(s.getsockname()[1], flush=True)\n\
+             time.sleep(60)",
+        ])
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut port = String::new();
+    BufReader::new(server.stdout.take().unwrap()).read_line(&mut port).unwrap();
+
+    mode_in(home.path())
+        .args(["kill", "--port", &format!(":{}", port.trim())])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("stopped 1 process(es)"));
+    assert!(server.wait().unwrap().code().is_none());
+}